@@ -182,6 +182,7 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("UpsertPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpsertPoints.points", ""),
+            ("UpsertPoints.update_filter", ""),
             ("DeletePoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.vectors", "custom(function = \"crate::grpc::validate::validate_named_vectors_not_empty\", message = \"must specify vectors to update\")"),
@@ -189,6 +190,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DeletePointVectors.vector_names", "length(min = 1, message = \"must specify vector names to delete\")"),
             ("GetPoints.collection_name", "length(min = 1, max = 255)"),
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SetPayloadPoints.update_filter", ""),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
@@ -213,6 +215,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchPointGroups.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("SearchParams.quantization", ""),
+            ("SearchParams.dimensions", "range(min = 1)"),
+            ("SearchParams.acorn", ""),
+            ("AcornSearchParams.max_selectivity", "custom = \"crate::grpc::validate::validate_f64_range_1\""),
             ("QuantizationSearchParams.oversampling", "custom = \"crate::grpc::validate::validate_f64_range_min_1\""),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
@@ -267,6 +272,16 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPoints.query_points", ""),
             ("QueryBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCounts.key", "length(min = 1)"),
+            ("FacetCounts.filter", ""),
+            ("FacetCounts.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SearchMatrixPoints.filter", ""),
+            ("SearchMatrixPoints.sample", "custom = \"crate::grpc::validate::validate_u64_range_min_2\""),
+            ("SearchMatrixPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
         ], &[])
         .type_attribute(".", "#[derive(serde::Serialize)]")
         // Service: points_internal_service.proto
@@ -304,6 +319,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPointsInternal.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCountsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCountsInternal.filter", ""),
+            ("FacetCountsInternal.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::str::FromStr as _;
 use std::time::Instant;
 
@@ -6,7 +7,8 @@ use chrono::{NaiveDateTime, Timelike};
 use itertools::Itertools;
 use segment::data_types::index::{
     BoolIndexType, DatetimeIndexType, FloatIndexType, GeoIndexType, IntegerIndexType,
-    KeywordIndexType, TextIndexType,
+    KeywordIndexType, Language, Snowball, SnowballLanguage, StopwordsInterface, TextIndexType,
+    UuidIndexType,
 };
 use segment::data_types::vectors as segment_vectors;
 use segment::json_path::JsonPath;
@@ -18,17 +20,21 @@ use uuid::Uuid;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, BoolIndexParams, CompressionRatio,
-    DatetimeIndexParams, DatetimeRange, Direction, FieldType, FloatIndexParams, GeoIndexParams,
+    raw_query, start_from, AcornSearchParams, BinaryQuantization, BinaryQuantizationEncoding,
+    BinaryQuantizationQueryEncoding, BoolIndexParams, CompressionRatio, DatetimeIndexParams,
+    DatetimeRange, Direction, FacetHit, FacetValue, FieldType, FloatIndexParams, GeoIndexParams,
     GeoLineString, GroupId, KeywordIndexParams, LookupLocation, MultiVectorComparator,
-    MultiVectorConfig, OrderBy, OrderValue, Range, RawVector, RecommendStrategy, SearchPointGroups,
-    SearchPoints, ShardKeySelector, SparseIndices, StartFrom, WithLookup,
+    MultiVectorConfig, MultiVectorPooling, OrderBy, OrderValue, Range, RawVector,
+    RecommendStrategy, SearchPointGroups, SearchPoints, ShardKeySelector, SnowballParams,
+    SparseIndices, StartFrom, StemmingAlgorithm, StopwordsSet, TextQuery, UuidIndexParams,
+    WithLookup,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
 use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
+use crate::grpc::qdrant::stemming_algorithm::StemmingParams;
 use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
@@ -36,8 +42,8 @@ use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
     Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
     GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
+    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MatchPhrase,
+    MinShould, MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
     PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
     PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
     QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
@@ -222,9 +228,12 @@ impl From<segment::data_types::index::TokenizerType> for TokenizerType {
 }
 
 impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::KeywordIndexParams) -> Self {
+    fn from(params: segment::data_types::index::KeywordIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {})),
+            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
+                is_tenant: params.is_tenant,
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
@@ -235,23 +244,30 @@ impl From<segment::data_types::index::IntegerIndexParams> for PayloadIndexParams
             index_params: Some(IndexParams::IntegerIndexParams(IntegerIndexParams {
                 lookup: params.lookup,
                 range: params.range,
+                is_principal: params.is_principal,
+                on_disk: params.on_disk,
             })),
         }
     }
 }
 
 impl From<segment::data_types::index::FloatIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::FloatIndexParams) -> Self {
+    fn from(params: segment::data_types::index::FloatIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {})),
+            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {
+                is_principal: params.is_principal,
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
 
 impl From<segment::data_types::index::GeoIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::GeoIndexParams) -> Self {
+    fn from(params: segment::data_types::index::GeoIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::GeoIndexParams(GeoIndexParams {})),
+            index_params: Some(IndexParams::GeoIndexParams(GeoIndexParams {
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
@@ -265,23 +281,43 @@ impl From<segment::data_types::index::TextIndexParams> for PayloadIndexParams {
                 lowercase: params.lowercase,
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
+                on_disk: params.on_disk,
+                stopwords: params.stopwords.map(StopwordsSet::from),
+                stemmer: params.stemmer.map(StemmingAlgorithm::from),
+                ascii_folding: params.ascii_folding,
             })),
         }
     }
 }
 
 impl From<segment::data_types::index::BoolIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::BoolIndexParams) -> Self {
+    fn from(params: segment::data_types::index::BoolIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::BoolIndexParams(BoolIndexParams {})),
+            index_params: Some(IndexParams::BoolIndexParams(BoolIndexParams {
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
 
 impl From<segment::data_types::index::DatetimeIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::DatetimeIndexParams) -> Self {
+    fn from(params: segment::data_types::index::DatetimeIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {})),
+            index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {
+                is_principal: params.is_principal,
+                on_disk: params.on_disk,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::UuidIndexParams> for PayloadIndexParams {
+    fn from(params: segment::data_types::index::UuidIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::UuidIndexParams(UuidIndexParams {
+                is_tenant: params.is_tenant,
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
@@ -306,6 +342,7 @@ impl From<segment::types::PayloadSchemaType> for PayloadSchemaType {
             segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
             segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
             segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+            segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
         }
     }
 }
@@ -320,6 +357,7 @@ impl From<segment::types::PayloadSchemaType> for FieldType {
             segment::types::PayloadSchemaType::Text => FieldType::Text,
             segment::types::PayloadSchemaType::Bool => FieldType::Bool,
             segment::types::PayloadSchemaType::Datetime => FieldType::Datetime,
+            segment::types::PayloadSchemaType::Uuid => FieldType::Uuid,
         }
     }
 }
@@ -349,15 +387,18 @@ impl From<segment::types::PayloadSchemaParams> for PayloadIndexParams {
             segment::types::PayloadSchemaParams::Text(p) => p.into(),
             segment::types::PayloadSchemaParams::Bool(p) => p.into(),
             segment::types::PayloadSchemaParams::Datetime(p) => p.into(),
+            segment::types::PayloadSchemaParams::Uuid(p) => p.into(),
         }
     }
 }
 
 impl TryFrom<KeywordIndexParams> for segment::data_types::index::KeywordIndexParams {
     type Error = Status;
-    fn try_from(_params: KeywordIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: KeywordIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::KeywordIndexParams {
             r#type: KeywordIndexType::Keyword,
+            is_tenant: params.is_tenant,
+            on_disk: params.on_disk,
         })
     }
 }
@@ -369,24 +410,29 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::index::IntegerIndexPar
             r#type: IntegerIndexType::Integer,
             lookup: params.lookup,
             range: params.range,
+            is_principal: params.is_principal,
+            on_disk: params.on_disk,
         })
     }
 }
 
 impl TryFrom<FloatIndexParams> for segment::data_types::index::FloatIndexParams {
     type Error = Status;
-    fn try_from(_params: FloatIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: FloatIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::FloatIndexParams {
             r#type: FloatIndexType::Float,
+            is_principal: params.is_principal,
+            on_disk: params.on_disk,
         })
     }
 }
 
 impl TryFrom<GeoIndexParams> for segment::data_types::index::GeoIndexParams {
     type Error = Status;
-    fn try_from(_params: GeoIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: GeoIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::GeoIndexParams {
             r#type: GeoIndexType::Geo,
+            on_disk: params.on_disk,
         })
     }
 }
@@ -402,24 +448,133 @@ impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            ascii_folding: params.ascii_folding,
+            stopwords: params
+                .stopwords
+                .map(StopwordsInterface::try_from)
+                .transpose()?,
+            stemmer: params
+                .stemmer
+                .map(segment::data_types::index::StemmingAlgorithm::try_from)
+                .transpose()?,
+            on_disk: params.on_disk,
         })
     }
 }
 
+impl From<StopwordsInterface> for StopwordsSet {
+    fn from(stopwords: StopwordsInterface) -> Self {
+        match stopwords {
+            StopwordsInterface::Language(language) => StopwordsSet {
+                languages: vec![language.to_string()],
+                custom: vec![],
+            },
+            StopwordsInterface::Set(set) => StopwordsSet {
+                languages: set
+                    .languages
+                    .into_iter()
+                    .flatten()
+                    .map(|language| language.to_string())
+                    .collect(),
+                custom: set.custom.into_iter().flatten().collect(),
+            },
+        }
+    }
+}
+
+impl TryFrom<StopwordsSet> for StopwordsInterface {
+    type Error = Status;
+    fn try_from(stopwords: StopwordsSet) -> Result<Self, Self::Error> {
+        let StopwordsSet { languages, custom } = stopwords;
+        let languages = languages
+            .iter()
+            .map(|language| {
+                Language::from_str(language).map_err(|_| {
+                    Status::invalid_argument(format!("unknown stopwords language: {language}"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Keep the short form, so that the same params from REST and gRPC are equal
+        if let ([language], true) = (languages.as_slice(), custom.is_empty()) {
+            return Ok(StopwordsInterface::Language(*language));
+        }
+
+        Ok(StopwordsInterface::Set(
+            segment::data_types::index::StopwordsSet {
+                languages: (!languages.is_empty()).then(|| languages.into_iter().collect()),
+                custom: (!custom.is_empty()).then(|| custom.into_iter().collect()),
+            },
+        ))
+    }
+}
+
+impl From<segment::data_types::index::StemmingAlgorithm> for StemmingAlgorithm {
+    fn from(stemmer: segment::data_types::index::StemmingAlgorithm) -> Self {
+        let stemming_params = match stemmer {
+            segment::data_types::index::StemmingAlgorithm::Snowball(params) => {
+                StemmingParams::Snowball(SnowballParams {
+                    language: params.language.to_string(),
+                })
+            }
+        };
+        StemmingAlgorithm {
+            stemming_params: Some(stemming_params),
+        }
+    }
+}
+
+impl TryFrom<StemmingAlgorithm> for segment::data_types::index::StemmingAlgorithm {
+    type Error = Status;
+    fn try_from(stemmer: StemmingAlgorithm) -> Result<Self, Self::Error> {
+        match stemmer.stemming_params {
+            Some(StemmingParams::Snowball(params)) => {
+                let language = SnowballLanguage::from_str(&params.language).map_err(|_| {
+                    Status::invalid_argument(format!(
+                        "unknown snowball language: {}",
+                        params.language
+                    ))
+                })?;
+                Ok(segment::data_types::index::StemmingAlgorithm::Snowball(
+                    segment::data_types::index::SnowballParams {
+                        r#type: Snowball::Snowball,
+                        language,
+                    },
+                ))
+            }
+            None => Err(Status::invalid_argument("stemming params are missing")),
+        }
+    }
+}
+
 impl TryFrom<BoolIndexParams> for segment::data_types::index::BoolIndexParams {
     type Error = Status;
-    fn try_from(_params: BoolIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: BoolIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::BoolIndexParams {
             r#type: BoolIndexType::Bool,
+            on_disk: params.on_disk,
         })
     }
 }
 
 impl TryFrom<DatetimeIndexParams> for segment::data_types::index::DatetimeIndexParams {
     type Error = Status;
-    fn try_from(_params: DatetimeIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: DatetimeIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::DatetimeIndexParams {
             r#type: DatetimeIndexType::Datetime,
+            is_principal: params.is_principal,
+            on_disk: params.on_disk,
+        })
+    }
+}
+
+impl TryFrom<UuidIndexParams> for segment::data_types::index::UuidIndexParams {
+    type Error = Status;
+    fn try_from(params: UuidIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::index::UuidIndexParams {
+            r#type: UuidIndexType::Uuid,
+            is_tenant: params.is_tenant,
+            on_disk: params.on_disk,
         })
     }
 }
@@ -450,6 +605,9 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
             IndexParams::DatetimeIndexParams(p) => {
                 segment::types::PayloadSchemaParams::Datetime(p.try_into()?)
             }
+            IndexParams::UuidIndexParams(p) => {
+                segment::types::PayloadSchemaParams::Uuid(p.try_into()?)
+            }
         })
     }
 }
@@ -472,6 +630,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                 PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
                 PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
                 PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
+                PayloadSchemaType::Uuid => segment::types::PayloadSchemaType::Uuid,
                 PayloadSchemaType::UnknownType => {
                     return Err(Status::invalid_argument(
                         "Malformed payload schema".to_string(),
@@ -589,6 +748,26 @@ impl From<segment::types::QuantizationSearchParams> for QuantizationSearchParams
     }
 }
 
+impl From<AcornSearchParams> for segment::types::AcornSearchParams {
+    fn from(params: AcornSearchParams) -> Self {
+        Self {
+            enable: params
+                .enable
+                .unwrap_or(segment::types::default_acorn_enable()),
+            max_selectivity: params.max_selectivity,
+        }
+    }
+}
+
+impl From<segment::types::AcornSearchParams> for AcornSearchParams {
+    fn from(params: segment::types::AcornSearchParams) -> Self {
+        Self {
+            enable: Some(params.enable),
+            max_selectivity: params.max_selectivity,
+        }
+    }
+}
+
 impl From<SearchParams> for segment::types::SearchParams {
     fn from(params: SearchParams) -> Self {
         Self {
@@ -596,6 +775,8 @@ impl From<SearchParams> for segment::types::SearchParams {
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
+            dimensions: params.dimensions.map(|x| x as usize),
+            acorn: params.acorn.map(|a| a.into()),
         }
     }
 }
@@ -607,6 +788,8 @@ impl From<segment::types::SearchParams> for SearchParams {
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
+            dimensions: params.dimensions.map(|x| x as u64),
+            acorn: params.acorn.map(|a| a.into()),
         }
     }
 }
@@ -629,6 +812,7 @@ impl From<segment_vectors::Vector> for Vector {
                 data: vector,
                 indices: None,
                 vectors_count: None,
+                packed_bits: None,
             },
             segment_vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
@@ -636,6 +820,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.indices,
                 }),
                 vectors_count: None,
+                packed_bits: None,
             },
             segment_vectors::Vector::MultiDense(vector) => {
                 let vector_count = vector.multi_vectors().count() as u32;
@@ -643,6 +828,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.flattened_vectors,
                     indices: None,
                     vectors_count: Some(vector_count),
+                    packed_bits: None,
                 }
             }
         }
@@ -653,6 +839,17 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     type Error = Status;
 
     fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        // packed bit vector
+        if let Some(packed_bits) = vector.packed_bits {
+            let packed: Vec<_> = packed_bits
+                .into_iter()
+                .map(segment_vectors::PackedBits)
+                .collect();
+            return Ok(segment_vectors::Vector::Dense(
+                segment_vectors::PackedBits::unpack(&packed),
+            ));
+        }
+
         // sparse vector
         if let Some(indices) = vector.indices {
             return Ok(segment_vectors::Vector::Sparse(
@@ -759,6 +956,70 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
     }
 }
 
+impl From<segment::data_types::facets::FacetValue> for FacetValue {
+    fn from(value: segment::data_types::facets::FacetValue) -> Self {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = match value {
+            segment::FacetValue::Keyword(value) => Variant::StringValue(value),
+            segment::FacetValue::Int(value) => Variant::IntegerValue(value),
+            segment::FacetValue::Bool(value) => Variant::BoolValue(value),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::data_types::facets::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("FacetValue should have a variant"))?;
+
+        let value = match variant {
+            Variant::StringValue(value) => segment::FacetValue::Keyword(value),
+            Variant::IntegerValue(value) => segment::FacetValue::Int(value),
+            Variant::BoolValue(value) => segment::FacetValue::Bool(value),
+        };
+
+        Ok(value)
+    }
+}
+
+impl From<segment::data_types::facets::FacetValueHit> for FacetHit {
+    fn from(hit: segment::data_types::facets::FacetValueHit) -> Self {
+        Self {
+            value: Some(hit.value.into()),
+            count: hit.count as u64,
+        }
+    }
+}
+
+impl TryFrom<FacetHit> for segment::data_types::facets::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(hit: FacetHit) -> Result<Self, Self::Error> {
+        let value = hit
+            .value
+            .ok_or_else(|| Status::invalid_argument("FacetHit should have a value"))?;
+
+        Ok(Self {
+            value: value.try_into()?,
+            count: hit.count as usize,
+        })
+    }
+}
+
 impl From<segment::types::ScoredPoint> for ScoredPoint {
     fn from(point: segment::types::ScoredPoint) -> Self {
         Self {
@@ -817,8 +1078,18 @@ impl TryFrom<Vectors> for segment_vectors::VectorStructInternal {
                         data,
                         indices,
                         vectors_count,
+                        packed_bits,
                     } = vector;
 
+                    if let Some(packed_bits) = packed_bits {
+                        let packed: Vec<_> = packed_bits
+                            .into_iter()
+                            .map(segment_vectors::PackedBits)
+                            .collect();
+                        return Ok(segment_vectors::VectorStructInternal::Single(
+                            segment_vectors::PackedBits::unpack(&packed),
+                        ));
+                    }
                     if indices.is_some() {
                         return Err(Status::invalid_argument(
                             "Sparse vector must be named".to_string(),
@@ -902,6 +1173,9 @@ impl From<segment::types::ScalarQuantization> for ScalarQuantization {
                 segment::types::ScalarType::Int8 => {
                     crate::grpc::qdrant::QuantizationType::Int8 as i32
                 }
+                segment::types::ScalarType::Int4 => {
+                    crate::grpc::qdrant::QuantizationType::Int4 as i32
+                }
             },
             quantile: config.quantile,
             always_ram: config.always_ram,
@@ -917,6 +1191,7 @@ impl TryFrom<ScalarQuantization> for segment::types::ScalarQuantization {
             scalar: segment::types::ScalarQuantizationConfig {
                 r#type: match QuantizationType::from_i32(value.r#type) {
                     Some(QuantizationType::Int8) => segment::types::ScalarType::Int8,
+                    Some(QuantizationType::Int4) => segment::types::ScalarType::Int4,
                     Some(QuantizationType::UnknownQuantization) | None => {
                         return Err(Status::invalid_argument("Unknown quantization type"));
                     }
@@ -973,6 +1248,12 @@ impl From<segment::types::BinaryQuantization> for BinaryQuantization {
         let config = value.binary;
         BinaryQuantization {
             always_ram: config.always_ram,
+            encoding: config
+                .encoding
+                .map(|encoding| BinaryQuantizationEncoding::from(encoding) as i32),
+            query_encoding: config
+                .query_encoding
+                .map(|encoding| BinaryQuantizationQueryEncoding::from(encoding) as i32),
         }
     }
 }
@@ -984,11 +1265,97 @@ impl TryFrom<BinaryQuantization> for segment::types::BinaryQuantization {
         Ok(segment::types::BinaryQuantization {
             binary: segment::types::BinaryQuantizationConfig {
                 always_ram: value.always_ram,
+                encoding: value
+                    .encoding
+                    .map(
+                        |encoding| match BinaryQuantizationEncoding::from_i32(encoding) {
+                            Some(encoding) => Ok(encoding.into()),
+                            None => Err(Status::invalid_argument(
+                                "Unknown binary quantization encoding".to_string(),
+                            )),
+                        },
+                    )
+                    .transpose()?,
+                query_encoding: value
+                    .query_encoding
+                    .map(
+                        |encoding| match BinaryQuantizationQueryEncoding::from_i32(encoding) {
+                            Some(encoding) => Ok(encoding.into()),
+                            None => Err(Status::invalid_argument(
+                                "Unknown binary quantization query encoding".to_string(),
+                            )),
+                        },
+                    )
+                    .transpose()?,
             },
         })
     }
 }
 
+impl From<segment::types::BinaryQuantizationEncoding> for BinaryQuantizationEncoding {
+    fn from(value: segment::types::BinaryQuantizationEncoding) -> Self {
+        match value {
+            segment::types::BinaryQuantizationEncoding::OneBit => {
+                BinaryQuantizationEncoding::OneBit
+            }
+            segment::types::BinaryQuantizationEncoding::OneAndHalfBits => {
+                BinaryQuantizationEncoding::OneAndHalfBits
+            }
+            segment::types::BinaryQuantizationEncoding::TwoBits => {
+                BinaryQuantizationEncoding::TwoBits
+            }
+        }
+    }
+}
+
+impl From<BinaryQuantizationEncoding> for segment::types::BinaryQuantizationEncoding {
+    fn from(value: BinaryQuantizationEncoding) -> Self {
+        match value {
+            BinaryQuantizationEncoding::OneBit => {
+                segment::types::BinaryQuantizationEncoding::OneBit
+            }
+            BinaryQuantizationEncoding::OneAndHalfBits => {
+                segment::types::BinaryQuantizationEncoding::OneAndHalfBits
+            }
+            BinaryQuantizationEncoding::TwoBits => {
+                segment::types::BinaryQuantizationEncoding::TwoBits
+            }
+        }
+    }
+}
+
+impl From<segment::types::BinaryQuantizationQueryEncoding> for BinaryQuantizationQueryEncoding {
+    fn from(value: segment::types::BinaryQuantizationQueryEncoding) -> Self {
+        match value {
+            segment::types::BinaryQuantizationQueryEncoding::Binary => {
+                BinaryQuantizationQueryEncoding::Binary
+            }
+            segment::types::BinaryQuantizationQueryEncoding::Scalar4Bits => {
+                BinaryQuantizationQueryEncoding::Scalar4Bits
+            }
+            segment::types::BinaryQuantizationQueryEncoding::Scalar8Bits => {
+                BinaryQuantizationQueryEncoding::Scalar8Bits
+            }
+        }
+    }
+}
+
+impl From<BinaryQuantizationQueryEncoding> for segment::types::BinaryQuantizationQueryEncoding {
+    fn from(value: BinaryQuantizationQueryEncoding) -> Self {
+        match value {
+            BinaryQuantizationQueryEncoding::Binary => {
+                segment::types::BinaryQuantizationQueryEncoding::Binary
+            }
+            BinaryQuantizationQueryEncoding::Scalar4Bits => {
+                segment::types::BinaryQuantizationQueryEncoding::Scalar4Bits
+            }
+            BinaryQuantizationQueryEncoding::Scalar8Bits => {
+                segment::types::BinaryQuantizationQueryEncoding::Scalar8Bits
+            }
+        }
+    }
+}
+
 impl From<segment::types::QuantizationConfig> for QuantizationConfig {
     fn from(value: segment::types::QuantizationConfig) -> Self {
         match value {
@@ -1036,6 +1403,49 @@ impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
     fn from(value: segment::types::MultiVectorConfig) -> Self {
         Self {
             comparator: MultiVectorComparator::from(value.comparator) as i32,
+            pooling: value.pooling.map(MultiVectorPooling::from),
+        }
+    }
+}
+
+impl From<segment::types::MultiVectorPooling> for MultiVectorPooling {
+    fn from(value: segment::types::MultiVectorPooling) -> Self {
+        use super::qdrant::multi_vector_pooling::Pooling;
+        let pooling = match value {
+            segment::types::MultiVectorPooling::TargetCount(target_count) => {
+                Pooling::TargetCount(target_count.get() as u64)
+            }
+            segment::types::MultiVectorPooling::PoolFactor(pool_factor) => {
+                Pooling::PoolFactor(pool_factor.get() as u64)
+            }
+        };
+        Self {
+            pooling: Some(pooling),
+        }
+    }
+}
+
+impl TryFrom<MultiVectorPooling> for segment::types::MultiVectorPooling {
+    type Error = Status;
+
+    fn try_from(value: MultiVectorPooling) -> Result<Self, Self::Error> {
+        use super::qdrant::multi_vector_pooling::Pooling;
+        let non_zero = |value: u64, name: &str| {
+            NonZeroUsize::new(value as usize)
+                .ok_or_else(|| Status::invalid_argument(format!("{name} must be greater than 0")))
+        };
+        match value.pooling {
+            Some(Pooling::TargetCount(target_count)) => Ok(Self::TargetCount(non_zero(
+                target_count,
+                "Pooling target_count",
+            )?)),
+            Some(Pooling::PoolFactor(pool_factor)) => Ok(Self::PoolFactor(non_zero(
+                pool_factor,
+                "Pooling pool_factor",
+            )?)),
+            None => Err(Status::invalid_argument(
+                "Malformed MultiVectorPooling type",
+            )),
         }
     }
 }
@@ -1044,6 +1454,9 @@ impl From<segment::types::MultiVectorComparator> for MultiVectorComparator {
     fn from(value: segment::types::MultiVectorComparator) -> Self {
         match value {
             segment::types::MultiVectorComparator::MaxSim => MultiVectorComparator::MaxSim,
+            segment::types::MultiVectorComparator::MinSim => MultiVectorComparator::MinSim,
+            segment::types::MultiVectorComparator::AvgSim => MultiVectorComparator::AvgSim,
+            segment::types::MultiVectorComparator::Chamfer => MultiVectorComparator::Chamfer,
         }
     }
 }
@@ -1056,6 +1469,7 @@ impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
             .ok_or_else(|| Status::invalid_argument("Unknown multi vector comparator"))?;
         Ok(segment::types::MultiVectorConfig {
             comparator: segment::types::MultiVectorComparator::from(comparator),
+            pooling: value.pooling.map(TryInto::try_into).transpose()?,
         })
     }
 }
@@ -1064,6 +1478,9 @@ impl From<MultiVectorComparator> for segment::types::MultiVectorComparator {
     fn from(value: MultiVectorComparator) -> Self {
         match value {
             MultiVectorComparator::MaxSim => segment::types::MultiVectorComparator::MaxSim,
+            MultiVectorComparator::MinSim => segment::types::MultiVectorComparator::MinSim,
+            MultiVectorComparator::AvgSim => segment::types::MultiVectorComparator::AvgSim,
+            MultiVectorComparator::Chamfer => segment::types::MultiVectorComparator::Chamfer,
         }
     }
 }
@@ -1556,6 +1973,9 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::ExceptKeywords(ints) => {
                     segment::types::Match::Except(ints.strings.into())
                 }
+                MatchValue::Phrase(MatchPhrase { phrase, slop }) => {
+                    segment::types::Match::Phrase(segment::types::MatchPhrase { phrase, slop })
+                }
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1573,6 +1993,9 @@ impl From<segment::types::Match> for Match {
             segment::types::Match::Text(segment::types::MatchText { text }) => {
                 MatchValue::Text(text)
             }
+            segment::types::Match::Phrase(segment::types::MatchPhrase { phrase, slop }) => {
+                MatchValue::Phrase(MatchPhrase { phrase, slop })
+            }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Keywords(strings) => {
                     let strings = strings.into_iter().collect();
@@ -1685,6 +2108,28 @@ impl From<segment::data_types::order_by::StartFrom> for StartFrom {
     }
 }
 
+impl TryFrom<TextQuery> for segment::data_types::text_query::TextQuery {
+    type Error = Status;
+
+    fn try_from(value: TextQuery) -> Result<Self, Self::Error> {
+        let TextQuery { key, query } = value;
+        Ok(Self {
+            key: json_path_from_proto(&key)?,
+            query,
+        })
+    }
+}
+
+impl From<segment::data_types::text_query::TextQuery> for TextQuery {
+    fn from(value: segment::data_types::text_query::TextQuery) -> Self {
+        let segment::data_types::text_query::TextQuery { key, query } = value;
+        Self {
+            key: key.to_string(),
+            query,
+        }
+    }
+}
+
 impl From<HnswConfigDiff> for segment::types::HnswConfig {
     fn from(hnsw_config: HnswConfigDiff) -> Self {
         Self {
@@ -1694,6 +2139,7 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            compressed_links: hnsw_config.compressed_links,
         }
     }
 }
@@ -1731,6 +2177,8 @@ impl TryFrom<Distance> for segment::types::Distance {
             Distance::Euclid => segment::types::Distance::Euclid,
             Distance::Dot => segment::types::Distance::Dot,
             Distance::Manhattan => segment::types::Distance::Manhattan,
+            Distance::Hamming => segment::types::Distance::Hamming,
+            Distance::Jaccard => segment::types::Distance::Jaccard,
         })
     }
 }
@@ -1796,6 +2244,7 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            unmatched_points: res.unmatched_points,
         }
     }
 }
@@ -1807,6 +2256,7 @@ impl From<UpdateResult> for UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status,
             clock_tag: None,
+            unmatched_points: res.unmatched_points,
         }
     }
 }
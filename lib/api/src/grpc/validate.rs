@@ -228,6 +228,19 @@ impl Validate for grpc::FieldCondition {
 
 impl Validate for grpc::Vector {
     fn validate(&self) -> Result<(), ValidationErrors> {
+        if self.packed_bits.is_some()
+            && (self.indices.is_some() || self.vectors_count.is_some() || !self.data.is_empty())
+        {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "packed_bits",
+                ValidationError::new(
+                    "`packed_bits` cannot be combined with `data`, `indices` or `vectors_count`",
+                ),
+            );
+            return Err(errors);
+        }
+
         match (&self.indices, self.vectors_count) {
             (Some(_), Some(_)) => {
                 let mut errors = ValidationErrors::new();
@@ -279,6 +292,11 @@ pub fn validate_u32_range_min_1(value: &Option<u32>) -> Result<(), ValidationErr
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
 }
 
+/// Validate the value is in `[2, ]` or `None`.
+pub fn validate_u64_range_min_2(value: &Option<u64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(2), None))
+}
+
 /// Validate the value is in `[100, ]` or `None`.
 pub fn validate_u64_range_min_100(value: &Option<u64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(100), None))
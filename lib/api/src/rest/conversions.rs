@@ -1,5 +1,5 @@
 use segment::data_types::order_by::OrderBy;
-use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::data_types::vectors::{PackedBits, DEFAULT_VECTOR_NAME};
 
 use super::schema::{BatchVectorStruct, ScoredPoint, Vector, VectorStruct};
 use super::{NearestQuery, OrderByInterface, Query, QueryInterface};
@@ -29,6 +29,16 @@ impl From<Vector> for segment::data_types::vectors::Vector {
                     segment::data_types::vectors::MultiDenseVectorInternal::new_unchecked(vector),
                 )
             }
+            // bit vectors are stored packed, but travel as dense 0/1 vectors internally
+            Vector::Bits(bits) => segment::data_types::vectors::Vector::Dense(
+                bits.into_iter()
+                    .map(|bit| if bit { 1.0 } else { 0.0 })
+                    .collect(),
+            ),
+            Vector::PackedBits(vector) => {
+                let packed: Vec<_> = vector.packed_bits.into_iter().map(PackedBits).collect();
+                segment::data_types::vectors::Vector::Dense(PackedBits::unpack(&packed))
+            }
         }
     }
 }
@@ -226,7 +236,10 @@ impl From<OrderByInterface> for OrderBy {
 impl From<QueryInterface> for Query {
     fn from(value: QueryInterface) -> Self {
         match value {
-            QueryInterface::Nearest(vector) => Query::Nearest(NearestQuery { nearest: vector }),
+            QueryInterface::Nearest(vector) => Query::Nearest(NearestQuery {
+                nearest: vector,
+                mmr: None,
+            }),
             QueryInterface::Query(query) => query,
         }
     }
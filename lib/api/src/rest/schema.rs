@@ -5,9 +5,10 @@ use common::types::ScoreType;
 use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQuery;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Condition, FieldCondition, Filter, Match, Payload, SearchParams, ShardKey,
+    Condition, FieldCondition, Filter, GeoPoint, Match, Payload, SearchParams, ShardKey,
     WithPayloadInterface, WithVector,
 };
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,15 @@ pub enum Vector {
     Dense(DenseVector),
     Sparse(sparse::common::sparse_vector::SparseVector),
     MultiDense(MultiDenseVector),
+    /// Binary vector for `bit` datatype, one boolean per dimension
+    Bits(Vec<bool>),
+    PackedBits(PackedBitsVector),
+}
+
+/// Binary vector for `bit` datatype, packed 8 dimensions per byte, most significant bit first
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct PackedBitsVector {
+    pub packed_bits: Vec<u8>,
 }
 
 /// Full vector data per point separator with single and multiple vector modes
@@ -47,6 +57,8 @@ impl VectorStruct {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
                 Vector::MultiDense(vector) => vector.is_empty(),
+                Vector::Bits(vector) => vector.is_empty(),
+                Vector::PackedBits(vector) => vector.packed_bits.is_empty(),
             }),
         }
     }
@@ -194,11 +206,31 @@ fn fusion_example() -> Fusion {
 /// Fusion algorithm allows to combine results of multiple prefetches.
 /// Available fusion algorithms:
 /// * `rrf` - Rank Reciprocal Fusion
+/// * `dbsf` - Distribution-Based Score Fusion
+/// * `weighted_sum` - Weighted sum of the min-max normalized scores of each prefetch
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[schemars(example = "fusion_example")]
 pub enum Fusion {
     Rrf,
+    Dbsf,
+    WeightedSum(WeightedSumFusion),
+}
+
+/// Sampling method.
+/// Available sampling methods:
+/// * `random` - Uniformly random points
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Sample {
+    Random,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WeightedSumFusion {
+    /// Multiplier for the normalized scores of each prefetch, in the same order as the prefetches.
+    pub weights: Vec<f32>,
 }
 
 fn multi_dense_vector_example() -> MultiDenseVector {
@@ -284,6 +316,8 @@ fn query_request_example() -> QueryRequest {
                 exact: false,
                 quantization: None,
                 indexed_only: false,
+                dimensions: None,
+                acorn: None,
             }),
             score_threshold: Some(0.25),
             limit: Some(10),
@@ -327,6 +361,8 @@ fn query_request_batch_example() -> QueryRequestBatch {
                         exact: false,
                         quantization: None,
                         indexed_only: false,
+                        dimensions: None,
+                        acorn: None,
                     }),
                     score_threshold: Some(0.25),
                     limit: Some(10),
@@ -416,11 +452,21 @@ pub enum Query {
 
     /// Fuse the results of multiple prefetches.
     Fusion(FusionQuery),
+
+    /// Score the results of the prefetches with a custom formula.
+    Formula(FormulaQuery),
+
+    /// Score points by BM25 relevance of the query text to a full-text indexed field.
+    Text(TextSearchQuery),
+
+    /// Sample points randomly.
+    Sample(SampleQuery),
 }
 
 fn nearest_query_example() -> NearestQuery {
     NearestQuery {
         nearest: VectorInput::DenseVector(vec![0.875, 0.140625, -0.15625, 0.96875]),
+        mmr: None,
     }
 }
 
@@ -429,6 +475,28 @@ fn nearest_query_example() -> NearestQuery {
 #[schemars(example = "nearest_query_example")]
 pub struct NearestQuery {
     pub nearest: VectorInput,
+
+    /// Perform MMR (Maximal Marginal Relevance) re-ranking of the nearest candidates,
+    /// using the same vector to calculate relevance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmr: Option<Mmr>,
+}
+
+/// Maximal Marginal Relevance (MMR) re-ranking of the candidates.
+///
+/// Points are selected greedily, each time taking the candidate with the best balance between
+/// similarity to the query and dissimilarity to the already selected points.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct Mmr {
+    /// Balance between relevance to the query (0.0) and diversity of the results (1.0).
+    /// Must be within [0, 1]. Default is 0.5
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub diversity: Option<f32>,
+
+    /// Number of nearest candidates to re-rank. Must be at least `limit` + `offset`.
+    /// Default is `limit` + `offset`
+    pub candidates_limit: Option<usize>,
 }
 
 fn recommend_query_example() -> RecommendQuery {
@@ -496,6 +564,237 @@ pub struct FusionQuery {
     pub fusion: Fusion,
 }
 
+fn sample_query_example() -> SampleQuery {
+    SampleQuery {
+        sample: Sample::Random,
+        seed: None,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(example = "sample_query_example")]
+pub struct SampleQuery {
+    pub sample: Sample,
+
+    /// Seed of the random sampling.
+    /// The same seed gives the same points as long as the collection is not changed.
+    /// If not set, a different sample is returned each time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+fn formula_query_example() -> FormulaQuery {
+    FormulaQuery {
+        formula: Expression::Sum(SumExpression {
+            sum: vec![
+                Expression::Variable("$score".to_string()),
+                Expression::Mult(MultExpression {
+                    mult: vec![
+                        Expression::Constant(0.5),
+                        Expression::Condition(Box::new(Condition::Field(
+                            FieldCondition::new_match(
+                                JsonPath::from_str("tag").unwrap(),
+                                Match::from("h1".to_string()),
+                            ),
+                        ))),
+                    ],
+                }),
+            ],
+        }),
+        defaults: HashMap::new(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(example = "formula_query_example")]
+pub struct FormulaQuery {
+    /// Expression to compute the score of each point.
+    pub formula: Expression,
+
+    /// Values to use for variables which are missing in the payload or in the prefetch results.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub defaults: HashMap<String, serde_json::Value>,
+}
+
+fn text_search_query_example() -> TextSearchQuery {
+    TextSearchQuery {
+        text: TextQuery {
+            key: JsonPath::from_str("description").unwrap(),
+            query: "quick brown fox".to_string(),
+        },
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(example = "text_search_query_example")]
+pub struct TextSearchQuery {
+    pub text: TextQuery,
+}
+
+/// Expression to compute the score of each point in a formula query.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Expression {
+    /// A constant number.
+    Constant(f32),
+    /// Payload key or reference to a prefetch score.
+    ///
+    /// `$score` refers to the score of the first prefetch, `$score[i]` to the score of the i-th prefetch.
+    Variable(String),
+    Mult(MultExpression),
+    Sum(SumExpression),
+    Neg(NegExpression),
+    Abs(AbsExpression),
+    Div(DivExpression),
+    Sqrt(SqrtExpression),
+    Pow(PowExpression),
+    Exp(ExpExpression),
+    Log10(Log10Expression),
+    Ln(LnExpression),
+    LinDecay(LinDecayExpression),
+    ExpDecay(ExpDecayExpression),
+    GaussDecay(GaussDecayExpression),
+    GeoDistance(GeoDistance),
+    Datetime(DatetimeExpression),
+    DatetimeKey(DatetimeKeyExpression),
+    /// Payload condition. Evaluates to 1.0 if the condition matches, 0.0 otherwise.
+    Condition(Box<Condition>),
+}
+
+/// Multiplies all the expressions.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MultExpression {
+    pub mult: Vec<Expression>,
+}
+
+/// Sums all the expressions.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SumExpression {
+    pub sum: Vec<Expression>,
+}
+
+/// Negates the expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NegExpression {
+    pub neg: Box<Expression>,
+}
+
+/// Absolute value of the expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AbsExpression {
+    pub abs: Box<Expression>,
+}
+
+/// Divides the left expression by the right expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DivExpression {
+    pub div: DivParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DivParams {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Value to use when the right expression evaluates to zero. If missing, the division fails.
+    pub by_zero_default: Option<ScoreType>,
+}
+
+/// Square root of the expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SqrtExpression {
+    pub sqrt: Box<Expression>,
+}
+
+/// Raises the base to the power of the exponent.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PowExpression {
+    pub pow: PowParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PowParams {
+    pub base: Box<Expression>,
+    pub exponent: Box<Expression>,
+}
+
+/// Raises e to the power of the expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExpExpression {
+    pub exp: Box<Expression>,
+}
+
+/// Base 10 logarithm of the expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Log10Expression {
+    pub log10: Box<Expression>,
+}
+
+/// Natural logarithm of the expression.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LnExpression {
+    pub ln: Box<Expression>,
+}
+
+/// Linear decay: `max(0, 1 - (1 - midpoint) * |x - target| / scale)`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LinDecayExpression {
+    pub lin_decay: DecayParamsExpression,
+}
+
+/// Exponential decay: `midpoint ^ (|x - target| / scale)`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExpDecayExpression {
+    pub exp_decay: DecayParamsExpression,
+}
+
+/// Gaussian decay: `midpoint ^ ((x - target) / scale) ^ 2`
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GaussDecayExpression {
+    pub gauss_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DecayParamsExpression {
+    /// The variable to decay
+    pub x: Box<Expression>,
+    /// The target value, where the decay function evaluates to 1.0. Default is 0.0
+    pub target: Option<Box<Expression>>,
+    /// The distance from the target at which the decay function evaluates to `midpoint`. Must be positive. Default is 1.0
+    pub scale: Option<f32>,
+    /// The value of the decay function at `scale` distance from the target. Must be within (0, 1). Default is 0.5
+    pub midpoint: Option<f32>,
+}
+
+/// Haversine distance, in meters, between the origin and the geo point in the payload.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeoDistance {
+    pub geo_distance: GeoDistanceParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeoDistanceParams {
+    /// The origin geo point to measure from
+    pub origin: GeoPoint,
+    /// Payload field with the destination geo point
+    pub to: JsonPath,
+}
+
+/// A constant datetime, evaluated as seconds since the Unix epoch.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DatetimeExpression {
+    /// RFC 3339 datetime
+    pub datetime: String,
+}
+
+/// A datetime payload field, evaluated as seconds since the Unix epoch.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DatetimeKeyExpression {
+    pub datetime_key: JsonPath,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct Prefetch {
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
@@ -4,6 +4,7 @@ use validator::{Validate, ValidationError};
 use super::schema::{BatchVectorStruct, Vector, VectorStruct};
 use super::{
     ContextInput, Fusion, OrderByInterface, Query, QueryInterface, RecommendInput, VectorInput,
+    WeightedSumFusion,
 };
 use crate::rest::NamedVectorStruct;
 
@@ -40,6 +41,7 @@ impl Validate for Vector {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
+            Vector::Bits(_) | Vector::PackedBits(_) => Ok(()),
         }
     }
 }
@@ -66,12 +68,18 @@ impl Validate for QueryInterface {
 impl Validate for Query {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
-            Query::Nearest(vector) => vector.nearest.validate(),
+            Query::Nearest(nearest) => {
+                nearest.nearest.validate()?;
+                nearest.mmr.as_ref().map_or(Ok(()), Validate::validate)
+            }
             Query::Recommend(recommend) => recommend.recommend.validate(),
             Query::Discover(discover) => discover.discover.validate(),
             Query::Context(context) => context.context.validate(),
             Query::Fusion(fusion) => fusion.fusion.validate(),
             Query::OrderBy(order_by) => order_by.order_by.validate(),
+            Query::Formula(_formula) => Ok(()), // validated during parsing
+            Query::Text(text) => text.text.validate(),
+            Query::Sample(_sample) => Ok(()),
         }
     }
 }
@@ -124,8 +132,30 @@ impl Validate for ContextInput {
 impl Validate for Fusion {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
-            Fusion::Rrf => Ok(()),
+            Fusion::Rrf | Fusion::Dbsf => Ok(()),
+            Fusion::WeightedSum(weighted_sum) => weighted_sum.validate(),
+        }
+    }
+}
+
+impl Validate for WeightedSumFusion {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        if self.weights.is_empty() {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add(
+                "weights",
+                ValidationError::new("At least one weight must be provided"),
+            );
+            return Err(errors);
+        }
+
+        if self.weights.iter().any(|weight| !weight.is_finite()) {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add("weights", ValidationError::new("Weights must be finite"));
+            return Err(errors);
         }
+
+        Ok(())
     }
 }
 
@@ -277,6 +277,13 @@ impl Collection {
                     .and_modify(|info_schema| info_schema.points += response_schema.points)
                     .or_insert(response_schema);
             }
+
+            for (name, response_pooling) in response.multivector_pooling {
+                info.multivector_pooling
+                    .entry(name)
+                    .and_modify(|info_pooling| info_pooling.merge(response_pooling))
+                    .or_insert(response_pooling);
+            }
         }
 
         // Do not display vectors count, as it is an approximate number
@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use common::types::ScoreType;
+use itertools::Itertools;
+use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{
+    Condition, Filter, HasIdCondition, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+};
+use tokio::time::Instant;
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, SearchMatrixOffsetsResponse,
+    SearchMatrixPair, SearchMatrixPairsResponse, SearchMatrixRequestInternal,
+    DEFAULT_SEARCH_MATRIX_LIMIT, DEFAULT_SEARCH_MATRIX_SAMPLE,
+};
+use crate::operations::universal_query::shard_query::{
+    SampleInternal, ScoringQuery, ShardQueryRequest,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionSearchMatrixRequest {
+    /// Number of points to sample
+    pub sample_size: usize,
+    /// Number of nearest neighbours to find for each sampled point
+    pub limit_per_sample: usize,
+    /// Sample only points which satisfy this filter
+    pub filter: Option<Filter>,
+    /// Name of the vector to compare the points by
+    pub using: String,
+}
+
+impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
+    fn from(request: SearchMatrixRequestInternal) -> Self {
+        let SearchMatrixRequestInternal {
+            filter,
+            sample,
+            limit,
+            using,
+        } = request;
+
+        Self {
+            sample_size: sample.unwrap_or(DEFAULT_SEARCH_MATRIX_SAMPLE),
+            limit_per_sample: limit.unwrap_or(DEFAULT_SEARCH_MATRIX_LIMIT),
+            filter,
+            using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+        }
+    }
+}
+
+/// Nearest neighbours of each sampled point, among the other sampled points
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionSearchMatrixResponse {
+    /// Sampled points, sorted by id
+    pub sample_ids: Vec<PointIdType>,
+    /// Nearest neighbours of each point of `sample_ids`, in the same order
+    pub nearests: Vec<Vec<ScoredPoint>>,
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        } = response;
+
+        let pairs = sample_ids
+            .into_iter()
+            .zip(nearests)
+            .flat_map(|(a, nearest)| {
+                nearest.into_iter().map(move |point| SearchMatrixPair {
+                    a,
+                    b: point.id,
+                    score: point.score,
+                })
+            })
+            .collect();
+
+        Self { pairs }
+    }
+}
+
+impl From<CollectionSearchMatrixResponse> for SearchMatrixOffsetsResponse {
+    fn from(response: CollectionSearchMatrixResponse) -> Self {
+        let CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        } = response;
+
+        let total = nearests.iter().map(Vec::len).sum();
+        let mut offsets_row = Vec::with_capacity(total);
+        let mut offsets_col = Vec::with_capacity(total);
+        let mut scores: Vec<ScoreType> = Vec::with_capacity(total);
+
+        for (row, nearest) in nearests.into_iter().enumerate() {
+            for point in nearest {
+                // Neighbours are always taken from the sample
+                let Ok(col) = sample_ids.binary_search(&point.id) else {
+                    continue;
+                };
+                offsets_row.push(row as u64);
+                offsets_col.push(col as u64);
+                scores.push(point.score);
+            }
+        }
+
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: sample_ids,
+        }
+    }
+}
+
+impl Collection {
+    /// Randomly sample points matching the filter, then find the nearest neighbours
+    /// of each of them among the sampled points.
+    pub async fn search_points_matrix(
+        &self,
+        request: CollectionSearchMatrixRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<CollectionSearchMatrixResponse> {
+        let CollectionSearchMatrixRequest {
+            sample_size,
+            limit_per_sample,
+            filter,
+            using,
+        } = request;
+
+        if sample_size == 0 || limit_per_sample == 0 {
+            return Ok(CollectionSearchMatrixResponse::default());
+        }
+
+        let start = Instant::now();
+
+        // Sample the points together with the vector to search by
+        let sample_request = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(SampleInternal::Random {
+                seed: rand::random(),
+            })),
+            filter,
+            score_threshold: None,
+            limit: sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Selector(vec![using.clone()]),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let sampled_points = self
+            .query(
+                sample_request,
+                read_consistency,
+                shard_selection.clone(),
+                timeout,
+            )
+            .await?;
+
+        // Points without the vector can't be compared with the others
+        let mut sampled_vectors: Vec<_> = sampled_points
+            .into_iter()
+            .filter_map(|point| {
+                let vector = point.vector.as_ref()?.get(&using)?.to_owned();
+                Some((point.id, vector))
+            })
+            .collect();
+
+        if sampled_vectors.len() < 2 {
+            return Ok(CollectionSearchMatrixResponse::default());
+        }
+
+        sampled_vectors.sort_unstable_by_key(|(id, _)| *id);
+
+        let sample_ids = sampled_vectors.iter().map(|(id, _)| *id).collect_vec();
+
+        // Every search is restricted to the sampled points
+        let sample_filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            sample_ids.iter().copied().collect::<HashSet<_>>(),
+        )));
+
+        let searches = sampled_vectors
+            .into_iter()
+            .map(|(_, vector)| CoreSearchRequest {
+                query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                    vector,
+                    using.clone(),
+                )),
+                filter: Some(sample_filter.clone()),
+                params: None,
+                // One more, as the point itself is found among its own neighbours
+                limit: limit_per_sample + 1,
+                offset: 0,
+                with_payload: None,
+                with_vector: None,
+                score_threshold: None,
+            })
+            .collect();
+
+        let timeout = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
+
+        let nearests = self
+            .core_search_batch(
+                CoreSearchRequestBatch { searches },
+                read_consistency,
+                shard_selection,
+                timeout,
+            )
+            .await?;
+
+        let nearests = sample_ids
+            .iter()
+            .zip(nearests)
+            .map(|(id, nearest)| {
+                nearest
+                    .into_iter()
+                    .filter(|point| point.id != *id)
+                    .take(limit_per_sample)
+                    .collect()
+            })
+            .collect();
+
+        Ok(CollectionSearchMatrixResponse {
+            sample_ids,
+            nearests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+
+    fn neighbour(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: ExtendedPointId::from(id),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    fn response() -> CollectionSearchMatrixResponse {
+        CollectionSearchMatrixResponse {
+            sample_ids: vec![1, 2, 3]
+                .into_iter()
+                .map(ExtendedPointId::from)
+                .collect(),
+            nearests: vec![
+                vec![neighbour(2, 0.9), neighbour(3, 0.5)],
+                vec![neighbour(1, 0.9)],
+                vec![neighbour(1, 0.5)],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_search_matrix_pairs() {
+        let SearchMatrixPairsResponse { pairs } = response().into();
+
+        let pairs = pairs
+            .into_iter()
+            .map(|SearchMatrixPair { a, b, score }| (a, b, score))
+            .collect_vec();
+        assert_eq!(
+            pairs,
+            vec![
+                (1.into(), 2.into(), 0.9),
+                (1.into(), 3.into(), 0.5),
+                (2.into(), 1.into(), 0.9),
+                (3.into(), 1.into(), 0.5),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_search_matrix_offsets() {
+        let SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids,
+        } = response().into();
+
+        assert_eq!(ids, response().sample_ids);
+        assert_eq!(offsets_row, vec![0, 0, 1, 2]);
+        assert_eq!(offsets_col, vec![1, 2, 0, 0]);
+        assert_eq!(scores, vec![0.9, 0.5, 0.9, 0.5]);
+    }
+}
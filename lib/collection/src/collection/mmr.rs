@@ -0,0 +1,204 @@
+use segment::data_types::vectors::{DenseVector, VectorRef, VectorStructInternal};
+use segment::types::{Distance, ScoredPoint, WithVector};
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::shard_query::MmrInternal;
+
+/// Re-rank the nearest candidates with Maximal Marginal Relevance.
+///
+/// Points are selected greedily, each time taking the candidate with the best
+/// `lambda * sim(query, candidate) - (1 - lambda) * max(sim(candidate, selected))`.
+/// The first point is always the most relevant one.
+///
+/// Scores are kept as returned by the nearest search, only the order of the points changes.
+pub fn mmr_rerank(
+    candidates: Vec<ScoredPoint>,
+    mmr: &MmrInternal,
+    distance: Distance,
+    limit: usize,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    let MmrInternal {
+        vector,
+        using,
+        lambda,
+        candidates_limit: _,
+    } = mmr;
+
+    let query = match VectorRef::from(vector) {
+        VectorRef::Dense(query) => distance.preprocess_vector(query.to_vec()),
+        VectorRef::Sparse(_) | VectorRef::MultiDense(_) => {
+            return Err(CollectionError::bad_request(
+                "MMR is only supported for dense vectors",
+            ))
+        }
+    };
+
+    let vectors: Vec<DenseVector> = candidates
+        .iter()
+        .map(
+            |point| match point.vector.as_ref().and_then(|v| v.get(using)) {
+                Some(VectorRef::Dense(vector)) => Ok(distance.preprocess_vector(vector.to_vec())),
+                _ => Err(CollectionError::service_error(format!(
+                    "MMR candidate {} has no dense vector {using}",
+                    point.id,
+                ))),
+            },
+        )
+        .collect::<CollectionResult<_>>()?;
+
+    let relevance: Vec<_> = vectors
+        .iter()
+        .map(|vector| distance.similarity(&query, vector))
+        .collect();
+
+    // Max similarity of each candidate to the already selected points
+    let mut max_similarity = vec![f32::NEG_INFINITY; vectors.len()];
+    let mut remaining: Vec<usize> = (0..vectors.len()).collect();
+    let mut selected: Vec<usize> = Vec::with_capacity(limit.min(vectors.len()));
+
+    while selected.len() < limit && !remaining.is_empty() {
+        if let Some(&last) = selected.last() {
+            for &idx in &remaining {
+                let similarity = distance.similarity(&vectors[idx], &vectors[last]);
+                max_similarity[idx] = max_similarity[idx].max(similarity);
+            }
+        }
+
+        let mmr_score = |idx: usize| {
+            if selected.is_empty() {
+                relevance[idx]
+            } else {
+                lambda * relevance[idx] - (1.0 - lambda) * max_similarity[idx]
+            }
+        };
+
+        // Ties are resolved in favor of the candidate ranked higher by the nearest search
+        let mut best_pos = 0;
+        let mut best_score = mmr_score(remaining[0]);
+        for (pos, &idx) in remaining.iter().enumerate().skip(1) {
+            let score = mmr_score(idx);
+            if score > best_score {
+                best_pos = pos;
+                best_score = score;
+            }
+        }
+
+        selected.push(remaining.remove(best_pos));
+    }
+
+    let mut candidates: Vec<_> = candidates.into_iter().map(Some).collect();
+    let reranked = selected
+        .into_iter()
+        .filter_map(|idx| candidates[idx].take())
+        .collect();
+
+    Ok(reranked)
+}
+
+/// Drop the `using` vector from the point, if it was fetched only for re-ranking
+pub fn strip_candidate_vector(point: &mut ScoredPoint, using: &str, with_vector: &WithVector) {
+    match with_vector {
+        WithVector::Bool(true) => {}
+        WithVector::Bool(false) => point.vector = None,
+        WithVector::Selector(names) => {
+            if names.iter().any(|name| name == using) {
+                return;
+            }
+            point.vector = match point.vector.take() {
+                Some(VectorStructInternal::Named(mut vectors)) => {
+                    vectors.remove(using);
+                    (!vectors.is_empty()).then_some(VectorStructInternal::Named(vectors))
+                }
+                // Single and multi-dense vectors are the default vector, which is the `using` one
+                Some(VectorStructInternal::Single(_) | VectorStructInternal::MultiDense(_))
+                | None => None,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::{Vector, DEFAULT_VECTOR_NAME};
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+
+    fn candidate(id: u64, vector: DenseVector, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: ExtendedPointId::from(id),
+            version: 0,
+            score,
+            payload: None,
+            vector: Some(VectorStructInternal::Single(vector)),
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    fn mmr(query: DenseVector, diversity: f32) -> MmrInternal {
+        MmrInternal {
+            vector: Vector::Dense(query),
+            using: DEFAULT_VECTOR_NAME.to_string(),
+            lambda: 1.0 - diversity,
+            candidates_limit: 10,
+        }
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<ExtendedPointId> {
+        points.iter().map(|point| point.id).collect()
+    }
+
+    #[test]
+    fn test_mmr_rerank() {
+        let query = vec![0.8, 0.6];
+        // Two near duplicates close to the query, and one point a bit further but different
+        let candidates = vec![
+            candidate(2, vec![1.0, 0.01], 0.806),
+            candidate(1, vec![1.0, 0.0], 0.8),
+            candidate(3, vec![0.0, 1.0], 0.6),
+        ];
+
+        // No diversity - same order as the nearest search
+        let reranked = mmr_rerank(
+            candidates.clone(),
+            &mmr(query.clone(), 0.0),
+            Distance::Dot,
+            3,
+        )
+        .unwrap();
+        assert_eq!(ids(&reranked), ids(&candidates));
+
+        // With diversity, the near duplicate is pushed down
+        let reranked = mmr_rerank(
+            candidates.clone(),
+            &mmr(query.clone(), 0.5),
+            Distance::Dot,
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            ids(&reranked),
+            vec![ExtendedPointId::from(2), ExtendedPointId::from(3)],
+        );
+        // Scores are not changed
+        assert_eq!(reranked[1].score, 0.6);
+
+        // Euclid distance is smaller-better, similarity is handled by the metric
+        let reranked = mmr_rerank(candidates, &mmr(query, 0.5), Distance::Euclid, 2).unwrap();
+        assert_eq!(
+            ids(&reranked),
+            vec![ExtendedPointId::from(2), ExtendedPointId::from(3)],
+        );
+    }
+
+    #[test]
+    fn test_strip_candidate_vector() {
+        let mut point = candidate(1, vec![1.0, 0.0], 1.0);
+        strip_candidate_vector(&mut point, DEFAULT_VECTOR_NAME, &WithVector::Bool(true));
+        assert!(point.vector.is_some());
+
+        strip_candidate_vector(&mut point, DEFAULT_VECTOR_NAME, &WithVector::Bool(false));
+        assert!(point.vector.is_none());
+    }
+}
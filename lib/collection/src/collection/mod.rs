@@ -1,4 +1,6 @@
 mod collection_ops;
+pub mod distance_matrix;
+mod mmr;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
@@ -1,9 +1,11 @@
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::{Direction, OrderBy};
 use segment::types::{Filter, ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
@@ -188,7 +190,14 @@ impl Collection {
             }
         } else {
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+            // Each shard reports its own points, which didn't match the `update_filter`
+            for shard_result in results {
+                result
+                    .unmatched_points
+                    .extend(shard_result?.unmatched_points);
+            }
+            Ok(result)
         }
     }
 
@@ -382,6 +391,53 @@ impl Collection {
         Ok(CountResult { count })
     }
 
+    /// Count points with each value of the facet field.
+    ///
+    /// Counts of the same values are summed up over all shards, and only then cut to the limit.
+    /// A value may be out of the top of some shard, so with several shards exact counts
+    /// are requested for all values, and estimations are over-fetched.
+    pub async fn facet(
+        &self,
+        mut request: FacetParams,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        if request.limit == 0 {
+            return Ok(FacetResponse::default());
+        }
+
+        merge_filters(
+            &mut request.filter,
+            self.shards_holder.read().await.resharding_filter(),
+        );
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let limit = request.limit;
+        if shards.len() > 1 {
+            request.limit = if request.exact {
+                usize::MAX
+            } else {
+                limit.saturating_mul(FACET_SHARD_OVERSAMPLING)
+            };
+        }
+        let request = Arc::new(request);
+        let requests = shards.into_iter().map(|(shard, _shard_key)| {
+            shard.facet(
+                request.clone(),
+                read_consistency,
+                shard_selection.is_shard_id(),
+                timeout,
+            )
+        });
+
+        let responses = future::try_join_all(requests).await?;
+
+        Ok(FacetResponse::merge(responses, limit))
+    }
+
     pub async fn retrieve(
         &self,
         request: PointRequestInternal,
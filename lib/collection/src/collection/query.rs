@@ -4,12 +4,12 @@ use std::time::Duration;
 
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::types::{Order, ScoredPoint};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::sync::RwLockReadGuard;
 use tokio::time::Instant;
 
+use super::mmr::{mmr_rerank, strip_candidate_vector};
 use super::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
@@ -22,7 +22,7 @@ use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+    ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
 
 struct IntermediateQueryInfo<'a> {
@@ -114,26 +114,36 @@ impl Collection {
                     .merge_intermediate_results_from_shards(request, shards_results)
                     .await?;
 
-                let result = if let Some(ScoringQuery::Fusion(fusion)) = &request.query {
-                    // If the root query is a Fusion, the returned results correspond to each the prefetches.
-                    let mut fused = match fusion {
-                        Fusion::Rrf => rrf_scoring(merged_intermediates),
-                    };
-                    if let Some(score_threshold) = request.score_threshold {
-                        fused = fused
-                            .into_iter()
-                            .take_while(|point| point.score >= score_threshold)
-                            .collect();
+                let result = match &request.query {
+                    Some(ScoringQuery::Fusion(fusion)) => {
+                        // If the root query is a Fusion, the returned results correspond to each the prefetches.
+                        let mut fused = fusion.fuse(merged_intermediates);
+                        if let Some(score_threshold) = request.score_threshold {
+                            fused = fused
+                                .into_iter()
+                                .take_while(|point| point.score >= score_threshold)
+                                .collect();
+                        }
+                        fused
+                    }
+                    Some(ScoringQuery::Mmr(mmr)) => {
+                        // Candidates of all shards are re-ranked together, so the diversification is global
+                        let candidates = pop_single_result(&mut merged_intermediates)?;
+                        let distance = self
+                            .collection_config
+                            .read()
+                            .await
+                            .params
+                            .get_distance(&mmr.using)?;
+                        let mut reranked =
+                            mmr_rerank(candidates, mmr, distance, request.offset + request.limit)?;
+                        for point in &mut reranked {
+                            strip_candidate_vector(point, &mmr.using, &request.with_vector);
+                        }
+                        reranked
                     }
-                    fused
-                } else {
                     // Otherwise, it will be a list with a single list of scored points.
-                    debug_assert_eq!(merged_intermediates.len(), 1);
-                    merged_intermediates.pop().ok_or_else(|| {
-                        CollectionError::service_error(
-                            "Query response was expected to have one list of results.",
-                        )
-                    })?
+                    _ => pop_single_result(&mut merged_intermediates)?,
                 };
 
                 let result: Vec<ScoredPoint> = result
@@ -329,6 +339,13 @@ impl Collection {
     }
 }
 
+fn pop_single_result(results: &mut ShardQueryResponse) -> CollectionResult<Vec<ScoredPoint>> {
+    debug_assert_eq!(results.len(), 1);
+    results.pop().ok_or_else(|| {
+        CollectionError::service_error("Query response was expected to have one list of results.")
+    })
+}
+
 /// Returns a list of the query that corresponds to each of the results in each shard.
 ///
 /// Example: `[info1, info2, info3]` corresponds to `[result1, result2, result3]` of each shard
@@ -351,9 +368,14 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
             .collect_vec()
     } else {
         // Otherwise, we expect the root result
+        let take = match &request.query {
+            // All the candidates are needed for re-ranking
+            Some(ScoringQuery::Mmr(mmr)) => mmr.candidates_limit,
+            _ => request.offset + request.limit,
+        };
         vec![IntermediateQueryInfo {
             scoring_query: request.query.as_ref(),
-            take: request.offset + request.limit,
+            take,
         }]
     }
 }
@@ -3,7 +3,7 @@ use segment::types::SeqNumberType;
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::collection_manager::segments_updater::*;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionResult, UpdateOutcome};
 use crate::operations::CollectionUpdateOperations;
 
 /// Implementation of the update operation
@@ -14,7 +14,7 @@ impl CollectionUpdater {
     fn handle_update_result(
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
-        operation_result: &CollectionResult<usize>,
+        operation_result: &CollectionResult<UpdateOutcome>,
     ) {
         match operation_result {
             Ok(_) => {
@@ -42,7 +42,7 @@ impl CollectionUpdater {
         segments: &RwLock<SegmentHolder>,
         op_num: SeqNumberType,
         operation: CollectionUpdateOperations,
-    ) -> CollectionResult<usize> {
+    ) -> CollectionResult<UpdateOutcome> {
         // Allow only one update at a time, ensure no data races between segments.
         // let _lock = self.update_lock.lock().unwrap();
         let operation_result = match operation {
@@ -50,13 +50,13 @@ impl CollectionUpdater {
                 process_point_operation(segments, op_num, point_operation)
             }
             CollectionUpdateOperations::VectorOperation(vector_operation) => {
-                process_vector_operation(segments, op_num, vector_operation)
+                process_vector_operation(segments, op_num, vector_operation).map(Into::into)
             }
             CollectionUpdateOperations::PayloadOperation(payload_operation) => {
                 process_payload_operation(segments, op_num, payload_operation)
             }
             CollectionUpdateOperations::FieldIndexOperation(index_operation) => {
-                process_field_index_operation(segments, op_num, &index_operation)
+                process_field_index_operation(segments, op_num, &index_operation).map(Into::into)
             }
         };
 
@@ -72,7 +72,7 @@ mod tests {
     use segment::data_types::vectors::{
         only_default_vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
     };
-    use segment::types::{Payload, WithPayload};
+    use segment::types::{Condition, FieldCondition, Filter, Payload, PointIdType, WithPayload};
     use serde_json::json;
     use tempfile::Builder;
 
@@ -81,7 +81,9 @@ mod tests {
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
     use crate::collection_manager::segments_updater::upsert_points;
     use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
-    use crate::operations::point_ops::{PointOperations, PointStruct};
+    use crate::operations::point_ops::{
+        ConditionalInsertOperationInternal, PointOperations, PointStruct,
+    };
 
     #[test]
     fn test_sync_ops() {
@@ -218,6 +220,7 @@ mod tests {
                 points: Some(points.clone()),
                 filter: None,
                 key: None,
+                update_filter: None,
             }),
         )
         .unwrap();
@@ -301,4 +304,79 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
     }
+
+    #[test]
+    fn test_conditional_ops() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        // Points 1 and 2 are red, point 3 is blue
+        let is_red = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color".parse().unwrap(),
+            "red".to_string().into(),
+        )));
+
+        let points = [1u64, 3, 500]
+            .into_iter()
+            .map(|id| PointStruct {
+                id: id.into(),
+                vector: VectorStructInternal::from(vec![2., 2., 2., 2.]).into(),
+                payload: Some(json!({ "color": "green" }).into()),
+            })
+            .collect_vec();
+
+        let outcome = process_point_operation(
+            &segments,
+            100,
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op: points.into(),
+                condition: is_red.clone(),
+            }),
+        )
+        .unwrap();
+
+        // Blue point is left untouched, new point is inserted regardless of the condition
+        assert_eq!(outcome.unmatched_points, vec![3.into()]);
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into(), 3.into(), 500.into()],
+            &WithPayload::from(true),
+            &false.into(),
+        )
+        .unwrap();
+        let color =
+            |id: u64| records[&PointIdType::from(id)].payload.clone().unwrap().0["color"].clone();
+        assert_eq!(color(1), json!("green"));
+        assert_eq!(color(3), json!(["blue"]));
+        assert_eq!(color(500), json!("green"));
+
+        // Point 1 is not red anymore
+        let outcome = process_payload_operation(
+            &segments,
+            101,
+            PayloadOps::SetPayload(SetPayloadOp {
+                payload: json!({ "size": 5 }).into(),
+                points: Some(vec![1.into(), 2.into()]),
+                filter: None,
+                key: None,
+                update_filter: Some(is_red),
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(outcome.affected_points, 1);
+        assert_eq!(outcome.unmatched_points, vec![1.into()]);
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into(), 2.into()],
+            &WithPayload::from(true),
+            &false.into(),
+        )
+        .unwrap();
+        let payload = |id: u64| records[&PointIdType::from(id)].payload.clone().unwrap();
+        assert!(!payload(1).contains_key("size"));
+        assert!(payload(2).contains_key("size"));
+    }
 }
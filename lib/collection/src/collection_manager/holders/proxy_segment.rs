@@ -1,15 +1,19 @@
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitVec;
-use common::types::{PointOffsetType, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::common::sampling::SampleKey;
+use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
@@ -663,6 +667,107 @@ impl SegmentEntry for ProxySegment {
         Ok(read_points)
     }
 
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<SampleKey> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_random_filtered(limit, filter, seed)
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_random_filtered(
+                limit,
+                Some(&wrapped_filter),
+                seed,
+            )
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_random_filtered(limit, filter, seed);
+        // Same point has the same key in both segments
+        read_points.append(&mut write_segment_points);
+        read_points.sort_unstable();
+        read_points.dedup();
+        read_points.truncate(limit);
+        read_points
+    }
+
+    fn read_text_scored<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        query: &'a TextQuery,
+        query_context: SegmentQueryContext,
+    ) -> OperationResult<Vec<(ScoreType, PointIdType)>> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().read_text_scored(
+                limit,
+                filter,
+                query,
+                query_context.clone(),
+            )?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment.get().read().read_text_scored(
+                limit,
+                Some(&wrapped_filter),
+                query,
+                query_context.clone(),
+            )?
+        };
+        let mut write_segment_points = self.write_segment.get().read().read_text_scored(
+            limit,
+            filter,
+            query,
+            query_context,
+        )?;
+        read_points.append(&mut write_segment_points);
+        read_points.sort_unstable_by(|(score_a, _), (score_b, _)| score_b.total_cmp(score_a));
+        Ok(read_points)
+    }
+
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let mut counts = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(request, is_stopped)?
+        } else {
+            let wrapped_request = FacetParams {
+                filter: Some(self.add_deleted_points_condition_to_filter(
+                    request.filter.as_ref(),
+                    &deleted_points,
+                )),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .facet(&wrapped_request, is_stopped)?
+        };
+        let write_segment_counts = self.write_segment.get().read().facet(request, is_stopped)?;
+        for (value, count) in write_segment_counts {
+            *counts.entry(value).or_insert(0) += count;
+        }
+        Ok(counts)
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -1537,6 +1642,7 @@ mod tests {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
                 (
@@ -1549,6 +1655,7 @@ mod tests {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
             ]),
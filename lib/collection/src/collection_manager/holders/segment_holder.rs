@@ -538,12 +538,40 @@ impl<'s> SegmentHolder {
         &self,
         op_num: SeqNumberType,
         ids: &[PointIdType],
+        point_operation: F,
+        update_nonappendable: G,
+    ) -> OperationResult<HashSet<PointIdType>>
+    where
+        F: FnMut(PointIdType, &mut RwLockWriteGuard<dyn SegmentEntry>) -> OperationResult<bool>,
+        G: FnMut(&dyn SegmentEntry) -> bool,
+    {
+        self.apply_points_with_conditional_move_if(
+            op_num,
+            ids,
+            |_, _| true,
+            point_operation,
+            update_nonappendable,
+        )
+    }
+
+    /// Same as [`Self::apply_points_with_conditional_move`], but points for which
+    /// `precondition` returns false are skipped.
+    ///
+    /// The precondition is checked in the segment containing the point, under its write lock
+    /// and before the point is moved, so skipped points are left untouched.
+    /// Skipped points are still included into the returned set, as they were processed.
+    pub fn apply_points_with_conditional_move_if<F, G, H>(
+        &self,
+        op_num: SeqNumberType,
+        ids: &[PointIdType],
+        mut precondition: H,
         mut point_operation: F,
         update_nonappendable: G,
     ) -> OperationResult<HashSet<PointIdType>>
     where
         F: FnMut(PointIdType, &mut RwLockWriteGuard<dyn SegmentEntry>) -> OperationResult<bool>,
         G: FnMut(&dyn SegmentEntry) -> bool,
+        H: FnMut(PointIdType, &dyn SegmentEntry) -> bool,
     {
         let _update_guard = self.update_tracker.update();
 
@@ -563,6 +591,11 @@ impl<'s> SegmentHolder {
                     }
                 }
 
+                if !precondition(point_id, &**write_segment) {
+                    applied_points.insert(point_id);
+                    return Ok(false);
+                }
+
                 let is_applied = if update_nonappendable || write_segment.is_appendable() {
                     point_operation(point_id, write_segment)?
                 } else {
@@ -851,6 +851,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            compressed_links: None,
         };
 
         {
@@ -412,14 +412,16 @@ pub trait SegmentOptimizer {
 
         self.check_cancellation(stopped)?;
 
-        for segment in optimizing_segments {
-            match segment {
-                LockedSegment::Original(segment_arc) => {
-                    let segment_guard = segment_arc.read();
-                    segment_builder.update_from(&segment_guard, stopped)?;
-                }
-                LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
-            }
+        {
+            let segment_guards: Vec<_> = optimizing_segments
+                .iter()
+                .map(|segment| match segment {
+                    LockedSegment::Original(segment_arc) => segment_arc.read(),
+                    LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
+                })
+                .collect();
+            let segments: Vec<_> = segment_guards.iter().map(|guard| &**guard).collect();
+            segment_builder.update(&segments, stopped)?;
         }
 
         for field in proxy_deleted_indexes.read().iter() {
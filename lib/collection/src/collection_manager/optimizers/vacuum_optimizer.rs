@@ -433,6 +433,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            compressed_links: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -197,26 +197,32 @@ impl SegmentsSearcher {
                 })
         }
 
-        // Do blocking calls in a blocking task: `segment.get().read()` calls might block async runtime
-        let task = {
-            let segments = segments.clone();
+        Self::fill_query_context(segments, query_context).await
+    }
 
-            tokio::task::spawn_blocking(move || {
-                let segments = segments.read();
+    /// Collect statistics, requested by the `query_context`, over all segments.
+    ///
+    /// Returns `None` if there are no segments.
+    pub async fn fill_query_context(
+        segments: LockedSegmentHolder,
+        mut query_context: QueryContext,
+    ) -> CollectionResult<Option<QueryContext>> {
+        // Do blocking calls in a blocking task: `segment.get().read()` calls might block async runtime
+        let task = tokio::task::spawn_blocking(move || {
+            let segments = segments.read();
 
-                if segments.is_empty() {
-                    return None;
-                }
+            if segments.is_empty() {
+                return None;
+            }
 
-                let segments = segments.non_appendable_then_appendable_segments();
-                for locked_segment in segments {
-                    let segment = locked_segment.get();
-                    let segment_guard = segment.read();
-                    segment_guard.fill_query_context(&mut query_context);
-                }
-                Some(query_context)
-            })
-        };
+            let segments = segments.non_appendable_then_appendable_segments();
+            for locked_segment in segments {
+                let segment = locked_segment.get();
+                let segment_guard = segment.read();
+                segment_guard.fill_query_context(&mut query_context);
+            }
+            Some(query_context)
+        });
 
         Ok(task.await?)
     }
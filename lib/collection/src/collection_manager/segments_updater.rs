@@ -10,14 +10,14 @@ use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructIntern
 use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
+    Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
     SeqNumberType,
 };
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, UpdateOutcome};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
 use crate::operations::FieldIndexOperations;
 
@@ -33,6 +33,44 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Checks if the point may be updated, i.e. there is no `update_filter` or the point matches it.
+/// Points which don't match are recorded into `unmatched_points`.
+///
+/// Must be called under the write lock of the segment, which contains the point,
+/// so the point can't change between the check and the update.
+/// It is used as a precondition of [`SegmentHolder::apply_points_with_conditional_move_if`],
+/// so that unmatched points are not moved into appendable segments either.
+fn is_update_allowed(
+    segment: &dyn SegmentEntry,
+    point_id: PointIdType,
+    update_filter: Option<&Filter>,
+    unmatched_points: &mut HashSet<PointIdType>,
+) -> bool {
+    let Some(update_filter) = update_filter else {
+        return true;
+    };
+    let point_filter =
+        Filter::new_must(Condition::HasId(HashSet::from([point_id]).into())).merge(update_filter);
+    let is_allowed = !segment
+        .read_filtered(None, Some(1), Some(&point_filter))
+        .is_empty();
+    if !is_allowed {
+        unmatched_points.insert(point_id);
+    }
+    is_allowed
+}
+
+/// Outcome of an update, which skipped `unmatched_points` out of all `processed_points`
+fn conditional_update_outcome(
+    processed_points: &HashSet<PointIdType>,
+    unmatched_points: HashSet<PointIdType>,
+) -> UpdateOutcome {
+    UpdateOutcome {
+        affected_points: processed_points.len() - unmatched_points.len(),
+        unmatched_points: unmatched_points.into_iter().collect(),
+    }
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -112,21 +150,28 @@ pub(crate) fn delete_vectors_by_filter(
     delete_vectors(segments, op_num, &affected_points, vector_names)
 }
 
+/// Overwrite payload of the points, skipping points which don't match the `update_filter`
 pub(crate) fn overwrite_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
     payload: &Payload,
     points: &[PointIdType],
-) -> CollectionResult<usize> {
-    let updated_points = segments.apply_points_with_conditional_move(
+    update_filter: Option<&Filter>,
+) -> CollectionResult<UpdateOutcome> {
+    let mut unmatched_points = HashSet::new();
+    let updated_points = segments.apply_points_with_conditional_move_if(
         op_num,
         points,
+        |id, segment| is_update_allowed(segment, id, update_filter, &mut unmatched_points),
         |id, write_segment| write_segment.set_full_payload(op_num, id, payload),
         |segment| segment.get_indexed_fields().is_empty(),
     )?;
 
     check_unprocessed_points(points, &updated_points)?;
-    Ok(updated_points.len())
+    Ok(conditional_update_outcome(
+        &updated_points,
+        unmatched_points,
+    ))
 }
 
 pub(crate) fn overwrite_payload_by_filter(
@@ -134,21 +179,26 @@ pub(crate) fn overwrite_payload_by_filter(
     op_num: SeqNumberType,
     payload: &Payload,
     filter: &Filter,
-) -> CollectionResult<usize> {
+    update_filter: Option<&Filter>,
+) -> CollectionResult<UpdateOutcome> {
     let affected_points = points_by_filter(segments, filter)?;
-    overwrite_payload(segments, op_num, payload, &affected_points)
+    overwrite_payload(segments, op_num, payload, &affected_points, update_filter)
 }
 
+/// Set payload of the points, skipping points which don't match the `update_filter`
 pub(crate) fn set_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
     payload: &Payload,
     points: &[PointIdType],
     key: &Option<JsonPath>,
-) -> CollectionResult<usize> {
-    let updated_points = segments.apply_points_with_conditional_move(
+    update_filter: Option<&Filter>,
+) -> CollectionResult<UpdateOutcome> {
+    let mut unmatched_points = HashSet::new();
+    let updated_points = segments.apply_points_with_conditional_move_if(
         op_num,
         points,
+        |id, segment| is_update_allowed(segment, id, update_filter, &mut unmatched_points),
         |id, write_segment| write_segment.set_payload(op_num, id, payload, key),
         |segment| {
             segment.get_indexed_fields().keys().all(|indexed_path| {
@@ -158,7 +208,10 @@ pub(crate) fn set_payload(
     )?;
 
     check_unprocessed_points(points, &updated_points)?;
-    Ok(updated_points.len())
+    Ok(conditional_update_outcome(
+        &updated_points,
+        unmatched_points,
+    ))
 }
 
 fn points_by_filter(
@@ -180,9 +233,17 @@ pub(crate) fn set_payload_by_filter(
     payload: &Payload,
     filter: &Filter,
     key: &Option<JsonPath>,
-) -> CollectionResult<usize> {
+    update_filter: Option<&Filter>,
+) -> CollectionResult<UpdateOutcome> {
     let affected_points = points_by_filter(segments, filter)?;
-    set_payload(segments, op_num, payload, &affected_points, key)
+    set_payload(
+        segments,
+        op_num,
+        payload,
+        &affected_points,
+        key,
+        update_filter,
+    )
 }
 
 pub(crate) fn delete_payload(
@@ -385,6 +446,22 @@ pub(crate) fn upsert_points<'a, T>(
     op_num: SeqNumberType,
     points: T,
 ) -> CollectionResult<usize>
+where
+    T: IntoIterator<Item = &'a PointStruct>,
+{
+    let outcome = upsert_points_conditional(segments, op_num, points, None)?;
+    Ok(outcome.affected_points)
+}
+
+/// Checks point id in each segment, update point if found and it matches the `update_filter`.
+/// All not found points are inserted into random segment.
+/// Returns: number of updated points and existing points, which didn't match the `update_filter`.
+pub(crate) fn upsert_points_conditional<'a, T>(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: T,
+    update_filter: Option<&Filter>,
+) -> CollectionResult<UpdateOutcome>
 where
     T: IntoIterator<Item = &'a PointStruct>,
 {
@@ -393,9 +470,11 @@ where
     let ids: Vec<PointIdType> = points_map.keys().copied().collect();
 
     // Update points in writable segments
-    let updated_points = segments.apply_points_with_conditional_move(
+    let mut unmatched_points = HashSet::new();
+    let updated_points = segments.apply_points_with_conditional_move_if(
         op_num,
         &ids,
+        |id, segment| is_update_allowed(segment, id, update_filter, &mut unmatched_points),
         |id, write_segment| {
             let point = points_map[&id];
             upsert_with_payload(
@@ -409,7 +488,7 @@ where
         |_| false,
     )?;
 
-    let mut res = updated_points.len();
+    let mut res = updated_points.len() - unmatched_points.len();
     // Insert new points, which was not updated or existed
     let new_point_ids = ids
         .iter()
@@ -439,47 +518,67 @@ where
         RwLockWriteGuard::unlock_fair(write_segment);
     };
 
-    Ok(res)
+    Ok(UpdateOutcome {
+        affected_points: res,
+        unmatched_points: unmatched_points.into_iter().collect(),
+    })
+}
+
+fn insert_operation_points(operation: PointInsertOperationsInternal) -> Vec<PointStruct> {
+    match operation {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
+            let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
+            let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+            match batch.payloads {
+                None => vectors_iter
+                    .map(|(id, vectors)| PointStruct {
+                        id,
+                        vector: VectorStructInternal::from(vectors).into(),
+                        payload: None,
+                    })
+                    .collect(),
+                Some(payloads) => vectors_iter
+                    .zip(payloads)
+                    .map(|((id, vectors), payload)| PointStruct {
+                        id,
+                        vector: VectorStructInternal::from(vectors).into(),
+                        payload,
+                    })
+                    .collect(),
+            }
+        }
+        PointInsertOperationsInternal::PointsList(points) => points,
+    }
 }
 
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     point_operation: PointOperations,
-) -> CollectionResult<usize> {
+) -> CollectionResult<UpdateOutcome> {
     match point_operation {
-        PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
+        PointOperations::DeletePoints { ids, .. } => {
+            let res = delete_points(&segments.read(), op_num, &ids)?;
+            Ok(res.into())
+        }
         PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
-                    let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+            let points = insert_operation_points(operation);
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
-            Ok(res)
+            Ok(res.into())
+        }
+        PointOperations::UpsertPointsConditional(operation) => {
+            let points = insert_operation_points(operation.points_op);
+            upsert_points_conditional(
+                &segments.read(),
+                op_num,
+                points.iter(),
+                Some(&operation.condition),
+            )
         }
         PointOperations::DeletePointsByFilter(filter) => {
-            delete_points_by_filter(&segments.read(), op_num, &filter)
+            let res = delete_points_by_filter(&segments.read(), op_num, &filter)?;
+            Ok(res.into())
         }
         PointOperations::SyncPoints(operation) => {
             let (deleted, new, updated) = sync_points(
@@ -489,7 +588,7 @@ pub(crate) fn process_point_operation(
                 operation.to_id,
                 &operation.points,
             )?;
-            Ok(deleted + new + updated)
+            Ok((deleted + new + updated).into())
         }
     }
 }
@@ -516,14 +615,29 @@ pub(crate) fn process_payload_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     payload_operation: PayloadOps,
-) -> CollectionResult<usize> {
+) -> CollectionResult<UpdateOutcome> {
     match payload_operation {
         PayloadOps::SetPayload(sp) => {
             let payload: Payload = sp.payload;
+            let update_filter = sp.update_filter.as_ref();
             if let Some(points) = sp.points {
-                set_payload(&segments.read(), op_num, &payload, &points, &sp.key)
+                set_payload(
+                    &segments.read(),
+                    op_num,
+                    &payload,
+                    &points,
+                    &sp.key,
+                    update_filter,
+                )
             } else if let Some(filter) = sp.filter {
-                set_payload_by_filter(&segments.read(), op_num, &payload, &filter, &sp.key)
+                set_payload_by_filter(
+                    &segments.read(),
+                    op_num,
+                    &payload,
+                    &filter,
+                    &sp.key,
+                    update_filter,
+                )
             } else {
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
@@ -531,7 +645,7 @@ pub(crate) fn process_payload_operation(
             }
         }
         PayloadOps::DeletePayload(dp) => {
-            if let Some(points) = dp.points {
+            let res = if let Some(points) = dp.points {
                 delete_payload(&segments.read(), op_num, &points, &dp.keys)
             } else if let Some(filter) = dp.filter {
                 delete_payload_by_filter(&segments.read(), op_num, &filter, &dp.keys)
@@ -539,20 +653,30 @@ pub(crate) fn process_payload_operation(
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
                 })
-            }
+            }?;
+            Ok(res.into())
         }
         PayloadOps::ClearPayload { ref points, .. } => {
-            clear_payload(&segments.read(), op_num, points)
+            let res = clear_payload(&segments.read(), op_num, points)?;
+            Ok(res.into())
         }
         PayloadOps::ClearPayloadByFilter(ref filter) => {
-            clear_payload_by_filter(&segments.read(), op_num, filter)
+            let res = clear_payload_by_filter(&segments.read(), op_num, filter)?;
+            Ok(res.into())
         }
         PayloadOps::OverwritePayload(sp) => {
             let payload: Payload = sp.payload;
+            let update_filter = sp.update_filter.as_ref();
             if let Some(points) = sp.points {
-                overwrite_payload(&segments.read(), op_num, &payload, &points)
+                overwrite_payload(&segments.read(), op_num, &payload, &points, update_filter)
             } else if let Some(filter) = sp.filter {
-                overwrite_payload_by_filter(&segments.read(), op_num, &payload, &filter)
+                overwrite_payload_by_filter(
+                    &segments.read(),
+                    op_num,
+                    &payload,
+                    &filter,
+                    update_filter,
+                )
             } else {
                 Err(CollectionError::BadRequest {
                     description: "No points or filter specified".to_string(),
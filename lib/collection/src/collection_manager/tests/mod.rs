@@ -8,7 +8,11 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::data_types::vectors::{only_default_vector, VectorStructInternal};
 use segment::entry::entry_point::SegmentEntry;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType};
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, FieldCondition, Filter, PayloadFieldSchema, PayloadKeyType, PointIdType, Range,
+};
+use serde_json::json;
 use tempfile::Builder;
 
 use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
@@ -16,7 +20,9 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
-use crate::collection_manager::segments_updater::upsert_points;
+use crate::collection_manager::segments_updater::{
+    set_payload, upsert_points, upsert_points_conditional,
+};
 use crate::operations::point_ops::PointStruct;
 
 fn wrap_proxy(segments: LockedSegmentHolder, sid: SegmentId, path: &Path) -> SegmentId {
@@ -233,3 +239,78 @@ fn test_upsert_points_in_smallest_segment() {
         }
     }
 }
+
+#[test]
+fn test_conditional_update_does_not_move_unmatched_points() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let segment1 = build_segment_1(dir.path());
+    let mut segment2 = build_segment_2(dir.path());
+
+    segment2
+        .set_payload(20, 11.into(), &json!({ "price": 20 }).into(), &None)
+        .unwrap();
+    segment2
+        .set_payload(20, 12.into(), &json!({ "price": 5 }).into(), &None)
+        .unwrap();
+    segment2
+        .set_payload(20, 13.into(), &json!({ "price": 30 }).into(), &None)
+        .unwrap();
+    segment2.appendable_flag = false;
+
+    let mut holder = SegmentHolder::default();
+
+    let sid1 = holder.add_new(segment1);
+    let sid2 = holder.add_new(segment2);
+
+    let update_filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        JsonPath::new("price"),
+        Range {
+            lt: Some(10.0),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    )));
+
+    let points: Vec<_> = [11, 12]
+        .into_iter()
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    let outcome = upsert_points_conditional(&holder, 100, &points, Some(&update_filter)).unwrap();
+    assert_eq!(outcome.affected_points, 1);
+    assert_eq!(outcome.unmatched_points, vec![PointIdType::from(11)]);
+
+    let outcome = set_payload(
+        &holder,
+        101,
+        &json!({ "discount": true }).into(),
+        &[13.into()],
+        &None,
+        Some(&update_filter),
+    )
+    .unwrap();
+    assert_eq!(outcome.affected_points, 0);
+    assert_eq!(outcome.unmatched_points, vec![PointIdType::from(13)]);
+
+    let segment1 = holder.get(sid1).unwrap().get();
+    let segment1_read = segment1.read();
+    let segment2 = holder.get(sid2).unwrap().get();
+    let segment2_read = segment2.read();
+
+    // Matched point is moved into the appendable segment and updated
+    assert!(segment1_read.has_point(12.into()));
+    assert!(!segment2_read.has_point(12.into()));
+    assert_eq!(segment1_read.point_version(12.into()), Some(100));
+
+    // Unmatched points are left untouched in the non-appendable segment
+    for point_id in [11, 13] {
+        assert!(!segment1_read.has_point(point_id.into()));
+        assert!(segment2_read.has_point(point_id.into()));
+        assert_eq!(segment2_read.point_version(point_id.into()), Some(20));
+    }
+}
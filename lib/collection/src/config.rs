@@ -361,6 +361,7 @@ impl CollectionParams {
                         },
                         multivector_config: params.multivector_config,
                         datatype: params.datatype.map(VectorStorageDatatype::from),
+                        truncated_view: params.truncated_view.clone(),
                     },
                 )
             })
@@ -83,6 +83,10 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Store links of HNSW graph compressed. Makes the graph several times smaller at the cost
+    /// of slightly slower search. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_links: Option<bool>,
 }
 
 #[derive(
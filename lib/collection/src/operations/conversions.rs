@@ -18,7 +18,9 @@ use itertools::Itertools;
 use segment::data_types::vectors::{
     BatchVectorStructInternal, NamedQuery, Vector, VectorStructInternal,
 };
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint};
+use segment::types::{
+    Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint, TruncatedViewConfig,
+};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 use sparse::common::sparse_vector::{validate_sparse_vector_impl, SparseVector};
 use tonic::Status;
@@ -54,7 +56,8 @@ use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
+    LocalShardInfo, MultiVectorPoolingStats, OptimizersStatus, RecommendRequestInternal, Record,
+    RemoteShardInfo, SearchMatrixOffsetsResponse, SearchMatrixPair, SearchMatrixPairsResponse,
     ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
 };
 use crate::optimizers_builder::OptimizersConfig;
@@ -236,6 +239,7 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            compressed_links: value.compressed_links,
         }
     }
 }
@@ -249,6 +253,7 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            compressed_links: value.compressed_links,
         }
     }
 }
@@ -332,6 +337,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             segments_count,
             config,
             payload_schema,
+            multivector_pooling,
         } = value;
 
         api::grpc::qdrant::CollectionInfo {
@@ -403,6 +409,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    compressed_links: config.hnsw_config.compressed_links,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -434,6 +441,22 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                 .into_iter()
                 .map(|(k, v)| (k.to_string(), v.into()))
                 .collect(),
+            multivector_pooling: multivector_pooling
+                .into_iter()
+                .map(|(name, pooling)| {
+                    let MultiVectorPoolingStats {
+                        original_vectors_count,
+                        pooled_vectors_count,
+                        pooling_ratio,
+                    } = pooling;
+                    let pooling = api::grpc::qdrant::MultiVectorPoolingInfo {
+                        original_vectors_count: original_vectors_count as u64,
+                        pooled_vectors_count: pooled_vectors_count as u64,
+                        pooling_ratio,
+                    };
+                    (name, pooling)
+                })
+                .collect(),
         }
     }
 }
@@ -555,6 +578,11 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            truncated_view: vector_params
+                .truncated_view
+                .map(|view| TruncatedViewConfig {
+                    source: view.source,
+                }),
         })
     }
 }
@@ -567,6 +595,7 @@ fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>
                 api::grpc::qdrant::Datatype::Uint8 => Ok(Some(Datatype::Uint8)),
                 api::grpc::qdrant::Datatype::Float32 => Ok(Some(Datatype::Float32)),
                 api::grpc::qdrant::Datatype::Float16 => Ok(Some(Datatype::Float16)),
+                api::grpc::qdrant::Datatype::Bit => Ok(Some(Datatype::Bit)),
                 api::grpc::qdrant::Datatype::Default => Ok(None),
             }
         } else {
@@ -805,6 +834,23 @@ impl TryFrom<api::grpc::qdrant::GetCollectionInfoResponse> for CollectionInfo {
                     .into_iter()
                     .map(|(k, v)| Ok::<_, Status>((json_path_from_proto(&k)?, v.try_into()?)))
                     .try_collect()?,
+                multivector_pooling: collection_info_response
+                    .multivector_pooling
+                    .into_iter()
+                    .map(|(name, pooling)| {
+                        let api::grpc::qdrant::MultiVectorPoolingInfo {
+                            original_vectors_count,
+                            pooled_vectors_count,
+                            pooling_ratio,
+                        } = pooling;
+                        let pooling = MultiVectorPoolingStats {
+                            original_vectors_count: original_vectors_count as usize,
+                            pooled_vectors_count: pooled_vectors_count as usize,
+                            pooling_ratio,
+                        };
+                        (name, pooling)
+                    })
+                    .collect(),
             }),
         }
     }
@@ -926,6 +972,7 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status.into(),
             clock_tag: res.clock_tag.map(Into::into),
+            unmatched_points: res.unmatched_points.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -944,6 +991,11 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
             clock_tag: res.clock_tag.map(Into::into),
+            unmatched_points: res
+                .unmatched_points
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
         };
 
         Ok(res)
@@ -1007,6 +1059,43 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     }
 }
 
+impl From<SearchMatrixPair> for api::grpc::qdrant::SearchMatrixPair {
+    fn from(value: SearchMatrixPair) -> Self {
+        let SearchMatrixPair { a, b, score } = value;
+        Self {
+            a: Some(a.into()),
+            b: Some(b.into()),
+            score,
+        }
+    }
+}
+
+impl From<SearchMatrixPairsResponse> for api::grpc::qdrant::SearchMatrixPairs {
+    fn from(value: SearchMatrixPairsResponse) -> Self {
+        let SearchMatrixPairsResponse { pairs } = value;
+        Self {
+            pairs: pairs.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl From<SearchMatrixOffsetsResponse> for api::grpc::qdrant::SearchMatrixOffsets {
+    fn from(value: SearchMatrixOffsetsResponse) -> Self {
+        let SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids,
+        } = value;
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: ids.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
     type Error = Status;
     fn try_from(value: api::grpc::qdrant::SearchPoints) -> Result<Self, Self::Error> {
@@ -1507,6 +1596,8 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
                 Distance::Euclid => api::grpc::qdrant::Distance::Euclid,
                 Distance::Dot => api::grpc::qdrant::Distance::Dot,
                 Distance::Manhattan => api::grpc::qdrant::Distance::Manhattan,
+                Distance::Hamming => api::grpc::qdrant::Distance::Hamming,
+                Distance::Jaccard => api::grpc::qdrant::Distance::Jaccard,
             }
             .into(),
             hnsw_config: value.hnsw_config.map(Into::into),
@@ -1518,6 +1609,11 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             multivector_config: value
                 .multivector_config
                 .map(api::grpc::qdrant::MultiVectorConfig::from),
+            truncated_view: value.truncated_view.map(|view| {
+                api::grpc::qdrant::TruncatedViewConfig {
+                    source: view.source,
+                }
+            }),
         }
     }
 }
@@ -1528,6 +1624,7 @@ impl From<Datatype> for api::grpc::qdrant::Datatype {
             Datatype::Float32 => api::grpc::qdrant::Datatype::Float32,
             Datatype::Uint8 => api::grpc::qdrant::Datatype::Uint8,
             Datatype::Float16 => api::grpc::qdrant::Datatype::Float16,
+            Datatype::Bit => api::grpc::qdrant::Datatype::Bit,
         }
     }
 }
@@ -379,6 +379,11 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
+            let upsert_conditional =
+                Self::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsList(Vec::new()),
+                    condition: Filter::default(),
+                });
             let delete = Self::DeletePoints { ids: Vec::new() };
 
             let delete_by_filter = Self::DeletePointsByFilter(Filter {
@@ -396,6 +401,7 @@ mod tests {
 
             prop_oneof![
                 Just(upsert),
+                Just(upsert_conditional),
                 Just(delete),
                 Just(delete_by_filter),
                 Just(sync),
@@ -443,6 +449,7 @@ mod tests {
                 points: None,
                 filter: None,
                 key: None,
+                update_filter: None,
             });
 
             let overwrite = Self::OverwritePayload(SetPayloadOp {
@@ -450,6 +457,7 @@ mod tests {
                 points: None,
                 filter: None,
                 key: None,
+                update_filter: None,
             });
 
             let delete = Self::DeletePayload(DeletePayloadOp {
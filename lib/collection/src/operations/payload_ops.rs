@@ -23,6 +23,9 @@ pub struct SetPayload {
     pub shard_key: Option<ShardKeySelector>,
     /// Assigns payload to each point that satisfy this path of property
     pub key: Option<JsonPath>,
+    /// If specified, only points matching this filter are updated, others are left untouched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_filter: Option<Filter>,
 }
 
 /// This data structure is used inside shard operations queue
@@ -39,6 +42,9 @@ pub struct SetPayloadOp {
     pub filter: Option<Filter>,
     /// Payload selector to indicate property of payload, e.g. `a.b.c`
     pub key: Option<JsonPath>,
+    /// Only points matching this condition are updated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_filter: Option<Filter>,
 }
 
 #[derive(Deserialize)]
@@ -48,6 +54,7 @@ struct SetPayloadShadow {
     pub filter: Option<Filter>,
     pub shard_key: Option<ShardKeySelector>,
     pub key: Option<JsonPath>,
+    pub update_filter: Option<Filter>,
 }
 
 pub struct PointsSelectorValidationError;
@@ -72,6 +79,7 @@ impl TryFrom<SetPayloadShadow> for SetPayload {
                 filter: value.filter,
                 shard_key: value.shard_key,
                 key: value.key,
+                update_filter: value.update_filter,
             })
         } else {
             Err(PointsSelectorValidationError)
@@ -221,6 +229,7 @@ impl SplitByShard for SetPayloadOp {
                         payload: self.payload.clone(),
                         filter: self.filter.clone(),
                         key: self.key.clone(),
+                        update_filter: self.update_filter.clone(),
                     }
                 })
             }
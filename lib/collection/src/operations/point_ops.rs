@@ -138,6 +138,11 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, only existing points matching this filter are updated, others are left untouched.
+    /// New points are inserted regardless of the filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub update_filter: Option<Filter>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -146,6 +151,11 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, only existing points matching this filter are updated, others are left untouched.
+    /// New points are inserted regardless of the filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub update_filter: Option<Filter>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -196,11 +206,27 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
-    pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
-        match self {
-            PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
-            PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
-        }
+    pub fn decompose(self) -> (Option<ShardKeySelector>, PointOperations) {
+        let (shard_key, points_op, update_filter) = match self {
+            PointInsertOperations::PointsBatch(batch) => {
+                (batch.shard_key, batch.batch.into(), batch.update_filter)
+            }
+            PointInsertOperations::PointsList(list) => {
+                (list.shard_key, list.points.into(), list.update_filter)
+            }
+        };
+
+        let operation = match update_filter {
+            None => PointOperations::UpsertPoints(points_op),
+            Some(condition) => {
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op,
+                    condition,
+                })
+            }
+        };
+
+        (shard_key, operation)
     }
 }
 
@@ -216,6 +242,14 @@ pub enum PointInsertOperationsInternal {
     PointsList(Vec<PointStruct>),
 }
 
+/// Upsert, which updates only existing points matching the `condition`
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ConditionalInsertOperationInternal {
+    pub points_op: PointInsertOperationsInternal,
+    /// Condition existing points have to match to be updated
+    pub condition: Filter,
+}
+
 impl Validate for PointInsertOperationsInternal {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -302,6 +336,7 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            update_filter: None,
         })
     }
 }
@@ -311,6 +346,7 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            update_filter: None,
         })
     }
 }
@@ -333,6 +369,8 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert points or update existing points matching the condition
+    UpsertPointsConditional(ConditionalInsertOperationInternal),
     /// Delete point if exists
     DeletePoints { ids: Vec<PointIdType> },
     /// Delete points by given filter criteria
@@ -345,6 +383,7 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::UpsertPointsConditional(_) => true,
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
@@ -356,6 +395,10 @@ impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
+            PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                conditional_upsert.points_op.validate()?;
+                conditional_upsert.condition.validate()
+            }
             PointOperations::DeletePoints { ids: _ } => Ok(()),
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
@@ -524,6 +567,15 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op,
+                condition,
+            }) => points_op.split_by_shard(ring).map(|points_op| {
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op,
+                    condition: condition.clone(),
+                })
+            }),
             PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
                 .map(|ids| PointOperations::DeletePoints { ids }),
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
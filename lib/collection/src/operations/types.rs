@@ -1,4 +1,5 @@
 use std::backtrace::Backtrace;
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error as _;
 use std::fmt::Write as _;
@@ -20,15 +21,17 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::facets::FacetParams;
 use segment::data_types::groups::GroupId;
-use segment::data_types::order_by::OrderValue;
+use segment::data_types::order_by::{OrderBy, OrderValue};
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStructInternal, DEFAULT_VECTOR_NAME,
 };
 use segment::types::{
-    Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    Distance, Filter, MultiVectorConfig, MultiVectorPoolingInfo, Payload, PayloadIndexInfo,
+    PayloadKeyType, PointIdType, QuantizationConfig, SearchParams, SeqNumberType, ShardKey,
+    TruncatedViewConfig, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use serde;
@@ -131,6 +134,40 @@ pub struct CollectionInfo {
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
+    /// Effect of storage-time pooling, per multivector name with pooling enabled
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub multivector_pooling: HashMap<String, MultiVectorPoolingStats>,
+}
+
+/// Effect of storage-time pooling of a multivector
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+pub struct MultiVectorPoolingStats {
+    /// Number of vectors of all points before pooling
+    pub original_vectors_count: usize,
+    /// Number of vectors stored after pooling
+    pub pooled_vectors_count: usize,
+    /// Fraction of vectors kept after pooling
+    pub pooling_ratio: f32,
+}
+
+impl MultiVectorPoolingStats {
+    pub fn merge(&mut self, other: Self) {
+        *self = MultiVectorPoolingInfo {
+            num_original_vectors: self.original_vectors_count + other.original_vectors_count,
+            num_pooled_vectors: self.pooled_vectors_count + other.pooled_vectors_count,
+        }
+        .into();
+    }
+}
+
+impl From<MultiVectorPoolingInfo> for MultiVectorPoolingStats {
+    fn from(info: MultiVectorPoolingInfo) -> Self {
+        Self {
+            original_vectors_count: info.num_original_vectors,
+            pooled_vectors_count: info.num_pooled_vectors,
+            pooling_ratio: info.pooling_ratio(),
+        }
+    }
 }
 
 impl CollectionInfo {
@@ -144,6 +181,7 @@ impl CollectionInfo {
             segments_count: 0,
             config: collection_config,
             payload_schema: HashMap::new(),
+            multivector_pooling: HashMap::new(),
         }
     }
 }
@@ -159,6 +197,11 @@ impl From<CollectionInfoInternal> for CollectionInfo {
             segments_count: info.segments_count,
             config: info.config,
             payload_schema: info.payload_schema,
+            multivector_pooling: info
+                .multivector_pooling
+                .into_iter()
+                .map(|(name, pooling)| (name, pooling.into()))
+                .collect(),
         }
     }
 }
@@ -189,6 +232,8 @@ pub struct CollectionInfoInternal {
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
+    /// Storage-time pooling counts, per multivector name with pooling enabled
+    pub multivector_pooling: HashMap<String, MultiVectorPoolingInfo>,
 }
 
 /// Current clustering distribution for the collection
@@ -289,7 +334,7 @@ pub enum UpdateStatus {
     ClockRejected,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, JsonSchema)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateResult {
     /// Sequential number of the operation
@@ -303,6 +348,29 @@ pub struct UpdateResult {
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
+
+    /// Existing points, which were not updated because they didn't match the `update_filter`.
+    /// Only reported if the request waits for the changes to be applied.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmatched_points: Vec<PointIdType>,
+}
+
+/// Outcome of an operation applied to the segments of a local shard
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UpdateOutcome {
+    /// Number of points affected by the operation
+    pub affected_points: usize,
+    /// Existing points, which were not updated because they didn't match the `update_filter`
+    pub unmatched_points: Vec<PointIdType>,
+}
+
+impl From<usize> for UpdateOutcome {
+    fn from(affected_points: usize) -> Self {
+        Self {
+            affected_points,
+            unmatched_points: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -357,8 +425,42 @@ pub struct QueryScrollRequestInternal {
     /// Options for specifying which vectors to include into response. Default is false.
     pub with_vector: WithVector,
 
-    /// Order the records by a payload field.
-    pub order_by: Option<OrderByInterface>,
+    /// Order of the scrolled points.
+    pub scroll_order: ScrollOrder,
+}
+
+/// Order of the points of a query scroll
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ScrollOrder {
+    /// Ascending order of the point ids
+    #[default]
+    ById,
+    /// Order by a payload field
+    ByField(OrderBy),
+    /// Random sample of the matching points, ordered by their sample keys for the seed
+    Random { seed: u64 },
+}
+
+/// Text relevance search, used as a part of query request
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTextRequestInternal {
+    /// Query text and the full-text indexed field to score
+    pub query: TextQuery,
+
+    /// Max number of results
+    pub limit: usize,
+
+    /// Look only for points which satisfies this conditions. If not provided - all points.
+    pub filter: Option<Filter>,
+
+    /// Return only points with better score than this threshold
+    pub score_threshold: Option<ScoreType>,
+
+    /// Select which payload to return with the response.
+    pub with_payload: WithPayloadInterface,
+
+    /// Options for specifying which vectors to include into response.
+    pub with_vector: WithVector,
 }
 
 impl ScrollRequestInternal {
@@ -812,6 +914,127 @@ pub struct CountResult {
     pub count: usize,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub facet_request: FacetRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Facet Request
+/// Counts the number of points with each value of the payload field, which satisfy the given filter.
+/// Only the most frequent values are returned.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequestInternal {
+    /// Payload key with a keyword, integer or bool index
+    pub key: PayloadKeyType,
+    /// Max number of values to return. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// If true, count exact number of points with each value.
+    /// If false, estimate the counts faster with the filter cardinality estimation. Default: false
+    pub exact: Option<bool>,
+}
+
+pub const DEFAULT_FACET_LIMIT: usize = 10;
+
+/// How many times more values each shard returns for an approximate facet request,
+/// so that values which are not in the top of every shard still get their counts
+pub const FACET_SHARD_OVERSAMPLING: usize = 4;
+
+impl From<FacetRequestInternal> for FacetParams {
+    fn from(request: FacetRequestInternal) -> Self {
+        let FacetRequestInternal {
+            key,
+            limit,
+            filter,
+            exact,
+        } = request;
+
+        Self {
+            key,
+            limit: limit.unwrap_or(DEFAULT_FACET_LIMIT),
+            filter,
+            exact: exact.unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub search_matrix_request: SearchMatrixRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Search Matrix Request
+/// Randomly samples points which satisfy the given filter,
+/// then finds the nearest neighbours of each of them among the sampled points.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequestInternal {
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// How many points to select and search within. Default: 10
+    #[validate(range(min = 2))]
+    pub sample: Option<usize>,
+    /// How many neighbours per sample to find. Default: 3
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Define which vector name to use for querying. If missing, the default vector is used.
+    pub using: Option<String>,
+}
+
+pub const DEFAULT_SEARCH_MATRIX_SAMPLE: usize = 10;
+pub const DEFAULT_SEARCH_MATRIX_LIMIT: usize = 3;
+
+/// Pair of points with the score between them
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPair {
+    /// Sampled point
+    pub a: PointIdType,
+    /// One of the nearest neighbours of `a` among the sampled points
+    pub b: PointIdType,
+    pub score: ScoreType,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPairsResponse {
+    /// List of pairs of points with scores
+    pub pairs: Vec<SearchMatrixPair>,
+}
+
+/// Sparse representation of the nearest neighbours matrix.
+/// `offsets_row[i]`, `offsets_col[i]` and `scores[i]` describe a single pair of points,
+/// by the positions of the points in `ids`.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixOffsetsResponse {
+    /// Row indices of the matrix
+    pub offsets_row: Vec<u64>,
+    /// Column indices of the matrix
+    pub offsets_col: Vec<u64>,
+    /// Scores associated with matrix coordinates
+    pub scores: Vec<ScoreType>,
+    /// Ids of the sampled points
+    pub ids: Vec<PointIdType>,
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 #[error("{0}")]
 pub enum CollectionError {
@@ -1012,6 +1235,10 @@ impl From<OperationError> for CollectionError {
             },
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
             OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingFullTextIndexForQuery { .. } => {
+                Self::bad_input(format!("{err}"))
+            }
+            OperationError::MissingMapIndexForFacet { .. } => Self::bad_input(format!("{err}")),
         }
     }
 }
@@ -1213,6 +1440,7 @@ pub enum Datatype {
     Float32,
     Uint8,
     Float16,
+    Bit,
 }
 
 impl From<Datatype> for VectorStorageDatatype {
@@ -1221,12 +1449,14 @@ impl From<Datatype> for VectorStorageDatatype {
             Datatype::Float32 => VectorStorageDatatype::Float32,
             Datatype::Uint8 => VectorStorageDatatype::Uint8,
             Datatype::Float16 => VectorStorageDatatype::Float16,
+            Datatype::Bit => VectorStorageDatatype::Bit,
         }
     }
 }
 
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[validate(schema(function = "validate_vector_params_datatype"))]
 #[serde(rename_all = "snake_case")]
 pub struct VectorParams {
     /// Size of a vectors used
@@ -1261,10 +1491,35 @@ pub struct VectorParams {
     ///   2 bytes.
     /// - For `uint8` datatype - vectors are stored as unsigned 8-bit integers, 1 byte.
     ///   It expects vector elements to be in range `[0, 255]`.
+    /// - For `bit` datatype - vectors are stored as single bits, 8 dimensions per byte.
+    ///   Positive elements are stored as `1`, others as `0`. Vector size must be a multiple of 8.
     pub datatype: Option<Datatype>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivector_config: Option<MultiVectorConfig>,
+
+    /// If set, this vector is a view over the first `size` dimensions of the `source` vector.
+    /// It has its own index, but shares the storage of the source vector and can't be written to
+    /// directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_view: Option<TruncatedViewConfig>,
+}
+
+/// Bit vectors are packed by 8 dimensions and are not supported for multivectors.
+pub fn validate_vector_params_datatype(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.datatype != Some(Datatype::Bit) {
+        return Ok(());
+    }
+    let message = if params.size.get() % u64::from(u8::BITS) != 0 {
+        "vector size must be a multiple of 8 for bit datatype"
+    } else if params.multivector_config.is_some() {
+        "bit datatype is not supported for multivectors"
+    } else {
+        return Ok(());
+    };
+    let mut err = ValidationError::new("datatype");
+    err.message = Some(Cow::from(message));
+    Err(err)
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1558,12 +1813,74 @@ impl Anonymize for VectorsConfig {
 impl Validate for VectorsConfig {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            VectorsConfig::Single(single) => single.validate(),
-            VectorsConfig::Multi(multi) => common::validation::validate_iter(multi.values()),
+            VectorsConfig::Single(single) => {
+                single.validate()?;
+                if single.truncated_view.is_some() {
+                    let mut errors = ValidationErrors::new();
+                    errors.add(
+                        "truncated_view",
+                        truncated_view_error("truncated view requires named vectors".into()),
+                    );
+                    return Err(errors);
+                }
+                Ok(())
+            }
+            VectorsConfig::Multi(multi) => {
+                common::validation::validate_iter(multi.values())?;
+                validate_truncated_views(multi)
+            }
         }
     }
 }
 
+fn truncated_view_error(message: Cow<'static, str>) -> ValidationError {
+    let mut err = ValidationError::new("truncated_view");
+    err.message = Some(message);
+    err
+}
+
+/// Truncated views must refer to an existing plain dense vector with matching params.
+fn validate_truncated_views(
+    vectors: &BTreeMap<String, VectorParams>,
+) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    for (name, params) in vectors {
+        let Some(view) = &params.truncated_view else {
+            continue;
+        };
+        let message = match vectors.get(&view.source) {
+            None => format!("vector `{name}` refers to unknown source `{}`", view.source),
+            Some(source) if source.truncated_view.is_some() => {
+                format!("vector `{name}` can't be a view over another view")
+            }
+            Some(source)
+                if source.multivector_config.is_some() || params.multivector_config.is_some() =>
+            {
+                format!("vector `{name}`: truncated views are not supported for multivectors")
+            }
+            Some(source) if params.size > source.size => format!(
+                "vector `{name}` size must not exceed the size of `{}`",
+                view.source,
+            ),
+            Some(source)
+                if params.distance != source.distance || params.datatype != source.datatype =>
+            {
+                format!(
+                    "vector `{name}` must have the same distance and datatype as `{}`",
+                    view.source,
+                )
+            }
+            Some(_) => continue,
+        };
+        errors.add("truncated_view", truncated_view_error(message.into()));
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 impl From<VectorParams> for VectorsConfig {
     fn from(params: VectorParams) -> Self {
         VectorsConfig::Single(params)
@@ -4,6 +4,7 @@ use api::rest::{LookupLocation, RecommendStrategy};
 use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{
     MultiDenseVectorInternal, NamedQuery, NamedVectorStruct, Vector, VectorRef, DEFAULT_VECTOR_NAME,
 };
@@ -13,7 +14,10 @@ use segment::types::{
 };
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 
-use super::shard_query::{Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::formula::FormulaInternal;
+use super::shard_query::{
+    Fusion, MmrInternal, SampleInternal, ScoringQuery, ShardPrefetch, ShardQueryRequest,
+};
 use crate::common::fetch_vectors::ReferencedVectors;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -61,32 +65,72 @@ pub enum Query {
     /// Score points against some vector(s)
     Vector(VectorQuery<VectorInput>),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Score the results of the prefetches with a formula
+    Formula(FormulaInternal),
+
+    /// Score points by BM25 relevance of the query text
+    Text(TextQuery),
+
+    /// Sample points randomly
+    Sample(Sample),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sample {
+    /// Uniformly random points, the same seed gives the same points for unchanged data
+    Random { seed: Option<u64> },
+}
+
+impl From<Sample> for SampleInternal {
+    fn from(sample: Sample) -> Self {
+        match sample {
+            // Every shard has to sample with the same seed
+            Sample::Random { seed } => SampleInternal::Random {
+                seed: seed.unwrap_or_else(rand::random),
+            },
+        }
+    }
 }
 
 impl Query {
+    /// `request_limit` is the number of points the query has to return, including the offset.
+    /// It is the default number of candidates for MMR.
     pub fn try_into_scoring_query(
         self,
         ids_to_vectors: &ReferencedVectors,
         lookup_vector_name: &str,
         lookup_collection: Option<&String>,
         using: String,
+        request_limit: usize,
     ) -> CollectionResult<ScoringQuery> {
         let scoring_query = match self {
             Query::Vector(vector_query) => {
-                let query_enum = vector_query
-                    // Homogenize the input into raw vectors
-                    .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)
+                // Homogenize the input into raw vectors
+                let vector_query = vector_query.ids_into_vectors(
+                    ids_to_vectors,
+                    lookup_vector_name,
+                    lookup_collection,
+                );
+
+                match vector_query {
+                    VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr }) => {
+                        ScoringQuery::Mmr(mmr.into_internal(nearest, using, request_limit)?)
+                    }
                     // Turn into QueryEnum
-                    .into_query_enum(using)?;
-                ScoringQuery::Vector(query_enum)
+                    vector_query => ScoringQuery::Vector(vector_query.into_query_enum(using)?),
+                }
             }
             Query::Fusion(fusion) => ScoringQuery::Fusion(fusion),
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
+            Query::Formula(formula) => ScoringQuery::Formula(formula.parse()?),
+            Query::Text(text) => ScoringQuery::Text(text),
+            Query::Sample(sample) => ScoringQuery::Sample(SampleInternal::from(sample)),
         };
 
         Ok(scoring_query)
@@ -110,6 +154,7 @@ impl VectorInput {
 #[derive(Clone, Debug, PartialEq)]
 pub enum VectorQuery<T> {
     Nearest(T),
+    NearestWithMmr(NearestWithMmr<T>),
     RecommendAverageVector(RecoQuery<T>),
     RecommendBestScore(RecoQuery<T>),
     Discover(DiscoveryQuery<T>),
@@ -121,6 +166,7 @@ impl<T> VectorQuery<T> {
     pub fn flat_iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
         match self {
             VectorQuery::Nearest(input) => Box::new(std::iter::once(input)),
+            VectorQuery::NearestWithMmr(query) => Box::new(std::iter::once(&query.nearest)),
             VectorQuery::RecommendAverageVector(query) => Box::new(query.flat_iter()),
             VectorQuery::RecommendBestScore(query) => Box::new(query.flat_iter()),
             VectorQuery::Discover(query) => Box::new(query.flat_iter()),
@@ -147,6 +193,13 @@ impl VectorQuery<VectorInput> {
 
                 VectorQuery::Nearest(vector)
             }
+            VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr }) => {
+                let nearest = ids_to_vectors
+                    .resolve_reference(lookup_collection, lookup_vector_name, nearest)
+                    .unwrap();
+
+                VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr })
+            }
             VectorQuery::RecommendAverageVector(reco) => {
                 let (positives, negatives) = Self::resolve_reco_reference(
                     reco,
@@ -242,6 +295,10 @@ impl VectorQuery<Vector> {
             VectorQuery::Nearest(vector) => {
                 QueryEnum::Nearest(NamedVectorStruct::new_from_vector(vector, using))
             }
+            VectorQuery::NearestWithMmr(NearestWithMmr { nearest, mmr: _ }) => {
+                // Re-ranking happens at collection level, this is only the nearest search
+                QueryEnum::Nearest(NamedVectorStruct::new_from_vector(nearest, using))
+            }
             VectorQuery::RecommendAverageVector(reco) => {
                 // Get average vector
                 let search_vector = avg_vector_for_recommendation(
@@ -268,6 +325,57 @@ impl VectorQuery<Vector> {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct NearestWithMmr<T> {
+    pub nearest: T,
+    pub mmr: Mmr,
+}
+
+/// Maximal Marginal Relevance re-ranking of the nearest candidates
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mmr {
+    /// Balance between relevance (0.0) and diversity (1.0)
+    pub diversity: Option<f32>,
+    /// Number of nearest candidates to re-rank
+    pub candidates_limit: Option<usize>,
+}
+
+impl Mmr {
+    pub const DEFAULT_DIVERSITY: f32 = 0.5;
+
+    fn into_internal(
+        self,
+        vector: Vector,
+        using: String,
+        request_limit: usize,
+    ) -> CollectionResult<MmrInternal> {
+        let Mmr {
+            diversity,
+            candidates_limit,
+        } = self;
+
+        if !matches!(vector, Vector::Dense(_)) {
+            return Err(CollectionError::bad_request(
+                "MMR is only supported for dense vectors",
+            ));
+        }
+
+        let candidates_limit = candidates_limit.unwrap_or(request_limit);
+        if candidates_limit < request_limit {
+            return Err(CollectionError::bad_request(format!(
+                "MMR candidates_limit ({candidates_limit}) must be at least limit + offset ({request_limit})",
+            )));
+        }
+
+        Ok(MmrInternal {
+            vector,
+            using,
+            lambda: 1.0 - diversity.unwrap_or(Self::DEFAULT_DIVERSITY),
+            candidates_limit,
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CollectionPrefetch {
     pub prefetch: Vec<CollectionPrefetch>,
@@ -341,6 +449,14 @@ impl CollectionPrefetch {
             self.score_threshold,
         )?;
 
+        // Candidates are re-ranked after merging the results of all shards,
+        // which only happens for the root query
+        if let Some(Query::Vector(VectorQuery::NearestWithMmr(_))) = &self.query {
+            return Err(CollectionError::bad_request(
+                "MMR can only be used in the root query, not in prefetches",
+            ));
+        }
+
         let lookup_vector_name = self.get_lookup_vector_name();
         let lookup_collection = self.get_lookup_collection().cloned();
         let using = self.using.clone();
@@ -353,6 +469,7 @@ impl CollectionPrefetch {
                     &lookup_vector_name,
                     lookup_collection.as_ref(),
                     using,
+                    self.limit,
                 )
             })
             .transpose()?;
@@ -463,6 +580,7 @@ impl CollectionQueryRequest {
                     &query_lookup_vector_name,
                     query_lookup_collection.as_ref(),
                     using,
+                    self.limit + self.offset,
                 )
             })
             .transpose()?;
@@ -507,6 +625,11 @@ impl CollectionQueryRequest {
                         "Can't use score_threshold with an order_by query.",
                     ));
                 }
+                Some(Query::Sample(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "Can't use score_threshold with a sample query.",
+                    ));
+                }
                 None => {
                     return Err(CollectionError::bad_request(
                         "A query is needed to use the score_threshold. Can't have score_threshold without defining a query.",
@@ -525,6 +648,56 @@ impl CollectionQueryRequest {
             }
         }
 
+        // Check that formula queries are not combined with a using vector name
+        if let Some(Query::Formula(_)) = query {
+            if using != DEFAULT_VECTOR_NAME {
+                return Err(CollectionError::bad_request(
+                    "Formula queries cannot be combined with the 'using' field.",
+                ));
+            }
+        }
+
+        // Check that text queries are not combined with a using vector name
+        if let Some(Query::Text(_)) = query {
+            if using != DEFAULT_VECTOR_NAME {
+                return Err(CollectionError::bad_request(
+                    "Text queries cannot be combined with the 'using' field.",
+                ));
+            }
+        }
+
+        // Check that sample queries are not combined with a using vector name
+        if let Some(Query::Sample(_)) = query {
+            if using != DEFAULT_VECTOR_NAME {
+                return Err(CollectionError::bad_request(
+                    "Sample queries cannot be combined with the 'using' field.",
+                ));
+            }
+        }
+
+        // Check that MMR diversity is a valid balance
+        if let Some(Query::Vector(VectorQuery::NearestWithMmr(NearestWithMmr { mmr, .. }))) = query
+        {
+            if let Some(diversity) = mmr.diversity {
+                if !(0.0..=1.0).contains(&diversity) {
+                    return Err(CollectionError::bad_request(format!(
+                        "MMR diversity must be within [0, 1], got {diversity}",
+                    )));
+                }
+            }
+        }
+
+        // Check that there is exactly one weight per prefetch
+        if let Some(Query::Fusion(Fusion::WeightedSum { weights })) = query {
+            if weights.len() != prefetch.len() {
+                return Err(CollectionError::bad_request(format!(
+                    "Weighted sum fusion expects one weight per prefetch, got {} weights for {} prefetches.",
+                    weights.len(),
+                    prefetch.len(),
+                )));
+            }
+        }
+
         Ok(())
     }
 }
@@ -601,14 +774,37 @@ mod from_rest {
     impl From<rest::Query> for Query {
         fn from(value: rest::Query) -> Self {
             match value {
-                rest::Query::Nearest(nearest) => {
-                    Query::Vector(VectorQuery::Nearest(From::from(nearest.nearest)))
-                }
+                rest::Query::Nearest(rest::NearestQuery { nearest, mmr }) => match mmr {
+                    Some(mmr) => Query::Vector(VectorQuery::NearestWithMmr(NearestWithMmr {
+                        nearest: From::from(nearest),
+                        mmr: Mmr::from(mmr),
+                    })),
+                    None => Query::Vector(VectorQuery::Nearest(From::from(nearest))),
+                },
                 rest::Query::Recommend(recommend) => Query::Vector(From::from(recommend.recommend)),
                 rest::Query::Discover(discover) => Query::Vector(From::from(discover.discover)),
                 rest::Query::Context(context) => Query::Vector(From::from(context.context)),
                 rest::Query::OrderBy(order_by) => Query::OrderBy(OrderBy::from(order_by.order_by)),
                 rest::Query::Fusion(fusion) => Query::Fusion(Fusion::from(fusion.fusion)),
+                rest::Query::Formula(formula) => Query::Formula(FormulaInternal::from(formula)),
+                rest::Query::Text(text) => Query::Text(text.text),
+                rest::Query::Sample(rest::SampleQuery { sample, seed }) => match sample {
+                    rest::Sample::Random => Query::Sample(Sample::Random { seed }),
+                },
+            }
+        }
+    }
+
+    impl From<rest::Mmr> for Mmr {
+        fn from(value: rest::Mmr) -> Self {
+            let rest::Mmr {
+                diversity,
+                candidates_limit,
+            } = value;
+
+            Self {
+                diversity,
+                candidates_limit,
             }
         }
     }
@@ -691,6 +887,10 @@ mod from_rest {
         fn from(value: rest::Fusion) -> Self {
             match value {
                 rest::Fusion::Rrf => Fusion::Rrf,
+                rest::Fusion::Dbsf => Fusion::Dbsf,
+                rest::Fusion::WeightedSum(rest::WeightedSumFusion { weights }) => {
+                    Fusion::WeightedSum { weights }
+                }
             }
         }
     }
@@ -807,12 +1007,60 @@ pub mod from_grpc {
                 Variant::Context(context) => Query::Vector(TryFrom::try_from(context)?),
                 Variant::OrderBy(order_by) => Query::OrderBy(OrderBy::try_from(order_by)?),
                 Variant::Fusion(fusion) => Query::Fusion(Fusion::try_from(fusion)?),
+                Variant::WeightedSum(weighted_sum) => Query::Fusion(Fusion::from(weighted_sum)),
+                Variant::Formula(formula) => Query::Formula(FormulaInternal::try_from(formula)?),
+                Variant::Text(text) => Query::Text(TextQuery::try_from(text)?),
+                Variant::Sample(sample) => Query::Sample(Sample::try_from(sample)?),
+                Variant::NearestWithMmr(nearest_with_mmr) => {
+                    Query::Vector(VectorQuery::try_from(nearest_with_mmr)?)
+                }
             };
 
             Ok(query)
         }
     }
 
+    impl TryFrom<grpc::SampleQuery> for Sample {
+        type Error = Status;
+
+        fn try_from(value: grpc::SampleQuery) -> Result<Self, Self::Error> {
+            let grpc::SampleQuery { sample, seed } = value;
+
+            let sample = grpc::Sample::from_i32(sample).ok_or_else(|| {
+                Status::invalid_argument(format!("invalid sample type value {sample}"))
+            })?;
+
+            Ok(match sample {
+                grpc::Sample::Random => Sample::Random { seed },
+            })
+        }
+    }
+
+    impl TryFrom<grpc::NearestInputWithMmr> for VectorQuery<VectorInput> {
+        type Error = Status;
+
+        fn try_from(value: grpc::NearestInputWithMmr) -> Result<Self, Self::Error> {
+            let grpc::NearestInputWithMmr { nearest, mmr } = value;
+
+            let nearest = VectorInput::try_from(nearest.ok_or_else(|| {
+                Status::invalid_argument("NearestInputWithMmr nearest is missing")
+            })?)?;
+            let grpc::Mmr {
+                diversity,
+                candidates_limit,
+            } =
+                mmr.ok_or_else(|| Status::invalid_argument("NearestInputWithMmr mmr is missing"))?;
+
+            Ok(VectorQuery::NearestWithMmr(NearestWithMmr {
+                nearest,
+                mmr: Mmr {
+                    diversity,
+                    candidates_limit: candidates_limit.map(|limit| limit as usize),
+                },
+            }))
+        }
+    }
+
     impl TryFrom<grpc::RecommendInput> for VectorQuery<VectorInput> {
         type Error = Status;
 
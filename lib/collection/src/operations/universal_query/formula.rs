@@ -0,0 +1,1087 @@
+//! Formula queries, used to rescore the results of prefetches with an arbitrary expression.
+//!
+//! The user-facing expression is first converted into [`FormulaInternal`], and then parsed into
+//! a [`ParsedFormula`], which resolves variables and datetimes, and can be evaluated for each point.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use common::types::ScoreType;
+use segment::json_path::JsonPath;
+use segment::payload_storage::query_checker::check_point_payload;
+use segment::types::{
+    Condition, DateTimePayloadType, Filter, GeoPoint, Payload, PayloadContainer, PointIdType,
+};
+use serde_json::Value;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+const SCORE_VARIABLE: &str = "$score";
+
+const DEFAULT_DECAY_MIDPOINT: f32 = 0.5;
+const DEFAULT_DECAY_SCALE: f32 = 1.0;
+
+/// Internal representation of a formula query, before parsing the variables.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormulaInternal {
+    pub formula: ExpressionInternal,
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionInternal {
+    Constant(f32),
+    Variable(String),
+    Condition(Box<Condition>),
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+    },
+    Datetime(String),
+    DatetimeKey(JsonPath),
+    Mult(Vec<ExpressionInternal>),
+    Sum(Vec<ExpressionInternal>),
+    Neg(Box<ExpressionInternal>),
+    Abs(Box<ExpressionInternal>),
+    Div {
+        left: Box<ExpressionInternal>,
+        right: Box<ExpressionInternal>,
+        by_zero_default: Option<ScoreType>,
+    },
+    Sqrt(Box<ExpressionInternal>),
+    Pow {
+        base: Box<ExpressionInternal>,
+        exponent: Box<ExpressionInternal>,
+    },
+    Exp(Box<ExpressionInternal>),
+    Log10(Box<ExpressionInternal>),
+    Ln(Box<ExpressionInternal>),
+    Decay {
+        kind: DecayKind,
+        x: Box<ExpressionInternal>,
+        target: Option<Box<ExpressionInternal>>,
+        scale: Option<f32>,
+        midpoint: Option<f32>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecayKind {
+    /// Linear decay
+    Lin,
+    /// Exponential decay
+    Exp,
+    /// Gaussian decay
+    Gauss,
+}
+
+/// A variable that takes its value from the point being scored
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VariableId {
+    /// Score of the point in the prefetch with this index
+    Score(usize),
+    /// Value of a payload field
+    Payload(JsonPath),
+}
+
+impl FromStr for VariableId {
+    type Err = CollectionError;
+
+    fn from_str(var: &str) -> Result<Self, Self::Err> {
+        let Some(score_ref) = var.strip_prefix(SCORE_VARIABLE) else {
+            let path = JsonPath::from_str(var).map_err(|_| {
+                CollectionError::bad_request(format!("Invalid payload key in formula: {var}"))
+            })?;
+            return Ok(VariableId::Payload(path));
+        };
+
+        if score_ref.is_empty() {
+            return Ok(VariableId::Score(0));
+        }
+
+        score_ref
+            .strip_prefix('[')
+            .and_then(|idx| idx.strip_suffix(']'))
+            .and_then(|idx| idx.parse().ok())
+            .map(VariableId::Score)
+            .ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Invalid score reference in formula: {var}, expected `{SCORE_VARIABLE}` or `{SCORE_VARIABLE}[<prefetch index>]`"
+                ))
+            })
+    }
+}
+
+impl std::fmt::Display for VariableId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableId::Score(0) => write!(f, "{SCORE_VARIABLE}"),
+            VariableId::Score(idx) => write!(f, "{SCORE_VARIABLE}[{idx}]"),
+            VariableId::Payload(path) => write!(f, "{path}"),
+        }
+    }
+}
+
+/// Formula ready to be evaluated for each point
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedFormula {
+    pub formula: ParsedExpression,
+    pub defaults: HashMap<VariableId, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParsedExpression {
+    Constant(f32),
+    Variable(VariableId),
+    Condition(Box<Condition>),
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+    },
+    Datetime(DateTimePayloadType),
+    DatetimeKey(JsonPath),
+    Mult(Vec<ParsedExpression>),
+    Sum(Vec<ParsedExpression>),
+    Neg(Box<ParsedExpression>),
+    Abs(Box<ParsedExpression>),
+    Div {
+        left: Box<ParsedExpression>,
+        right: Box<ParsedExpression>,
+        by_zero_default: Option<ScoreType>,
+    },
+    Sqrt(Box<ParsedExpression>),
+    Pow {
+        base: Box<ParsedExpression>,
+        exponent: Box<ParsedExpression>,
+    },
+    Exp(Box<ParsedExpression>),
+    Log10(Box<ParsedExpression>),
+    Ln(Box<ParsedExpression>),
+    Decay {
+        kind: DecayKind,
+        x: Box<ParsedExpression>,
+        target: Option<Box<ParsedExpression>>,
+        scale: f32,
+        midpoint: f32,
+    },
+}
+
+impl FormulaInternal {
+    pub fn parse(self) -> CollectionResult<ParsedFormula> {
+        let FormulaInternal { formula, defaults } = self;
+
+        let defaults = defaults
+            .into_iter()
+            .map(|(var, value)| Ok((VariableId::from_str(&var)?, value)))
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(ParsedFormula {
+            formula: formula.parse()?,
+            defaults,
+        })
+    }
+}
+
+impl ExpressionInternal {
+    fn parse(self) -> CollectionResult<ParsedExpression> {
+        let parse_boxed = |expr: Box<ExpressionInternal>| expr.parse().map(Box::new);
+
+        let parsed = match self {
+            ExpressionInternal::Constant(c) => ParsedExpression::Constant(c),
+            ExpressionInternal::Variable(var) => {
+                ParsedExpression::Variable(VariableId::from_str(&var)?)
+            }
+            ExpressionInternal::Condition(condition) => ParsedExpression::Condition(condition),
+            ExpressionInternal::GeoDistance { origin, key } => {
+                ParsedExpression::GeoDistance { origin, key }
+            }
+            ExpressionInternal::Datetime(datetime) => {
+                let datetime = DateTimePayloadType::from_str(&datetime).map_err(|_| {
+                    CollectionError::bad_request(format!(
+                        "'{datetime}' is not in a supported date/time format, please use RFC 3339"
+                    ))
+                })?;
+                ParsedExpression::Datetime(datetime)
+            }
+            ExpressionInternal::DatetimeKey(key) => ParsedExpression::DatetimeKey(key),
+            ExpressionInternal::Mult(exprs) => ParsedExpression::Mult(
+                exprs
+                    .into_iter()
+                    .map(ExpressionInternal::parse)
+                    .collect::<CollectionResult<_>>()?,
+            ),
+            ExpressionInternal::Sum(exprs) => ParsedExpression::Sum(
+                exprs
+                    .into_iter()
+                    .map(ExpressionInternal::parse)
+                    .collect::<CollectionResult<_>>()?,
+            ),
+            ExpressionInternal::Neg(expr) => ParsedExpression::Neg(parse_boxed(expr)?),
+            ExpressionInternal::Abs(expr) => ParsedExpression::Abs(parse_boxed(expr)?),
+            ExpressionInternal::Div {
+                left,
+                right,
+                by_zero_default,
+            } => ParsedExpression::Div {
+                left: parse_boxed(left)?,
+                right: parse_boxed(right)?,
+                by_zero_default,
+            },
+            ExpressionInternal::Sqrt(expr) => ParsedExpression::Sqrt(parse_boxed(expr)?),
+            ExpressionInternal::Pow { base, exponent } => ParsedExpression::Pow {
+                base: parse_boxed(base)?,
+                exponent: parse_boxed(exponent)?,
+            },
+            ExpressionInternal::Exp(expr) => ParsedExpression::Exp(parse_boxed(expr)?),
+            ExpressionInternal::Log10(expr) => ParsedExpression::Log10(parse_boxed(expr)?),
+            ExpressionInternal::Ln(expr) => ParsedExpression::Ln(parse_boxed(expr)?),
+            ExpressionInternal::Decay {
+                kind,
+                x,
+                target,
+                scale,
+                midpoint,
+            } => {
+                let scale = scale.unwrap_or(DEFAULT_DECAY_SCALE);
+                if !(scale > 0.0) {
+                    return Err(CollectionError::bad_request(format!(
+                        "Decay scale must be positive, got {scale}"
+                    )));
+                }
+
+                let midpoint = midpoint.unwrap_or(DEFAULT_DECAY_MIDPOINT);
+                if !(midpoint > 0.0 && midpoint < 1.0) {
+                    return Err(CollectionError::bad_request(format!(
+                        "Decay midpoint must be between 0.0 and 1.0 exclusive, got {midpoint}"
+                    )));
+                }
+
+                ParsedExpression::Decay {
+                    kind,
+                    x: parse_boxed(x)?,
+                    target: target.map(parse_boxed).transpose()?,
+                    scale,
+                    midpoint,
+                }
+            }
+        };
+
+        Ok(parsed)
+    }
+}
+
+impl From<ParsedFormula> for FormulaInternal {
+    fn from(value: ParsedFormula) -> Self {
+        let ParsedFormula { formula, defaults } = value;
+
+        Self {
+            formula: ExpressionInternal::from(formula),
+            defaults: defaults
+                .into_iter()
+                .map(|(var, value)| (var.to_string(), value))
+                .collect(),
+        }
+    }
+}
+
+impl From<ParsedExpression> for ExpressionInternal {
+    fn from(value: ParsedExpression) -> Self {
+        let boxed = |expr: Box<ParsedExpression>| Box::new(ExpressionInternal::from(*expr));
+
+        match value {
+            ParsedExpression::Constant(c) => ExpressionInternal::Constant(c),
+            ParsedExpression::Variable(var) => ExpressionInternal::Variable(var.to_string()),
+            ParsedExpression::Condition(condition) => ExpressionInternal::Condition(condition),
+            ParsedExpression::GeoDistance { origin, key } => {
+                ExpressionInternal::GeoDistance { origin, key }
+            }
+            ParsedExpression::Datetime(datetime) => {
+                ExpressionInternal::Datetime(datetime.0.to_rfc3339())
+            }
+            ParsedExpression::DatetimeKey(key) => ExpressionInternal::DatetimeKey(key),
+            ParsedExpression::Mult(exprs) => {
+                ExpressionInternal::Mult(exprs.into_iter().map(From::from).collect())
+            }
+            ParsedExpression::Sum(exprs) => {
+                ExpressionInternal::Sum(exprs.into_iter().map(From::from).collect())
+            }
+            ParsedExpression::Neg(expr) => ExpressionInternal::Neg(boxed(expr)),
+            ParsedExpression::Abs(expr) => ExpressionInternal::Abs(boxed(expr)),
+            ParsedExpression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => ExpressionInternal::Div {
+                left: boxed(left),
+                right: boxed(right),
+                by_zero_default,
+            },
+            ParsedExpression::Sqrt(expr) => ExpressionInternal::Sqrt(boxed(expr)),
+            ParsedExpression::Pow { base, exponent } => ExpressionInternal::Pow {
+                base: boxed(base),
+                exponent: boxed(exponent),
+            },
+            ParsedExpression::Exp(expr) => ExpressionInternal::Exp(boxed(expr)),
+            ParsedExpression::Log10(expr) => ExpressionInternal::Log10(boxed(expr)),
+            ParsedExpression::Ln(expr) => ExpressionInternal::Ln(boxed(expr)),
+            ParsedExpression::Decay {
+                kind,
+                x,
+                target,
+                scale,
+                midpoint,
+            } => ExpressionInternal::Decay {
+                kind,
+                x: boxed(x),
+                target: target.map(boxed),
+                scale: Some(scale),
+                midpoint: Some(midpoint),
+            },
+        }
+    }
+}
+
+/// Everything needed to evaluate a formula for a single point
+struct PointContext<'a> {
+    point_id: PointIdType,
+    /// Score of the point in each prefetch, if it was found there
+    scores: &'a [Option<ScoreType>],
+    payload: &'a Payload,
+    defaults: &'a HashMap<VariableId, Value>,
+}
+
+impl ParsedFormula {
+    /// Whether the formula needs the payload of the points to be evaluated
+    pub fn requires_payload(&self) -> bool {
+        self.formula.requires_payload()
+    }
+
+    /// Computes the score of a point.
+    ///
+    /// `scores` holds the score of the point in each of the prefetches, in the same order as the prefetches.
+    pub fn evaluate(
+        &self,
+        point_id: PointIdType,
+        scores: &[Option<ScoreType>],
+        payload: &Payload,
+    ) -> CollectionResult<ScoreType> {
+        let context = PointContext {
+            point_id,
+            scores,
+            payload,
+            defaults: &self.defaults,
+        };
+
+        let score = self.formula.evaluate(&context)?;
+
+        if !score.is_finite() {
+            return Err(CollectionError::bad_request(format!(
+                "Formula evaluated to a non-finite number for point {point_id}"
+            )));
+        }
+
+        Ok(score as ScoreType)
+    }
+}
+
+impl ParsedExpression {
+    fn requires_payload(&self) -> bool {
+        match self {
+            ParsedExpression::Constant(_) | ParsedExpression::Datetime(_) => false,
+            ParsedExpression::Variable(var) => matches!(var, VariableId::Payload(_)),
+            ParsedExpression::Condition(_)
+            | ParsedExpression::GeoDistance { .. }
+            | ParsedExpression::DatetimeKey(_) => true,
+            ParsedExpression::Mult(exprs) | ParsedExpression::Sum(exprs) => {
+                exprs.iter().any(ParsedExpression::requires_payload)
+            }
+            ParsedExpression::Neg(expr)
+            | ParsedExpression::Abs(expr)
+            | ParsedExpression::Sqrt(expr)
+            | ParsedExpression::Exp(expr)
+            | ParsedExpression::Log10(expr)
+            | ParsedExpression::Ln(expr) => expr.requires_payload(),
+            ParsedExpression::Div { left, right, .. } => {
+                left.requires_payload() || right.requires_payload()
+            }
+            ParsedExpression::Pow { base, exponent } => {
+                base.requires_payload() || exponent.requires_payload()
+            }
+            ParsedExpression::Decay { x, target, .. } => {
+                x.requires_payload() || target.as_ref().is_some_and(|t| t.requires_payload())
+            }
+        }
+    }
+
+    /// Evaluates the expression with double precision, as datetimes don't fit into `f32`
+    fn evaluate(&self, ctx: &PointContext) -> CollectionResult<f64> {
+        let value = match self {
+            ParsedExpression::Constant(c) => f64::from(*c),
+            ParsedExpression::Variable(var) => match var {
+                VariableId::Score(idx) => match ctx.scores.get(*idx) {
+                    None => {
+                        return Err(CollectionError::bad_request(format!(
+                            "Formula references {var}, but there are only {} prefetches",
+                            ctx.scores.len()
+                        )))
+                    }
+                    Some(Some(score)) => f64::from(*score),
+                    // The point was not found by this prefetch
+                    Some(None) => match ctx.defaults.get(var) {
+                        Some(default) => value_as_number(var, default)?,
+                        None => 0.0,
+                    },
+                },
+                VariableId::Payload(key) => {
+                    let value = payload_value(ctx, var, key)?;
+                    value_as_number(var, value)?
+                }
+            },
+            ParsedExpression::Condition(condition) => {
+                let filter = Filter::new_must(condition.as_ref().clone());
+                if check_point_payload(ctx.point_id, ctx.payload, &filter) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ParsedExpression::GeoDistance { origin, key } => {
+                let var = VariableId::Payload(key.clone());
+                let value = payload_value(ctx, &var, key)?;
+                let destination: GeoPoint =
+                    serde_json::from_value(value.clone()).map_err(|_| {
+                        CollectionError::bad_request(format!(
+                            "Expected a geo point in {var} for point {}, got {value}",
+                            ctx.point_id
+                        ))
+                    })?;
+                origin.haversine_distance(&destination)
+            }
+            ParsedExpression::Datetime(datetime) => datetime_as_seconds(datetime),
+            ParsedExpression::DatetimeKey(key) => {
+                let var = VariableId::Payload(key.clone());
+                let value = payload_value(ctx, &var, key)?;
+                let datetime = value
+                    .as_str()
+                    .and_then(|datetime| DateTimePayloadType::from_str(datetime).ok())
+                    .ok_or_else(|| {
+                        CollectionError::bad_request(format!(
+                            "Expected an RFC 3339 datetime in {var} for point {}, got {value}",
+                            ctx.point_id
+                        ))
+                    })?;
+                datetime_as_seconds(&datetime)
+            }
+            ParsedExpression::Mult(exprs) => {
+                let mut product = 1.0;
+                for expr in exprs {
+                    product *= expr.evaluate(ctx)?;
+                }
+                product
+            }
+            ParsedExpression::Sum(exprs) => {
+                let mut sum = 0.0;
+                for expr in exprs {
+                    sum += expr.evaluate(ctx)?;
+                }
+                sum
+            }
+            ParsedExpression::Neg(expr) => -expr.evaluate(ctx)?,
+            ParsedExpression::Abs(expr) => expr.evaluate(ctx)?.abs(),
+            ParsedExpression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => {
+                let left = left.evaluate(ctx)?;
+                let right = right.evaluate(ctx)?;
+                if right == 0.0 {
+                    match by_zero_default {
+                        Some(default) => f64::from(*default),
+                        None => {
+                            return Err(CollectionError::bad_request(format!(
+                                "Division by zero in formula for point {}",
+                                ctx.point_id
+                            )))
+                        }
+                    }
+                } else {
+                    left / right
+                }
+            }
+            ParsedExpression::Sqrt(expr) => expr.evaluate(ctx)?.sqrt(),
+            ParsedExpression::Pow { base, exponent } => {
+                base.evaluate(ctx)?.powf(exponent.evaluate(ctx)?)
+            }
+            ParsedExpression::Exp(expr) => expr.evaluate(ctx)?.exp(),
+            ParsedExpression::Log10(expr) => expr.evaluate(ctx)?.log10(),
+            ParsedExpression::Ln(expr) => expr.evaluate(ctx)?.ln(),
+            ParsedExpression::Decay {
+                kind,
+                x,
+                target,
+                scale,
+                midpoint,
+            } => {
+                let x = x.evaluate(ctx)?;
+                let target = match target {
+                    Some(target) => target.evaluate(ctx)?,
+                    None => 0.0,
+                };
+                decay(*kind, x, target, f64::from(*scale), f64::from(*midpoint))
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+/// Decay functions evaluate to 1.0 at the target, and to `midpoint` at `scale` distance from it.
+fn decay(kind: DecayKind, x: f64, target: f64, scale: f64, midpoint: f64) -> f64 {
+    let distance = (x - target).abs() / scale;
+    match kind {
+        DecayKind::Lin => (1.0 - (1.0 - midpoint) * distance).max(0.0),
+        DecayKind::Exp => midpoint.powf(distance),
+        DecayKind::Gauss => midpoint.powf(distance * distance),
+    }
+}
+
+fn datetime_as_seconds(datetime: &DateTimePayloadType) -> f64 {
+    // Qdrant's timestamps are in microseconds
+    datetime.timestamp() as f64 / 1_000_000.0
+}
+
+/// Looks up the first value of a payload field, falling back to the defaults
+fn payload_value<'a>(
+    ctx: &PointContext<'a>,
+    var: &VariableId,
+    key: &JsonPath,
+) -> CollectionResult<&'a Value> {
+    let values = ctx.payload.get_value(key);
+    let value = values
+        .iter()
+        .copied()
+        .flat_map(|value| match value {
+            Value::Array(array) => array.first(),
+            _ => Some(value),
+        })
+        .find(|value| !value.is_null());
+
+    value.or_else(|| ctx.defaults.get(var)).ok_or_else(|| {
+        CollectionError::bad_request(format!(
+            "No value found for {var} in point {}, and no default was provided",
+            ctx.point_id
+        ))
+    })
+}
+
+fn value_as_number(var: &VariableId, value: &Value) -> CollectionResult<f64> {
+    value.as_f64().ok_or_else(|| {
+        CollectionError::bad_request(format!("Expected a number for {var}, got {value}"))
+    })
+}
+
+pub mod from_rest {
+    use api::rest::schema as rest;
+
+    use super::*;
+
+    impl From<rest::FormulaQuery> for FormulaInternal {
+        fn from(value: rest::FormulaQuery) -> Self {
+            let rest::FormulaQuery { formula, defaults } = value;
+
+            Self {
+                formula: ExpressionInternal::from(formula),
+                defaults,
+            }
+        }
+    }
+
+    fn decay_from_rest(kind: DecayKind, params: rest::DecayParamsExpression) -> ExpressionInternal {
+        let rest::DecayParamsExpression {
+            x,
+            target,
+            scale,
+            midpoint,
+        } = params;
+
+        ExpressionInternal::Decay {
+            kind,
+            x: Box::new(ExpressionInternal::from(*x)),
+            target: target.map(|target| Box::new(ExpressionInternal::from(*target))),
+            scale,
+            midpoint,
+        }
+    }
+
+    impl From<rest::Expression> for ExpressionInternal {
+        fn from(value: rest::Expression) -> Self {
+            let boxed = |expr: Box<rest::Expression>| Box::new(ExpressionInternal::from(*expr));
+
+            match value {
+                rest::Expression::Constant(c) => ExpressionInternal::Constant(c),
+                rest::Expression::Variable(var) => ExpressionInternal::Variable(var),
+                rest::Expression::Condition(condition) => ExpressionInternal::Condition(condition),
+                rest::Expression::GeoDistance(rest::GeoDistance { geo_distance }) => {
+                    let rest::GeoDistanceParams { origin, to } = geo_distance;
+                    ExpressionInternal::GeoDistance { origin, key: to }
+                }
+                rest::Expression::Datetime(rest::DatetimeExpression { datetime }) => {
+                    ExpressionInternal::Datetime(datetime)
+                }
+                rest::Expression::DatetimeKey(rest::DatetimeKeyExpression { datetime_key }) => {
+                    ExpressionInternal::DatetimeKey(datetime_key)
+                }
+                rest::Expression::Mult(rest::MultExpression { mult }) => {
+                    ExpressionInternal::Mult(mult.into_iter().map(From::from).collect())
+                }
+                rest::Expression::Sum(rest::SumExpression { sum }) => {
+                    ExpressionInternal::Sum(sum.into_iter().map(From::from).collect())
+                }
+                rest::Expression::Neg(rest::NegExpression { neg }) => {
+                    ExpressionInternal::Neg(boxed(neg))
+                }
+                rest::Expression::Abs(rest::AbsExpression { abs }) => {
+                    ExpressionInternal::Abs(boxed(abs))
+                }
+                rest::Expression::Div(rest::DivExpression { div }) => {
+                    let rest::DivParams {
+                        left,
+                        right,
+                        by_zero_default,
+                    } = div;
+                    ExpressionInternal::Div {
+                        left: boxed(left),
+                        right: boxed(right),
+                        by_zero_default,
+                    }
+                }
+                rest::Expression::Sqrt(rest::SqrtExpression { sqrt }) => {
+                    ExpressionInternal::Sqrt(boxed(sqrt))
+                }
+                rest::Expression::Pow(rest::PowExpression { pow }) => {
+                    let rest::PowParams { base, exponent } = pow;
+                    ExpressionInternal::Pow {
+                        base: boxed(base),
+                        exponent: boxed(exponent),
+                    }
+                }
+                rest::Expression::Exp(rest::ExpExpression { exp }) => {
+                    ExpressionInternal::Exp(boxed(exp))
+                }
+                rest::Expression::Log10(rest::Log10Expression { log10 }) => {
+                    ExpressionInternal::Log10(boxed(log10))
+                }
+                rest::Expression::Ln(rest::LnExpression { ln }) => {
+                    ExpressionInternal::Ln(boxed(ln))
+                }
+                rest::Expression::LinDecay(rest::LinDecayExpression { lin_decay }) => {
+                    decay_from_rest(DecayKind::Lin, lin_decay)
+                }
+                rest::Expression::ExpDecay(rest::ExpDecayExpression { exp_decay }) => {
+                    decay_from_rest(DecayKind::Exp, exp_decay)
+                }
+                rest::Expression::GaussDecay(rest::GaussDecayExpression { gauss_decay }) => {
+                    decay_from_rest(DecayKind::Gauss, gauss_decay)
+                }
+            }
+        }
+    }
+}
+
+pub mod from_grpc {
+    use api::grpc::conversions::{json_path_from_proto, payload_to_proto, proto_to_payloads};
+    use api::grpc::qdrant as grpc;
+    use tonic::Status;
+
+    use super::*;
+
+    impl TryFrom<grpc::Formula> for FormulaInternal {
+        type Error = Status;
+
+        fn try_from(value: grpc::Formula) -> Result<Self, Self::Error> {
+            let grpc::Formula {
+                expression,
+                defaults,
+            } = value;
+
+            let expression = expression
+                .ok_or_else(|| Status::invalid_argument("Formula expression is missing"))?;
+
+            Ok(Self {
+                formula: ExpressionInternal::try_from(expression)?,
+                defaults: proto_to_payloads(defaults)?.0.into_iter().collect(),
+            })
+        }
+    }
+
+    impl TryFrom<grpc::Formula> for ParsedFormula {
+        type Error = Status;
+
+        fn try_from(value: grpc::Formula) -> Result<Self, Self::Error> {
+            FormulaInternal::try_from(value)?
+                .parse()
+                .map_err(|err| Status::invalid_argument(err.to_string()))
+        }
+    }
+
+    fn required_expression(
+        expression: Option<Box<grpc::Expression>>,
+        field: &str,
+    ) -> Result<Box<ExpressionInternal>, Status> {
+        let expression = expression
+            .ok_or_else(|| Status::invalid_argument(format!("Expression {field} is missing")))?;
+        Ok(Box::new(ExpressionInternal::try_from(*expression)?))
+    }
+
+    fn decay_from_grpc(
+        kind: DecayKind,
+        params: grpc::DecayParamsExpression,
+    ) -> Result<ExpressionInternal, Status> {
+        let grpc::DecayParamsExpression {
+            x,
+            target,
+            scale,
+            midpoint,
+        } = params;
+
+        Ok(ExpressionInternal::Decay {
+            kind,
+            x: required_expression(x, "x")?,
+            target: target
+                .map(|target| ExpressionInternal::try_from(*target).map(Box::new))
+                .transpose()?,
+            scale,
+            midpoint,
+        })
+    }
+
+    impl TryFrom<grpc::Expression> for ExpressionInternal {
+        type Error = Status;
+
+        fn try_from(value: grpc::Expression) -> Result<Self, Self::Error> {
+            use grpc::expression::Variant;
+
+            let variant = value
+                .variant
+                .ok_or_else(|| Status::invalid_argument("Expression variant is missing"))?;
+
+            let boxed =
+                |expr: Box<grpc::Expression>| ExpressionInternal::try_from(*expr).map(Box::new);
+
+            let expression = match variant {
+                Variant::Constant(c) => ExpressionInternal::Constant(c),
+                Variant::Variable(var) => ExpressionInternal::Variable(var),
+                Variant::Condition(condition) => {
+                    ExpressionInternal::Condition(Box::new(Condition::try_from(condition)?))
+                }
+                Variant::GeoDistance(grpc::GeoDistance { origin, to }) => {
+                    let origin = origin
+                        .ok_or_else(|| Status::invalid_argument("GeoDistance origin is missing"))?;
+                    ExpressionInternal::GeoDistance {
+                        origin: GeoPoint::from(origin),
+                        key: json_path_from_proto(&to)?,
+                    }
+                }
+                Variant::Datetime(datetime) => ExpressionInternal::Datetime(datetime),
+                Variant::DatetimeKey(key) => {
+                    ExpressionInternal::DatetimeKey(json_path_from_proto(&key)?)
+                }
+                Variant::Mult(grpc::MultExpression { mult }) => ExpressionInternal::Mult(
+                    mult.into_iter()
+                        .map(TryFrom::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                Variant::Sum(grpc::SumExpression { sum }) => ExpressionInternal::Sum(
+                    sum.into_iter()
+                        .map(TryFrom::try_from)
+                        .collect::<Result<_, _>>()?,
+                ),
+                Variant::Div(div) => {
+                    let grpc::DivExpression {
+                        left,
+                        right,
+                        by_zero_default,
+                    } = *div;
+                    ExpressionInternal::Div {
+                        left: required_expression(left, "left")?,
+                        right: required_expression(right, "right")?,
+                        by_zero_default,
+                    }
+                }
+                Variant::Neg(expr) => ExpressionInternal::Neg(boxed(expr)?),
+                Variant::Abs(expr) => ExpressionInternal::Abs(boxed(expr)?),
+                Variant::Sqrt(expr) => ExpressionInternal::Sqrt(boxed(expr)?),
+                Variant::Pow(pow) => {
+                    let grpc::PowExpression { base, exponent } = *pow;
+                    ExpressionInternal::Pow {
+                        base: required_expression(base, "base")?,
+                        exponent: required_expression(exponent, "exponent")?,
+                    }
+                }
+                Variant::Exp(expr) => ExpressionInternal::Exp(boxed(expr)?),
+                Variant::Log10(expr) => ExpressionInternal::Log10(boxed(expr)?),
+                Variant::Ln(expr) => ExpressionInternal::Ln(boxed(expr)?),
+                Variant::ExpDecay(params) => decay_from_grpc(DecayKind::Exp, *params)?,
+                Variant::GaussDecay(params) => decay_from_grpc(DecayKind::Gauss, *params)?,
+                Variant::LinDecay(params) => decay_from_grpc(DecayKind::Lin, *params)?,
+            };
+
+            Ok(expression)
+        }
+    }
+
+    impl From<ParsedFormula> for grpc::Formula {
+        fn from(value: ParsedFormula) -> Self {
+            let FormulaInternal { formula, defaults } = FormulaInternal::from(value);
+
+            Self {
+                expression: Some(grpc::Expression::from(formula)),
+                defaults: payload_to_proto(Payload(defaults.into_iter().collect())),
+            }
+        }
+    }
+
+    impl From<ExpressionInternal> for grpc::Expression {
+        fn from(value: ExpressionInternal) -> Self {
+            use grpc::expression::Variant;
+
+            let boxed = |expr: Box<ExpressionInternal>| Box::new(grpc::Expression::from(*expr));
+
+            let variant = match value {
+                ExpressionInternal::Constant(c) => Variant::Constant(c),
+                ExpressionInternal::Variable(var) => Variant::Variable(var),
+                ExpressionInternal::Condition(condition) => {
+                    Variant::Condition(grpc::Condition::from(*condition))
+                }
+                ExpressionInternal::GeoDistance { origin, key } => {
+                    Variant::GeoDistance(grpc::GeoDistance {
+                        origin: Some(grpc::GeoPoint::from(origin)),
+                        to: key.to_string(),
+                    })
+                }
+                ExpressionInternal::Datetime(datetime) => Variant::Datetime(datetime),
+                ExpressionInternal::DatetimeKey(key) => Variant::DatetimeKey(key.to_string()),
+                ExpressionInternal::Mult(exprs) => Variant::Mult(grpc::MultExpression {
+                    mult: exprs.into_iter().map(From::from).collect(),
+                }),
+                ExpressionInternal::Sum(exprs) => Variant::Sum(grpc::SumExpression {
+                    sum: exprs.into_iter().map(From::from).collect(),
+                }),
+                ExpressionInternal::Neg(expr) => Variant::Neg(boxed(expr)),
+                ExpressionInternal::Abs(expr) => Variant::Abs(boxed(expr)),
+                ExpressionInternal::Div {
+                    left,
+                    right,
+                    by_zero_default,
+                } => Variant::Div(Box::new(grpc::DivExpression {
+                    left: Some(boxed(left)),
+                    right: Some(boxed(right)),
+                    by_zero_default,
+                })),
+                ExpressionInternal::Sqrt(expr) => Variant::Sqrt(boxed(expr)),
+                ExpressionInternal::Pow { base, exponent } => {
+                    Variant::Pow(Box::new(grpc::PowExpression {
+                        base: Some(boxed(base)),
+                        exponent: Some(boxed(exponent)),
+                    }))
+                }
+                ExpressionInternal::Exp(expr) => Variant::Exp(boxed(expr)),
+                ExpressionInternal::Log10(expr) => Variant::Log10(boxed(expr)),
+                ExpressionInternal::Ln(expr) => Variant::Ln(boxed(expr)),
+                ExpressionInternal::Decay {
+                    kind,
+                    x,
+                    target,
+                    scale,
+                    midpoint,
+                } => {
+                    let params = Box::new(grpc::DecayParamsExpression {
+                        x: Some(boxed(x)),
+                        target: target.map(boxed),
+                        scale,
+                        midpoint,
+                    });
+                    match kind {
+                        DecayKind::Lin => Variant::LinDecay(params),
+                        DecayKind::Exp => Variant::ExpDecay(params),
+                        DecayKind::Gauss => Variant::GaussDecay(params),
+                    }
+                }
+            };
+
+            Self {
+                variant: Some(variant),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{FieldCondition, Match};
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse(formula: ExpressionInternal, defaults: HashMap<String, Value>) -> ParsedFormula {
+        FormulaInternal { formula, defaults }.parse().unwrap()
+    }
+
+    fn var(name: &str) -> Box<ExpressionInternal> {
+        Box::new(ExpressionInternal::Variable(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_variables() {
+        assert_eq!(
+            VariableId::from_str("$score").unwrap(),
+            VariableId::Score(0)
+        );
+        assert_eq!(
+            VariableId::from_str("$score[2]").unwrap(),
+            VariableId::Score(2)
+        );
+        assert_eq!(
+            VariableId::from_str("a.b[0]").unwrap(),
+            VariableId::Payload(JsonPath::from_str("a.b[0]").unwrap())
+        );
+        assert!(VariableId::from_str("$score[x]").is_err());
+        assert!(VariableId::from_str("$scores").is_err());
+
+        for var in ["$score", "$score[3]", "a.b"] {
+            assert_eq!(VariableId::from_str(var).unwrap().to_string(), var);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_formula() {
+        // 0.7 * $score + 0.2 * price + 0.5 if tag == "x"
+        let formula = parse(
+            ExpressionInternal::Sum(vec![
+                ExpressionInternal::Mult(vec![ExpressionInternal::Constant(0.7), *var("$score")]),
+                ExpressionInternal::Mult(vec![ExpressionInternal::Constant(0.2), *var("price")]),
+                ExpressionInternal::Mult(vec![
+                    ExpressionInternal::Constant(0.5),
+                    ExpressionInternal::Condition(Box::new(Condition::Field(
+                        FieldCondition::new_match(
+                            JsonPath::from_str("tag").unwrap(),
+                            Match::from("x".to_string()),
+                        ),
+                    ))),
+                ]),
+            ]),
+            HashMap::new(),
+        );
+        assert!(formula.requires_payload());
+
+        let payload = Payload::from(json!({"price": 10, "tag": "x"}));
+        let score = formula.evaluate(1.into(), &[Some(2.0)], &payload).unwrap();
+        assert!((score - (1.4 + 2.0 + 0.5)).abs() < 1e-5);
+
+        let payload = Payload::from(json!({"price": 10, "tag": "y"}));
+        let score = formula.evaluate(1.into(), &[Some(2.0)], &payload).unwrap();
+        assert!((score - (1.4 + 2.0)).abs() < 1e-5);
+
+        // Missing payload value without default
+        let payload = Payload::from(json!({"tag": "x"}));
+        assert!(formula.evaluate(1.into(), &[Some(2.0)], &payload).is_err());
+    }
+
+    #[test]
+    fn test_defaults_and_missing_scores() {
+        let formula = parse(
+            ExpressionInternal::Sum(vec![*var("$score"), *var("$score[1]"), *var("boost")]),
+            HashMap::from([("boost".to_string(), json!(3.0))]),
+        );
+
+        let payload = Payload::default();
+        let score = formula
+            .evaluate(1.into(), &[Some(1.0), None], &payload)
+            .unwrap();
+        assert_eq!(score, 4.0);
+
+        let score = formula
+            .evaluate(1.into(), &[Some(1.0), Some(0.5)], &payload)
+            .unwrap();
+        assert_eq!(score, 4.5);
+
+        // Out of bounds prefetch reference
+        assert!(formula.evaluate(1.into(), &[Some(1.0)], &payload).is_err());
+    }
+
+    #[test]
+    fn test_decay_functions() {
+        for kind in [DecayKind::Lin, DecayKind::Exp, DecayKind::Gauss] {
+            assert_eq!(decay(kind, 5.0, 5.0, 2.0, 0.3), 1.0);
+            assert!((decay(kind, 7.0, 5.0, 2.0, 0.3) - 0.3).abs() < 1e-9);
+            assert!((decay(kind, 3.0, 5.0, 2.0, 0.3) - 0.3).abs() < 1e-9);
+        }
+
+        // Linear decay reaches zero
+        assert_eq!(decay(DecayKind::Lin, 100.0, 0.0, 1.0, 0.5), 0.0);
+
+        let invalid_midpoint = FormulaInternal {
+            formula: ExpressionInternal::Decay {
+                kind: DecayKind::Exp,
+                x: var("$score"),
+                target: None,
+                scale: None,
+                midpoint: Some(1.0),
+            },
+            defaults: HashMap::new(),
+        };
+        assert!(invalid_midpoint.parse().is_err());
+    }
+
+    #[test]
+    fn test_datetime_and_geo() {
+        // Decays by half each day since the target date
+        let formula = parse(
+            ExpressionInternal::Decay {
+                kind: DecayKind::Exp,
+                x: Box::new(ExpressionInternal::DatetimeKey(
+                    JsonPath::from_str("published_at").unwrap(),
+                )),
+                target: Some(Box::new(ExpressionInternal::Datetime(
+                    "2024-01-10T00:00:00Z".to_string(),
+                ))),
+                scale: Some(86400.0),
+                midpoint: Some(0.5),
+            },
+            HashMap::new(),
+        );
+
+        let payload = Payload::from(json!({"published_at": "2024-01-08T00:00:00Z"}));
+        let score = formula.evaluate(1.into(), &[], &payload).unwrap();
+        assert!((score - 0.25).abs() < 1e-6);
+
+        let formula = parse(
+            ExpressionInternal::GeoDistance {
+                origin: GeoPoint { lon: 0.0, lat: 0.0 },
+                key: JsonPath::from_str("location").unwrap(),
+            },
+            HashMap::new(),
+        );
+        let payload = Payload::from(json!({"location": {"lon": 0.5, "lat": 0.5}}));
+        let distance = formula.evaluate(1.into(), &[], &payload).unwrap();
+        assert!((distance - 78626.3).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_parsed_formula_roundtrip() {
+        let formula = parse(
+            ExpressionInternal::Div {
+                left: var("$score[1]"),
+                right: Box::new(ExpressionInternal::Datetime(
+                    "2024-01-10T00:00:00Z".to_string(),
+                )),
+                by_zero_default: Some(1.0),
+            },
+            HashMap::from([("$score[1]".to_string(), json!(0.5))]),
+        );
+
+        let reparsed = FormulaInternal::from(formula.clone()).parse().unwrap();
+        assert_eq!(formula, reparsed);
+    }
+}
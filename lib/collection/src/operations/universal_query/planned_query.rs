@@ -1,12 +1,12 @@
 //! Types used within `LocalShard` to represent a planned `ShardQueryRequest`
 
-use api::rest::OrderByInterface;
 use common::types::ScoreType;
 use segment::types::{Filter, WithPayloadInterface, WithVector};
 
 use super::shard_query::{ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, QueryScrollRequestInternal,
+    QueryTextRequestInternal, ScrollOrder,
 };
 
 const MAX_PREFETCH_DEPTH: usize = 64;
@@ -26,6 +26,9 @@ pub struct PlannedQuery {
 
     /// All the leaf scrolls
     pub scrolls: Vec<QueryScrollRequestInternal>,
+
+    /// All the leaf text relevance searches
+    pub text_searches: Vec<QueryTextRequestInternal>,
 }
 
 /// Defines how to merge multiple [sources](Source)
@@ -55,6 +58,9 @@ pub enum Source {
     /// A reference offset into the scrolls list
     ScrollsIdx(usize),
 
+    /// A reference offset into the text searches list
+    TextSearchesIdx(usize),
+
     /// A nested prefetch
     Prefetch(MergePlan),
 }
@@ -110,6 +116,7 @@ impl PlannedQuery {
                 let sources = recurse_prefetches(
                     &mut self.searches,
                     &mut self.scrolls,
+                    &mut self.text_searches,
                     prefetches,
                     offset,
                     filter,
@@ -126,12 +133,24 @@ impl PlannedQuery {
                 let sources = recurse_prefetches(
                     &mut self.searches,
                     &mut self.scrolls,
+                    &mut self.text_searches,
                     prefetches,
                     offset,
                     filter,
                     None,
                 )?;
 
+                // MMR candidates are only rescored by the nearest search,
+                // re-ranking happens at collection level and needs their vectors
+                let (rescore, limit, with_vector) = match rescore {
+                    ScoringQuery::Mmr(mmr) => (
+                        ScoringQuery::Vector(mmr.nearest_query()),
+                        mmr.candidates_limit,
+                        mmr.with_candidates_vector(with_vector),
+                    ),
+                    rescore => (rescore, limit, with_vector),
+                };
+
                 MergePlan {
                     sources,
                     rescore_params: Some(RescoreParams {
@@ -163,15 +182,69 @@ impl PlannedQuery {
 
                     vec![Source::SearchesIdx(idx)]
                 }
+                Some(ScoringQuery::Mmr(mmr)) => {
+                    // Candidates come from 1 core search, they are re-ranked at collection level
+                    let core_search = CoreSearchRequest {
+                        query: mmr.nearest_query(),
+                        filter,
+                        score_threshold,
+                        with_vector: Some(mmr.with_candidates_vector(with_vector)),
+                        with_payload: Some(with_payload),
+                        offset: 0,
+                        params,
+                        limit: mmr.candidates_limit,
+                    };
+
+                    let idx = self.searches.len();
+                    self.searches.push(core_search);
+
+                    vec![Source::SearchesIdx(idx)]
+                }
                 Some(ScoringQuery::Fusion(_)) => {
                     return Err(CollectionError::bad_request(
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
+                Some(ScoringQuery::Text(text_query)) => {
+                    // Everything should come from 1 text search
+                    let text_search = QueryTextRequestInternal {
+                        query: text_query,
+                        limit,
+                        filter,
+                        score_threshold,
+                        with_payload,
+                        with_vector,
+                    };
+
+                    let idx = self.text_searches.len();
+                    self.text_searches.push(text_search);
+
+                    vec![Source::TextSearchesIdx(idx)]
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
-                        order_by: Some(OrderByInterface::Struct(order_by)),
+                        scroll_order: ScrollOrder::ByField(order_by),
+                        limit,
+                        filter,
+                        with_vector,
+                        with_payload,
+                    };
+
+                    let idx = self.scrolls.len();
+                    self.scrolls.push(scroll);
+
+                    vec![Source::ScrollsIdx(idx)]
+                }
+                Some(ScoringQuery::Sample(sample)) => {
+                    // Everything should come from 1 random scroll
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::from(sample),
                         limit,
                         filter,
                         with_vector,
@@ -186,7 +259,7 @@ impl PlannedQuery {
                 None => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
-                        order_by: None,
+                        scroll_order: ScrollOrder::ById,
                         limit,
                         filter,
                         with_vector,
@@ -217,6 +290,7 @@ impl PlannedQuery {
 fn recurse_prefetches(
     core_searches: &mut Vec<CoreSearchRequest>,
     scrolls: &mut Vec<QueryScrollRequestInternal>,
+    text_searches: &mut Vec<QueryTextRequestInternal>,
     prefetches: Vec<ShardPrefetch>,
     root_offset: usize, // Offset is added to all prefetches, so we make sure we have enough
     propagate_filter: Option<Filter>, // Global filter to apply to all prefetches
@@ -239,6 +313,13 @@ fn recurse_prefetches(
             score_threshold,
         } = prefetch;
 
+        // MMR re-ranking happens only at collection level, after merging the shard results
+        if let Some(ScoringQuery::Mmr(_)) = query {
+            return Err(CollectionError::bad_request(
+                "cannot apply MMR in prefetches".to_string(),
+            ));
+        }
+
         // Offset is replicated at each step from the root to the leaves
         let limit = prefetch_limit + root_offset;
 
@@ -250,6 +331,7 @@ fn recurse_prefetches(
             let inner_sources = recurse_prefetches(
                 core_searches,
                 scrolls,
+                text_searches,
                 prefetches,
                 root_offset,
                 filter,
@@ -292,14 +374,49 @@ fn recurse_prefetches(
 
                     Source::SearchesIdx(idx)
                 }
+                Some(ScoringQuery::Mmr(_)) => unreachable!("MMR in prefetches is rejected above"),
                 Some(ScoringQuery::Fusion(_)) => {
                     return Err(CollectionError::bad_request(
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
+                Some(ScoringQuery::Text(text_query)) => {
+                    let text_search = QueryTextRequestInternal {
+                        query: text_query,
+                        limit,
+                        filter,
+                        score_threshold,
+                        with_payload: with_payload.clone(),
+                        with_vector: with_vector.clone(),
+                    };
+
+                    let idx = text_searches.len();
+                    text_searches.push(text_search);
+
+                    Source::TextSearchesIdx(idx)
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     let scroll = QueryScrollRequestInternal {
-                        order_by: Some(OrderByInterface::Struct(order_by)),
+                        scroll_order: ScrollOrder::ByField(order_by),
+                        filter,
+                        with_vector: with_vector.clone(),
+                        with_payload: with_payload.clone(),
+                        limit,
+                    };
+
+                    let idx = scrolls.len();
+                    scrolls.push(scroll);
+
+                    Source::ScrollsIdx(idx)
+                }
+                Some(ScoringQuery::Sample(sample)) => {
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::from(sample),
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
@@ -313,7 +430,7 @@ fn recurse_prefetches(
                 }
                 None => {
                     let scroll = QueryScrollRequestInternal {
-                        order_by: None,
+                        scroll_order: ScrollOrder::ById,
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
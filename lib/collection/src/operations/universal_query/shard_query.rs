@@ -1,15 +1,19 @@
 use api::grpc::qdrant as grpc;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::reciprocal_rank_fusion::rrf_scoring;
+use segment::common::score_fusion::{score_fusion, ScoreFusion};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{NamedQuery, NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
 use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
 use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
 use tonic::Status;
 
+use super::formula::ParsedFormula;
 use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionResult, ScrollOrder};
 
 /// Internal response type for a universal query request.
 ///
@@ -45,7 +49,27 @@ impl ShardQueryRequest {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Fusion {
+    /// Reciprocal rank fusion
     Rrf,
+    /// Distribution-based score fusion
+    Dbsf,
+    /// Weighted sum of the min-max normalized scores, one weight per prefetch
+    WeightedSum { weights: Vec<f32> },
+}
+
+impl Fusion {
+    /// Combines the results of each prefetch into a single list, sorted from best to worst.
+    ///
+    /// Expects the sources in the same order as the prefetches.
+    pub fn fuse(&self, sources: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+        match self {
+            Fusion::Rrf => rrf_scoring(sources),
+            Fusion::Dbsf => score_fusion(sources, ScoreFusion::dbsf()),
+            Fusion::WeightedSum { weights } => {
+                score_fusion(sources, ScoreFusion::weighted_sum(weights.clone()))
+            }
+        }
+    }
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -54,20 +78,96 @@ pub enum ScoringQuery {
     /// Score points against some vector(s)
     Vector(QueryEnum),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Score the results of the prefetches with a formula
+    Formula(ParsedFormula),
+
+    /// Score points by BM25 relevance of the query text
+    Text(TextQuery),
+
+    /// Find the nearest candidates, to be re-ranked with MMR after merging the shard results
+    Mmr(MmrInternal),
+
+    /// Sample points, scored by their random sample keys
+    Sample(SampleInternal),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleInternal {
+    /// Uniformly random sample of the matching points.
+    ///
+    /// The seed is fixed before the request is sent to the shards, so that their samples can be merged.
+    Random { seed: u64 },
+}
+
+impl From<SampleInternal> for ScrollOrder {
+    fn from(sample: SampleInternal) -> Self {
+        match sample {
+            SampleInternal::Random { seed } => ScrollOrder::Random { seed },
+        }
+    }
+}
+
+/// Maximal Marginal Relevance re-ranking of the nearest candidates.
+///
+/// Shards only fetch the candidates together with their `using` vector,
+/// the re-ranking itself happens at collection level, so that the diversification is global.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MmrInternal {
+    /// Query vector, also used to calculate the relevance of the candidates
+    pub vector: Vector,
+
+    /// Name of the vector to search and to diversify by
+    pub using: String,
+
+    /// Weight of the relevance to the query, `1 - diversity`
+    pub lambda: f32,
+
+    /// Number of nearest candidates to fetch for re-ranking
+    pub candidates_limit: usize,
+}
+
+impl MmrInternal {
+    /// The nearest search which fetches the candidates
+    pub fn nearest_query(&self) -> QueryEnum {
+        QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+            self.vector.clone(),
+            self.using.clone(),
+        ))
+    }
+
+    /// Make sure the `using` vector is returned with the candidates, as re-ranking needs it
+    pub fn with_candidates_vector(&self, with_vector: WithVector) -> WithVector {
+        match with_vector {
+            WithVector::Bool(true) => WithVector::Bool(true),
+            WithVector::Bool(false) => WithVector::Selector(vec![self.using.clone()]),
+            WithVector::Selector(mut names) => {
+                if !names.contains(&self.using) {
+                    names.push(self.using.clone());
+                }
+                WithVector::Selector(names)
+            }
+        }
+    }
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
             ScoringQuery::Fusion(fusion) => match fusion {
-                Fusion::Rrf => true,
+                Fusion::Rrf | Fusion::Dbsf | Fusion::WeightedSum { .. } => true,
             },
-            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) => false,
+            ScoringQuery::Vector(_)
+            | ScoringQuery::OrderBy(_)
+            | ScoringQuery::Formula(_)
+            | ScoringQuery::Text(_)
+            | ScoringQuery::Mmr(_)
+            | ScoringQuery::Sample(_) => false,
         }
     }
 
@@ -75,6 +175,7 @@ impl ScoringQuery {
     pub fn get_vector_name(&self) -> Option<&str> {
         match self {
             ScoringQuery::Vector(query) => Some(query.get_vector_name()),
+            ScoringQuery::Mmr(mmr) => Some(&mmr.using),
             _ => None,
         }
     }
@@ -96,9 +197,16 @@ impl ScoringQuery {
                     }
                 }
                 ScoringQuery::Fusion(fusion) => match fusion {
-                    Fusion::Rrf => Order::LargeBetter,
+                    Fusion::Rrf | Fusion::Dbsf | Fusion::WeightedSum { .. } => Order::LargeBetter,
                 },
                 ScoringQuery::OrderBy(order_by) => Order::from(order_by.direction()),
+                ScoringQuery::Formula(_) | ScoringQuery::Text(_) => Order::LargeBetter,
+                // Points with the smallest sample keys are sampled first
+                ScoringQuery::Sample(_) => Order::SmallBetter,
+                // Candidates are merged by their similarity to the query
+                ScoringQuery::Mmr(mmr) => {
+                    collection_params.get_distance(&mmr.using)?.distance_order()
+                }
             },
             None => {
                 // Order by ID
@@ -292,14 +400,58 @@ impl From<api::grpc::qdrant::Fusion> for Fusion {
     fn from(fusion: api::grpc::qdrant::Fusion) -> Self {
         match fusion {
             api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf,
+            api::grpc::qdrant::Fusion::Dbsf => Fusion::Dbsf,
+        }
+    }
+}
+
+impl TryFrom<grpc::SampleQuery> for SampleInternal {
+    type Error = Status;
+
+    fn try_from(sample: grpc::SampleQuery) -> Result<Self, Self::Error> {
+        let grpc::SampleQuery { sample, seed } = sample;
+
+        let sample = grpc::Sample::from_i32(sample).ok_or_else(|| {
+            Status::invalid_argument(format!("invalid sample type value {sample}"))
+        })?;
+        // Shards of the same request must sample with the same seed
+        let seed = seed.ok_or_else(|| Status::invalid_argument("missing field: sample.seed"))?;
+
+        let sample = match sample {
+            grpc::Sample::Random => SampleInternal::Random { seed },
+        };
+        Ok(sample)
+    }
+}
+
+impl From<SampleInternal> for grpc::SampleQuery {
+    fn from(sample: SampleInternal) -> Self {
+        match sample {
+            SampleInternal::Random { seed } => grpc::SampleQuery {
+                sample: grpc::Sample::Random as i32,
+                seed: Some(seed),
+            },
         }
     }
 }
 
-impl From<Fusion> for api::grpc::qdrant::Fusion {
+impl From<grpc::WeightedSumFusion> for Fusion {
+    fn from(weighted_sum: grpc::WeightedSumFusion) -> Self {
+        let grpc::WeightedSumFusion { weights } = weighted_sum;
+        Fusion::WeightedSum { weights }
+    }
+}
+
+impl From<Fusion> for grpc::query_shard_points::query::Score {
     fn from(fusion: Fusion) -> Self {
+        use grpc::query_shard_points::query::Score;
+
         match fusion {
-            Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
+            Fusion::Rrf => Score::Fusion(api::grpc::qdrant::Fusion::Rrf as i32),
+            Fusion::Dbsf => Score::Fusion(api::grpc::qdrant::Fusion::Dbsf as i32),
+            Fusion::WeightedSum { weights } => {
+                Score::WeightedSum(grpc::WeightedSumFusion { weights })
+            }
         }
     }
 }
@@ -322,6 +474,33 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
+            grpc::query_shard_points::query::Score::WeightedSum(weighted_sum) => {
+                ScoringQuery::Fusion(Fusion::from(weighted_sum))
+            }
+            grpc::query_shard_points::query::Score::Formula(formula) => {
+                ScoringQuery::Formula(ParsedFormula::try_from(formula)?)
+            }
+            grpc::query_shard_points::query::Score::Text(text) => {
+                ScoringQuery::Text(TextQuery::try_from(text)?)
+            }
+            grpc::query_shard_points::query::Score::Mmr(mmr) => {
+                let grpc::MmrInternal {
+                    vector,
+                    lambda,
+                    candidates_limit,
+                } = mmr;
+                let vector =
+                    vector.ok_or_else(|| Status::invalid_argument("missing field: mmr.vector"))?;
+                ScoringQuery::Mmr(MmrInternal {
+                    vector: Vector::try_from(vector)?,
+                    using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+                    lambda,
+                    candidates_limit: candidates_limit as usize,
+                })
+            }
+            grpc::query_shard_points::query::Score::Sample(sample) => {
+                ScoringQuery::Sample(SampleInternal::try_from(sample)?)
+            }
         };
 
         Ok(scoring_query)
@@ -360,11 +539,35 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
                 score: Some(Score::Vector(grpc::RawQuery::from(query))),
             },
             ScoringQuery::Fusion(fusion) => Self {
-                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::from(fusion) as i32)),
+                score: Some(Score::from(fusion)),
             },
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Formula(formula) => Self {
+                score: Some(Score::Formula(grpc::Formula::from(formula))),
+            },
+            ScoringQuery::Text(text) => Self {
+                score: Some(Score::Text(grpc::TextQuery::from(text))),
+            },
+            ScoringQuery::Mmr(mmr) => {
+                let MmrInternal {
+                    vector,
+                    using: _, // sent as the `using` of the query
+                    lambda,
+                    candidates_limit,
+                } = mmr;
+                Self {
+                    score: Some(Score::Mmr(grpc::MmrInternal {
+                        vector: Some(grpc::RawVector::from(vector)),
+                        lambda,
+                        candidates_limit: candidates_limit as u64,
+                    })),
+                }
+            }
+            ScoringQuery::Sample(sample) => Self {
+                score: Some(Score::Sample(grpc::SampleQuery::from(sample))),
+            },
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::num::NonZeroU64;
 
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
+use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, TruncatedViewConfig};
 
 use crate::operations::config_diff::HnswConfigDiff;
 use crate::operations::types::{Datatype, VectorParams};
@@ -20,6 +20,7 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivector_config: None,
+                truncated_view: None,
             },
         }
     }
@@ -49,6 +50,13 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_truncated_view(mut self, source: impl Into<String>) -> Self {
+        self.vector_params.truncated_view = Some(TruncatedViewConfig {
+            source: source.into(),
+        });
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
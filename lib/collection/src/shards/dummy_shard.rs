@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -110,4 +111,13 @@ impl ShardOperation for DummyShard {
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         self.dummy()
     }
+
+    async fn facet(
+        &self,
+        _: Arc<FacetParams>,
+        _search_runtime_handle: &Handle,
+        _timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.dummy()
+    }
 }
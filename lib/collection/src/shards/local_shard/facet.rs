@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use segment::data_types::facets::{FacetParams, FacetResponse};
+use tokio::runtime::Handle;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl LocalShard {
+    /// Count values of the facet field in all segments and take the most frequent of them.
+    pub(super) async fn do_facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+        let stopping_guard = StoppingGuard::new();
+
+        let (non_appendable, appendable) = self.segments().read().split_segments();
+
+        let facet = |segment: LockedSegment| {
+            let request = request.clone();
+            let is_stopped = stopping_guard.get_is_stopped();
+
+            search_runtime_handle
+                .spawn_blocking(move || segment.get().read().facet(&request, &is_stopped))
+        };
+
+        let all_reads = async {
+            let non_appendable = try_join_all(non_appendable.into_iter().map(facet)).await?;
+            let appendable = try_join_all(appendable.into_iter().map(facet)).await?;
+            CollectionResult::Ok(non_appendable.into_iter().chain(appendable))
+        };
+
+        let all_reads = tokio::time::timeout(timeout, all_reads)
+            .await
+            .map_err(|_| {
+                log::debug!("Facet timeout reached: {} seconds", timeout.as_secs());
+                CollectionError::timeout(timeout.as_secs() as usize, "Facet")
+            })??;
+
+        // Points are not duplicated across segments of a consistent shard,
+        // so counts of all segments are simply summed up
+        let mut counts = HashMap::new();
+        for segment_counts in all_reads {
+            for (value, count) in segment_counts? {
+                *counts.entry(value).or_insert(0) += count;
+            }
+        }
+
+        Ok(FacetResponse::top_hits(counts, request.limit))
+    }
+}
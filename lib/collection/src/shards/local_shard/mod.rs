@@ -1,9 +1,11 @@
 pub mod clock_map;
 pub mod disk_usage_watcher;
+pub(super) mod facet;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
 pub(super) mod shard_ops;
+pub(super) mod text_search;
 
 use std::collections::{BTreeSet, HashMap};
 use std::mem::size_of;
@@ -27,8 +29,8 @@ use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, MultiVectorPoolingInfo, PayloadIndexInfo, PayloadKeyType,
+    PayloadStorageType, PointIdType, QuantizationConfig, SegmentConfig, SegmentType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file};
@@ -974,6 +976,7 @@ impl LocalShard {
         let mut segments_count = 0;
         let mut status = CollectionStatus::Green;
         let mut schema: HashMap<PayloadKeyType, PayloadIndexInfo> = Default::default();
+        let mut multivector_pooling: HashMap<String, MultiVectorPoolingInfo> = Default::default();
         let mut optimizer_status = OptimizersStatus::Ok;
 
         {
@@ -995,6 +998,11 @@ impl LocalShard {
                         .and_modify(|entry| entry.points += val.points)
                         .or_insert(val);
                 }
+                for (name, vector_data) in segment_info.vector_data {
+                    if let Some(pooling) = vector_data.pooling {
+                        *multivector_pooling.entry(name).or_default() += pooling;
+                    }
+                }
             }
             if !segments.failed_operation.is_empty() || segments.optimizer_errors.is_some() {
                 status = CollectionStatus::Red;
@@ -1027,6 +1035,7 @@ impl LocalShard {
             segments_count,
             config: collection_config,
             payload_schema: schema,
+            multivector_pooling,
         }
     }
 
@@ -1,41 +1,52 @@
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
-use api::rest::OrderByInterface;
+use common::types::ScoreType;
 use futures::future::BoxFuture;
 use futures::FutureExt;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
+use segment::types::{
+    Filter, HasIdCondition, Payload, PointIdType, ScoredPoint, SeqNumberType, WithPayload,
+    WithPayloadInterface, WithVector,
+};
+use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::runtime::Handle;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    QueryScrollRequestInternal,
+    QueryScrollRequestInternal, QueryTextRequestInternal, ScrollOrder,
 };
+use crate::operations::universal_query::formula::ParsedFormula;
 use crate::operations::universal_query::planned_query::{
     MergePlan, PlannedQuery, RescoreParams, Source,
 };
-use crate::operations::universal_query::shard_query::{Fusion, ScoringQuery, ShardQueryResponse};
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryResponse};
 
 pub enum FetchedSource {
     Search(usize),
     Scroll(usize),
+    TextSearch(usize),
 }
 
 struct PrefetchResults {
     search_results: Vec<Vec<ScoredPoint>>,
     scroll_results: Vec<Vec<ScoredPoint>>,
+    text_search_results: Vec<Vec<ScoredPoint>>,
 }
 
 impl PrefetchResults {
-    fn new(search_results: Vec<Vec<ScoredPoint>>, scroll_results: Vec<Vec<ScoredPoint>>) -> Self {
+    fn new(
+        search_results: Vec<Vec<ScoredPoint>>,
+        scroll_results: Vec<Vec<ScoredPoint>>,
+        text_search_results: Vec<Vec<ScoredPoint>>,
+    ) -> Self {
         Self {
             search_results,
             scroll_results,
+            text_search_results,
         }
     }
 
@@ -43,6 +54,7 @@ impl PrefetchResults {
         match element {
             FetchedSource::Search(idx) => self.search_results.get(idx).map(Cow::Borrowed),
             FetchedSource::Scroll(idx) => self.scroll_results.get(idx).map(Cow::Borrowed),
+            FetchedSource::TextSearch(idx) => self.text_search_results.get(idx).map(Cow::Borrowed),
         }
         .ok_or_else(|| CollectionError::service_error("Expected a prefetched source to exist"))
     }
@@ -69,9 +81,17 @@ impl LocalShard {
         let scrolls_f =
             self.query_scroll_batch(Arc::new(request.scrolls), search_runtime_handle, timeout);
 
-        // execute both searches and scrolls concurrently
-        let (search_results, scroll_results) = tokio::try_join!(searches_f, scrolls_f)?;
-        let prefetch_holder = PrefetchResults::new(search_results, scroll_results);
+        let text_searches_f = self.query_text_batch(
+            Arc::new(request.text_searches),
+            search_runtime_handle,
+            timeout,
+        );
+
+        // execute searches, scrolls and text searches concurrently
+        let (search_results, scroll_results, text_search_results) =
+            tokio::try_join!(searches_f, scrolls_f, text_searches_f)?;
+        let prefetch_holder =
+            PrefetchResults::new(search_results, scroll_results, text_search_results);
 
         // decrease timeout by the time spent so far
         let timeout = timeout.saturating_sub(start_time.elapsed());
@@ -156,6 +176,9 @@ impl LocalShard {
                     Source::ScrollsIdx(idx) => {
                         cow_sources.push(prefetch_holder.get(FetchedSource::Scroll(idx))?)
                     }
+                    Source::TextSearchesIdx(idx) => {
+                        cow_sources.push(prefetch_holder.get(FetchedSource::TextSearch(idx))?)
+                    }
                     Source::Prefetch(prefetch) => {
                         let merged = self
                             .recurse_prefetch(
@@ -213,26 +236,37 @@ impl LocalShard {
         } = rescore_params;
 
         match rescore {
-            ScoringQuery::Fusion(Fusion::Rrf) => {
+            ScoringQuery::Fusion(fusion) => {
                 let sources: Vec<_> = sources.map(Cow::into_owned).collect();
 
-                let top_rrf = rrf_scoring(sources);
+                let top_fused = fusion.fuse(sources);
 
-                let top_rrf: Vec<_> = if let Some(score_threshold) = score_threshold {
-                    top_rrf
+                let top_fused: Vec<_> = if let Some(score_threshold) = score_threshold {
+                    top_fused
                         .into_iter()
                         .take_while(|point| point.score >= score_threshold)
                         .take(limit)
                         .collect()
                 } else {
-                    top_rrf.into_iter().take(limit).collect()
+                    top_fused.into_iter().take(limit).collect()
                 };
 
-                let filled_top_rrf = self
-                    .fill_with_payload_or_vectors(top_rrf, with_payload, with_vector)
+                let filled_top_fused = self
+                    .fill_with_payload_or_vectors(top_fused, with_payload, with_vector)
                     .await?;
 
-                Ok(filled_top_rrf)
+                Ok(filled_top_fused)
+            }
+            ScoringQuery::Formula(formula) => {
+                self.rescore_with_formula(
+                    formula,
+                    sources,
+                    score_threshold,
+                    limit,
+                    with_payload,
+                    with_vector,
+                )
+                .await
             }
             ScoringQuery::OrderBy(order_by) => {
                 // create single scroll request for rescoring query
@@ -245,7 +279,7 @@ impl LocalShard {
                     filter: Some(filter),
                     with_payload,
                     with_vector,
-                    order_by: Some(OrderByInterface::Struct(order_by)),
+                    scroll_order: ScrollOrder::ByField(order_by),
                 };
 
                 self.query_scroll_batch(
@@ -261,6 +295,56 @@ impl LocalShard {
                     )
                 })
             }
+            ScoringQuery::Text(text_query) => {
+                // create single text search request for rescoring query
+                let filter = filter_with_sources_ids(sources);
+
+                let text_request = QueryTextRequestInternal {
+                    query: text_query,
+                    limit,
+                    filter: Some(filter),
+                    score_threshold,
+                    with_payload,
+                    with_vector,
+                };
+
+                self.query_text_batch(Arc::new(vec![text_request]), search_runtime_handle, timeout)
+                    .await?
+                    .pop()
+                    .ok_or_else(|| {
+                        CollectionError::service_error(
+                            "Rescoring with text query didn't return expected batch of results",
+                        )
+                    })
+            }
+            ScoringQuery::Sample(sample) => {
+                // create single random scroll request over the sources
+                let filter = filter_with_sources_ids(sources);
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload,
+                    with_vector,
+                    scroll_order: ScrollOrder::from(sample),
+                };
+
+                self.query_scroll_batch(
+                    Arc::new(vec![scroll_request]),
+                    search_runtime_handle,
+                    timeout,
+                )
+                .await?
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with sample query didn't return expected batch of results",
+                    )
+                })
+            }
+            ScoringQuery::Mmr(_) => Err(CollectionError::service_error(
+                "MMR candidates are expected to be rescored with the nearest query",
+            )),
             ScoringQuery::Vector(query_enum) => {
                 // create single search request for rescoring query
                 let filter = filter_with_sources_ids(sources);
@@ -298,6 +382,78 @@ impl LocalShard {
     }
 }
 
+impl LocalShard {
+    /// Scores the points of all sources with the formula.
+    ///
+    /// Sources are expected in the same order as the prefetches, to resolve the `$score[i]` variables.
+    async fn rescore_with_formula<'a>(
+        &self,
+        formula: ParsedFormula,
+        sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
+        score_threshold: Option<ScoreType>,
+        limit: usize,
+        with_payload: WithPayloadInterface,
+        with_vector: WithVector,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let sources: Vec<_> = sources.collect();
+
+        // Version of each point, and its score in each of the sources
+        let mut points_scores: HashMap<PointIdType, (SeqNumberType, Vec<Option<ScoreType>>)> =
+            HashMap::new();
+        for (source_idx, source) in sources.iter().enumerate() {
+            for point in source.iter() {
+                let (_, scores) = points_scores
+                    .entry(point.id)
+                    .or_insert_with(|| (point.version, vec![None; sources.len()]));
+                scores[source_idx] = Some(point.score);
+            }
+        }
+
+        let point_ids: Vec<_> = points_scores.keys().copied().collect();
+
+        let payloads = if formula.requires_payload() {
+            SegmentsSearcher::retrieve(
+                self.segments(),
+                &point_ids,
+                &WithPayload::from(true),
+                &WithVector::Bool(false),
+            )?
+            .into_iter()
+            .map(|(id, record)| (id, record.payload.unwrap_or_default()))
+            .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let empty_payload = Payload::default();
+        let mut rescored = Vec::with_capacity(points_scores.len());
+        for (point_id, (version, scores)) in points_scores {
+            let payload = payloads.get(&point_id).unwrap_or(&empty_payload);
+            let score = formula.evaluate(point_id, &scores, payload)?;
+
+            if score_threshold.is_some_and(|threshold| score < threshold) {
+                continue;
+            }
+
+            rescored.push(ScoredPoint {
+                id: point_id,
+                version,
+                score,
+                payload: None,
+                vector: None,
+                shard_key: None,
+                order_value: None,
+            });
+        }
+
+        rescored.sort_unstable_by(|a, b| ScoredPointTies(b).cmp(&ScoredPointTies(a)));
+        rescored.truncate(limit);
+
+        self.fill_with_payload_or_vectors(rescored, with_payload, with_vector)
+            .await
+    }
+}
+
 /// Extracts point ids from sources, and creates a filter to only include those ids.
 fn filter_with_sources_ids<'a>(sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>) -> Filter {
     let mut point_ids = HashSet::new();
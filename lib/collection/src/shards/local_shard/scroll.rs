@@ -1,8 +1,10 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use common::types::ScoreType;
 use futures::future::try_join_all;
 use itertools::Itertools as _;
+use segment::common::sampling::sample_key_score;
 use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -13,7 +15,7 @@ use super::LocalShard;
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
-    CollectionError, CollectionResult, QueryScrollRequestInternal, Record,
+    CollectionError, CollectionResult, QueryScrollRequestInternal, Record, ScrollOrder,
 };
 
 impl LocalShard {
@@ -51,7 +53,7 @@ impl LocalShard {
             limit,
             with_vector,
             filter,
-            order_by,
+            scroll_order,
             with_payload,
         } = request;
 
@@ -59,10 +61,8 @@ impl LocalShard {
 
         let offset_id = None;
 
-        let order_by = order_by.clone().map(OrderBy::from);
-
-        let point_results = match order_by {
-            None => self
+        let point_results = match scroll_order {
+            ScrollOrder::ById => self
                 .scroll_by_id(
                     offset_id,
                     limit,
@@ -83,7 +83,7 @@ impl LocalShard {
                     order_value: None,
                 })
                 .collect(),
-            Some(order_by) => {
+            ScrollOrder::ByField(order_by) => {
                 let (records, values) = self
                     .scroll_by_field(
                         limit,
@@ -91,7 +91,7 @@ impl LocalShard {
                         with_vector,
                         filter.as_ref(),
                         search_runtime_handle,
-                        &order_by,
+                        order_by,
                     )
                     .await?;
 
@@ -109,6 +109,32 @@ impl LocalShard {
                     })
                     .collect()
             }
+            ScrollOrder::Random { seed } => {
+                let (records, scores) = self
+                    .scroll_randomly(
+                        limit,
+                        with_payload,
+                        with_vector,
+                        filter.as_ref(),
+                        search_runtime_handle,
+                        *seed,
+                    )
+                    .await?;
+
+                records
+                    .into_iter()
+                    .zip(scores)
+                    .map(|(record, score)| ScoredPoint {
+                        id: record.id,
+                        version: 0,
+                        score,
+                        payload: record.payload,
+                        vector: record.vector,
+                        shard_key: record.shard_key,
+                        order_value: None,
+                    })
+                    .collect()
+            }
         };
 
         Ok(point_results)
@@ -219,4 +245,59 @@ impl LocalShard {
 
         Ok((ordered_records, values))
     }
+
+    /// Random sample of up to `limit` points of the shard, sorted by their sample keys for the `seed`.
+    ///
+    /// Returns the records together with the scores of their sample keys.
+    pub async fn scroll_randomly(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        seed: u64,
+    ) -> CollectionResult<(Vec<Record>, Vec<ScoreType>)> {
+        let segments = self.segments();
+
+        let (non_appendable, appendable) = segments.read().split_segments();
+
+        let read_random_filtered = |segment: LockedSegment| {
+            let filter = filter.cloned();
+
+            search_runtime_handle.spawn_blocking(move || {
+                segment
+                    .get()
+                    .read()
+                    .read_random_filtered(limit, filter.as_ref(), seed)
+            })
+        };
+
+        let non_appendable =
+            try_join_all(non_appendable.into_iter().map(read_random_filtered)).await?;
+        let appendable = try_join_all(appendable.into_iter().map(read_random_filtered)).await?;
+
+        // Keys don't depend on the segment, so the smallest keys of all segments
+        // are a sample of the whole shard, with each segment contributing proportionally to its size
+        let (scores, point_ids): (Vec<_>, Vec<_>) = non_appendable
+            .into_iter()
+            .chain(appendable)
+            .kmerge()
+            .dedup()
+            .take(limit)
+            .map(|key| (sample_key_score(&key), key.1))
+            .unzip();
+
+        let with_payload = WithPayload::from(with_payload_interface);
+        let records_map =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+
+        let (records, scores) = point_ids
+            .iter()
+            .zip(scores)
+            .filter_map(|(point, score)| Some((records_map.get(point).cloned()?, score)))
+            .unzip();
+
+        Ok((records, scores))
+    }
 }
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -72,6 +73,7 @@ impl ShardOperation for LocalShard {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
                         clock_tag: operation.clock_tag,
+                        unmatched_points: Vec::new(),
                     });
                 }
 
@@ -89,17 +91,19 @@ impl ShardOperation for LocalShard {
         };
 
         if let Some(receiver) = callback_receiver {
-            let _res = receiver.await??;
+            let outcome = receiver.await??;
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
                 clock_tag: operation.clock_tag,
+                unmatched_points: outcome.unmatched_points,
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
                 clock_tag: operation.clock_tag,
+                unmatched_points: Vec::new(),
             })
         }
     }
@@ -207,4 +211,13 @@ impl ShardOperation for LocalShard {
         self.do_planned_query(planned_query, search_runtime_handle, timeout)
             .await
     }
+
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.do_facet(request, search_runtime_handle, timeout).await
+    }
 }
@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use itertools::Itertools as _;
+use segment::data_types::query_context::QueryContext;
+use segment::types::{ScoredPoint, WithPayload};
+use tokio::runtime::Handle;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::operations::types::{CollectionError, CollectionResult, QueryTextRequestInternal};
+
+impl LocalShard {
+    /// Basic parallel batching of text relevance searches, used for the universal query API.
+    pub(super) async fn query_text_batch(
+        &self,
+        batch: Arc<Vec<QueryTextRequestInternal>>,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Corpus statistics are collected over all segments, so that scores are comparable
+        let mut query_context = QueryContext::default();
+        for request in batch.iter() {
+            query_context.init_text_statistics(&request.query);
+        }
+        let query_context_opt =
+            SegmentsSearcher::fill_query_context(self.segments().clone(), query_context).await?;
+        let Some(query_context) = query_context_opt else {
+            // No segments to search
+            return Ok(vec![vec![]; batch.len()]);
+        };
+        let query_context = Arc::new(query_context);
+
+        let searches = batch
+            .iter()
+            .map(|request| self.query_text(request, &query_context, search_runtime_handle));
+
+        // execute all the searches concurrently
+        let all_search_results = try_join_all(searches);
+        tokio::time::timeout(timeout, all_search_results)
+            .await
+            .map_err(|_| {
+                log::debug!("Query text timeout reached: {} seconds", timeout.as_secs());
+                CollectionError::timeout(timeout.as_secs() as usize, "Query text")
+            })?
+    }
+
+    /// Score points of all segments with BM25 relevance of the query text.
+    async fn query_text(
+        &self,
+        request: &QueryTextRequestInternal,
+        query_context: &Arc<QueryContext>,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let QueryTextRequestInternal {
+            query,
+            limit,
+            filter,
+            score_threshold,
+            with_payload,
+            with_vector,
+        } = request;
+
+        let limit = *limit;
+        let segments = self.segments();
+
+        let (non_appendable, appendable) = segments.read().split_segments();
+
+        let read_text_scored = |segment: LockedSegment| {
+            let filter = filter.clone();
+            let query = query.clone();
+            let query_context = query_context.clone();
+
+            search_runtime_handle.spawn_blocking(move || {
+                segment.get().read().read_text_scored(
+                    limit,
+                    filter.as_ref(),
+                    &query,
+                    query_context.get_segment_query_context(),
+                )
+            })
+        };
+
+        let non_appendable = try_join_all(non_appendable.into_iter().map(read_text_scored)).await?;
+        let appendable = try_join_all(appendable.into_iter().map(read_text_scored)).await?;
+
+        let all_reads = non_appendable
+            .into_iter()
+            .chain(appendable)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The same point might be present in several segments
+        let mut seen_ids = HashSet::new();
+        let (scores, point_ids): (Vec<_>, Vec<_>) = all_reads
+            .into_iter()
+            .flatten()
+            .sorted_unstable_by(|(score_a, _), (score_b, _)| score_b.total_cmp(score_a))
+            .filter(|(_, point_id)| seen_ids.insert(*point_id))
+            .take_while(|(score, _)| score_threshold.map_or(true, |threshold| *score >= threshold))
+            .take(limit)
+            .unzip();
+
+        let with_payload = WithPayload::from(with_payload);
+
+        // Fetch with the requested vector and payload
+        let records_map =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+
+        let scored_points = point_ids
+            .iter()
+            .zip(scores)
+            .filter_map(|(point_id, score)| {
+                records_map.get(point_id).map(|record| ScoredPoint {
+                    id: record.id,
+                    version: 0,
+                    score,
+                    payload: record.payload.clone(),
+                    vector: record.vector.clone(),
+                    shard_key: record.shard_key.clone(),
+                    order_value: None,
+                })
+            })
+            .collect();
+
+        Ok(scored_points)
+    }
+}
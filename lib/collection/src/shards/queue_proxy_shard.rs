@@ -6,6 +6,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -306,6 +307,21 @@ impl ShardOperation for QueueProxyShard {
             .query_batch(requests, search_runtime_handle, timeout)
             .await
     }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .facet(request, search_runtime_handle, timeout)
+            .await
+    }
 }
 
 // Safe guard in debug mode to ensure that `finalize()` is called before dropping
@@ -591,6 +607,19 @@ impl ShardOperation for Inner {
             .query_batch(request, search_runtime_handle, timeout)
             .await
     }
+
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(
+        &self,
+        request: Arc<FacetParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<FacetResponse> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .facet(request, search_runtime_handle, timeout)
+            .await
+    }
 }
 
 /// Transfer batch of operations without retries
@@ -399,9 +399,10 @@ impl ShardReplicaSet {
         }
 
         // there are enough successes, return the first one
+        // prefer completed updates, as only they report points not matching the `update_filter`
         let (_, res) = successes
             .into_iter()
-            .next()
+            .min_by_key(|(_, res)| res.status != UpdateStatus::Completed)
             .expect("successes is not empty");
 
         Ok(Some(res))
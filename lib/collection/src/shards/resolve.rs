@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::facets::FacetResponse;
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
@@ -84,6 +85,27 @@ impl Resolve for Vec<ShardQueryResponse> {
     }
 }
 
+impl Resolve for FacetResponse {
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let hits = responses
+            .into_iter()
+            .map(|response| response.hits)
+            .collect();
+
+        // Approximate counts may differ between replicas with different segments layout,
+        // so only values are compared
+        let mut resolved = Resolver::resolve(
+            hits,
+            |hit| hit.value.clone(),
+            |this, other| this.value == other.value,
+            condition,
+        );
+
+        resolved.sort_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+        Self { hits: resolved }
+    }
+}
+
 fn record_eq(this: &Record, other: &Record) -> bool {
     this.id == other.id && this.vector == other.vector && payload_eq(&this.payload, &other.payload)
 }
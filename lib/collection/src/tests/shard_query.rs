@@ -1,17 +1,29 @@
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use common::cpu::CpuBudget;
-use segment::data_types::vectors::{NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{PointIdType, WithPayloadInterface, WithVector};
+use segment::data_types::text_query::TextQuery;
+use segment::data_types::vectors::{
+    NamedVectorStruct, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
+use segment::json_path::JsonPath;
+use segment::types::{
+    GeoPoint, PayloadFieldSchema, PayloadSchemaType, PointIdType, WithPayloadInterface, WithVector,
+};
+use serde_json::json;
 use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
 
+use crate::operations::point_ops::PointStruct;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::CollectionError;
+use crate::operations::universal_query::formula::{ExpressionInternal, FormulaInternal};
 use crate::operations::universal_query::shard_query::{
     Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest,
 };
+use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
@@ -401,3 +413,261 @@ async fn test_shard_query_payload_vector() {
         assert!(scored_point.payload.is_some());
     });
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_formula_rescoring() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    // closest points to the location of point 5 get the highest score
+    let formula = FormulaInternal {
+        formula: ExpressionInternal::Neg(Box::new(ExpressionInternal::GeoDistance {
+            origin: GeoPoint {
+                lon: 32.12,
+                lat: 14.12,
+            },
+            key: JsonPath::from_str("location").unwrap(),
+        })),
+        defaults: HashMap::new(),
+    }
+    .parse()
+    .unwrap();
+
+    // formula query without prefetches
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Formula(formula.clone())),
+        filter: None,
+        score_threshold: None,
+        limit: 2,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let sources_scores = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await;
+    let expected_error =
+        CollectionError::bad_request("cannot apply Formula without prefetches".to_string());
+    assert!(matches!(sources_scores, Err(err) if err == expected_error));
+
+    let nearest_query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    ));
+    let nearest_query_prefetch = ShardPrefetch {
+        prefetches: vec![], // no recursion here
+        query: Some(ScoringQuery::Vector(nearest_query)),
+        limit: 5,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+    let query = ShardQueryRequest {
+        prefetches: vec![nearest_query_prefetch],
+        query: Some(ScoringQuery::Formula(formula)),
+        filter: None,
+        score_threshold: None,
+        limit: 2,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(true),
+    };
+
+    let sources_scores = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    // formula results are merged at the shard level
+    assert_eq!(sources_scores.len(), 1);
+
+    let ids: Vec<PointIdType> = sources_scores[0].iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![5.into(), 4.into()]);
+
+    // the point at the origin has no distance to it
+    assert_eq!(sources_scores[0][0].score, 0.0);
+    assert!(sources_scores[0][1].score < 0.0);
+    sources_scores[0].iter().for_each(|scored_point| {
+        assert!(scored_point.payload.is_some());
+    });
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_text() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: "description".parse().unwrap(),
+            field_schema: Some(PayloadFieldSchema::FieldType(PayloadSchemaType::Text)),
+        }),
+    );
+    shard.update(create_index.into(), true).await.unwrap();
+
+    let descriptions = [
+        "the quick brown fox jumps over the lazy dog",
+        "fox",
+        "a dog sleeps",
+        "the brown bear",
+    ];
+    let points = descriptions
+        .iter()
+        .enumerate()
+        .map(|(idx, description)| PointStruct {
+            id: (idx as u64 + 1).into(),
+            vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0, 4.0]).into(),
+            payload: Some(serde_json::from_value(json!({ "description": description })).unwrap()),
+        })
+        .collect::<Vec<_>>();
+    let upsert = CollectionUpdateOperations::PointOperation(points.into());
+    shard.update(upsert.into(), true).await.unwrap();
+
+    let text_query = TextQuery {
+        key: JsonPath::from_str("description").unwrap(),
+        query: "brown fox".to_string(),
+    };
+
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Text(text_query.clone())),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(true),
+    };
+
+    let sources_scores = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    assert_eq!(sources_scores.len(), 1);
+
+    // short documents rank higher, documents without query tokens are not returned
+    let ids: Vec<PointIdType> = sources_scores[0].iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![2.into(), 1.into(), 4.into()]);
+    sources_scores[0].iter().for_each(|scored_point| {
+        assert!(scored_point.score > 0.0);
+        assert!(scored_point.payload.is_some());
+    });
+
+    // text query is also applicable to prefetches
+    let text_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Text(text_query)),
+        limit: 2,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+    let nearest_query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    ));
+    let query = ShardQueryRequest {
+        prefetches: vec![text_prefetch],
+        query: Some(ScoringQuery::Vector(nearest_query)),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let sources_scores = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    let mut ids: Vec<PointIdType> = sources_scores[0].iter().map(|point| point.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![1.into(), 2.into()]);
+
+    // missing full-text index
+    let query = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Text(TextQuery {
+            key: JsonPath::from_str("missing").unwrap(),
+            query: "fox".to_string(),
+        })),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let result = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+}
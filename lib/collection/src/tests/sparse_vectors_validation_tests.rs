@@ -64,6 +64,7 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        update_filter: None,
     });
 }
 
@@ -72,6 +73,7 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        update_filter: None,
     });
 }
 
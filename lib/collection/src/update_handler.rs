@@ -29,7 +29,7 @@ use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::config::CollectionParams;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, UpdateOutcome};
 use crate::operations::CollectionUpdateOperations;
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::local_shard::LocalShardClocks;
@@ -53,7 +53,7 @@ pub struct OperationData {
     /// If operation was requested to wait for result
     pub wait: bool,
     /// Callback notification channel
-    pub sender: Option<oneshot::Sender<CollectionResult<usize>>>,
+    pub sender: Option<oneshot::Sender<CollectionResult<UpdateOutcome>>>,
 }
 
 /// Signal, used to inform Updater process
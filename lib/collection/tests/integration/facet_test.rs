@@ -0,0 +1,116 @@
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::ScrollRequestInternal;
+use collection::operations::CollectionUpdateOperations;
+use itertools::Itertools;
+use segment::data_types::facets::{FacetParams, FacetValue, FacetValueHit};
+use segment::data_types::vectors::BatchVectorStructInternal;
+use segment::types::{
+    Payload, PayloadFieldSchema, PayloadSchemaType, PointIdType, WithPayloadInterface,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::common::{simple_collection_fixture, N_SHARDS};
+
+const COLOR_KEY: &str = "color";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_facet_merges_all_shard_counts() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let upsert = |ids: Vec<PointIdType>, payloads: Option<Vec<Option<Payload>>>| {
+        let vectors = vec![vec![1.0, 0.0, 0.0, 0.0]; ids.len()];
+        CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids,
+                vectors: BatchVectorStructInternal::from(vectors).into(),
+                payloads,
+            }
+            .into(),
+        )
+    };
+
+    let ids = (0..120).map(PointIdType::from).collect_vec();
+    collection
+        .update_from_client_simple(upsert(ids, None), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            COLOR_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // On every shard, 3 points are "common" and 4 points have a value of that shard only.
+    // So "common" is the most frequent value overall, but it is not the top of any shard.
+    let mut ids = Vec::new();
+    let mut payloads = Vec::new();
+    for shard_id in 0..N_SHARDS {
+        let shard_points = collection
+            .scroll_by(
+                ScrollRequestInternal {
+                    offset: None,
+                    limit: Some(7),
+                    filter: None,
+                    with_payload: Some(WithPayloadInterface::Bool(false)),
+                    with_vector: false.into(),
+                    order_by: None,
+                },
+                None,
+                &ShardSelectorInternal::ShardId(shard_id),
+            )
+            .await
+            .unwrap()
+            .points;
+        assert_eq!(shard_points.len(), 7);
+
+        for (i, point) in shard_points.into_iter().enumerate() {
+            let color = if i < 3 {
+                "common".to_string()
+            } else {
+                format!("shard {shard_id}")
+            };
+            ids.push(point.id);
+            payloads.push(Some(Payload::from(json!({ COLOR_KEY: color }))));
+        }
+    }
+    collection
+        .update_from_client_simple(upsert(ids, Some(payloads)), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    for exact in [true, false] {
+        let request = FacetParams {
+            key: COLOR_KEY.parse().unwrap(),
+            limit: 2,
+            filter: None,
+            exact,
+        };
+
+        let response = collection
+            .facet(request, None, &ShardSelectorInternal::All, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.hits,
+            vec![
+                FacetValueHit {
+                    value: FacetValue::Keyword("common".to_string()),
+                    count: 3 * N_SHARDS as usize,
+                },
+                FacetValueHit {
+                    value: FacetValue::Keyword("shard 0".to_string()),
+                    count: 4,
+                },
+            ],
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use collection::collection::Collection;
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::query_enum::QueryEnum;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::universal_query::shard_query::{
+    Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest,
+};
+use collection::operations::CollectionUpdateOperations;
+use segment::data_types::vectors::{
+    NamedVectorStruct, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{PointIdType, ScoredPoint, WithPayloadInterface, WithVector};
+use tempfile::Builder;
+
+use crate::common::{simple_collection_fixture, N_SHARDS};
+
+const NUM_POINTS: u64 = 1000;
+
+async fn fixture_with_points(path: &std::path::Path, shard_number: u32) -> Collection {
+    let collection = simple_collection_fixture(path, shard_number).await;
+
+    // Both prefetches below get distinct scores for every point, so there are no ties to break
+    let points = (0..NUM_POINTS)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![
+                i as f32,
+                ((i * 7919) % NUM_POINTS) as f32,
+                0.0,
+                0.0,
+            ])
+            .into(),
+            payload: None,
+        })
+        .collect();
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    collection
+}
+
+fn fusion_request(fusion: Fusion) -> ShardQueryRequest {
+    let prefetch = |vector: Vec<f32>| ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            NamedVectorStruct::new_from_vector(Vector::Dense(vector), DEFAULT_VECTOR_NAME),
+        ))),
+        limit: 100,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+
+    ShardQueryRequest {
+        prefetches: vec![
+            prefetch(vec![1.0, 0.0, 0.0, 0.0]),
+            prefetch(vec![0.0, 1.0, 0.0, 0.0]),
+        ],
+        query: Some(ScoringQuery::Fusion(fusion)),
+        filter: None,
+        score_threshold: None,
+        limit: 20,
+        offset: 5,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    }
+}
+
+fn assert_same_results(single_shard: &[ScoredPoint], multi_shard: &[ScoredPoint]) {
+    assert_eq!(single_shard.len(), multi_shard.len());
+    for (expected, actual) in single_shard.iter().zip(multi_shard) {
+        assert_eq!(expected.id, actual.id);
+        assert!(
+            (expected.score - actual.score).abs() < 1e-5,
+            "{} is not close to {}",
+            expected.score,
+            actual.score,
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fusion_is_shard_independent() {
+    let single_shard_dir = Builder::new()
+        .prefix("test_fusion_single_shard")
+        .tempdir()
+        .unwrap();
+    let multi_shard_dir = Builder::new()
+        .prefix("test_fusion_multi_shard")
+        .tempdir()
+        .unwrap();
+
+    let single_shard = fixture_with_points(single_shard_dir.path(), 1).await;
+    let multi_shard = fixture_with_points(multi_shard_dir.path(), N_SHARDS).await;
+
+    // RRF is left out, as it produces ties which are broken arbitrarily
+    let fusions = [
+        Fusion::Dbsf,
+        Fusion::WeightedSum {
+            weights: vec![0.7, 0.3],
+        },
+    ];
+
+    for fusion in fusions {
+        let single_shard_result = single_shard
+            .query(
+                fusion_request(fusion.clone()),
+                None,
+                ShardSelectorInternal::All,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let multi_shard_result = multi_shard
+            .query(
+                fusion_request(fusion.clone()),
+                None,
+                ShardSelectorInternal::All,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(single_shard_result.len(), 20, "{fusion:?}");
+        assert_same_results(&single_shard_result, &multi_shard_result);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_weighted_sum_fusion_follows_weights() {
+    let collection_dir = Builder::new()
+        .prefix("test_weighted_sum_fusion")
+        .tempdir()
+        .unwrap();
+
+    let collection = fixture_with_points(collection_dir.path(), N_SHARDS).await;
+
+    // All the weight on the first prefetch, results must be ordered as the first prefetch
+    let mut request = fusion_request(Fusion::WeightedSum {
+        weights: vec![1.0, 0.0],
+    });
+    request.offset = 0;
+
+    let result = collection
+        .query(request, None, ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+
+    let expected_ids: Vec<PointIdType> = (0..20).map(|i| (NUM_POINTS - 1 - i).into()).collect();
+    let actual_ids: Vec<_> = result.iter().map(|point| point.id).collect();
+    assert_eq!(actual_ids, expected_ids);
+
+    // Best point gets the max normalized score
+    assert!((result[0].score - 1.0).abs() < 1e-5);
+}
@@ -5,6 +5,10 @@ pub mod collection_test;
 #[cfg(test)]
 pub mod common;
 #[cfg(test)]
+pub mod facet_test;
+#[cfg(test)]
+pub mod fusion_test;
+#[cfg(test)]
 pub mod grouping_test;
 #[cfg(test)]
 pub mod lookup_test;
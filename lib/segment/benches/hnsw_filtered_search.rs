@@ -0,0 +1,176 @@
+#[cfg(not(target_os = "windows"))]
+mod prof;
+
+use std::collections::HashSet;
+
+use common::types::{PointOffsetType, ScoredPointOffset};
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::rngs::StdRng;
+use rand::{thread_rng, SeedableRng};
+use segment::fixtures::index_fixtures::{
+    random_vector, FakeFilterContext, ModuloFilterContext, TestRawScorerProducer,
+};
+use segment::index::hnsw_index::graph_layers::GraphLayers;
+use segment::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use segment::index::hnsw_index::graph_links::{GraphLinksFormat, GraphLinksRam};
+use segment::index::hnsw_index::point_scorer::FilteredScorer;
+use segment::payload_storage::FilterContext;
+use segment::spaces::simple::CosineMetric;
+
+const NUM_VECTORS: usize = 50000;
+const DIM: usize = 64;
+const M: usize = 16;
+const PAYLOAD_M: usize = 16;
+const TOP: usize = 10;
+const EF_CONSTRUCT: usize = 100;
+const EF: usize = 100;
+const USE_HEURISTIC: bool = true;
+/// Every `FILTER_MODULO`-th point matches the filter, i.e. selectivity is 2%
+const FILTER_MODULO: PointOffsetType = 50;
+const RECALL_QUERIES: usize = 100;
+
+const FILTER: ModuloFilterContext = ModuloFilterContext::new(FILTER_MODULO);
+
+fn build_graph(
+    vector_holder: &TestRawScorerProducer<CosineMetric>,
+    with_payload_links: bool,
+) -> GraphLayers<GraphLinksRam> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let fake_filter_context = FakeFilterContext {};
+
+    let mut graph_layers_builder =
+        GraphLayersBuilder::new(NUM_VECTORS, M, M * 2, EF_CONSTRUCT, 10, USE_HEURISTIC);
+    for idx in 0..(NUM_VECTORS as PointOffsetType) {
+        let added_vector = vector_holder.vectors.get(idx).to_vec();
+        let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        let level = graph_layers_builder.get_random_layer(&mut rng);
+        graph_layers_builder.set_levels(idx, level);
+        graph_layers_builder.link_new_point(idx, scorer);
+    }
+
+    // Same as building additional links for a payload index block with `payload_m`
+    if with_payload_links {
+        let mut additional_graph = GraphLayersBuilder::new_with_params(
+            NUM_VECTORS,
+            PAYLOAD_M,
+            PAYLOAD_M * 2,
+            EF_CONSTRUCT,
+            1,
+            USE_HEURISTIC,
+            false,
+        );
+        for idx in (0..(NUM_VECTORS as PointOffsetType)).filter(|&idx| FILTER.check(idx)) {
+            let block_filter_context = ModuloFilterContext {
+                excluded_point: Some(idx),
+                ..FILTER
+            };
+            let added_vector = vector_holder.vectors.get(idx).to_vec();
+            let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&block_filter_context));
+            additional_graph.link_new_point(idx, scorer);
+        }
+        graph_layers_builder.merge_from_other(additional_graph);
+    }
+
+    graph_layers_builder
+        .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
+        .unwrap()
+}
+
+fn ids(result: &[ScoredPointOffset]) -> HashSet<PointOffsetType> {
+    result.iter().map(|scored| scored.idx).collect()
+}
+
+/// Average fraction of the exact filtered top found by the given search
+fn recall(
+    vector_holder: &TestRawScorerProducer<CosineMetric>,
+    search: impl Fn(FilteredScorer) -> Vec<ScoredPointOffset>,
+) -> f64 {
+    let mut rng = StdRng::seed_from_u64(43);
+    let filtered_points: Vec<_> = (0..NUM_VECTORS as PointOffsetType)
+        .filter(|&idx| FILTER.check(idx))
+        .collect();
+
+    let mut hits = 0;
+    for _ in 0..RECALL_QUERIES {
+        let query = random_vector(&mut rng, DIM);
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+
+        let mut scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&FILTER));
+        let mut exact = scorer
+            .score_points(&mut filtered_points.clone(), 0)
+            .to_vec();
+        exact.sort_by(|a, b| b.score.total_cmp(&a.score));
+        exact.truncate(TOP);
+
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&FILTER));
+        hits += ids(&search(scorer)).intersection(&ids(&exact)).count();
+    }
+    hits as f64 / (RECALL_QUERIES * TOP) as f64
+}
+
+fn hnsw_filtered_benchmark(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let vector_holder = TestRawScorerProducer::<CosineMetric>::new(DIM, NUM_VECTORS, &mut rng);
+    let mut group = c.benchmark_group("hnsw-filtered-search-group");
+    let mut rng = thread_rng();
+
+    let graph_layers = build_graph(&vector_holder, false);
+    let payload_graph_layers = build_graph(&vector_holder, true);
+
+    eprintln!(
+        "recall: hnsw = {}, acorn = {}, payload_m = {}",
+        recall(&vector_holder, |scorer| graph_layers
+            .search(TOP, EF, scorer, None)),
+        recall(&vector_holder, |scorer| graph_layers
+            .search_acorn(TOP, EF, scorer, None)),
+        recall(&vector_holder, |scorer| payload_graph_layers
+            .search(TOP, EF, scorer, None)),
+    );
+
+    group.bench_function("hnsw_filtered_search", |b| {
+        b.iter(|| {
+            let query = random_vector(&mut rng, DIM);
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&FILTER));
+            graph_layers.search(TOP, EF, scorer, None);
+        })
+    });
+
+    group.bench_function("acorn_filtered_search", |b| {
+        b.iter(|| {
+            let query = random_vector(&mut rng, DIM);
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&FILTER));
+            graph_layers.search_acorn(TOP, EF, scorer, None);
+        })
+    });
+
+    group.bench_function("payload_m_filtered_search", |b| {
+        b.iter(|| {
+            let query = random_vector(&mut rng, DIM);
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&FILTER));
+            payload_graph_layers.search(TOP, EF, scorer, None);
+        })
+    });
+
+    group.finish();
+}
+
+#[cfg(not(target_os = "windows"))]
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(prof::FlamegraphProfiler::new(100));
+    targets = hnsw_filtered_benchmark
+}
+
+#[cfg(target_os = "windows")]
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = hnsw_filtered_benchmark
+}
+
+criterion_main!(benches);
@@ -7,7 +7,7 @@ use rand::rngs::StdRng;
 use rand::{thread_rng, SeedableRng};
 use segment::fixtures::index_fixtures::{random_vector, FakeFilterContext, TestRawScorerProducer};
 use segment::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
-use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::graph_links::{GraphLinksFormat, GraphLinksRam};
 use segment::index::hnsw_index::point_scorer::FilteredScorer;
 use segment::spaces::simple::CosineMetric;
 
@@ -37,7 +37,7 @@ fn hnsw_benchmark(c: &mut Criterion) {
         graph_layers_builder.link_new_point(idx, scorer);
     }
     let graph_layers = graph_layers_builder
-        .into_graph_layers::<GraphLinksRam>(None)
+        .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
         .unwrap();
 
     group.bench_function("hnsw_search", |b| {
@@ -48,6 +48,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
                 quantization_config: None,
                 multivector_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -72,6 +73,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         max_indexing_threads: 0,
         on_disk: None,
         payload_m: None,
+        compressed_links: None,
     };
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
     let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
@@ -84,6 +86,8 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         quantized_vectors: quantized_vectors.clone(),
         payload_index: segment.payload_index.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -0,0 +1,227 @@
+use std::ops::Range;
+
+use bitvec::prelude::{BitSlice, BitVec};
+
+/// Summary of free blocks in a region
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegionGaps {
+    /// Number of free blocks at the start of the region
+    pub leading: usize,
+    /// Number of free blocks at the end of the region
+    pub trailing: usize,
+    /// Largest number of consecutive free blocks in the region
+    pub max: usize,
+}
+
+impl RegionGaps {
+    fn from_bits(bits: &BitSlice) -> Self {
+        let len = bits.len();
+        let leading = bits.first_one().unwrap_or(len);
+        let trailing = len - bits.last_one().map_or(0, |idx| idx + 1);
+
+        let mut max = 0;
+        let mut gap_start = 0;
+        for used in bits.iter_ones() {
+            max = max.max(used - gap_start);
+            gap_start = used + 1;
+        }
+        max = max.max(len - gap_start);
+
+        Self {
+            leading,
+            trailing,
+            max,
+        }
+    }
+}
+
+/// Occupancy of the storage blocks, set bit marks a used block.
+///
+/// Free blocks are summarized per region, so the search for a gap of the required size
+/// only scans regions which are known to have one.
+#[derive(Debug)]
+pub struct Bitmask {
+    bits: BitVec,
+    regions: Vec<RegionGaps>,
+    region_size: usize,
+    /// Number of used blocks
+    used: usize,
+}
+
+impl Bitmask {
+    pub fn new(region_size: usize) -> Self {
+        Self {
+            bits: BitVec::new(),
+            regions: Vec::new(),
+            region_size,
+            used: 0,
+        }
+    }
+
+    /// Create a bitmask of `len` blocks, where only blocks in `used` ranges are occupied
+    pub fn from_used(
+        len: usize,
+        region_size: usize,
+        used: impl IntoIterator<Item = Range<usize>>,
+    ) -> Self {
+        debug_assert_eq!(len % region_size, 0);
+        let mut bits = BitVec::repeat(false, len);
+        for range in used {
+            bits[range].fill(true);
+        }
+        let regions = bits
+            .chunks(region_size)
+            .map(RegionGaps::from_bits)
+            .collect();
+        let used = bits.count_ones();
+        Self {
+            bits,
+            regions,
+            region_size,
+            used,
+        }
+    }
+
+    /// Total number of blocks
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Number of used blocks
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Add `count` free blocks at the end, must be a multiple of the region size
+    pub fn extend(&mut self, count: usize) {
+        debug_assert_eq!(count % self.region_size, 0);
+        self.bits.resize(self.bits.len() + count, false);
+        let all_free = RegionGaps {
+            leading: self.region_size,
+            trailing: self.region_size,
+            max: self.region_size,
+        };
+        self.regions
+            .resize(self.bits.len() / self.region_size, all_free);
+    }
+
+    /// Remove blocks after the first `len` ones, must be a multiple of the region size
+    pub fn truncate(&mut self, len: usize) {
+        debug_assert_eq!(len % self.region_size, 0);
+        if len >= self.bits.len() {
+            return;
+        }
+        self.used -= self.bits[len..].count_ones();
+        self.bits.truncate(len);
+        self.regions.truncate(len / self.region_size);
+    }
+
+    pub fn mark(&mut self, blocks: Range<usize>, used: bool) {
+        if blocks.is_empty() {
+            return;
+        }
+        let first_region = blocks.start / self.region_size;
+        let last_region = (blocks.end - 1) / self.region_size;
+        let used_before = self.bits[blocks.clone()].count_ones();
+        if used {
+            self.used += blocks.len() - used_before;
+        } else {
+            self.used -= used_before;
+        }
+        self.bits[blocks].fill(used);
+        for region in first_region..=last_region {
+            let region_bits =
+                &self.bits[region * self.region_size..(region + 1) * self.region_size];
+            self.regions[region] = RegionGaps::from_bits(region_bits);
+        }
+    }
+
+    /// Number of free blocks at the very end
+    pub fn trailing_free(&self) -> usize {
+        let mut free = 0;
+        for gaps in self.regions.iter().rev() {
+            free += gaps.trailing;
+            if gaps.trailing < self.region_size {
+                break;
+            }
+        }
+        free
+    }
+
+    /// Find the first gap of at least `count` consecutive free blocks
+    pub fn find_free(&self, count: usize) -> Option<usize> {
+        // Free blocks at the end of previous regions, which may continue into the next one
+        let mut run_start = 0;
+        let mut run_len = 0;
+
+        for (region, gaps) in self.regions.iter().enumerate() {
+            let region_start = region * self.region_size;
+            if run_len == 0 {
+                run_start = region_start;
+            }
+            if run_len + gaps.leading >= count {
+                return Some(run_start);
+            }
+            if gaps.max >= count {
+                let region_bits = &self.bits[region_start..region_start + self.region_size];
+                if let Some(offset) = Self::find_free_in(region_bits, count) {
+                    return Some(region_start + offset);
+                }
+            }
+            if gaps.leading == self.region_size {
+                run_len += self.region_size;
+            } else {
+                run_start = region_start + self.region_size - gaps.trailing;
+                run_len = gaps.trailing;
+            }
+        }
+        None
+    }
+
+    fn find_free_in(bits: &BitSlice, count: usize) -> Option<usize> {
+        let mut gap_start = 0;
+        for used in bits.iter_ones().chain(std::iter::once(bits.len())) {
+            if used - gap_start >= count {
+                return Some(gap_start);
+            }
+            gap_start = used + 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_free_across_regions() {
+        let mut bitmask = Bitmask::new(8);
+        bitmask.extend(32);
+        assert_eq!(bitmask.find_free(32), Some(0));
+        assert_eq!(bitmask.find_free(33), None);
+
+        bitmask.mark(0..5, true);
+        bitmask.mark(6..14, true);
+        assert_eq!(bitmask.find_free(1), Some(5));
+        assert_eq!(bitmask.find_free(2), Some(14));
+        // Gap spans the end of the second region and two full regions
+        assert_eq!(bitmask.find_free(18), Some(14));
+        assert_eq!(bitmask.find_free(19), None);
+        assert_eq!(bitmask.trailing_free(), 18);
+
+        bitmask.mark(6..14, false);
+        assert_eq!(bitmask.find_free(27), Some(5));
+        assert_eq!(bitmask.trailing_free(), 27);
+        assert_eq!(bitmask.used(), 5);
+
+        let restored = Bitmask::from_used(32, 8, [0..5]);
+        assert_eq!(restored.regions, bitmask.regions);
+        assert_eq!(restored.used(), 5);
+
+        bitmask.truncate(8);
+        assert_eq!(bitmask.len(), 8);
+        assert_eq!(bitmask.trailing_free(), 3);
+        assert_eq!(bitmask.find_free(4), None);
+    }
+}
@@ -0,0 +1,632 @@
+//! Storage of variable sized values, addressed by point offsets.
+//!
+//! Values are written into fixed size blocks of memory mapped pages. A value occupies a range of
+//! consecutive blocks, which may span over several pages. Free blocks are tracked with a bitmask,
+//! which is summarized per region, so that gaps left by updated or deleted values are found
+//! quickly and reused by new values.
+//!
+//! Blocks of replaced values are released only after the tracker is flushed. This way the
+//! persisted tracker never points to blocks, which are already reused by other values.
+//!
+//! Once too much of the storage is free, values from the tail pages are moved into gaps of
+//! the preceding ones. Pages at the end, which become completely free, are removed on flush.
+
+mod bitmask;
+mod page;
+mod tracker;
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::file_operations::{atomic_save_json, read_json};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+
+use self::bitmask::Bitmask;
+use self::page::Page;
+use self::tracker::{write_updates, Tracker, ValuePointer};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+
+const CONFIG_FILE: &str = "config.json";
+const TRACKER_FILE: &str = "tracker.dat";
+
+/// Fraction of free blocks, after which the storage is compacted
+const COMPACTION_FREE_RATIO: f64 = 0.5;
+
+/// Value, which can be stored in the [`BlobStore`]
+pub trait Blob: Sized {
+    fn to_bytes(&self) -> Vec<u8>;
+
+    fn from_bytes(data: &[u8]) -> OperationResult<Self>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Size of a single page file
+    pub page_size_bytes: usize,
+    /// Size of the minimal unit of allocation
+    pub block_size_bytes: usize,
+    /// Number of blocks, summarized together for the search of free space
+    pub region_size_blocks: usize,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            page_size_bytes: 8 * 1024 * 1024, // 8 MiB
+            block_size_bytes: 128,
+            region_size_blocks: 8192,
+        }
+    }
+}
+
+impl StorageConfig {
+    fn blocks_per_page(&self) -> usize {
+        self.page_size_bytes / self.block_size_bytes
+    }
+
+    fn blocks_count(&self, length: usize) -> usize {
+        length.div_ceil(self.block_size_bytes).max(1)
+    }
+
+    /// Range of global block indices, occupied by the value
+    fn pointer_blocks(&self, pointer: ValuePointer) -> Range<usize> {
+        let start =
+            pointer.page_id as usize * self.blocks_per_page() + pointer.block_offset as usize;
+        start..start + self.blocks_count(pointer.length as usize)
+    }
+
+    fn validate(&self) -> OperationResult<()> {
+        let valid = self.block_size_bytes > 0
+            && self.region_size_blocks > 0
+            && self.page_size_bytes % self.block_size_bytes == 0
+            && self.blocks_per_page() % self.region_size_blocks == 0;
+        if !valid {
+            return Err(OperationError::service_error(format!(
+                "Invalid blob storage config: {self:?}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub struct BlobStore<V> {
+    path: PathBuf,
+    config: StorageConfig,
+    tracker: Tracker,
+    pages: Arc<RwLock<Vec<Page>>>,
+    bitmask: Arc<RwLock<Bitmask>>,
+    /// Pointers to replaced values, which are not yet released in the `bitmask`
+    pending_free: Arc<Mutex<Vec<ValuePointer>>>,
+    /// Values were moved by compaction, but their old blocks are not yet released
+    compaction_pending: Arc<AtomicBool>,
+    _value: PhantomData<V>,
+}
+
+impl<V> fmt::Debug for BlobStore<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlobStore")
+            .field("path", &self.path)
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V: Blob> BlobStore<V> {
+    /// Open existing storage or create a new one with the default config
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        Self::open_with_config(path, StorageConfig::default())
+    }
+
+    /// Open existing storage or create a new one with the given `config`
+    ///
+    /// Config of the existing storage takes precedence over the given one.
+    pub fn open_with_config(path: &Path, config: StorageConfig) -> OperationResult<Self> {
+        std::fs::create_dir_all(path)?;
+
+        let config_path = path.join(CONFIG_FILE);
+        let config: StorageConfig = if config_path.exists() {
+            read_json(&config_path)?
+        } else {
+            config.validate()?;
+            atomic_save_json(&config_path, &config)?;
+            config
+        };
+        config.validate()?;
+
+        let tracker = Tracker::open(&path.join(TRACKER_FILE))?;
+
+        let mut pages = Vec::new();
+        loop {
+            let page_path = Self::page_path(path, pages.len());
+            if !page_path.exists() {
+                break;
+            }
+            pages.push(Page::open(&page_path)?);
+        }
+
+        let used_blocks = tracker
+            .iter()
+            .map(|(_, pointer)| config.pointer_blocks(pointer));
+        let bitmask = Bitmask::from_used(
+            pages.len() * config.blocks_per_page(),
+            config.region_size_blocks,
+            used_blocks,
+        );
+
+        Ok(Self {
+            path: path.to_owned(),
+            config,
+            tracker,
+            pages: Arc::new(RwLock::new(pages)),
+            bitmask: Arc::new(RwLock::new(bitmask)),
+            pending_free: Default::default(),
+            compaction_pending: Default::default(),
+            _value: PhantomData,
+        })
+    }
+
+    fn page_path(path: &Path, page_id: usize) -> PathBuf {
+        path.join(format!("page_{page_id}.dat"))
+    }
+
+    /// Find free blocks for `blocks_count` blocks, new pages are created if required
+    fn allocate(&self, blocks_count: usize) -> OperationResult<usize> {
+        let mut bitmask = self.bitmask.write();
+        let start = match bitmask.find_free(blocks_count) {
+            Some(start) => start,
+            None => {
+                let start = bitmask.len() - bitmask.trailing_free();
+                let blocks_per_page = self.config.blocks_per_page();
+                let mut pages = self.pages.write();
+                while pages.len() * blocks_per_page < start + blocks_count {
+                    let page_path = Self::page_path(&self.path, pages.len());
+                    pages.push(Page::create(&page_path, self.config.page_size_bytes)?);
+                    bitmask.extend(blocks_per_page);
+                }
+                start
+            }
+        };
+        bitmask.mark(start..start + blocks_count, true);
+        Ok(start)
+    }
+
+    /// Store the value of the point, replacing the previous one
+    pub fn put(&mut self, point_offset: PointOffsetType, value: &V) -> OperationResult<()> {
+        let data = value.to_bytes();
+        let length = u32::try_from(data.len()).map_err(|_| {
+            OperationError::service_error(format!("Value is too large: {} bytes", data.len()))
+        })?;
+        let start_block = self.allocate(self.config.blocks_count(data.len()))?;
+        self.write_value(point_offset, start_block, length, &data);
+        self.compact_if_needed()
+    }
+
+    /// Write `data` into the allocated blocks and update the pointer of the point
+    fn write_value(
+        &mut self,
+        point_offset: PointOffsetType,
+        start_block: usize,
+        length: u32,
+        data: &[u8],
+    ) {
+        let blocks_per_page = self.config.blocks_per_page();
+        let pointer = ValuePointer {
+            page_id: (start_block / blocks_per_page) as u32,
+            block_offset: (start_block % blocks_per_page) as u32,
+            length,
+        };
+
+        {
+            let mut pages = self.pages.write();
+            let page_size = self.config.page_size_bytes;
+            let mut offset = start_block * self.config.block_size_bytes;
+            for chunk in Self::split_by_pages(page_size, offset, data) {
+                pages[offset / page_size].write(offset % page_size, chunk);
+                offset += chunk.len();
+            }
+        }
+
+        // Release old blocks only after the new pointer is registered for the flush
+        if let Some(old_pointer) = self.tracker.set(point_offset, Some(pointer)) {
+            self.pending_free.lock().push(old_pointer);
+        }
+    }
+
+    /// Compact the storage, if the fraction of free blocks is too large
+    fn compact_if_needed(&mut self) -> OperationResult<()> {
+        if self.compaction_pending.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let (used_blocks, total_blocks) = {
+            let bitmask = self.bitmask.read();
+            (bitmask.used(), bitmask.len())
+        };
+        let free_blocks = total_blocks - used_blocks;
+        if (free_blocks as f64) <= total_blocks as f64 * COMPACTION_FREE_RATIO {
+            return Ok(());
+        }
+        self.compact()
+    }
+
+    /// Move values from the tail pages into gaps of the preceding pages
+    ///
+    /// Old blocks of the moved values are released on the next flush,
+    /// which also removes the tail pages, if they became completely free.
+    pub fn compact(&mut self) -> OperationResult<()> {
+        let blocks_per_page = self.config.blocks_per_page();
+        let (used_blocks, total_blocks) = {
+            let bitmask = self.bitmask.read();
+            (bitmask.used(), bitmask.len())
+        };
+        // Pages required to keep all the values, with a spare one for fragmentation
+        let kept_blocks = (used_blocks.div_ceil(blocks_per_page) + 1) * blocks_per_page;
+        if kept_blocks >= total_blocks {
+            return Ok(());
+        }
+
+        let moved_values: Vec<_> = self
+            .tracker
+            .iter()
+            .filter(|(_, pointer)| self.config.pointer_blocks(*pointer).end > kept_blocks)
+            .collect();
+        for (point_offset, pointer) in moved_values {
+            let blocks_count = self.config.blocks_count(pointer.length as usize);
+            let start_block = {
+                let mut bitmask = self.bitmask.write();
+                match bitmask.find_free(blocks_count) {
+                    Some(start) if start + blocks_count <= kept_blocks => {
+                        bitmask.mark(start..start + blocks_count, true);
+                        start
+                    }
+                    // No gap for the value in the kept pages, leave it in place
+                    _ => continue,
+                }
+            };
+            let data = self.with_bytes(pointer, <[u8]>::to_vec);
+            self.write_value(point_offset, start_block, pointer.length, &data);
+        }
+
+        self.compaction_pending.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Split `data`, which starts at the given byte `offset`, into chunks within single pages
+    fn split_by_pages(
+        page_size: usize,
+        offset: usize,
+        mut data: &[u8],
+    ) -> impl Iterator<Item = &[u8]> {
+        let first_chunk_len = page_size - offset % page_size;
+        let mut chunk_len = first_chunk_len;
+        std::iter::from_fn(move || {
+            if data.is_empty() {
+                return None;
+            }
+            let (chunk, rest) = data.split_at(chunk_len.min(data.len()));
+            data = rest;
+            chunk_len = page_size;
+            Some(chunk)
+        })
+    }
+
+    pub fn get(&self, point_offset: PointOffsetType) -> OperationResult<Option<V>> {
+        let Some(pointer) = self.tracker.get(point_offset) else {
+            return Ok(None);
+        };
+        self.with_bytes(pointer, V::from_bytes).map(Some)
+    }
+
+    /// Call `f` with the stored bytes of the value
+    fn with_bytes<T>(&self, pointer: ValuePointer, f: impl FnOnce(&[u8]) -> T) -> T {
+        let page_size = self.config.page_size_bytes;
+        let start = pointer.page_id as usize * page_size
+            + pointer.block_offset as usize * self.config.block_size_bytes;
+        let length = pointer.length as usize;
+
+        let pages = self.pages.read();
+        // Most of values fit into a single page, read them without copying
+        if start % page_size + length <= page_size {
+            return f(pages[start / page_size].read(start % page_size, length));
+        }
+
+        let mut data = Vec::with_capacity(length);
+        let mut offset = start;
+        while data.len() < length {
+            let chunk_len = (page_size - offset % page_size).min(length - data.len());
+            data.extend_from_slice(pages[offset / page_size].read(offset % page_size, chunk_len));
+            offset += chunk_len;
+        }
+        f(&data)
+    }
+
+    /// Delete the value of the point, returns false if there was no value
+    pub fn delete(&mut self, point_offset: PointOffsetType) -> OperationResult<bool> {
+        match self.tracker.set(point_offset, None) {
+            Some(old_pointer) => {
+                self.pending_free.lock().push(old_pointer);
+                self.compact_if_needed()?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Iterate over all stored values, until `callback` returns false
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, V) -> OperationResult<bool>,
+    {
+        for (point_offset, _) in self.tracker.iter() {
+            let Some(value) = self.get(point_offset)? else {
+                continue;
+            };
+            if !callback(point_offset, value)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Upper bound of stored point offsets
+    pub fn max_point_offset(&self) -> usize {
+        self.tracker.len()
+    }
+
+    /// Remove all values and pages
+    pub fn clear(&mut self) -> OperationResult<()> {
+        self.tracker.clear()?;
+        self.pending_free.lock().clear();
+        self.compaction_pending.store(false, Ordering::Relaxed);
+        let mut pages = self.pages.write();
+        for page in pages.drain(..) {
+            let page_path = page.path().to_owned();
+            drop(page);
+            std::fs::remove_file(page_path)?;
+        }
+        *self.bitmask.write() = Bitmask::new(self.config.region_size_blocks);
+        Ok(())
+    }
+
+    /// Load all pages into the page cache
+    pub fn populate(&self) {
+        for page in self.pages.read().iter() {
+            page.populate();
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.path.join(CONFIG_FILE)];
+        if self.tracker.path().exists() {
+            files.push(self.tracker.path().to_owned());
+        }
+        files.extend(self.pages.read().iter().map(|page| page.path().to_owned()));
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let config = self.config;
+        let tracker_path = self.tracker.path().to_owned();
+        let pending_updates = self.tracker.pending_updates();
+        let pending_free = self.pending_free.clone();
+        let compaction_pending = self.compaction_pending.clone();
+        let pages = self.pages.clone();
+        let bitmask = self.bitmask.clone();
+
+        Box::new(move || {
+            // Take released pointers first: any of them is replaced by an update,
+            // which is already registered in the pending updates
+            let released = std::mem::take(&mut *pending_free.lock());
+            let updates = std::mem::take(&mut *pending_updates.lock());
+
+            let result = (|| {
+                // Values must be persisted before the pointers to them
+                for page in pages.read().iter() {
+                    page.flush()?;
+                }
+                write_updates(&tracker_path, &updates)
+            })();
+
+            if let Err(err) = result {
+                // Keep changes for the next flush, newer updates take precedence
+                let mut pending_updates = pending_updates.lock();
+                for (point_offset, pointer) in updates {
+                    pending_updates.entry(point_offset).or_insert(pointer);
+                }
+                pending_free.lock().extend(released);
+                return Err(err);
+            }
+
+            let mut bitmask = bitmask.write();
+            for pointer in released {
+                bitmask.mark(config.pointer_blocks(pointer), false);
+            }
+
+            // Remove pages at the end, which are not used anymore, e.g. after compaction
+            let blocks_per_page = config.blocks_per_page();
+            let free_pages = bitmask.trailing_free() / blocks_per_page;
+            if free_pages > 0 {
+                let mut pages = pages.write();
+                let kept_pages = pages.len() - free_pages;
+                bitmask.truncate(kept_pages * blocks_per_page);
+                // Remove from the end, so that remaining pages are consecutive at any moment
+                for page in pages.drain(kept_pages..).rev() {
+                    let page_path = page.path().to_owned();
+                    drop(page);
+                    std::fs::remove_file(page_path)?;
+                }
+            }
+            compaction_pending.store(false, Ordering::Relaxed);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use tempfile::Builder;
+
+    use super::*;
+
+    impl Blob for Vec<u8> {
+        fn to_bytes(&self) -> Vec<u8> {
+            self.clone()
+        }
+
+        fn from_bytes(data: &[u8]) -> OperationResult<Self> {
+            Ok(data.to_vec())
+        }
+    }
+
+    fn small_config() -> StorageConfig {
+        StorageConfig {
+            page_size_bytes: 1024,
+            block_size_bytes: 16,
+            region_size_blocks: 16,
+        }
+    }
+
+    #[test]
+    fn test_put_get_delete_reopen() {
+        let dir = Builder::new().prefix("blob_store").tempdir().unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut expected: Vec<Option<Vec<u8>>> = vec![None; 100];
+        let mut storage = BlobStore::open_with_config(dir.path(), small_config()).unwrap();
+
+        for _ in 0..1000 {
+            let point_offset = rng.gen_range(0..expected.len());
+            if rng.gen_bool(0.2) {
+                assert_eq!(
+                    storage.delete(point_offset as PointOffsetType).unwrap(),
+                    expected[point_offset].is_some(),
+                );
+                expected[point_offset] = None;
+            } else {
+                // Some values are larger than a page
+                let len = rng.gen_range(0..2000);
+                let value: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                storage
+                    .put(point_offset as PointOffsetType, &value)
+                    .unwrap();
+                expected[point_offset] = Some(value);
+            }
+            if rng.gen_bool(0.1) {
+                storage.flusher()().unwrap();
+            }
+        }
+
+        let check = |storage: &BlobStore<Vec<u8>>| {
+            for (point_offset, value) in expected.iter().enumerate() {
+                assert_eq!(
+                    &storage.get(point_offset as PointOffsetType).unwrap(),
+                    value
+                );
+            }
+        };
+
+        check(&storage);
+        storage.flusher()().unwrap();
+        let pages_count = storage.pages.read().len();
+        drop(storage);
+
+        let storage = BlobStore::open_with_config(dir.path(), StorageConfig::default()).unwrap();
+        assert_eq!(storage.config, small_config());
+        assert_eq!(storage.pages.read().len(), pages_count);
+        check(&storage);
+    }
+
+    #[test]
+    fn test_reuse_released_blocks() {
+        let dir = Builder::new().prefix("blob_store").tempdir().unwrap();
+        let mut storage = BlobStore::open_with_config(dir.path(), small_config()).unwrap();
+
+        let value = vec![1u8; 500];
+        for point_offset in 0..10 {
+            storage.put(point_offset, &value).unwrap();
+        }
+        storage.flusher()().unwrap();
+        let pages_count = storage.pages.read().len();
+
+        // Released blocks are not reused before the flush
+        for point_offset in 0..10 {
+            storage.put(point_offset, &value).unwrap();
+        }
+        assert!(storage.pages.read().len() > pages_count);
+        storage.flusher()().unwrap();
+        let pages_count = storage.pages.read().len();
+
+        for point_offset in 0..10 {
+            storage.put(point_offset, &value).unwrap();
+        }
+        assert_eq!(storage.pages.read().len(), pages_count);
+    }
+
+    #[test]
+    fn test_compaction_shrinks_files() {
+        let dir = Builder::new().prefix("blob_store").tempdir().unwrap();
+        let mut storage = BlobStore::open_with_config(dir.path(), small_config()).unwrap();
+
+        let files_size = |storage: &BlobStore<Vec<u8>>| -> u64 {
+            storage
+                .files()
+                .iter()
+                .map(|path| std::fs::metadata(path).unwrap().len())
+                .sum()
+        };
+
+        // Overwrite values many times, so that they are spread over many pages
+        for round in 0..10u8 {
+            for point_offset in 0..100 {
+                storage.put(point_offset, &vec![round; 300]).unwrap();
+            }
+            storage.flusher()().unwrap();
+        }
+        let pages_count = storage.pages.read().len();
+        let size_before = files_size(&storage);
+
+        // Only every 10th value is kept, but it is stored at the end of the storage
+        for point_offset in 0..100 {
+            storage.put(point_offset, &vec![10; 300]).unwrap();
+        }
+        storage.flusher()().unwrap();
+        for point_offset in (0..100).filter(|point_offset| point_offset % 10 != 0) {
+            storage.delete(point_offset).unwrap();
+        }
+        storage.flusher()().unwrap();
+
+        // Compaction is triggered by the next update and takes effect on flush
+        storage.put(0, &vec![11; 300]).unwrap();
+        storage.flusher()().unwrap();
+
+        let expected = |point_offset: PointOffsetType| match point_offset {
+            0 => Some(vec![11; 300]),
+            point_offset if point_offset % 10 == 0 => Some(vec![10; 300]),
+            _ => None,
+        };
+        for point_offset in 0..100 {
+            assert_eq!(storage.get(point_offset).unwrap(), expected(point_offset));
+        }
+
+        // Values are kept within the pages required for the used blocks and a spare one
+        let compacted_pages_count = storage.pages.read().len();
+        assert!(compacted_pages_count <= 5, "{compacted_pages_count} pages");
+        assert!(compacted_pages_count < pages_count);
+        assert!(files_size(&storage) < size_before);
+
+        drop(storage);
+        let storage = BlobStore::open_with_config(dir.path(), small_config()).unwrap();
+        assert_eq!(storage.pages.read().len(), compacted_pages_count);
+        for point_offset in 0..100 {
+            assert_eq!(storage.get(point_offset).unwrap(), expected(point_offset));
+        }
+    }
+}
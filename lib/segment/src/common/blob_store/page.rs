@@ -0,0 +1,54 @@
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use memmap2::MmapMut;
+use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
+
+use crate::common::operation_error::OperationResult;
+
+/// Fixed size file with stored blocks, mapped into memory
+#[derive(Debug)]
+pub struct Page {
+    path: PathBuf,
+    mmap: MmapMut,
+}
+
+impl Page {
+    pub fn create(path: &Path, size: usize) -> OperationResult<Self> {
+        create_and_ensure_length(path, size)?;
+        Self::open(path)
+    }
+
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mmap = open_write_mmap(path)?;
+        Ok(Self {
+            path: path.to_owned(),
+            mmap,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn read(&self, offset: usize, len: usize) -> &[u8] {
+        &self.mmap[offset..offset + len]
+    }
+
+    pub fn write(&mut self, offset: usize, data: &[u8]) {
+        self.mmap[offset..offset + data.len()].copy_from_slice(data);
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        Ok(self.mmap.flush()?)
+    }
+
+    /// Read the whole page, so that it is loaded into the page cache
+    pub fn populate(&self) {
+        let mut dst = [0; 8192];
+        for chunk in self.mmap.chunks(dst.len()) {
+            dst[..chunk.len()].copy_from_slice(chunk);
+        }
+        black_box(dst);
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+
+use crate::common::operation_error::OperationResult;
+
+/// Size of the persisted pointer: page id, block offset, length and "is set" flag
+const ENTRY_SIZE: usize = 4 * std::mem::size_of::<u32>();
+
+/// Location of a stored value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValuePointer {
+    /// Page of the first block of the value
+    pub page_id: u32,
+    /// First block of the value inside of the page
+    pub block_offset: u32,
+    /// Length of the value in bytes, it may continue on the following pages
+    pub length: u32,
+}
+
+pub type PendingUpdates = BTreeMap<PointOffsetType, Option<ValuePointer>>;
+
+/// Mapping from point offsets to locations of their values
+///
+/// All pointers are kept in memory, changes are written into the file on flush.
+#[derive(Debug)]
+pub struct Tracker {
+    path: PathBuf,
+    pointers: Vec<Option<ValuePointer>>,
+    pending_updates: Arc<Mutex<PendingUpdates>>,
+}
+
+impl Tracker {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let pointers = if path.exists() {
+            std::fs::read(path)?
+                .chunks_exact(ENTRY_SIZE)
+                .map(decode_entry)
+                .collect()
+        } else {
+            vec![]
+        };
+        Ok(Self {
+            path: path.to_owned(),
+            pointers,
+            pending_updates: Default::default(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, point_offset: PointOffsetType) -> Option<ValuePointer> {
+        self.pointers.get(point_offset as usize).copied().flatten()
+    }
+
+    /// Set pointer of the point, returns the previous one
+    pub fn set(
+        &mut self,
+        point_offset: PointOffsetType,
+        pointer: Option<ValuePointer>,
+    ) -> Option<ValuePointer> {
+        let idx = point_offset as usize;
+        if idx >= self.pointers.len() {
+            if pointer.is_none() {
+                return None;
+            }
+            self.pointers.resize(idx + 1, None);
+        }
+        self.pending_updates.lock().insert(point_offset, pointer);
+        std::mem::replace(&mut self.pointers[idx], pointer)
+    }
+
+    /// Number of tracked points, including points without a value
+    pub fn len(&self) -> usize {
+        self.pointers.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (PointOffsetType, ValuePointer)> + '_ {
+        self.pointers
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, pointer)| Some((idx as PointOffsetType, (*pointer)?)))
+    }
+
+    pub fn pending_updates(&self) -> Arc<Mutex<PendingUpdates>> {
+        self.pending_updates.clone()
+    }
+
+    /// Drop all pointers, including not yet persisted ones
+    pub fn clear(&mut self) -> OperationResult<()> {
+        self.pointers.clear();
+        self.pending_updates.lock().clear();
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `updates` into the tracker file at `path` and sync it to disk
+pub fn write_updates(path: &Path, updates: &PendingUpdates) -> OperationResult<()> {
+    if updates.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)?;
+    let mut last_written = None;
+    for (&point_offset, pointer) in updates {
+        // Updates are sorted, avoid seeking for consecutive points
+        if last_written.map_or(true, |last| last + 1 != point_offset) {
+            file.seek(SeekFrom::Start(point_offset as u64 * ENTRY_SIZE as u64))?;
+        }
+        file.write_all(&encode_entry(*pointer))?;
+        last_written = Some(point_offset);
+    }
+    file.sync_data()?;
+    Ok(())
+}
+
+fn encode_entry(pointer: Option<ValuePointer>) -> [u8; ENTRY_SIZE] {
+    let (fields, is_set) = match pointer {
+        Some(pointer) => ([pointer.page_id, pointer.block_offset, pointer.length], 1),
+        None => ([0; 3], 0),
+    };
+    let mut entry = [0; ENTRY_SIZE];
+    for (chunk, field) in entry
+        .chunks_exact_mut(4)
+        .zip(fields.into_iter().chain([is_set]))
+    {
+        chunk.copy_from_slice(&field.to_le_bytes());
+    }
+    entry
+}
+
+fn decode_entry(entry: &[u8]) -> Option<ValuePointer> {
+    let field = |idx: usize| u32::from_le_bytes(entry[idx * 4..idx * 4 + 4].try_into().unwrap());
+    (field(3) != 0).then(|| ValuePointer {
+        page_id: field(0),
+        block_offset: field(1),
+        length: field(2),
+    })
+}
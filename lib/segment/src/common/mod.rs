@@ -1,4 +1,5 @@
 pub mod anonymize;
+pub mod blob_store;
 pub mod error_logging;
 pub mod macros;
 pub mod mmap_type;
@@ -8,6 +9,7 @@ pub mod reciprocal_rank_fusion;
 pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_buffered_update_wrapper;
 pub mod rocksdb_wrapper;
+pub mod sampling;
 pub mod score_fusion;
 pub mod utils;
 pub mod validate_snapshot_archive;
@@ -132,11 +134,33 @@ pub fn check_named_vectors(
     segment_config: &SegmentConfig,
 ) -> OperationResult<()> {
     for (vector_name, vector_data) in vectors.iter() {
+        check_vector_writable(vector_name, segment_config)?;
         check_vector(vector_name, &vector_data.into(), segment_config)?;
     }
     Ok(())
 }
 
+/// Check that the given vector owns its storage, so it can be written to.
+///
+/// Returns an error for truncated views.
+pub fn check_vector_writable(
+    vector_name: &str,
+    segment_config: &SegmentConfig,
+) -> OperationResult<()> {
+    let is_view = segment_config
+        .vector_data
+        .get(vector_name)
+        .is_some_and(|config| config.truncated_view.is_some());
+    if is_view {
+        return Err(OperationError::ValidationError {
+            description: format!(
+                "Vector `{vector_name}` is a truncated view and can't be written to"
+            ),
+        });
+    }
+    Ok(())
+}
+
 /// Get the vector config for the given name, or return a name error.
 ///
 /// Returns an error if incompatible.
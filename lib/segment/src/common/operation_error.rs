@@ -58,6 +58,10 @@ pub enum OperationError {
     WrongPayloadKey { description: String },
     #[error("No range index for `order_by` key: `{key}`. Please create one to use `order_by`. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Range conditions")]
     MissingRangeIndexForOrderBy { key: String },
+    #[error("No full-text index for text query key: `{key}`. Please create one to score points by text relevance")]
+    MissingFullTextIndexForQuery { key: String },
+    #[error("No keyword, integer or bool index for facet key: `{key}`. Please create one to count facet values")]
+    MissingMapIndexForFacet { key: String },
 }
 
 impl OperationError {
@@ -0,0 +1,85 @@
+//! Reproducible random sampling of points.
+//!
+//! Every point gets a pseudo-random key derived from the seed and the point id,
+//! a sample of `n` points is the `n` points with the smallest keys.
+//! Keys don't depend on how points are distributed across segments and shards,
+//! so partial samples are merged by the key, and each segment or shard contributes
+//! proportionally to the number of its matching points.
+
+use common::types::ScoreType;
+
+use crate::types::{ExtendedPointId, PointIdType};
+
+/// Number of significant bits of the key, all of them are exactly representable by `f32`
+const SAMPLE_KEY_BITS: u32 = 24;
+
+/// Key of a point in the random sample, smaller keys are sampled first.
+///
+/// Ties are resolved by the point id.
+pub type SampleKey = (u32, PointIdType);
+
+/// Key of the point in the sample drawn with the given `seed`
+pub fn sample_key(seed: u64, point_id: PointIdType) -> SampleKey {
+    let id_hash = match point_id {
+        ExtendedPointId::NumId(num) => num,
+        ExtendedPointId::Uuid(uuid) => {
+            let (high, low) = uuid.as_u64_pair();
+            mix64(high) ^ low
+        }
+    };
+    let hash = mix64(seed ^ mix64(id_hash));
+    ((hash >> (u64::BITS - SAMPLE_KEY_BITS)) as u32, point_id)
+}
+
+/// Score of the sampled point, in `[0, 1)` and ordered the same way as its key
+pub fn sample_key_score(key: &SampleKey) -> ScoreType {
+    key.0 as ScoreType / (1u32 << SAMPLE_KEY_BITS) as ScoreType
+}
+
+/// Finalizer of the SplitMix64 generator, spreads any change of the input over all bits
+fn mix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_sample_key_is_reproducible() {
+        let point_id = PointIdType::Uuid(Uuid::from_u128(0x1234_5678_9abc_def0));
+        assert_eq!(sample_key(42, point_id), sample_key(42, point_id));
+        assert_ne!(sample_key(42, point_id), sample_key(43, point_id));
+    }
+
+    #[test]
+    fn test_sample_keys_are_uniform() {
+        const POINTS: u64 = 10_000;
+        const BUCKETS: usize = 10;
+
+        let mut counts = [0usize; BUCKETS];
+        for point_id in 0..POINTS {
+            let score = sample_key_score(&sample_key(7, point_id.into()));
+            assert!((0.0..1.0).contains(&score));
+            counts[(score * BUCKETS as ScoreType) as usize] += 1;
+        }
+
+        let expected = POINTS as usize / BUCKETS;
+        for count in counts {
+            assert!(count.abs_diff(expected) < expected / 10, "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn test_sample_key_score_keeps_order() {
+        let mut keys: Vec<_> = (0..1000u64).map(|id| sample_key(1, id.into())).collect();
+        keys.sort_unstable();
+        let scores: Vec<_> = keys.iter().map(sample_key_score).collect();
+        assert!(scores.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
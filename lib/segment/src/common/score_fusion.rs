@@ -18,6 +18,30 @@ pub struct ScoreFusion {
     pub order: Order,
 }
 
+impl ScoreFusion {
+    /// Distribution-Based Score Fusion
+    ///
+    /// Normalizes each list using its mean and the 3rd standard deviation as extremes, then sums the scores.
+    pub fn dbsf() -> Self {
+        Self {
+            method: Aggregation::Sum,
+            norm: Normalization::Distr,
+            weights: vec![],
+            order: Order::LargeBetter,
+        }
+    }
+
+    /// Normalizes each list using its minimum and maximum scores, then sums the weighted scores.
+    pub fn weighted_sum(weights: Vec<f32>) -> Self {
+        Self {
+            method: Aggregation::Sum,
+            norm: Normalization::MinMax,
+            weights,
+            order: Order::LargeBetter,
+        }
+    }
+}
+
 /// Defines how to combine the scores of the same point in different lists
 pub enum Aggregation {
     /// Sums the scores
@@ -54,6 +54,7 @@ impl From<SegmentConfigV5> for SegmentConfig {
                         .unwrap_or_else(|| old_segment.storage_type.into()),
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 };
 
                 (vector_name, new_data)
@@ -152,6 +153,7 @@ mod tests {
                             max_indexing_threads: 0,
                             on_disk: None,
                             payload_m: Some(10),
+                            compressed_links: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -183,6 +185,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                compressed_links: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -258,6 +261,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                compressed_links: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::json_path::JsonPath;
+use crate::types::{Filter, IntPayloadType, Match, ValueVariants};
+
+/// Parameters of a facet counts request, shared by all segments of a shard
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetParams {
+    /// Payload key with a keyword, integer or bool index
+    pub key: JsonPath,
+
+    /// Max number of values to return
+    pub limit: usize,
+
+    /// Count only points which satisfy this filter
+    pub filter: Option<Filter>,
+
+    /// If `true` - count the values of every matching point,
+    /// otherwise rely on the cardinality estimation of the filter for each value
+    pub exact: bool,
+}
+
+/// A single value of the faceted field
+#[derive(
+    Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Int(IntPayloadType),
+    Bool(bool),
+}
+
+impl From<FacetValue> for ValueVariants {
+    fn from(value: FacetValue) -> Self {
+        match value {
+            FacetValue::Keyword(keyword) => ValueVariants::Keyword(keyword),
+            FacetValue::Int(integer) => ValueVariants::Integer(integer),
+            FacetValue::Bool(boolean) => ValueVariants::Bool(boolean),
+        }
+    }
+}
+
+impl From<FacetValue> for Match {
+    fn from(value: FacetValue) -> Self {
+        Match::new_value(ValueVariants::from(value))
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct FacetValueHit {
+    pub value: FacetValue,
+    pub count: usize,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq, Default)]
+pub struct FacetResponse {
+    pub hits: Vec<FacetValueHit>,
+}
+
+impl FacetResponse {
+    /// Take `limit` values with the largest counts, ties are broken by value
+    pub fn top_hits(counts: HashMap<FacetValue, usize>, limit: usize) -> Self {
+        let hits = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(value, count)| FacetValueHit { value, count })
+            .sorted_unstable_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)))
+            .take(limit)
+            .collect();
+        Self { hits }
+    }
+
+    /// Sum counts of the same values from several responses and take top `limit` of them
+    pub fn merge(responses: impl IntoIterator<Item = FacetResponse>, limit: usize) -> Self {
+        let mut counts: HashMap<FacetValue, usize> = HashMap::new();
+        for hit in responses.into_iter().flat_map(|response| response.hits) {
+            *counts.entry(hit.value).or_default() += hit.count;
+        }
+        Self::top_hits(counts, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_facet_responses() {
+        let hit = |value: &str, count| FacetValueHit {
+            value: FacetValue::Keyword(value.to_string()),
+            count,
+        };
+
+        let first = FacetResponse {
+            hits: vec![hit("red", 10), hit("green", 4), hit("blue", 3)],
+        };
+        let second = FacetResponse {
+            hits: vec![hit("blue", 9), hit("red", 1), hit("black", 4)],
+        };
+
+        let merged = FacetResponse::merge([first, second], 3);
+        assert_eq!(
+            merged.hits,
+            vec![hit("blue", 12), hit("red", 11), hit("black", 4)],
+        );
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::BTreeSet;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
 
 // Keyword
 
@@ -16,8 +19,11 @@ pub struct KeywordIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: KeywordIndexType,
 
+    /// If true - used for tenant optimization. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_tenant: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
-    #[cfg(any())]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
 }
@@ -43,9 +49,13 @@ pub struct IntegerIndexParams {
     /// If true - support ranges filters.
     pub range: bool,
 
+    /// If true - use this key to organize storage of the collection data.
+    /// This option assumes, that this key will be used in majority of filtered requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_principal: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg(any())]
     pub on_disk: Option<bool>,
 }
 
@@ -55,6 +65,8 @@ impl Default for IntegerIndexParams {
             r#type: Default::default(),
             lookup: true,
             range: true,
+            is_principal: None,
+            on_disk: None,
         }
     }
 }
@@ -74,9 +86,13 @@ pub struct FloatIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: FloatIndexType,
 
+    /// If true - use this key to organize storage of the collection data.
+    /// This option assumes, that this key will be used in majority of filtered requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_principal: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg(any())]
     pub on_disk: Option<bool>,
 }
 
@@ -97,7 +113,6 @@ pub struct GeoIndexParams {
 
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg(any())]
     pub on_disk: Option<bool>,
 }
 
@@ -138,6 +153,123 @@ pub struct TextIndexParams {
     /// If true, lowercase all tokens. Default: true.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub lowercase: Option<bool>,
+
+    /// If true, normalize tokens by folding accented characters to ASCII (e.g. "ação" -> "acao"). Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii_folding: Option<bool>,
+
+    /// Ignore this set of tokens. Can select from predefined languages and/or provide a custom set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopwords: Option<StopwordsInterface>,
+
+    /// Algorithm for stemming. Default: disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stemmer: Option<StemmingAlgorithm>,
+
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+/// Languages with predefined stop-words
+#[derive(
+    Debug,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    Clone,
+    Copy,
+    PartialEq,
+    Hash,
+    Eq,
+    PartialOrd,
+    Ord,
+    EnumString,
+    Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Language {
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Russian,
+    Spanish,
+    Swedish,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StopwordsInterface {
+    Language(Language),
+    Set(StopwordsSet),
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct StopwordsSet {
+    /// Languages, which predefined stop-words are used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub languages: Option<BTreeSet<Language>>,
+
+    /// Custom stop-words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom: Option<BTreeSet<String>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StemmingAlgorithm {
+    Snowball(SnowballParams),
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Snowball {
+    #[default]
+    Snowball,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SnowballParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: Snowball,
+
+    pub language: SnowballLanguage,
+}
+
+/// Languages supported by the Snowball stemmer
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq, EnumString, Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SnowballLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
 }
 
 // Bool
@@ -157,7 +289,6 @@ pub struct BoolIndexParams {
 
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg(any())]
     pub on_disk: Option<bool>,
 }
 
@@ -176,8 +307,36 @@ pub struct DatetimeIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: DatetimeIndexType,
 
+    /// If true - use this key to organize storage of the collection data.
+    /// This option assumes, that this key will be used in majority of filtered requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_principal: Option<bool>,
+
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+// UUID
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UuidIndexType {
+    #[default]
+    Uuid,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct UuidIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: UuidIndexType,
+
+    /// If true - used for tenant optimization. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_tenant: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg(any())]
     pub on_disk: Option<bool>,
 }
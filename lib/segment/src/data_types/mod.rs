@@ -1,8 +1,10 @@
+pub mod facets;
 pub mod groups;
 pub mod index;
 pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
 pub mod query_context;
+pub mod text_query;
 pub mod tiny_map;
 pub mod vectors;
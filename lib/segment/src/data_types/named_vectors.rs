@@ -7,11 +7,14 @@ use super::primitive::PrimitiveVectorElement;
 use super::tiny_map;
 use super::vectors::{
     DenseVector, MultiDenseVectorInternal, TypedMultiDenseVector, TypedMultiDenseVectorRef, Vector,
-    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+    VectorElementType, VectorElementTypeBit, VectorElementTypeByte, VectorElementTypeHalf,
+    VectorRef,
 };
 use crate::common::operation_error::OperationError;
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::types::{Distance, VectorDataConfig, VectorStorageDatatype};
 
 type CowKey<'a> = Cow<'a, str>;
@@ -330,6 +333,12 @@ impl<'a> NamedVectors<'a> {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementType>>::preprocess(dense_vector)
                 }
+                Distance::Hamming => {
+                    <HammingMetric as Metric<VectorElementType>>::preprocess(dense_vector)
+                }
+                Distance::Jaccard => {
+                    <JaccardMetric as Metric<VectorElementType>>::preprocess(dense_vector)
+                }
             },
             Some(VectorStorageDatatype::Uint8) => match config.distance {
                 Distance::Cosine => {
@@ -344,6 +353,12 @@ impl<'a> NamedVectors<'a> {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
                 }
+                Distance::Hamming => {
+                    <HammingMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
+                }
+                Distance::Jaccard => {
+                    <JaccardMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
+                }
             },
             Some(VectorStorageDatatype::Float16) => match config.distance {
                 Distance::Cosine => {
@@ -358,6 +373,32 @@ impl<'a> NamedVectors<'a> {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
                 }
+                Distance::Hamming => {
+                    <HammingMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
+                }
+                Distance::Jaccard => {
+                    <JaccardMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
+                }
+            },
+            Some(VectorStorageDatatype::Bit) => match config.distance {
+                Distance::Cosine => {
+                    <CosineMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Euclid => {
+                    <EuclidMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Dot => {
+                    <DotProductMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Manhattan => {
+                    <ManhattanMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Hamming => {
+                    <HammingMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
+                Distance::Jaccard => {
+                    <JaccardMetric as Metric<VectorElementTypeBit>>::preprocess(dense_vector)
+                }
             },
         }
     }
@@ -6,9 +6,14 @@ use serde::{Deserialize, Serialize};
 
 use super::named_vectors::CowMultiVector;
 use super::vectors::TypedMultiDenseVector;
-use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use crate::data_types::vectors::{
+    PackedBits, VectorElementType, VectorElementTypeBit, VectorElementTypeByte,
+    VectorElementTypeHalf,
+};
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
 
 pub trait PrimitiveVectorElement:
@@ -151,6 +156,12 @@ impl PrimitiveVectorElement for VectorElementTypeByte {
                 Distance::Manhattan => {
                     <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
                 }
+                Distance::Hamming => {
+                    <HammingMetric as Metric<VectorElementType>>::preprocess(vector)
+                }
+                Distance::Jaccard => {
+                    <JaccardMetric as Metric<VectorElementType>>::preprocess(vector)
+                }
             };
             Cow::from(preprocessed_vector)
         }
@@ -188,3 +199,48 @@ impl PrimitiveVectorElement for VectorElementTypeByte {
         ))
     }
 }
+
+/// Bit vectors are packed 8 dimensions per element,
+/// so the number of elements is the vector dimension divided by 8.
+impl PrimitiveVectorElement for VectorElementTypeBit {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(PackedBits::pack(&vector))
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(PackedBits::unpack(&vector))
+    }
+
+    fn quantization_preprocess<'a>(
+        _quantization_config: &QuantizationConfig,
+        _distance: Distance,
+        vector: &'a [Self],
+    ) -> Cow<'a, [f32]> {
+        Cow::Owned(PackedBits::unpack(vector))
+    }
+
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Bit
+    }
+
+    fn from_float_multivector(
+        multivector: CowMultiVector<VectorElementType>,
+    ) -> CowMultiVector<Self> {
+        let multivector = multivector.as_vec_ref();
+        debug_assert_eq!(multivector.dim % PackedBits::BITS, 0);
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            PackedBits::pack(multivector.flattened_vectors),
+            multivector.dim / PackedBits::BITS,
+        ))
+    }
+
+    fn into_float_multivector(
+        multivector: CowMultiVector<Self>,
+    ) -> CowMultiVector<VectorElementType> {
+        let multivector = multivector.as_vec_ref();
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            PackedBits::unpack(multivector.flattened_vectors),
+            multivector.dim * PackedBits::BITS,
+        ))
+    }
+}
@@ -6,7 +6,9 @@ use std::sync::Arc;
 use bitvec::prelude::BitSlice;
 use sparse::common::types::{DimId, DimWeight};
 
+use crate::data_types::text_query::{TextQuery, TextStatistics};
 use crate::data_types::tiny_map;
+use crate::json_path::JsonPath;
 
 #[derive(Debug)]
 pub struct QueryContext {
@@ -26,6 +28,11 @@ pub struct QueryContext {
     /// Required for processing sparse vector search with `idf-dot` similarity.
     #[allow(dead_code)]
     idf: tiny_map::TinyMap<String, HashMap<DimId, usize>>,
+
+    /// Statistics of the full-text fields for the query tokens,
+    /// collected over all segments.
+    /// Required for BM25 scoring of text queries.
+    text_statistics: HashMap<JsonPath, TextStatistics>,
 }
 
 impl QueryContext {
@@ -35,6 +42,7 @@ impl QueryContext {
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
             idf: tiny_map::TinyMap::new(),
+            text_statistics: HashMap::new(),
         }
     }
 
@@ -79,6 +87,19 @@ impl QueryContext {
         &mut self.idf
     }
 
+    /// Register the text query, so the statistics of its key and tokens can be collected.
+    pub fn init_text_statistics(&mut self, query: &TextQuery) {
+        self.text_statistics
+            .entry(query.key.clone())
+            .or_default()
+            .queries
+            .push(query.query.clone());
+    }
+
+    pub fn mut_text_statistics(&mut self) -> &mut HashMap<JsonPath, TextStatistics> {
+        &mut self.text_statistics
+    }
+
     pub fn get_segment_query_context(&self) -> SegmentQueryContext {
         SegmentQueryContext {
             query_context: Some(self),
@@ -118,6 +139,11 @@ impl<'a> SegmentQueryContext<'a> {
         }
     }
 
+    /// Statistics of the full-text field, if they were collected for the query
+    pub fn get_text_statistics(&self, key: &JsonPath) -> Option<&'a TextStatistics> {
+        self.query_context?.text_statistics.get(key)
+    }
+
     pub fn with_deleted_points(mut self, deleted_points: &'a BitSlice) -> Self {
         self.deleted_points = Some(deleted_points);
         self
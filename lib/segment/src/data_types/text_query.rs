@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use common::types::ScoreType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::json_path::JsonPath;
+
+/// Free parameter of BM25, controls term frequency saturation
+const BM25_K1: f32 = 1.2;
+
+/// Free parameter of BM25, controls normalization by the document length
+const BM25_B: f32 = 0.75;
+
+fn text_query_example() -> TextQuery {
+    TextQuery {
+        key: JsonPath::from_str("description").unwrap(),
+        query: "quick brown fox".to_string(),
+    }
+}
+
+/// Score points by the BM25 relevance of a full-text indexed payload field to the query text
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[schemars(example = "text_query_example")]
+pub struct TextQuery {
+    /// Payload key with a full-text index
+    pub key: JsonPath,
+
+    /// Text to score the points against. It is tokenized the same way as the indexed field
+    #[validate(length(min = 1))]
+    pub query: String,
+}
+
+/// Corpus statistics of a full-text field, required for BM25 scoring.
+///
+/// Collected over all segments as a part of [`QueryContext`](super::query_context::QueryContext),
+/// so that scores of points from different segments are comparable.
+#[derive(Debug, Default, Clone)]
+pub struct TextStatistics {
+    /// Texts of the queries, tokens of which are counted in `document_frequencies`
+    pub queries: Vec<String>,
+
+    /// Number of points with an indexed document
+    pub points_count: usize,
+
+    /// Total length of all documents, in token occurrences
+    pub total_length: usize,
+
+    /// Number of documents containing each of the query tokens
+    pub document_frequencies: HashMap<String, usize>,
+}
+
+impl TextStatistics {
+    /// Inverse document frequency of the token, same formula as for the `idf` sparse vector modifier
+    pub fn idf(&self, token: &str) -> ScoreType {
+        let n = self.points_count as ScoreType;
+        let df = self.document_frequencies.get(token).copied().unwrap_or(0) as ScoreType;
+        ((n - df + 0.5) / (df + 0.5) + 1.).ln()
+    }
+
+    fn avg_length(&self) -> ScoreType {
+        if self.points_count == 0 {
+            return 1.0;
+        }
+        (self.total_length as ScoreType / self.points_count as ScoreType).max(1.0)
+    }
+
+    /// BM25 score contribution of a single token, found `token_frequency` times in the document.
+    ///
+    /// Document length is measured in token occurrences, same as the frequency.
+    pub fn bm25(
+        &self,
+        idf: ScoreType,
+        token_frequency: usize,
+        document_length: usize,
+    ) -> ScoreType {
+        let tf = token_frequency as ScoreType;
+        let length_norm = 1.0 - BM25_B + BM25_B * document_length as ScoreType / self.avg_length();
+        idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * length_norm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bm25_score() {
+        let statistics = TextStatistics {
+            points_count: 10,
+            total_length: 100,
+            document_frequencies: HashMap::from([
+                ("rare".to_string(), 1),
+                ("common".to_string(), 9),
+            ]),
+            ..Default::default()
+        };
+
+        // Rare tokens are more important
+        assert!(statistics.idf("rare") > statistics.idf("common"));
+        assert!(statistics.idf("unknown") > statistics.idf("rare"));
+
+        let idf = statistics.idf("rare");
+        // More occurrences give higher score, but it saturates
+        let once = statistics.bm25(idf, 1, 10);
+        let twice = statistics.bm25(idf, 2, 10);
+        assert!(twice > once);
+        assert!(twice < 2.0 * once);
+        assert!(statistics.bm25(idf, 1000, 10) < idf * (BM25_K1 + 1.0));
+
+        // Same occurrences in a shorter document give higher score
+        assert!(statistics.bm25(idf, 1, 5) > once);
+    }
+}
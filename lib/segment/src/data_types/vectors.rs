@@ -183,6 +183,49 @@ pub type VectorElementTypeHalf = f16;
 
 pub type VectorElementTypeByte = u8;
 
+/// Eight dimensions of a binary vector packed into a single byte, most significant bit first
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+#[repr(transparent)]
+pub struct PackedBits(pub u8);
+
+pub type VectorElementTypeBit = PackedBits;
+
+impl PackedBits {
+    /// Number of vector dimensions stored in a single element
+    pub const BITS: usize = u8::BITS as usize;
+
+    /// View packed bits as plain bytes
+    pub fn as_bytes(bits: &[PackedBits]) -> &[u8] {
+        // Safety: `PackedBits` is `repr(transparent)` over `u8`
+        unsafe { std::slice::from_raw_parts(bits.as_ptr().cast(), bits.len()) }
+    }
+
+    /// Pack a vector into bits, any positive element is a set bit
+    pub fn pack(vector: &[VectorElementType]) -> Vec<PackedBits> {
+        vector
+            .chunks(Self::BITS)
+            .map(|chunk| {
+                let byte = chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &x)| x > 0.0)
+                    .fold(0u8, |byte, (i, _)| byte | (0x80 >> i));
+                PackedBits(byte)
+            })
+            .collect()
+    }
+
+    /// Unpack bits into a vector of zeros and ones
+    pub fn unpack(bits: &[PackedBits]) -> Vec<VectorElementType> {
+        bits.iter()
+            .flat_map(|PackedBits(byte)| {
+                (0..Self::BITS).map(move |i| VectorElementType::from((byte << i) & 0x80 != 0))
+            })
+            .collect()
+    }
+}
+
 pub const DEFAULT_VECTOR_NAME: &str = "";
 
 pub type TypedDenseVector<T> = Vec<T>;
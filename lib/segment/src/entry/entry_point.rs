@@ -1,12 +1,16 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 
-use common::types::TelemetryDetail;
+use common::types::{ScoreType, TelemetryDetail};
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
+use crate::common::sampling::SampleKey;
+use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
+use crate::data_types::text_query::TextQuery;
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
 use crate::json_path::JsonPath;
@@ -126,6 +130,40 @@ pub trait SegmentEntry {
         order_by: &'a OrderBy,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
 
+    /// Return a random sample of up to `limit` points which satisfy the filtering condition.
+    ///
+    /// Points with the smallest sample keys for the `seed` are returned, sorted by the key.
+    /// Samples of different segments with the same seed can be merged by the key.
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<SampleKey>;
+
+    /// Return points with the best BM25 scores of the `query`, which satisfy filtering condition.
+    ///
+    /// Text statistics are expected to be collected over all segments with [`Self::fill_query_context`],
+    /// otherwise statistics of this segment only are used.
+    /// Will fail if there is no full-text index for the query key.
+    fn read_text_scored<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        query: &'a TextQuery,
+        query_context: SegmentQueryContext,
+    ) -> OperationResult<Vec<(ScoreType, PointIdType)>>;
+
+    /// Count points with each value of the `request.key` field, which satisfy filtering condition.
+    ///
+    /// In exact mode all values are returned, otherwise only the best `request.limit` estimations.
+    /// Will fail if there is no keyword, integer or bool index for the key.
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>>;
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
 
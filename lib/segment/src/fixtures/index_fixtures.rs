@@ -32,6 +32,28 @@ impl FilterContext for FakeFilterContext {
     }
 }
 
+/// Matches every `modulo`-th point
+pub struct ModuloFilterContext {
+    pub modulo: PointOffsetType,
+    /// Point which is being inserted, excluded while building payload links
+    pub excluded_point: Option<PointOffsetType>,
+}
+
+impl ModuloFilterContext {
+    pub const fn new(modulo: PointOffsetType) -> Self {
+        Self {
+            modulo,
+            excluded_point: None,
+        }
+    }
+}
+
+impl FilterContext for ModuloFilterContext {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        point_id % self.modulo == 0 && Some(point_id) != self.excluded_point
+    }
+}
+
 pub struct TestRawScorerProducer<TMetric: Metric<VectorElementType>> {
     pub vectors: ChunkedVectors<VectorElementType>,
     pub deleted_points: BitVec,
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::path::PathBuf;
 
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
@@ -7,6 +8,7 @@ use rand::{Rng, SeedableRng};
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::id_tracker::mutable_id_tracker::MutableIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::types::{PointIdType, SeqNumberType};
 
@@ -143,24 +145,44 @@ pub trait IdTracker: fmt::Debug {
     /// Finds inconsistencies between id mapping and versions storage.
     /// It might happen that point doesn't have version due to un-flushed WAL.
     /// This method makes those points usable again.
-    fn cleanup_versions(&mut self) -> OperationResult<()>;
+    fn cleanup_versions(&mut self) -> OperationResult<()> {
+        let mut to_remove = Vec::new();
+        for internal_id in self.iter_internal() {
+            if self.internal_version(internal_id).is_none() {
+                if let Some(external_id) = self.external_id(internal_id) {
+                    to_remove.push(external_id);
+                } else {
+                    debug_assert!(false, "internal id {} has no external id", internal_id);
+                }
+            }
+        }
+        for external_id in to_remove {
+            self.drop(external_id)?;
+            #[cfg(debug_assertions)] // Only for dev builds
+            {
+                log::debug!("dropped version for point {} without version", external_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Files of the tracker, which are not part of the segment database
+    fn files(&self) -> Vec<PathBuf>;
 }
 
 pub type IdTrackerSS = dyn IdTracker + Sync + Send;
 
 #[derive(Debug)]
 pub enum IdTrackerEnum {
-    MutableIdTracker(SimpleIdTracker),
-    ImmutableIdTracker(SimpleIdTracker),
+    MutableIdTracker(MutableIdTracker),
+    RocksDbIdTracker(SimpleIdTracker),
 }
 
 impl IdTracker for IdTrackerEnum {
     fn internal_version(&self, internal_id: PointOffsetType) -> Option<SeqNumberType> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.internal_version(internal_id),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
-                id_tracker.internal_version(internal_id)
-            }
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.internal_version(internal_id),
         }
     }
 
@@ -173,7 +195,7 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::MutableIdTracker(id_tracker) => {
                 id_tracker.set_internal_version(internal_id, version)
             }
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => {
                 id_tracker.set_internal_version(internal_id, version)
             }
         }
@@ -182,14 +204,14 @@ impl IdTracker for IdTrackerEnum {
     fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.internal_id(external_id),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.internal_id(external_id),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.internal_id(external_id),
         }
     }
 
     fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.external_id(internal_id),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.external_id(internal_id),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.external_id(internal_id),
         }
     }
 
@@ -202,7 +224,7 @@ impl IdTracker for IdTrackerEnum {
             IdTrackerEnum::MutableIdTracker(id_tracker) => {
                 id_tracker.set_link(external_id, internal_id)
             }
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => {
                 id_tracker.set_link(external_id, internal_id)
             }
         }
@@ -211,21 +233,21 @@ impl IdTracker for IdTrackerEnum {
     fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.drop(external_id),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.drop(external_id),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.drop(external_id),
         }
     }
 
     fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_external(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_external(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.iter_external(),
         }
     }
 
     fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_internal(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_internal(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.iter_internal(),
         }
     }
 
@@ -235,65 +257,70 @@ impl IdTracker for IdTrackerEnum {
     ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_from(external_id),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_from(external_id),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.iter_from(external_id),
         }
     }
 
     fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_ids(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_ids(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.iter_ids(),
         }
     }
 
     fn mapping_flusher(&self) -> Flusher {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.mapping_flusher(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.mapping_flusher(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.mapping_flusher(),
         }
     }
 
     fn versions_flusher(&self) -> Flusher {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.versions_flusher(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.versions_flusher(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.versions_flusher(),
         }
     }
 
     fn total_point_count(&self) -> usize {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.total_point_count(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.total_point_count(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.total_point_count(),
         }
     }
 
     fn deleted_point_count(&self) -> usize {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.deleted_point_count(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.deleted_point_count(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.deleted_point_count(),
         }
     }
 
     fn deleted_point_bitslice(&self) -> &BitSlice {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.deleted_point_bitslice(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.deleted_point_bitslice(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.deleted_point_bitslice(),
         }
     }
 
     fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.is_deleted_point(internal_id),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => {
-                id_tracker.is_deleted_point(internal_id)
-            }
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.is_deleted_point(internal_id),
         }
     }
 
     fn cleanup_versions(&mut self) -> OperationResult<()> {
         match self {
             IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.cleanup_versions(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.cleanup_versions(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.cleanup_versions(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        match self {
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.files(),
+            IdTrackerEnum::RocksDbIdTracker(id_tracker) => id_tracker.files(),
         }
     }
 }
@@ -1,4 +1,6 @@
 pub mod id_tracker_base;
+pub mod mutable_id_tracker;
+pub mod point_mappings;
 pub mod simple_id_tracker;
 
 pub use id_tracker_base::*;
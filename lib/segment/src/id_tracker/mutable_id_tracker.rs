@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::id_tracker::point_mappings::PointMappings;
+use crate::id_tracker::IdTracker;
+use crate::types::{PointIdType, SeqNumberType};
+
+const MAPPINGS_FILE_NAME: &str = "id_tracker.mappings";
+const VERSIONS_FILE_NAME: &str = "id_tracker.versions";
+
+const CHANGE_INSERT_NUM: u8 = 1;
+const CHANGE_INSERT_UUID: u8 = 2;
+const CHANGE_DELETE_NUM: u8 = 3;
+const CHANGE_DELETE_UUID: u8 = 4;
+
+const VERSION_SIZE: usize = std::mem::size_of::<SeqNumberType>();
+
+/// Don't bother compacting small mapping logs
+const COMPACTION_MIN_CHANGES: usize = 1024;
+
+/// Compact mapping log, if it has this many times more changes than live links
+const COMPACTION_RATIO: usize = 2;
+
+/// Single persisted change of the id mapping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MappingChange {
+    Insert(PointIdType, PointOffsetType),
+    Delete(PointIdType),
+}
+
+impl MappingChange {
+    fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            MappingChange::Insert(PointIdType::NumId(idx), internal_id) => {
+                writer.write_all(&[CHANGE_INSERT_NUM])?;
+                writer.write_all(&idx.to_le_bytes())?;
+                writer.write_all(&internal_id.to_le_bytes())
+            }
+            MappingChange::Insert(PointIdType::Uuid(uuid), internal_id) => {
+                writer.write_all(&[CHANGE_INSERT_UUID])?;
+                writer.write_all(uuid.as_bytes())?;
+                writer.write_all(&internal_id.to_le_bytes())
+            }
+            MappingChange::Delete(PointIdType::NumId(idx)) => {
+                writer.write_all(&[CHANGE_DELETE_NUM])?;
+                writer.write_all(&idx.to_le_bytes())
+            }
+            MappingChange::Delete(PointIdType::Uuid(uuid)) => {
+                writer.write_all(&[CHANGE_DELETE_UUID])?;
+                writer.write_all(uuid.as_bytes())
+            }
+        }
+    }
+
+    /// Decode a change from the start of `data`, returns the change and its encoded size
+    ///
+    /// Returns `None` if `data` doesn't start with a complete change.
+    fn read(data: &[u8]) -> Option<(Self, usize)> {
+        let (&tag, data) = data.split_first()?;
+        let num_id = |data: &[u8]| {
+            Some(PointIdType::NumId(u64::from_le_bytes(
+                data.get(..8)?.try_into().ok()?,
+            )))
+        };
+        let uuid = |data: &[u8]| Some(PointIdType::Uuid(Uuid::from_slice(data.get(..16)?).ok()?));
+        let internal_id = |data: &[u8]| {
+            Some(PointOffsetType::from_le_bytes(
+                data.get(..4)?.try_into().ok()?,
+            ))
+        };
+
+        let change = match tag {
+            CHANGE_INSERT_NUM => (
+                MappingChange::Insert(num_id(data)?, internal_id(&data[8..])?),
+                1 + 8 + 4,
+            ),
+            CHANGE_INSERT_UUID => (
+                MappingChange::Insert(uuid(data)?, internal_id(&data[16..])?),
+                1 + 16 + 4,
+            ),
+            CHANGE_DELETE_NUM => (MappingChange::Delete(num_id(data)?), 1 + 8),
+            CHANGE_DELETE_UUID => (MappingChange::Delete(uuid(data)?), 1 + 16),
+            _ => return None,
+        };
+        Some(change)
+    }
+}
+
+/// Id tracker, which persists mappings and versions in plain files of the segment
+///
+/// Mappings are stored as an append-only log of changes, which is replayed on load.
+/// Versions are stored as a flat array, indexed by internal id.
+#[derive(Debug)]
+pub struct MutableIdTracker {
+    segment_path: PathBuf,
+    internal_to_version: Vec<SeqNumberType>,
+    mappings: PointMappings,
+    pending_mappings: Arc<Mutex<Vec<MappingChange>>>,
+    pending_versions: Arc<Mutex<BTreeMap<PointOffsetType, SeqNumberType>>>,
+}
+
+impl MutableIdTracker {
+    pub fn open(segment_path: impl Into<PathBuf>) -> OperationResult<Self> {
+        let segment_path = segment_path.into();
+        let mappings_path = mappings_path(&segment_path);
+        let versions_path = versions_path(&segment_path);
+
+        let mappings = if mappings_path.exists() {
+            load_mappings(&mappings_path)?
+        } else {
+            File::create(&mappings_path)?.sync_all()?;
+            PointMappings::default()
+        };
+
+        let internal_to_version = if versions_path.exists() {
+            std::fs::read(&versions_path)?
+                .chunks_exact(VERSION_SIZE)
+                .map(|chunk| SeqNumberType::from_le_bytes(chunk.try_into().unwrap()))
+                .collect()
+        } else {
+            File::create(&versions_path)?.sync_all()?;
+            vec![]
+        };
+
+        #[cfg(debug_assertions)]
+        mappings.assert_mappings();
+
+        Ok(Self {
+            segment_path,
+            internal_to_version,
+            mappings,
+            pending_mappings: Default::default(),
+            pending_versions: Default::default(),
+        })
+    }
+
+    /// Remove tracker files from the segment directory
+    pub fn clear_storage(segment_path: &Path) -> OperationResult<()> {
+        for path in [mappings_path(segment_path), versions_path(segment_path)] {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IdTracker for MutableIdTracker {
+    fn internal_version(&self, internal_id: PointOffsetType) -> Option<SeqNumberType> {
+        self.internal_to_version.get(internal_id as usize).copied()
+    }
+
+    fn set_internal_version(
+        &mut self,
+        internal_id: PointOffsetType,
+        version: SeqNumberType,
+    ) -> OperationResult<()> {
+        if self.external_id(internal_id).is_some() {
+            if internal_id as usize >= self.internal_to_version.len() {
+                self.internal_to_version.resize(internal_id as usize + 1, 0);
+            }
+            self.internal_to_version[internal_id as usize] = version;
+            self.pending_versions.lock().insert(internal_id, version);
+        }
+        Ok(())
+    }
+
+    fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        self.mappings.internal_id(&external_id)
+    }
+
+    fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
+        self.mappings.external_id(internal_id)
+    }
+
+    fn set_link(
+        &mut self,
+        external_id: PointIdType,
+        internal_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        self.mappings.set_link(external_id, internal_id);
+        self.pending_mappings
+            .lock()
+            .push(MappingChange::Insert(external_id, internal_id));
+        Ok(())
+    }
+
+    fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
+        self.mappings.drop(&external_id);
+        self.pending_mappings
+            .lock()
+            .push(MappingChange::Delete(external_id));
+        Ok(())
+    }
+
+    fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
+        self.mappings.iter_external()
+    }
+
+    fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.mappings.iter_internal()
+    }
+
+    fn iter_from(
+        &self,
+        external_id: Option<PointIdType>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        self.mappings.iter_from(external_id)
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.iter_internal()
+    }
+
+    /// Creates a flusher function, that appends pending mapping changes to the mapping log.
+    /// This function should be called _before_ flushing the versions.
+    fn mapping_flusher(&self) -> Flusher {
+        let path = mappings_path(&self.segment_path);
+        let pending_mappings = self.pending_mappings.clone();
+        Box::new(move || {
+            let changes = std::mem::take(&mut *pending_mappings.lock());
+            if let Err(err) = append_mappings(&path, &changes) {
+                // Keep changes for the next flush, in front of the ones made meanwhile
+                let mut pending = pending_mappings.lock();
+                let newer = std::mem::replace(&mut *pending, changes);
+                pending.extend(newer);
+                return Err(err);
+            }
+            Ok(())
+        })
+    }
+
+    /// Creates a flusher function, that writes pending versions into the versions file.
+    /// This function should be called _after_ flushing the mappings.
+    fn versions_flusher(&self) -> Flusher {
+        let path = versions_path(&self.segment_path);
+        let pending_versions = self.pending_versions.clone();
+        Box::new(move || {
+            let versions = std::mem::take(&mut *pending_versions.lock());
+            if let Err(err) = write_versions(&path, &versions) {
+                // Keep versions for the next flush, unless they were updated meanwhile
+                let mut pending = pending_versions.lock();
+                for (internal_id, version) in versions {
+                    pending.entry(internal_id).or_insert(version);
+                }
+                return Err(err);
+            }
+            Ok(())
+        })
+    }
+
+    fn total_point_count(&self) -> usize {
+        self.mappings.total_point_count()
+    }
+
+    fn available_point_count(&self) -> usize {
+        self.mappings.available_point_count()
+    }
+
+    fn deleted_point_count(&self) -> usize {
+        self.total_point_count() - self.available_point_count()
+    }
+
+    fn deleted_point_bitslice(&self) -> &BitSlice {
+        self.mappings.deleted()
+    }
+
+    fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool {
+        self.mappings.is_deleted_point(internal_id)
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![
+            mappings_path(&self.segment_path),
+            versions_path(&self.segment_path),
+        ]
+    }
+}
+
+fn mappings_path(segment_path: &Path) -> PathBuf {
+    segment_path.join(MAPPINGS_FILE_NAME)
+}
+
+fn versions_path(segment_path: &Path) -> PathBuf {
+    segment_path.join(VERSIONS_FILE_NAME)
+}
+
+/// Replay the mapping log
+///
+/// An incomplete change at the end of the log is a result of an interrupted flush, it is cut off.
+/// The log is compacted if it mostly consists of outdated changes.
+fn load_mappings(path: &Path) -> OperationResult<PointMappings> {
+    let data = std::fs::read(path)?;
+    let mut mappings = PointMappings::default();
+    let mut changes_count = 0;
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let Some((change, size)) = MappingChange::read(&data[offset..]) else {
+            log::warn!(
+                "Truncating corrupted id tracker mappings {} at offset {offset}",
+                path.display(),
+            );
+            let file = OpenOptions::new().write(true).open(path)?;
+            file.set_len(offset as u64)?;
+            file.sync_all()?;
+            break;
+        };
+        match change {
+            MappingChange::Insert(external_id, internal_id) => {
+                mappings.set_link(external_id, internal_id);
+            }
+            MappingChange::Delete(external_id) => {
+                mappings.drop(&external_id);
+            }
+        }
+        changes_count += 1;
+        offset += size;
+    }
+
+    if changes_count > COMPACTION_MIN_CHANGES
+        && changes_count > mappings.available_point_count() * COMPACTION_RATIO
+    {
+        compact_mappings(path, &mappings)?;
+    }
+
+    Ok(mappings)
+}
+
+/// Rewrite the mapping log, so that it only contains live links
+fn compact_mappings(path: &Path, mappings: &PointMappings) -> OperationResult<()> {
+    let tmp_path = path.with_extension("mappings.tmp");
+    {
+        let file = File::create(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+        for (external_id, internal_id) in mappings.iter_from(None) {
+            MappingChange::Insert(external_id, internal_id).write(&mut writer)?;
+        }
+        writer
+            .into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+    }
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn append_mappings(path: &Path, changes: &[MappingChange]) -> OperationResult<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let initial_len = file.metadata()?.len();
+
+    let result = (|| {
+        let mut writer = BufWriter::new(&mut file);
+        for change in changes {
+            change.write(&mut writer)?;
+        }
+        writer.flush()?;
+        drop(writer);
+        file.sync_data()
+    })();
+
+    if let Err(err) = result {
+        // Don't leave a partially written change in front of the ones appended on retry
+        file.set_len(initial_len)?;
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+fn write_versions(
+    path: &Path,
+    versions: &BTreeMap<PointOffsetType, SeqNumberType>,
+) -> OperationResult<()> {
+    if versions.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(path)?;
+    let mut last_written = None;
+    for (&internal_id, version) in versions {
+        // Versions are sorted, avoid seeking for consecutive points
+        if last_written.map_or(true, |last| last + 1 != internal_id) {
+            file.seek(SeekFrom::Start(internal_id as u64 * VERSION_SIZE as u64))?;
+        }
+        file.write_all(&version.to_le_bytes())?;
+        last_written = Some(internal_id);
+    }
+    file.sync_data()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn flush(id_tracker: &MutableIdTracker) {
+        id_tracker.mapping_flusher()().unwrap();
+        id_tracker.versions_flusher()().unwrap();
+    }
+
+    #[test]
+    fn test_persistence() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let uuid = PointIdType::Uuid(Uuid::from_u128(123_u128));
+
+        {
+            let mut id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+            id_tracker.set_link(200.into(), 0).unwrap();
+            id_tracker.set_link(uuid, 1).unwrap();
+            id_tracker.set_link(100.into(), 2).unwrap();
+            for internal_id in 0..3 {
+                id_tracker.set_internal_version(internal_id, 10).unwrap();
+            }
+            flush(&id_tracker);
+
+            id_tracker.drop(200.into()).unwrap();
+            id_tracker.set_link(300.into(), 3).unwrap();
+            id_tracker.set_internal_version(3, 11).unwrap();
+            id_tracker.set_internal_version(2, 12).unwrap();
+            flush(&id_tracker);
+
+            // Not flushed, must be lost
+            id_tracker.set_link(400.into(), 4).unwrap();
+        }
+
+        let id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+        assert_eq!(
+            id_tracker.iter_from(None).collect_vec(),
+            vec![(100.into(), 2), (300.into(), 3), (uuid, 1)],
+        );
+        assert_eq!(id_tracker.internal_id(200.into()), None);
+        assert!(id_tracker.is_deleted_point(0));
+        assert_eq!(id_tracker.total_point_count(), 4);
+        assert_eq!(id_tracker.deleted_point_count(), 1);
+        assert_eq!(id_tracker.internal_version(1), Some(10));
+        assert_eq!(id_tracker.internal_version(2), Some(12));
+        assert_eq!(id_tracker.internal_version(3), Some(11));
+    }
+
+    #[test]
+    fn test_truncate_incomplete_change() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        {
+            let mut id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+            id_tracker.set_link(100.into(), 0).unwrap();
+            id_tracker.set_link(101.into(), 1).unwrap();
+            flush(&id_tracker);
+        }
+
+        // Simulate interrupted append
+        let path = mappings_path(dir.path());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[CHANGE_INSERT_NUM, 1, 2, 3]).unwrap();
+        drop(file);
+
+        {
+            let mut id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+            assert_eq!(id_tracker.available_point_count(), 2);
+            id_tracker.set_link(102.into(), 2).unwrap();
+            flush(&id_tracker);
+        }
+
+        let id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+        assert_eq!(
+            id_tracker.iter_external().collect_vec(),
+            vec![100.into(), 101.into(), 102.into()],
+        );
+    }
+
+    #[test]
+    fn test_compaction() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        {
+            let mut id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+            for external_id in 0..=COMPACTION_MIN_CHANGES as u64 {
+                id_tracker.set_link(external_id.into(), 0).unwrap();
+            }
+            flush(&id_tracker);
+        }
+
+        let path = mappings_path(dir.path());
+        let log_size = std::fs::metadata(&path).unwrap().len();
+
+        let id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+        let expected_id = PointIdType::NumId(COMPACTION_MIN_CHANGES as u64);
+        assert_eq!(id_tracker.iter_external().collect_vec(), vec![expected_id]);
+        assert!(std::fs::metadata(&path).unwrap().len() < log_size);
+
+        let id_tracker = MutableIdTracker::open(dir.path()).unwrap();
+        assert_eq!(id_tracker.iter_external().collect_vec(), vec![expected_id]);
+        assert_eq!(id_tracker.internal_id(expected_id), Some(0));
+    }
+}
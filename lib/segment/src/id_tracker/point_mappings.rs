@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use uuid::Uuid;
+
+use crate::types::PointIdType;
+
+/// In-memory mapping between external and internal point ids
+///
+/// Shared by id trackers, which only differ in how the mapping is persisted.
+#[derive(Debug, Default)]
+pub struct PointMappings {
+    deleted: BitVec,
+    internal_to_external: Vec<PointIdType>,
+    external_to_internal_num: BTreeMap<u64, PointOffsetType>,
+    external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
+}
+
+impl PointMappings {
+    pub fn internal_id(&self, external_id: &PointIdType) -> Option<PointOffsetType> {
+        match external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.get(idx).copied(),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.get(uuid).copied(),
+        }
+    }
+
+    pub fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
+        if let Some(deleted) = self.deleted.get(internal_id as usize) {
+            if !deleted {
+                return self.internal_to_external.get(internal_id as usize).copied();
+            }
+        }
+        None
+    }
+
+    /// Link `external_id` to `internal_id`
+    ///
+    /// Returns the external id, which was previously linked to the same internal id.
+    /// That link is removed, so the mapping stays consistent.
+    pub fn set_link(
+        &mut self,
+        external_id: PointIdType,
+        internal_id: PointOffsetType,
+    ) -> Option<PointIdType> {
+        let idx = internal_id as usize;
+        if idx >= self.internal_to_external.len() {
+            self.internal_to_external
+                .resize(idx + 1, PointIdType::NumId(u64::MAX));
+        }
+        if idx >= self.deleted.len() {
+            self.deleted.resize(idx + 1, true);
+        }
+
+        let replaced_id = if self.deleted[idx] || self.internal_to_external[idx] == external_id {
+            None
+        } else {
+            let replaced_id = self.internal_to_external[idx];
+            self.remove_external(&replaced_id);
+            Some(replaced_id)
+        };
+
+        self.internal_to_external[idx] = external_id;
+        self.deleted.set(idx, false);
+
+        match external_id {
+            PointIdType::NumId(idx) => {
+                self.external_to_internal_num.insert(idx, internal_id);
+            }
+            PointIdType::Uuid(uuid) => {
+                self.external_to_internal_uuid.insert(uuid, internal_id);
+            }
+        }
+
+        replaced_id
+    }
+
+    /// Remove the link of `external_id`, returns the internal id it was linked to
+    pub fn drop(&mut self, external_id: &PointIdType) -> Option<PointOffsetType> {
+        let internal_id = self.remove_external(external_id);
+        if let Some(internal_id) = internal_id {
+            self.deleted.set(internal_id as usize, true);
+            self.internal_to_external[internal_id as usize] = PointIdType::NumId(u64::MAX);
+        }
+        internal_id
+    }
+
+    fn remove_external(&mut self, external_id: &PointIdType) -> Option<PointOffsetType> {
+        match external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.remove(idx),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.remove(uuid),
+        }
+    }
+
+    pub fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
+        let iter_num = self
+            .external_to_internal_num
+            .keys()
+            .copied()
+            .map(PointIdType::NumId);
+        let iter_uuid = self
+            .external_to_internal_uuid
+            .keys()
+            .copied()
+            .map(PointIdType::Uuid);
+        // order is important here, we want to iterate over the u64 ids first
+        Box::new(iter_num.chain(iter_uuid))
+    }
+
+    pub fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            (0..self.internal_to_external.len() as PointOffsetType)
+                .filter(move |i| !self.deleted[*i as usize]),
+        )
+    }
+
+    pub fn iter_from(
+        &self,
+        external_id: Option<PointIdType>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let full_num_iter = || {
+            self.external_to_internal_num
+                .iter()
+                .map(|(k, v)| (PointIdType::NumId(*k), *v))
+        };
+        let offset_num_iter = |offset: u64| {
+            self.external_to_internal_num
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::NumId(*k), *v))
+        };
+        let full_uuid_iter = || {
+            self.external_to_internal_uuid
+                .iter()
+                .map(|(k, v)| (PointIdType::Uuid(*k), *v))
+        };
+        let offset_uuid_iter = |offset: Uuid| {
+            self.external_to_internal_uuid
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::Uuid(*k), *v))
+        };
+
+        match external_id {
+            None => {
+                let iter_num = full_num_iter();
+                let iter_uuid = full_uuid_iter();
+                // order is important here, we want to iterate over the u64 ids first
+                Box::new(iter_num.chain(iter_uuid))
+            }
+            Some(offset) => match offset {
+                PointIdType::NumId(idx) => {
+                    // Because u64 keys are less that uuid key, we can just use the full iterator for uuid
+                    let iter_num = offset_num_iter(idx);
+                    let iter_uuid = full_uuid_iter();
+                    // order is important here, we want to iterate over the u64 ids first
+                    Box::new(iter_num.chain(iter_uuid))
+                }
+                PointIdType::Uuid(uuid) => {
+                    // if offset is a uuid, we can only iterate over uuids
+                    Box::new(offset_uuid_iter(uuid))
+                }
+            },
+        }
+    }
+
+    pub fn total_point_count(&self) -> usize {
+        self.internal_to_external.len()
+    }
+
+    pub fn available_point_count(&self) -> usize {
+        self.external_to_internal_num.len() + self.external_to_internal_uuid.len()
+    }
+
+    pub fn is_deleted_point(&self, internal_id: PointOffsetType) -> bool {
+        let key = internal_id as usize;
+        if key >= self.deleted.len() {
+            return true;
+        }
+        self.deleted[key]
+    }
+
+    pub fn deleted(&self) -> &BitSlice {
+        &self.deleted
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn assert_mappings(&self) {
+        for (idx, id) in self.external_to_internal_num.iter() {
+            debug_assert!(
+                self.internal_to_external[*id as usize] == PointIdType::NumId(*idx),
+                "Internal id {id} is mapped to external id {}, but should be {}",
+                self.internal_to_external[*id as usize],
+                PointIdType::NumId(*idx)
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -5,6 +6,7 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 
 use self::memory::{BinaryItem, BinaryMemory};
+use self::mmap::MmapBinary;
 use super::{CardinalityEstimation, PayloadFieldIndex, PrimaryCondition, ValueIndexer};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
@@ -150,6 +152,11 @@ mod memory {
             self.indexed_count
         }
 
+        /// Number of point slots, including points without values
+        pub fn size(&self) -> usize {
+            self.trues.len()
+        }
+
         pub fn iter_has_true(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
             self.trues.iter_ones().map(|v| v as PointOffsetType)
         }
@@ -160,9 +167,259 @@ mod memory {
     }
 }
 
+mod mmap {
+    use std::path::{Path, PathBuf};
+
+    use common::types::PointOffsetType;
+    use io::storage_version::{StorageVersion, VERSION_FILE};
+
+    use super::memory::{BinaryItem, BinaryMemory};
+    use crate::common::mmap_type::{MmapBitSlice, MmapType};
+    use crate::common::operation_error::OperationResult;
+    use crate::common::Flusher;
+    use crate::index::field_index::mmap_point_to_values::{
+        combine_flushers, create_index_dir, create_mmap_bitslice, create_mmap_type,
+        is_current_index_version, open_mmap_bitslice, open_mmap_type, MmapIndexVersion,
+    };
+
+    const STATUS_FILE: &str = "status.dat";
+    const TRUES_FILE: &str = "trues.bin";
+    const FALSES_FILE: &str = "falses.bin";
+
+    #[repr(C)]
+    struct MmapBinaryStatus {
+        size: usize,
+        trues_count: usize,
+        falses_count: usize,
+        indexed_count: usize,
+    }
+
+    struct Storage {
+        status: MmapType<MmapBinaryStatus>,
+        trues: MmapBitSlice,
+        falses: MmapBitSlice,
+    }
+
+    /// Read-only analogue of [`BinaryMemory`], stored in memory mapped files
+    pub struct MmapBinary {
+        path: PathBuf,
+        storage: Option<Storage>,
+    }
+
+    impl MmapBinary {
+        pub fn new(path: &Path) -> Self {
+            Self {
+                path: path.to_owned(),
+                storage: None,
+            }
+        }
+
+        pub fn load(&mut self) -> OperationResult<bool> {
+            if !is_current_index_version(&self.path)? {
+                return Ok(false);
+            }
+
+            self.storage = Some(Storage {
+                status: open_mmap_type(&self.path.join(STATUS_FILE))?,
+                trues: open_mmap_bitslice(&self.path.join(TRUES_FILE))?,
+                falses: open_mmap_bitslice(&self.path.join(FALSES_FILE))?,
+            });
+            Ok(true)
+        }
+
+        /// Write all data of the `memory` storage into this one
+        pub fn build(&mut self, memory: &BinaryMemory) -> OperationResult<()> {
+            create_index_dir(&self.path)?;
+
+            let status = MmapBinaryStatus {
+                size: memory.size(),
+                trues_count: memory.trues_count(),
+                falses_count: memory.falses_count(),
+                indexed_count: memory.indexed_count(),
+            };
+            let ids = 0..memory.size() as PointOffsetType;
+
+            self.storage = Some(Storage {
+                status: create_mmap_type(&self.path.join(STATUS_FILE), status)?,
+                trues: create_mmap_bitslice(
+                    &self.path.join(TRUES_FILE),
+                    ids.clone().map(|id| memory.get(id).has_true()),
+                )?,
+                falses: create_mmap_bitslice(
+                    &self.path.join(FALSES_FILE),
+                    ids.map(|id| memory.get(id).has_false()),
+                )?,
+            });
+
+            // Version is saved last, so that incomplete index is rebuilt on load
+            MmapIndexVersion::save(&self.path)?;
+            Ok(())
+        }
+
+        pub fn clear(self) -> OperationResult<()> {
+            drop(self.storage);
+            if self.path.exists() {
+                std::fs::remove_dir_all(&self.path)?;
+            }
+            Ok(())
+        }
+
+        pub fn files(&self) -> Vec<PathBuf> {
+            if self.storage.is_none() {
+                return vec![];
+            }
+            vec![
+                self.path.join(VERSION_FILE),
+                self.path.join(STATUS_FILE),
+                self.path.join(TRUES_FILE),
+                self.path.join(FALSES_FILE),
+            ]
+        }
+
+        pub fn flusher(&self) -> Flusher {
+            match &self.storage {
+                Some(storage) => combine_flushers(vec![
+                    storage.status.flusher(),
+                    storage.trues.flusher(),
+                    storage.falses.flusher(),
+                ]),
+                None => Box::new(|| Ok(())),
+            }
+        }
+
+        pub fn get(&self, id: PointOffsetType) -> BinaryItem {
+            let Some(storage) = &self.storage else {
+                return BinaryItem::empty();
+            };
+            if id as usize >= storage.status.size {
+                return BinaryItem::empty();
+            }
+            BinaryItem::from_bools(storage.trues[id as usize], storage.falses[id as usize])
+        }
+
+        pub fn remove(&mut self, id: PointOffsetType) {
+            let Some(storage) = &mut self.storage else {
+                return;
+            };
+            if id as usize >= storage.status.size {
+                return;
+            }
+
+            let had_true = storage.trues.replace(id as usize, false);
+            let had_false = storage.falses.replace(id as usize, false);
+
+            if had_true {
+                storage.status.trues_count -= 1;
+            }
+            if had_false {
+                storage.status.falses_count -= 1;
+            }
+
+            if had_false || had_true {
+                storage.status.indexed_count -= 1;
+            }
+        }
+
+        fn status(&self) -> Option<&MmapBinaryStatus> {
+            self.storage.as_ref().map(|storage| &*storage.status)
+        }
+
+        pub fn trues_count(&self) -> usize {
+            self.status().map_or(0, |status| status.trues_count)
+        }
+
+        pub fn falses_count(&self) -> usize {
+            self.status().map_or(0, |status| status.falses_count)
+        }
+
+        pub fn indexed_count(&self) -> usize {
+            self.status().map_or(0, |status| status.indexed_count)
+        }
+
+        pub fn iter_has_true(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+            match &self.storage {
+                Some(storage) => Box::new(
+                    storage.trues[..storage.status.size]
+                        .iter_ones()
+                        .map(|v| v as PointOffsetType),
+                ),
+                None => Box::new(std::iter::empty()),
+            }
+        }
+
+        pub fn iter_has_false(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+            match &self.storage {
+                Some(storage) => Box::new(
+                    storage.falses[..storage.status.size]
+                        .iter_ones()
+                        .map(|v| v as PointOffsetType),
+                ),
+                None => Box::new(std::iter::empty()),
+            }
+        }
+    }
+}
+
+enum BinaryStorage {
+    Memory(BinaryMemory),
+    Mmap(Box<MmapBinary>),
+}
+
+impl BinaryStorage {
+    fn get(&self, id: PointOffsetType) -> BinaryItem {
+        match self {
+            BinaryStorage::Memory(memory) => memory.get(id),
+            BinaryStorage::Mmap(mmap) => mmap.get(id),
+        }
+    }
+
+    fn remove(&mut self, id: PointOffsetType) {
+        match self {
+            BinaryStorage::Memory(memory) => memory.remove(id),
+            BinaryStorage::Mmap(mmap) => mmap.remove(id),
+        }
+    }
+
+    fn trues_count(&self) -> usize {
+        match self {
+            BinaryStorage::Memory(memory) => memory.trues_count(),
+            BinaryStorage::Mmap(mmap) => mmap.trues_count(),
+        }
+    }
+
+    fn falses_count(&self) -> usize {
+        match self {
+            BinaryStorage::Memory(memory) => memory.falses_count(),
+            BinaryStorage::Mmap(mmap) => mmap.falses_count(),
+        }
+    }
+
+    fn indexed_count(&self) -> usize {
+        match self {
+            BinaryStorage::Memory(memory) => memory.indexed_count(),
+            BinaryStorage::Mmap(mmap) => mmap.indexed_count(),
+        }
+    }
+
+    fn iter_has_true(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            BinaryStorage::Memory(memory) => Box::new(memory.iter_has_true()),
+            BinaryStorage::Mmap(mmap) => mmap.iter_has_true(),
+        }
+    }
+
+    fn iter_has_false(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            BinaryStorage::Memory(memory) => Box::new(memory.iter_has_false()),
+            BinaryStorage::Mmap(mmap) => mmap.iter_has_false(),
+        }
+    }
+}
+
 pub struct BinaryIndex {
-    memory: BinaryMemory,
-    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+    storage: BinaryStorage,
+    /// `None` for on-disk index
+    db_wrapper: Option<DatabaseColumnScheduledDeleteWrapper>,
 }
 
 impl BinaryIndex {
@@ -173,8 +430,25 @@ impl BinaryIndex {
             &store_cf_name,
         ));
         Self {
-            memory: BinaryMemory::new(),
-            db_wrapper,
+            storage: BinaryStorage::Memory(BinaryMemory::new()),
+            db_wrapper: Some(db_wrapper),
+        }
+    }
+
+    pub fn new_mmap(path: &Path) -> BinaryIndex {
+        Self {
+            storage: BinaryStorage::Mmap(Box::new(MmapBinary::new(path))),
+            db_wrapper: None,
+        }
+    }
+
+    /// Fill the on-disk index with the data of another index.
+    pub fn build_from(&mut self, other: &BinaryIndex) -> OperationResult<()> {
+        match (&mut self.storage, &other.storage) {
+            (BinaryStorage::Mmap(mmap), BinaryStorage::Memory(memory)) => mmap.build(memory),
+            _ => Err(OperationError::service_error(
+                "Only on-disk binary index can be built from in-memory binary index",
+            )),
         }
     }
 
@@ -183,20 +457,23 @@ impl BinaryIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.db_wrapper.recreate_column_family()
+        match &self.db_wrapper {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
-            points_count: self.memory.indexed_count(),
-            points_values_count: self.memory.trues_count() + self.memory.falses_count(),
+            points_count: self.storage.indexed_count(),
+            points_values_count: self.storage.trues_count() + self.storage.falses_count(),
             histogram_bucket_size: None,
         }
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        let binary_item = self.memory.get(point_id);
+        let binary_item = self.storage.get(point_id);
         binary_item.has_true() as usize + binary_item.has_false() as usize
     }
 
@@ -206,23 +483,41 @@ impl BinaryIndex {
 
     /// Check if the point has a true value
     pub fn values_has_true(&self, point_id: PointOffsetType) -> bool {
-        self.memory.get(point_id).has_true()
+        self.storage.get(point_id).has_true()
     }
 
     /// Check if the point has a false value
     pub fn values_has_false(&self, point_id: PointOffsetType) -> bool {
-        self.memory.get(point_id).has_false()
+        self.storage.get(point_id).has_false()
+    }
+
+    /// Number of points with a true value
+    pub fn trues_count(&self) -> usize {
+        self.storage.trues_count()
+    }
+
+    /// Number of points with a false value
+    pub fn falses_count(&self) -> usize {
+        self.storage.falses_count()
     }
 }
 
 impl PayloadFieldIndex for BinaryIndex {
     fn load(&mut self) -> OperationResult<bool> {
-        if !self.db_wrapper.has_column_family()? {
+        let memory = match &mut self.storage {
+            BinaryStorage::Memory(memory) => memory,
+            BinaryStorage::Mmap(mmap) => return mmap.load(),
+        };
+        let Some(db_wrapper) = &self.db_wrapper else {
+            return Ok(false);
+        };
+
+        if !db_wrapper.has_column_family()? {
             return Ok(false);
         }
 
-        let db_lock = self.db_wrapper.lock_db();
-        let pending_deletes = self.db_wrapper.pending_deletes();
+        let db_lock = db_wrapper.lock_db();
+        let pending_deletes = db_wrapper.pending_deletes();
 
         for (key, value) in db_lock.iter_pending_deletes(pending_deletes)? {
             let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
@@ -230,17 +525,32 @@ impl PayloadFieldIndex for BinaryIndex {
             debug_assert_eq!(value.len(), 1);
 
             let item = BinaryItem::from(value[0]);
-            self.memory.set_or_insert(idx, &item);
+            memory.set_or_insert(idx, &item);
         }
         Ok(true)
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper.remove_column_family()
+        match (self.db_wrapper, self.storage) {
+            (Some(db_wrapper), _) => db_wrapper.remove_column_family(),
+            (None, BinaryStorage::Mmap(mmap)) => mmap.clear(),
+            (None, BinaryStorage::Memory(_)) => Ok(()),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        match &self.storage {
+            BinaryStorage::Memory(_) => vec![],
+            BinaryStorage::Mmap(mmap) => mmap.files(),
+        }
     }
 
     fn flusher(&self) -> crate::common::Flusher {
-        self.db_wrapper.flusher()
+        match (&self.db_wrapper, &self.storage) {
+            (Some(db_wrapper), _) => db_wrapper.flusher(),
+            (None, BinaryStorage::Mmap(mmap)) => mmap.flusher(),
+            (None, BinaryStorage::Memory(_)) => Box::new(|| Ok(())),
+        }
     }
 
     fn filter<'a>(
@@ -252,9 +562,9 @@ impl PayloadFieldIndex for BinaryIndex {
                 value: ValueVariants::Bool(value),
             })) => {
                 if *value {
-                    Ok(Box::new(self.memory.iter_has_true()))
+                    Ok(self.storage.iter_has_true())
                 } else {
-                    Ok(Box::new(self.memory.iter_has_false()))
+                    Ok(self.storage.iter_has_false())
                 }
             }
             _ => Err(OperationError::service_error("failed to filter")),
@@ -270,9 +580,9 @@ impl PayloadFieldIndex for BinaryIndex {
                 value: ValueVariants::Bool(value),
             })) => {
                 let count = if *value {
-                    self.memory.trues_count()
+                    self.storage.trues_count()
                 } else {
-                    self.memory.falses_count()
+                    self.storage.falses_count()
                 };
 
                 let estimation = CardinalityEstimation::exact(count)
@@ -309,8 +619,8 @@ impl PayloadFieldIndex for BinaryIndex {
 
         // just two possible blocks: true and false
         let iter = [
-            make_block(self.memory.trues_count(), true, key.clone()),
-            make_block(self.memory.falses_count(), false, key),
+            make_block(self.storage.trues_count(), true, key.clone()),
+            make_block(self.storage.falses_count(), false, key),
         ]
         .into_iter()
         .flatten();
@@ -319,7 +629,7 @@ impl PayloadFieldIndex for BinaryIndex {
     }
 
     fn count_indexed_points(&self) -> usize {
-        self.memory.indexed_count()
+        self.storage.indexed_count()
     }
 }
 
@@ -334,9 +644,16 @@ impl ValueIndexer<bool> for BinaryIndex {
 
         let item = BinaryItem::from_bools(has_true, has_false);
 
-        self.memory.set_or_insert(id, &item);
+        let BinaryStorage::Memory(memory) = &mut self.storage else {
+            return Err(OperationError::service_error(
+                "Can't add values to immutable binary index",
+            ));
+        };
+        memory.set_or_insert(id, &item);
 
-        self.db_wrapper.put(id.to_be_bytes(), item.as_bytes())?;
+        if let Some(db_wrapper) = &self.db_wrapper {
+            db_wrapper.put(id.to_be_bytes(), item.as_bytes())?;
+        }
 
         Ok(())
     }
@@ -346,8 +663,10 @@ impl ValueIndexer<bool> for BinaryIndex {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
-        self.memory.remove(id);
-        self.db_wrapper.remove(id.to_be_bytes())?;
+        self.storage.remove(id);
+        if let Some(db_wrapper) = &self.db_wrapper {
+            db_wrapper.remove(id.to_be_bytes())?;
+        }
         Ok(())
     }
 }
@@ -449,7 +768,7 @@ mod tests {
             });
 
         index.flusher()().unwrap();
-        let db = index.db_wrapper.get_database();
+        let db = index.db_wrapper.as_ref().unwrap().get_database();
 
         let mut new_index = BinaryIndex::new(db, FIELD_NAME);
         assert!(new_index.load().unwrap());
@@ -482,6 +801,48 @@ mod tests {
         assert!(point_offsets.is_empty());
     }
 
+    #[test]
+    fn mmap_index() {
+        let (tmp_dir, mut index) = new_binary_index();
+
+        bools_fixture()
+            .into_iter()
+            .enumerate()
+            .for_each(|(i, value)| {
+                index.add_point(i as u32, &[&value]).unwrap();
+            });
+
+        let mmap_path = tmp_dir.path().join("mmap");
+        let mut mmap_index = BinaryIndex::new_mmap(&mmap_path);
+        assert!(!mmap_index.load().unwrap());
+        mmap_index.build_from(&index).unwrap();
+        assert_eq!(mmap_index.count_indexed_points(), 9);
+        assert!(mmap_index.add_point(0, &[&json!(false)]).is_err());
+
+        mmap_index.remove_point(2).unwrap();
+        mmap_index.flusher()().unwrap();
+        drop(mmap_index);
+
+        let mut mmap_index = BinaryIndex::new_mmap(&mmap_path);
+        assert!(mmap_index.load().unwrap());
+        assert_eq!(mmap_index.count_indexed_points(), 8);
+
+        let point_offsets = mmap_index.filter(&match_bool(false)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![1, 3, 5, 6, 10]);
+
+        let point_offsets = mmap_index.filter(&match_bool(true)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![0, 3, 4, 6, 11]);
+
+        let cardinality = mmap_index.estimate_cardinality(&match_bool(true)).unwrap();
+        assert_eq!(cardinality.exp, 5);
+        assert!(mmap_index.values_has_false(10));
+        assert!(mmap_index.values_is_empty(2));
+
+        assert!(!mmap_index.files().is_empty());
+        mmap_index.clear().unwrap();
+        assert!(!mmap_path.exists());
+    }
+
     #[test]
     fn indexed_count() {
         let (_tmp_dir, mut index) = new_binary_index();
@@ -0,0 +1,93 @@
+use common::types::PointOffsetType;
+use itertools::Itertools;
+use smol_str::SmolStr;
+
+use super::binary_index::BinaryIndex;
+use super::map_index::MapIndex;
+use super::numeric_index::{NumericIndex, StreamRange};
+use crate::data_types::facets::FacetValue;
+use crate::types::{IntPayloadType, Range, RangeInterface};
+
+/// Field index which can enumerate its values together with the number of points having them
+pub enum FacetIndex<'a> {
+    Keyword(&'a MapIndex<SmolStr>),
+    IntMap(&'a MapIndex<IntPayloadType>),
+    Int(&'a NumericIndex<IntPayloadType>),
+    Bool(&'a BinaryIndex),
+}
+
+impl<'a> FacetIndex<'a> {
+    /// Unique values of the point
+    pub fn get_point_values(&self, point_id: PointOffsetType) -> Vec<FacetValue> {
+        let mut values: Vec<_> = match self {
+            FacetIndex::Keyword(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .map(|value| FacetValue::Keyword(value.to_string()))
+                .collect(),
+            FacetIndex::IntMap(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .map(FacetValue::Int)
+                .collect(),
+            FacetIndex::Int(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .copied()
+                .map(FacetValue::Int)
+                .collect(),
+            FacetIndex::Bool(index) => {
+                let mut values = Vec::with_capacity(2);
+                if index.values_has_true(point_id) {
+                    values.push(FacetValue::Bool(true));
+                }
+                if index.values_has_false(point_id) {
+                    values.push(FacetValue::Bool(false));
+                }
+                values
+            }
+        };
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+
+    /// All values of the index with the number of points having each of them
+    pub fn iter_values_with_counts(&self) -> Box<dyn Iterator<Item = (FacetValue, usize)> + 'a> {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(index.get_values_iterator().map(move |value| {
+                let count = index.get_points_with_value_count(&value).unwrap_or(0);
+                (FacetValue::Keyword(value.to_string()), count)
+            })),
+            FacetIndex::IntMap(index) => Box::new(index.get_values_iterator().map(move |value| {
+                let count = index.get_points_with_value_count(&value).unwrap_or(0);
+                (FacetValue::Int(value), count)
+            })),
+            FacetIndex::Int(index) => {
+                let full_range = RangeInterface::Float(Range {
+                    lt: None,
+                    gt: None,
+                    gte: None,
+                    lte: None,
+                });
+                Box::new(
+                    index
+                        .stream_range(&full_range)
+                        .map(|(value, _)| value)
+                        .dedup_with_count()
+                        .map(|(count, value)| (FacetValue::Int(value), count)),
+                )
+            }
+            FacetIndex::Bool(index) => Box::new(
+                [
+                    (FacetValue::Bool(true), index.trues_count()),
+                    (FacetValue::Bool(false), index.falses_count()),
+                ]
+                .into_iter(),
+            ),
+        }
+    }
+}
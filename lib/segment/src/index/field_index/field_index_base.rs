@@ -1,12 +1,15 @@
 use std::fmt::Formatter;
+use std::ops::Range;
+use std::path::PathBuf;
 
 use common::types::PointOffsetType;
 use serde_json::Value;
 use smol_str::SmolStr;
 
-use super::map_index::MapIndex;
+use super::facet_index::FacetIndex;
+use super::map_index::{uuid_to_int, MapIndex};
 use super::numeric_index::StreamRange;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::order_by::OrderValue;
 use crate::index::field_index::binary_index::BinaryIndex;
@@ -16,8 +19,9 @@ use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface,
+    AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match,
+    MatchAny, MatchExcept, MatchPhrase, MatchText, MatchValue, PayloadKeyType, RangeInterface,
+    UuidIntType, ValueVariants,
 };
 
 pub trait PayloadFieldIndex {
@@ -33,6 +37,9 @@ pub trait PayloadFieldIndex {
     /// Return function that flushes all pending updates to disk.
     fn flusher(&self) -> Flusher;
 
+    /// Files of on-disk index, empty if the index is stored in RocksDB
+    fn files(&self) -> Vec<PathBuf>;
+
     /// Get iterator over points fitting given `condition`
     /// Return `None` if condition does not match the index type
     fn filter<'a>(
@@ -101,6 +108,7 @@ pub enum FieldIndex {
     DatetimeIndex(NumericIndex<IntPayloadType>),
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<SmolStr>),
+    UuidMapIndex(MapIndex<UuidIntType>),
     FloatIndex(NumericIndex<FloatPayloadType>),
     GeoIndex(GeoMapIndex),
     FullTextIndex(FullTextIndex),
@@ -114,6 +122,7 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::DatetimeIndex(_index) => write!(f, "DatetimeIndex"),
             FieldIndex::IntMapIndex(_index) => write!(f, "IntMapIndex"),
             FieldIndex::KeywordIndex(_index) => write!(f, "KeywordIndex"),
+            FieldIndex::UuidMapIndex(_index) => write!(f, "UuidMapIndex"),
             FieldIndex::FloatIndex(_index) => write!(f, "FloatIndex"),
             FieldIndex::GeoIndex(_index) => write!(f, "GeoIndex"),
             FieldIndex::BinaryIndex(_index) => write!(f, "BinaryIndex"),
@@ -140,22 +149,54 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(_) => None,
             FieldIndex::IntMapIndex(_) => None,
             FieldIndex::KeywordIndex(_) => None,
+            // UUIDs are compared by value, so that different string representations match
+            FieldIndex::UuidMapIndex(uuid_index) => {
+                let values = ValueIndexer::get_values(uuid_index, payload_value);
+                match &condition.r#match {
+                    Some(Match::Value(MatchValue {
+                        value: ValueVariants::Keyword(keyword),
+                    })) => {
+                        let uuid = uuid_to_int(keyword);
+                        Some(uuid.is_some_and(|uuid| values.contains(&uuid)))
+                    }
+                    Some(Match::Any(MatchAny {
+                        any: AnyVariants::Keywords(list),
+                    })) => Some(
+                        list.iter()
+                            .filter_map(|keyword| uuid_to_int(keyword))
+                            .any(|uuid| values.contains(&uuid)),
+                    ),
+                    Some(Match::Except(MatchExcept {
+                        except: AnyVariants::Keywords(list),
+                    })) => {
+                        let excluded: Vec<_> = list
+                            .iter()
+                            .filter_map(|keyword| uuid_to_int(keyword))
+                            .collect();
+                        Some(values.iter().any(|uuid| !excluded.contains(uuid)))
+                    }
+                    _ => None,
+                }
+            }
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
-            FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
-                Some(Match::Text(MatchText { text })) => {
-                    let query = full_text_index.parse_query(text);
-                    for value in full_text_index.get_values(payload_value) {
-                        let document = full_text_index.parse_document(&value);
-                        if query.check_match(&document) {
-                            return Some(true);
-                        }
+            FieldIndex::FullTextIndex(full_text_index) => {
+                let query = match &condition.r#match {
+                    Some(Match::Text(MatchText { text })) => full_text_index.parse_query(text),
+                    Some(Match::Phrase(MatchPhrase { phrase, slop })) => {
+                        full_text_index.parse_phrase_query(phrase, *slop)
+                    }
+                    _ => return None,
+                };
+                for value in full_text_index.get_values(payload_value) {
+                    let document = full_text_index.parse_document(&value);
+                    if query.check_match(&document) {
+                        return Some(true);
                     }
-                    Some(false)
                 }
-                _ => None,
-            },
+                Some(false)
+            }
         }
     }
 
@@ -165,6 +206,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(payload_field_index) => payload_field_index,
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
+            FieldIndex::UuidMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
@@ -178,6 +220,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::IntMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
@@ -191,6 +234,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.clear(),
             FieldIndex::IntMapIndex(index) => index.clear(),
             FieldIndex::KeywordIndex(index) => index.clear(),
+            FieldIndex::UuidMapIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
@@ -204,6 +248,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.recreate(),
             FieldIndex::IntMapIndex(index) => index.recreate(),
             FieldIndex::KeywordIndex(index) => index.recreate(),
+            FieldIndex::UuidMapIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
@@ -211,10 +256,46 @@ impl FieldIndex {
         }
     }
 
+    /// Fill an empty on-disk index with the data of an in-memory index of the same type
+    pub fn build_from(&mut self, other: &FieldIndex) -> OperationResult<()> {
+        match (self, other) {
+            (FieldIndex::IntIndex(index), FieldIndex::IntIndex(other)) => index.build_from(other),
+            (FieldIndex::DatetimeIndex(index), FieldIndex::DatetimeIndex(other)) => {
+                index.build_from(other)
+            }
+            (FieldIndex::IntMapIndex(index), FieldIndex::IntMapIndex(other)) => {
+                index.build_from(other)
+            }
+            (FieldIndex::KeywordIndex(index), FieldIndex::KeywordIndex(other)) => {
+                index.build_from(other)
+            }
+            (FieldIndex::UuidMapIndex(index), FieldIndex::UuidMapIndex(other)) => {
+                index.build_from(other)
+            }
+            (FieldIndex::FloatIndex(index), FieldIndex::FloatIndex(other)) => {
+                index.build_from(other)
+            }
+            (FieldIndex::GeoIndex(index), FieldIndex::GeoIndex(other)) => index.build_from(other),
+            (FieldIndex::BinaryIndex(index), FieldIndex::BinaryIndex(other)) => {
+                index.build_from(other)
+            }
+            (FieldIndex::FullTextIndex(index), FieldIndex::FullTextIndex(other)) => {
+                index.build_from(other)
+            }
+            (index, other) => Err(OperationError::service_error(format!(
+                "Can't build {index:?} from {other:?}",
+            ))),
+        }
+    }
+
     pub fn count_indexed_points(&self) -> usize {
         self.get_payload_field_index().count_indexed_points()
     }
 
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.get_payload_field_index().files()
+    }
+
     pub fn flusher(&self) -> Flusher {
         self.get_payload_field_index().flusher()
     }
@@ -257,6 +338,9 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::UuidMapIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::FloatIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
@@ -278,6 +362,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.remove_point(point_id),
             FieldIndex::IntMapIndex(index) => index.remove_point(point_id),
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
+            FieldIndex::UuidMapIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
@@ -291,6 +376,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.get_telemetry_data(),
             FieldIndex::IntMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
+            FieldIndex::UuidMapIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
@@ -304,6 +390,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_count(point_id),
             FieldIndex::IntMapIndex(index) => index.values_count(point_id),
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
@@ -317,6 +404,7 @@ impl FieldIndex {
             FieldIndex::DatetimeIndex(index) => index.values_is_empty(point_id),
             FieldIndex::IntMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::UuidMapIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
@@ -324,6 +412,33 @@ impl FieldIndex {
         }
     }
 
+    /// Smallest range of point offsets, which contains all points matching the exact value `condition`
+    ///
+    /// Return `None` if the range can't be determined for the condition or index type.
+    pub fn get_points_range(&self, condition: &FieldCondition) -> Option<Range<PointOffsetType>> {
+        let Some(Match::Value(MatchValue {
+            value: ValueVariants::Keyword(keyword),
+        })) = &condition.r#match
+        else {
+            return None;
+        };
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(index.get_points_range(keyword.as_str())),
+            FieldIndex::UuidMapIndex(index) => Some(match uuid_to_int(keyword) {
+                Some(uuid) => index.get_points_range(&uuid),
+                // Not a UUID, so it can't match any indexed value
+                None => 0..0,
+            }),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BinaryIndex(_) => None,
+        }
+    }
+
     pub fn as_numeric(&self) -> Option<NumericFieldIndex> {
         match self {
             FieldIndex::IntIndex(index) => Some(NumericFieldIndex::IntIndex(index)),
@@ -331,11 +446,40 @@ impl FieldIndex {
             FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index)),
             FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
             | FieldIndex::GeoIndex(_)
             | FieldIndex::BinaryIndex(_)
             | FieldIndex::FullTextIndex(_) => None,
         }
     }
+
+    pub fn as_facet_index(&self) -> Option<FacetIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(FacetIndex::Keyword(index)),
+            FieldIndex::IntMapIndex(index) => Some(FacetIndex::IntMap(index)),
+            FieldIndex::IntIndex(index) => Some(FacetIndex::Int(index)),
+            FieldIndex::BinaryIndex(index) => Some(FacetIndex::Bool(index)),
+            FieldIndex::DatetimeIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
+
+    pub fn as_full_text(&self) -> Option<&FullTextIndex> {
+        match self {
+            FieldIndex::FullTextIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BinaryIndex(_) => None,
+        }
+    }
 }
 
 pub enum NumericFieldIndex<'a> {
@@ -1,33 +1,64 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use common::types::PointOffsetType;
 use serde::{Deserialize, Serialize};
 
+use super::mmap_inverted_index::MmapInvertedIndex;
 use super::posting_list::{CompressedPostingList, PostingList};
 use super::postings_iterator::{
     intersect_compressed_postings_iterator, intersect_postings_iterator,
 };
 use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
 use crate::types::{FieldCondition, Match, PayloadKeyType};
 
 pub type TokenId = u32;
 
+/// Position of a token in the text, counted in words
+pub type TokenPosition = u32;
+
+/// Tokens of a document, mapped to their sorted positions in the text
+pub type DocumentTokens = BTreeMap<String, Vec<TokenPosition>>;
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct Document {
     tokens: Vec<TokenId>,
+    /// Sorted positions of each token from `tokens`, if known.
+    /// Positions are moved into posting lists, when the document is indexed.
+    #[serde(default)]
+    positions: Vec<Vec<TokenPosition>>,
+    /// Number of token occurrences, token with unknown positions is counted once
+    #[serde(default)]
+    length: usize,
 }
 
 impl Document {
-    pub fn new(mut tokens: Vec<TokenId>) -> Self {
-        tokens.sort_unstable();
-        Self { tokens }
+    pub fn with_positions(mut tokens: Vec<(TokenId, Vec<TokenPosition>)>) -> Self {
+        tokens.sort_unstable_by_key(|(token, _)| *token);
+        let length = tokens
+            .iter()
+            .map(|(_, positions)| positions.len().max(1))
+            .sum();
+        let (tokens, positions) = tokens.into_iter().unzip();
+        Self {
+            tokens,
+            positions,
+            length,
+        }
     }
 
+    /// Number of unique tokens
     pub fn len(&self) -> usize {
         self.tokens.len()
     }
 
+    /// Number of token occurrences, used as document length for BM25 scoring
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
     pub fn is_empty(&self) -> bool {
         self.tokens.is_empty()
     }
@@ -39,11 +70,66 @@ impl Document {
     pub fn check(&self, token: TokenId) -> bool {
         self.tokens.binary_search(&token).is_ok()
     }
+
+    pub fn token_positions(&self, token: TokenId) -> Option<&[TokenPosition]> {
+        let idx = self.tokens.binary_search(&token).ok()?;
+        self.positions.get(idx).map(Vec::as_slice)
+    }
+
+    fn take_positions(&mut self) -> Vec<Vec<TokenPosition>> {
+        std::mem::take(&mut self.positions)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParsedQuery {
     pub tokens: Vec<Option<TokenId>>,
+    /// If set, `tokens` must also appear in the document as a phrase
+    pub phrase: Option<PhraseQuery>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PhraseQuery {
+    /// Positions of [`ParsedQuery::tokens`] in the query text
+    pub positions: Vec<TokenPosition>,
+    /// Number of additional tokens allowed between the tokens of the phrase
+    pub slop: TokenPosition,
+}
+
+impl PhraseQuery {
+    /// Check that the phrase tokens appear in the document in the same order,
+    /// with at most `slop` additional tokens in between.
+    ///
+    /// `token_positions` returns sorted positions of the i-th phrase token in the document.
+    pub fn check<'a>(
+        &self,
+        token_positions: impl Fn(usize) -> Option<&'a [TokenPosition]>,
+    ) -> bool {
+        let document_positions: Option<Vec<_>> =
+            (0..self.positions.len()).map(token_positions).collect();
+        let Some(document_positions) = document_positions else {
+            return false;
+        };
+        let (Some(&first), Some(&last)) = (self.positions.first(), self.positions.last()) else {
+            return false;
+        };
+        let max_span = (last - first).saturating_add(self.slop);
+
+        document_positions[0].iter().any(|&start| {
+            // Greedily take the closest occurrence of each next token,
+            // it gives the smallest possible span of the phrase
+            let mut current = start;
+            for (i, next_positions) in document_positions.iter().enumerate().skip(1) {
+                let min_next = current + (self.positions[i] - self.positions[i - 1]);
+                let next_idx = next_positions.partition_point(|&position| position < min_next);
+                match next_positions.get(next_idx) {
+                    Some(&next) => current = next,
+                    None => return false,
+                }
+            }
+            current - start <= max_span
+        })
+    }
 }
 
 impl ParsedQuery {
@@ -52,16 +138,22 @@ impl ParsedQuery {
             return false;
         }
         // Check that all tokens are in document
-        self.tokens
+        let all_tokens = self
+            .tokens
             .iter()
             // unwrap crash safety: all tokens exist in the vocabulary if it passes the above check
-            .all(|query_token| document.check(query_token.unwrap()))
+            .all(|query_token| document.check(query_token.unwrap()));
+        all_tokens
+            && self.phrase.as_ref().map_or(true, |phrase| {
+                phrase.check(|i| document.token_positions(self.tokens[i]?))
+            })
     }
 }
 
 pub enum InvertedIndex {
     Mutable(MutableInvertedIndex),
     Immutable(ImmutableInvertedIndex),
+    Mmap(Box<MmapInvertedIndex>),
 }
 
 impl InvertedIndex {
@@ -73,20 +165,35 @@ impl InvertedIndex {
         }
     }
 
-    pub fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
+    pub fn new_mmap(path: &Path) -> InvertedIndex {
+        InvertedIndex::Mmap(Box::new(MmapInvertedIndex::new(path)))
+    }
+
+    pub fn document_from_tokens(&mut self, tokens: &DocumentTokens) -> Document {
         let vocab = match self {
             InvertedIndex::Mutable(index) => &mut index.vocab,
             InvertedIndex::Immutable(index) => &mut index.vocab,
+            // Vocabulary of on-disk index can't be extended
+            InvertedIndex::Mmap(index) => {
+                return Document::with_positions(
+                    tokens
+                        .iter()
+                        .filter_map(|(token, positions)| {
+                            Some((index.get_token(token)?, positions.clone()))
+                        })
+                        .collect(),
+                );
+            }
         };
         Self::document_from_tokens_impl(vocab, tokens)
     }
 
     fn document_from_tokens_impl(
         vocab: &mut HashMap<String, TokenId>,
-        tokens: &BTreeSet<String>,
+        tokens: &DocumentTokens,
     ) -> Document {
         let mut document_tokens = vec![];
-        for token in tokens {
+        for (token, positions) in tokens {
             // check if in vocab
             let vocab_idx = match vocab.get(token) {
                 Some(&idx) => idx,
@@ -96,10 +203,10 @@ impl InvertedIndex {
                     next_token_id
                 }
             };
-            document_tokens.push(vocab_idx);
+            document_tokens.push((vocab_idx, positions.clone()));
         }
 
-        Document::new(document_tokens)
+        Document::with_positions(document_tokens)
     }
 
     pub fn index_document(
@@ -109,9 +216,9 @@ impl InvertedIndex {
     ) -> OperationResult<()> {
         match self {
             InvertedIndex::Mutable(index) => index.index_document(idx, document),
-            InvertedIndex::Immutable(_index) => Err(OperationError::service_error(
-                "Can't add values to immutable text index",
-            )),
+            InvertedIndex::Immutable(_) | InvertedIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable text index"),
+            ),
         }
     }
 
@@ -119,13 +226,48 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.remove_document(idx),
             InvertedIndex::Immutable(index) => index.remove_document(idx),
+            InvertedIndex::Mmap(index) => index.remove_document(idx),
         }
     }
 
     pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
-        match self {
+        let filtered = match self {
             InvertedIndex::Mutable(index) => index.filter(query),
             InvertedIndex::Immutable(index) => index.filter(query),
+            InvertedIndex::Mmap(index) => index.filter(query),
+        };
+        if query.phrase.is_none() {
+            return filtered;
+        }
+        let query = query.clone();
+        Box::new(filtered.filter(move |&point_id| self.check_phrase(&query, point_id)))
+    }
+
+    /// Check token positions of the phrase query, presence of all tokens is not checked
+    fn check_phrase(&self, query: &ParsedQuery, point_id: PointOffsetType) -> bool {
+        let Some(phrase) = &query.phrase else {
+            return true;
+        };
+        phrase.check(|i| self.token_positions(query.tokens[i]?, point_id))
+    }
+
+    pub fn token_positions(
+        &self,
+        token_id: TokenId,
+        point_id: PointOffsetType,
+    ) -> Option<&[TokenPosition]> {
+        match self {
+            InvertedIndex::Mutable(index) => index
+                .postings
+                .get(token_id as usize)?
+                .as_ref()?
+                .positions(&point_id),
+            InvertedIndex::Immutable(index) => index
+                .postings
+                .get(token_id as usize)?
+                .as_ref()?
+                .positions(&point_id),
+            InvertedIndex::Mmap(index) => index.token_positions(token_id, point_id),
         }
     }
 
@@ -134,12 +276,12 @@ impl InvertedIndex {
         query: &ParsedQuery,
         condition: &FieldCondition,
     ) -> CardinalityEstimation {
-        let points_count = match self {
-            InvertedIndex::Mutable(index) => index.points_count,
-            InvertedIndex::Immutable(index) => index.points_count,
-        };
-        let posting_lengths: Option<Vec<usize>> = query
-            .tokens
+        let points_count = self.points_count();
+        // Phrase may contain the same token several times
+        let mut query_tokens = query.tokens.clone();
+        query_tokens.sort_unstable();
+        query_tokens.dedup();
+        let posting_lengths: Option<Vec<usize>> = query_tokens
             .iter()
             .map(|&vocab_idx| match vocab_idx {
                 None => None,
@@ -157,6 +299,7 @@ impl InvertedIndex {
                         .unwrap()
                         .as_ref()
                         .map(|p| p.len()),
+                    Self::Mmap(index) => index.get_posting(idx).map(|p| p.len()),
                 },
             })
             .collect();
@@ -182,7 +325,8 @@ impl InvertedIndex {
         // Smallest posting is the largest possible cardinality
         let smallest_posting = postings.iter().min().copied().unwrap();
 
-        return if postings.len() == 1 {
+        // Positions of phrase tokens are not taken into account, so it's an upper bound estimation
+        return if query.tokens.len() == 1 {
             CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
                 min: smallest_posting,
@@ -195,6 +339,11 @@ impl InvertedIndex {
                 .map(|posting| *posting as f64 / points_count as f64)
                 .product();
             let exp = (expected_frac * points_count as f64) as usize;
+            // Each additional token of the phrase has to be at the right position
+            let exp = match &query.phrase {
+                Some(_) => exp / query.tokens.len(),
+                None => exp,
+            };
             CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
                 min: 0, // ToDo: make better estimation
@@ -233,12 +382,17 @@ impl InvertedIndex {
                     .vocab_with_positngs_len_iter()
                     .filter_map(map_filter_condition),
             ),
+            InvertedIndex::Mmap(index) => Box::new(
+                index
+                    .vocab_with_postings_len_iter()
+                    .filter_map(map_filter_condition),
+            ),
         }
     }
 
     pub fn build_index(
         &mut self,
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, DocumentTokens)>>,
     ) -> OperationResult<()> {
         let mut index = MutableInvertedIndex::default();
         index.build_index(iter)?;
@@ -250,22 +404,68 @@ impl InvertedIndex {
             InvertedIndex::Immutable(i) => {
                 *i = index.into();
             }
+            InvertedIndex::Mmap(i) => {
+                i.build(&index)?;
+            }
         }
 
         Ok(())
     }
 
-    pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
+    /// Fill the on-disk index with the data of another index.
+    pub fn build_from(&mut self, other: &InvertedIndex) -> OperationResult<()> {
+        match (self, other) {
+            (InvertedIndex::Mmap(index), InvertedIndex::Mutable(other)) => index.build(other),
+            _ => Err(OperationError::service_error(
+                "Only on-disk inverted index can be built from mutable inverted index",
+            )),
+        }
+    }
+
+    /// Load on-disk index. In-memory indexes are loaded with [`Self::build_index`].
+    pub fn load_mmap(&mut self) -> OperationResult<bool> {
+        match self {
+            InvertedIndex::Mmap(index) => index.load(),
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => Ok(false),
+        }
+    }
+
+    /// Remove files of on-disk index
+    pub fn clear_mmap(self) -> OperationResult<()> {
+        match self {
+            InvertedIndex::Mmap(index) => index.clear(),
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => Ok(()),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
         match self {
+            InvertedIndex::Mmap(index) => index.files(),
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => vec![],
+        }
+    }
+
+    pub fn mmap_flusher(&self) -> Flusher {
+        match self {
+            InvertedIndex::Mmap(index) => index.flusher(),
+            InvertedIndex::Mutable(_) | InvertedIndex::Immutable(_) => Box::new(|| Ok(())),
+        }
+    }
+
+    pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
+        let all_tokens = match self {
             InvertedIndex::Mutable(index) => index.check_match(parsed_query, point_id),
             InvertedIndex::Immutable(index) => index.check_match(parsed_query, point_id),
-        }
+            InvertedIndex::Mmap(index) => index.check_match(parsed_query, point_id),
+        };
+        all_tokens && self.check_phrase(parsed_query, point_id)
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
         match self {
             InvertedIndex::Mutable(index) => index.values_is_empty(point_id),
             InvertedIndex::Immutable(index) => index.values_is_empty(point_id),
+            InvertedIndex::Mmap(index) => index.values_is_empty(point_id),
         }
     }
 
@@ -273,6 +473,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.values_count(point_id),
             InvertedIndex::Immutable(index) => index.values_count(point_id),
+            InvertedIndex::Mmap(index) => index.values_count(point_id),
         }
     }
 
@@ -280,6 +481,7 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.points_count,
             InvertedIndex::Immutable(index) => index.points_count,
+            InvertedIndex::Mmap(index) => index.points_count(),
         }
     }
 
@@ -287,24 +489,98 @@ impl InvertedIndex {
         match self {
             InvertedIndex::Mutable(index) => index.vocab.get(token).copied(),
             InvertedIndex::Immutable(index) => index.vocab.get(token).copied(),
+            InvertedIndex::Mmap(index) => index.get_token(token),
+        }
+    }
+
+    /// Number of documents with the token.
+    /// Removed documents might still be counted in immutable indexes.
+    pub fn document_frequency(&self, token_id: TokenId) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index
+                .postings
+                .get(token_id as usize)
+                .and_then(Option::as_ref)
+                .map_or(0, PostingList::len),
+            InvertedIndex::Immutable(index) => index
+                .postings
+                .get(token_id as usize)
+                .and_then(Option::as_ref)
+                .map_or(0, CompressedPostingList::len),
+            InvertedIndex::Mmap(index) => index.get_posting(token_id).map_or(0, <[_]>::len),
+        }
+    }
+
+    /// Iterate over documents with the token.
+    /// Removed documents might still be present in immutable indexes.
+    pub fn posting_iter(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            InvertedIndex::Mutable(index) => {
+                match index
+                    .postings
+                    .get(token_id as usize)
+                    .and_then(Option::as_ref)
+                {
+                    Some(posting) => Box::new(posting.iter()),
+                    None => Box::new(std::iter::empty()),
+                }
+            }
+            InvertedIndex::Immutable(index) => {
+                match index
+                    .postings
+                    .get(token_id as usize)
+                    .and_then(Option::as_ref)
+                {
+                    Some(posting) => Box::new(posting.iter()),
+                    None => Box::new(std::iter::empty()),
+                }
+            }
+            InvertedIndex::Mmap(index) => match index.get_posting(token_id) {
+                Some(posting) => Box::new(posting.iter().copied()),
+                None => Box::new(std::iter::empty()),
+            },
+        }
+    }
+
+    /// Number of token occurrences in the document of the point
+    pub fn document_length(&self, point_id: PointOffsetType) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.get_doc(point_id).map_or(0, Document::length),
+            InvertedIndex::Immutable(index) => index.document_length(point_id),
+            InvertedIndex::Mmap(index) => index.document_length(point_id),
+        }
+    }
+
+    /// Total number of token occurrences in all documents
+    pub fn total_length(&self) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.total_length,
+            InvertedIndex::Immutable(index) => index.total_length,
+            InvertedIndex::Mmap(index) => index.total_length(),
         }
     }
 }
 
 #[derive(Default)]
 pub struct MutableInvertedIndex {
-    postings: Vec<Option<PostingList>>,
-    vocab: HashMap<String, TokenId>,
-    point_to_docs: Vec<Option<Document>>,
-    points_count: usize,
+    pub(super) postings: Vec<Option<PostingList>>,
+    pub(super) vocab: HashMap<String, TokenId>,
+    pub(super) point_to_docs: Vec<Option<Document>>,
+    pub(super) points_count: usize,
+    /// Number of token occurrences in all documents
+    pub(super) total_length: usize,
 }
 
 impl MutableInvertedIndex {
     fn build_index(
         &mut self,
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, DocumentTokens)>>,
     ) -> OperationResult<()> {
         self.points_count = 0;
+        self.total_length = 0;
         self.vocab.clear();
         self.postings.clear();
         self.point_to_docs.clear();
@@ -320,14 +596,17 @@ impl MutableInvertedIndex {
             }
 
             let document = InvertedIndex::document_from_tokens_impl(&mut self.vocab, &tokens);
+            self.total_length += document.length();
             self.point_to_docs[idx as usize] = Some(document);
         }
 
         // build postings from point_to_docs
         // build in order to increase document id
-        for (idx, doc) in self.point_to_docs.iter().enumerate() {
+        for (idx, doc) in self.point_to_docs.iter_mut().enumerate() {
             if let Some(doc) = doc {
+                let mut positions = doc.take_positions().into_iter();
                 for token_idx in doc.tokens() {
+                    let token_positions = positions.next().unwrap_or_default();
                     if self.postings.len() <= *token_idx as usize {
                         self.postings
                             .resize_with(*token_idx as usize + 1, Default::default);
@@ -337,8 +616,11 @@ impl MutableInvertedIndex {
                         .get_mut(*token_idx as usize)
                         .expect("posting must exist even if with None");
                     match posting {
-                        None => *posting = Some(PostingList::new(idx as PointOffsetType)),
-                        Some(vec) => vec.insert(idx as PointOffsetType),
+                        None => {
+                            *posting =
+                                Some(PostingList::new(idx as PointOffsetType, token_positions))
+                        }
+                        Some(vec) => vec.insert(idx as PointOffsetType, token_positions),
                     }
                 }
             }
@@ -347,14 +629,21 @@ impl MutableInvertedIndex {
         Ok(())
     }
 
-    fn index_document(&mut self, idx: PointOffsetType, document: Document) -> OperationResult<()> {
+    fn index_document(
+        &mut self,
+        idx: PointOffsetType,
+        mut document: Document,
+    ) -> OperationResult<()> {
         self.points_count += 1;
+        self.total_length += document.length();
         if self.point_to_docs.len() <= idx as usize {
             self.point_to_docs
                 .resize_with(idx as usize + 1, Default::default);
         }
 
+        let mut positions = document.take_positions().into_iter();
         for token_idx in document.tokens() {
+            let token_positions = positions.next().unwrap_or_default();
             let token_idx_usize = *token_idx as usize;
             if self.postings.len() <= token_idx_usize {
                 self.postings
@@ -365,8 +654,8 @@ impl MutableInvertedIndex {
                 .get_mut(token_idx_usize)
                 .expect("posting must exist even if with None");
             match posting {
-                None => *posting = Some(PostingList::new(idx)),
-                Some(vec) => vec.insert(idx),
+                None => *posting = Some(PostingList::new(idx, token_positions)),
+                Some(vec) => vec.insert(idx, token_positions),
             }
         }
         self.point_to_docs[idx as usize] = Some(document);
@@ -384,6 +673,7 @@ impl MutableInvertedIndex {
         };
 
         self.points_count -= 1;
+        self.total_length -= removed_doc.length();
 
         for removed_token in removed_doc.tokens() {
             // unwrap safety: posting list exists and contains the document id
@@ -455,7 +745,10 @@ pub struct ImmutableInvertedIndex {
     postings: Vec<Option<CompressedPostingList>>,
     vocab: HashMap<String, TokenId>,
     point_documents_tokens: Vec<Option<usize>>,
+    /// Number of token occurrences in each document
+    point_documents_lengths: Vec<usize>,
     points_count: usize,
+    total_length: usize,
 }
 
 impl ImmutableInvertedIndex {
@@ -465,6 +758,7 @@ impl ImmutableInvertedIndex {
         }
         self.point_documents_tokens[idx as usize] = None;
         self.points_count -= 1;
+        self.total_length -= self.point_documents_lengths[idx as usize];
         true
     }
 
@@ -509,6 +803,13 @@ impl ImmutableInvertedIndex {
         self.point_documents_tokens[point_id as usize].unwrap_or(0)
     }
 
+    fn document_length(&self, point_id: PointOffsetType) -> usize {
+        if self.values_is_empty(point_id) {
+            return 0;
+        }
+        self.point_documents_lengths[point_id as usize]
+    }
+
     fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         if parsed_query.tokens.contains(&None) {
             return false;
@@ -559,7 +860,13 @@ impl From<MutableInvertedIndex> for ImmutableInvertedIndex {
                 .iter()
                 .map(|doc| doc.as_ref().map(|doc| doc.len()))
                 .collect(),
+            point_documents_lengths: index
+                .point_to_docs
+                .iter()
+                .map(|doc| doc.as_ref().map_or(0, Document::length))
+                .collect(),
             points_count: index.points_count,
+            total_length: index.total_length,
         }
     }
 }
@@ -0,0 +1,315 @@
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::storage_version::{StorageVersion, VERSION_FILE};
+
+use super::inverted_index::{Document, MutableInvertedIndex, ParsedQuery, TokenId, TokenPosition};
+use super::postings_iterator::intersect_slice_postings_iterator;
+use crate::common::mmap_type::{MmapBitSlice, MmapSlice, MmapType};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::mmap_point_to_values::{
+    combine_flushers, create_index_dir, create_mmap_bitslice, create_mmap_slice, create_mmap_type,
+    is_current_index_version, open_mmap_bitslice, open_mmap_slice, open_mmap_type,
+    MmapIndexVersion, MmapPointToValues,
+};
+
+const STATUS_FILE: &str = "status.dat";
+const VOCAB_NAME: &str = "vocab";
+const POSTINGS_NAME: &str = "postings";
+const POSITIONS_NAME: &str = "positions";
+const POINT_TO_TOKENS_COUNT_FILE: &str = "point_to_tokens_count.bin";
+const POINT_TO_LENGTH_FILE: &str = "point_to_length.bin";
+const DELETED_POINTS_FILE: &str = "deleted_points.bin";
+
+#[repr(C)]
+struct MmapInvertedIndexStatus {
+    points_count: usize,
+    /// Number of token occurrences in all not removed documents
+    total_length: usize,
+}
+
+struct Storage {
+    status: MmapType<MmapInvertedIndexStatus>,
+    /// Token id -> token, sorted by the token
+    vocab: MmapPointToValues<u8>,
+    /// Token id -> sorted points
+    postings: MmapPointToValues<PointOffsetType>,
+    /// Posting entry -> sorted positions of the token in the point.
+    /// Entries are numbered in the order of flattened `postings`.
+    positions: MmapPointToValues<TokenPosition>,
+    point_to_tokens_count: MmapSlice<usize>,
+    /// Point -> number of token occurrences in the document
+    point_to_length: MmapSlice<usize>,
+    /// Points without a document, removed points are still present in postings
+    deleted_points: MmapBitSlice,
+}
+
+/// Read-only inverted index, stored in memory mapped files
+///
+/// Only removal of documents is supported.
+pub struct MmapInvertedIndex {
+    path: PathBuf,
+    storage: Option<Storage>,
+}
+
+impl MmapInvertedIndex {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+        }
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        if !is_current_index_version(&self.path)? {
+            return Ok(false);
+        }
+
+        self.storage = Some(Storage {
+            status: open_mmap_type(&self.path.join(STATUS_FILE))?,
+            vocab: MmapPointToValues::open(&self.path, VOCAB_NAME)?,
+            postings: MmapPointToValues::open(&self.path, POSTINGS_NAME)?,
+            positions: MmapPointToValues::open(&self.path, POSITIONS_NAME)?,
+            point_to_tokens_count: open_mmap_slice(&self.path.join(POINT_TO_TOKENS_COUNT_FILE))?,
+            point_to_length: open_mmap_slice(&self.path.join(POINT_TO_LENGTH_FILE))?,
+            deleted_points: open_mmap_bitslice(&self.path.join(DELETED_POINTS_FILE))?,
+        });
+        Ok(true)
+    }
+
+    /// Write all data of the `mutable` index into this one
+    pub fn build(&mut self, mutable: &MutableInvertedIndex) -> OperationResult<()> {
+        create_index_dir(&self.path)?;
+
+        let mut vocab: Vec<(&str, TokenId)> = mutable
+            .vocab
+            .iter()
+            .map(|(token, &token_id)| (token.as_str(), token_id))
+            .collect();
+        vocab.sort_unstable();
+
+        let tokens: Vec<&[u8]> = vocab.iter().map(|(token, _)| token.as_bytes()).collect();
+        let postings: Vec<Vec<PointOffsetType>> = vocab
+            .iter()
+            .map(
+                |&(_, token_id)| match mutable.postings.get(token_id as usize) {
+                    Some(Some(posting)) => posting.iter().collect(),
+                    _ => vec![],
+                },
+            )
+            .collect();
+        let positions: Vec<&[TokenPosition]> = vocab
+            .iter()
+            .filter_map(|&(_, token_id)| mutable.postings.get(token_id as usize)?.as_ref())
+            .flat_map(|posting| posting.iter_positions())
+            .collect();
+
+        let status = MmapInvertedIndexStatus {
+            points_count: mutable.points_count,
+            total_length: mutable.total_length,
+        };
+
+        self.storage = Some(Storage {
+            status: create_mmap_type(&self.path.join(STATUS_FILE), status)?,
+            vocab: MmapPointToValues::create(&self.path, VOCAB_NAME, &tokens)?,
+            postings: MmapPointToValues::create(&self.path, POSTINGS_NAME, &postings)?,
+            positions: MmapPointToValues::create(&self.path, POSITIONS_NAME, &positions)?,
+            point_to_tokens_count: create_mmap_slice(
+                &self.path.join(POINT_TO_TOKENS_COUNT_FILE),
+                mutable
+                    .point_to_docs
+                    .iter()
+                    .map(|doc| doc.as_ref().map_or(0, |doc| doc.len())),
+            )?,
+            point_to_length: create_mmap_slice(
+                &self.path.join(POINT_TO_LENGTH_FILE),
+                mutable
+                    .point_to_docs
+                    .iter()
+                    .map(|doc| doc.as_ref().map_or(0, Document::length)),
+            )?,
+            deleted_points: create_mmap_bitslice(
+                &self.path.join(DELETED_POINTS_FILE),
+                mutable.point_to_docs.iter().map(Option::is_none),
+            )?,
+        });
+
+        // Version is saved last, so that incomplete index is rebuilt on load
+        MmapIndexVersion::save(&self.path)?;
+        Ok(())
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        drop(self.storage);
+        if self.path.exists() {
+            std::fs::remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![
+            self.path.join(VERSION_FILE),
+            self.path.join(STATUS_FILE),
+            self.path.join(POINT_TO_TOKENS_COUNT_FILE),
+            self.path.join(POINT_TO_LENGTH_FILE),
+            self.path.join(DELETED_POINTS_FILE),
+        ];
+        files.extend(storage.vocab.files());
+        files.extend(storage.postings.files());
+        files.extend(storage.positions.files());
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => combine_flushers(vec![
+                storage.status.flusher(),
+                storage.deleted_points.flusher(),
+            ]),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    pub fn points_count(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.points_count)
+    }
+
+    /// Binary search of the token id in the sorted vocabulary
+    pub fn get_token(&self, token: &str) -> Option<TokenId> {
+        let vocab = &self.storage.as_ref()?.vocab;
+        let (mut low, mut high) = (0, vocab.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match vocab.get_values(mid as TokenId)?.cmp(token.as_bytes()) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Some(mid as TokenId),
+            }
+        }
+        None
+    }
+
+    pub fn get_posting(&self, token_id: TokenId) -> Option<&[PointOffsetType]> {
+        self.storage.as_ref()?.postings.get_values(token_id)
+    }
+
+    /// Sorted positions of the token in the point
+    pub fn token_positions(
+        &self,
+        token_id: TokenId,
+        point_id: PointOffsetType,
+    ) -> Option<&[TokenPosition]> {
+        let storage = self.storage.as_ref()?;
+        let posting = storage.postings.get_values(token_id)?;
+        let rank = posting.binary_search(&point_id).ok()?;
+        let offset = storage.postings.values_offset(token_id)?;
+        storage
+            .positions
+            .get_values((offset + rank) as PointOffsetType)
+    }
+
+    pub fn remove_document(&mut self, idx: PointOffsetType) -> bool {
+        if self.values_is_empty(idx) {
+            return false; // Already removed or never actually existed
+        }
+        let Some(storage) = &mut self.storage else {
+            return false;
+        };
+        storage.deleted_points.set(idx as usize, true);
+        storage.status.points_count -= 1;
+        storage.status.total_length -= storage.point_to_length[idx as usize];
+        true
+    }
+
+    pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let postings_opt: Option<Vec<_>> = query
+            .tokens
+            .iter()
+            .map(|&token_id| self.get_posting(token_id?))
+            .collect();
+        let Some(postings) = postings_opt else {
+            // There are unseen tokens -> no matches
+            return Box::new(vec![].into_iter());
+        };
+        if postings.is_empty() {
+            // Empty request -> no matches
+            return Box::new(vec![].into_iter());
+        }
+
+        // deleted documents are still in the postings
+        let filter = move |idx| !self.values_is_empty(idx);
+        intersect_slice_postings_iterator(postings, filter)
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        let Some(storage) = &self.storage else {
+            return true;
+        };
+        storage
+            .deleted_points
+            .get(point_id as usize)
+            .map_or(true, |deleted| *deleted)
+            || point_id as usize >= storage.point_to_tokens_count.len()
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        if self.values_is_empty(point_id) {
+            return 0;
+        }
+        self.storage.as_ref().map_or(0, |storage| {
+            storage.point_to_tokens_count[point_id as usize]
+        })
+    }
+
+    pub fn document_length(&self, point_id: PointOffsetType) -> usize {
+        if self.values_is_empty(point_id) {
+            return 0;
+        }
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.point_to_length[point_id as usize])
+    }
+
+    pub fn total_length(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.total_length)
+    }
+
+    pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
+        if parsed_query.tokens.contains(&None) {
+            return false;
+        }
+        // check presence of the document
+        if self.values_is_empty(point_id) {
+            return false;
+        }
+        // Check that all tokens are in document
+        parsed_query.tokens.iter().all(|query_token| {
+            query_token
+                .and_then(|token_id| self.get_posting(token_id))
+                .is_some_and(|posting| posting.binary_search(&point_id).is_ok())
+        })
+    }
+
+    pub fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        let vocab_size = self
+            .storage
+            .as_ref()
+            .map_or(0, |storage| storage.vocab.len());
+        (0..vocab_size as TokenId).filter_map(|token_id| {
+            let storage = self.storage.as_ref()?;
+            let token = std::str::from_utf8(storage.vocab.get_values(token_id)?).ok()?;
+            let posting = storage.postings.get_values(token_id)?;
+            Some((token, posting.len()))
+        })
+    }
+}
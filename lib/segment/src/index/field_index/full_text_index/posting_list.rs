@@ -1,34 +1,49 @@
 use bitpacking::BitPacker;
 use common::types::PointOffsetType;
 
+use super::inverted_index::TokenPosition;
+
 type BitPackerImpl = bitpacking::BitPacker4x;
 
 #[derive(Clone, Debug, Default)]
 pub struct PostingList {
     list: Vec<PointOffsetType>,
+    /// Positions of the token in each document of the `list`
+    positions: Vec<Vec<TokenPosition>>,
 }
 
 impl PostingList {
-    pub fn new(idx: PointOffsetType) -> Self {
-        Self { list: vec![idx] }
+    pub fn new(idx: PointOffsetType, positions: Vec<TokenPosition>) -> Self {
+        Self {
+            list: vec![idx],
+            positions: vec![positions],
+        }
     }
 
-    pub fn insert(&mut self, idx: PointOffsetType) {
+    pub fn insert(&mut self, idx: PointOffsetType, positions: Vec<TokenPosition>) {
         if self.list.is_empty() || idx > *self.list.last().unwrap() {
             self.list.push(idx);
-        } else if let Err(insertion_idx) = self.list.binary_search(&idx) {
-            // Yes, this is O(n) but:
-            // 1. That would give us maximal search performance with minimal memory usage
-            // 2. Documents are inserted mostly sequentially, especially in large segments
-            // 3. Vector indexing is more expensive anyway
-            // 4. For loading, insertion is strictly in increasing order
-            self.list.insert(insertion_idx, idx);
+            self.positions.push(positions);
+        } else {
+            match self.list.binary_search(&idx) {
+                Ok(existing_idx) => self.positions[existing_idx] = positions,
+                Err(insertion_idx) => {
+                    // Yes, this is O(n) but:
+                    // 1. That would give us maximal search performance with minimal memory usage
+                    // 2. Documents are inserted mostly sequentially, especially in large segments
+                    // 3. Vector indexing is more expensive anyway
+                    // 4. For loading, insertion is strictly in increasing order
+                    self.list.insert(insertion_idx, idx);
+                    self.positions.insert(insertion_idx, positions);
+                }
+            }
         }
     }
 
     pub fn remove(&mut self, idx: PointOffsetType) {
         if let Ok(removal_idx) = self.list.binary_search(&idx) {
             self.list.remove(removal_idx);
+            self.positions.remove(removal_idx);
         }
     }
 
@@ -43,6 +58,17 @@ impl PostingList {
     pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
         self.list.iter().copied()
     }
+
+    /// Sorted positions of the token in the document `val`
+    pub fn positions(&self, val: &PointOffsetType) -> Option<&[TokenPosition]> {
+        let idx = self.list.binary_search(val).ok()?;
+        Some(&self.positions[idx])
+    }
+
+    /// Positions of the token in each document, in the order of [`Self::iter`]
+    pub fn iter_positions(&self) -> impl Iterator<Item = &[TokenPosition]> + '_ {
+        self.positions.iter().map(Vec::as_slice)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -52,6 +78,10 @@ pub struct CompressedPostingList {
     chunks: Vec<CompressedPostingChunk>,
     // last postings that are not compressed because they are not aligned with the block size
     reminder_postings: Vec<PointOffsetType>,
+    // positions of the token in all documents, concatenated in the order of postings
+    positions: Vec<TokenPosition>,
+    // start of positions of each document in `positions`, has one extra element at the end
+    positions_offsets: Vec<u32>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -105,11 +135,22 @@ impl CompressedPostingList {
             );
         }
 
+        // concatenate positions
+        let mut positions_offsets = Vec::with_capacity(posting_list.positions.len() + 1);
+        positions_offsets.push(0);
+        for document_positions in &posting_list.positions {
+            let last_offset = *positions_offsets.last().unwrap();
+            positions_offsets.push(last_offset + document_positions.len() as u32);
+        }
+        let positions = posting_list.positions.into_iter().flatten().collect();
+
         Self {
             last_doc_id: *posting_list.list.last().unwrap(),
             data,
             chunks,
             reminder_postings: noncompressed_postings,
+            positions,
+            positions_offsets,
         }
     }
 
@@ -133,10 +174,38 @@ impl CompressedPostingList {
         }
     }
 
+    /// Sorted positions of the token in the document `val`
+    pub fn positions(&self, val: &PointOffsetType) -> Option<&[TokenPosition]> {
+        let rank = self.rank(val)?;
+        let start = *self.positions_offsets.get(rank)? as usize;
+        let end = *self.positions_offsets.get(rank + 1)? as usize;
+        Some(&self.positions[start..end])
+    }
+
     pub fn len(&self) -> usize {
         self.chunks.len() * BitPackerImpl::BLOCK_LEN + self.reminder_postings.len()
     }
 
+    /// Index of the value in the posting list, if present
+    fn rank(&self, val: &PointOffsetType) -> Option<usize> {
+        if !self.is_in_postings_range(*val) {
+            return None;
+        }
+
+        match self.find_chunk(val, None) {
+            Some(chunk_index) => {
+                let mut decompressed = [0u32; BitPackerImpl::BLOCK_LEN];
+                self.decompress_chunk(&BitPackerImpl::new(), chunk_index, &mut decompressed);
+                let idx = decompressed.binary_search(val).ok()?;
+                Some(chunk_index * BitPackerImpl::BLOCK_LEN + idx)
+            }
+            None => {
+                let idx = self.reminder_postings.binary_search(val).ok()?;
+                Some(self.chunks.len() * BitPackerImpl::BLOCK_LEN + idx)
+            }
+        }
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
         let bitpacker = BitPackerImpl::new();
         (0..self.chunks.len())
@@ -328,7 +397,7 @@ mod tests {
         let mut posting_list = PostingList::default();
         for i in 0..999 {
             set.insert(step * i);
-            posting_list.insert(step * i);
+            posting_list.insert(step * i, vec![i, i + 10]);
         }
         let compressed_posting_list = CompressedPostingList::new(posting_list);
         (compressed_posting_list, set)
@@ -344,6 +413,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compressed_posting_positions() {
+        for step in 1..3 {
+            let (compressed_posting_list, set) = generate_compressed_posting_list(step);
+            for i in 0..step * 1000 {
+                let positions = compressed_posting_list.positions(&i);
+                if set.contains(&i) {
+                    let expected = [i / step, i / step + 10];
+                    assert_eq!(positions, Some(expected.as_slice()));
+                } else {
+                    assert!(positions.is_none());
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_compressed_posting_visitor() {
         for build_step in 0..3 {
@@ -49,6 +49,31 @@ pub fn intersect_compressed_postings_iterator<'a>(
     Box::new(and_iter)
 }
 
+pub fn intersect_slice_postings_iterator<'a>(
+    mut postings: Vec<&'a [PointOffsetType]>,
+    filter: impl Fn(PointOffsetType) -> bool + 'a,
+) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+    let smallest_posting_idx = postings
+        .iter()
+        .enumerate()
+        .min_by_key(|(_idx, posting)| posting.len())
+        .map(|(idx, _posting)| idx)
+        .unwrap();
+    let smallest_posting = postings.remove(smallest_posting_idx);
+
+    let and_iter = smallest_posting
+        .iter()
+        .copied()
+        .filter(move |doc_id| filter(*doc_id))
+        .filter(move |doc_id| {
+            postings
+                .iter()
+                .all(|posting| posting.binary_search(doc_id).is_ok())
+        });
+
+    Box::new(and_iter)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,21 +81,21 @@ mod tests {
     #[test]
     fn test_postings_iterator() {
         let mut p1 = PostingList::default();
-        p1.insert(1);
-        p1.insert(2);
-        p1.insert(3);
-        p1.insert(4);
-        p1.insert(5);
+        p1.insert(1, vec![]);
+        p1.insert(2, vec![]);
+        p1.insert(3, vec![]);
+        p1.insert(4, vec![]);
+        p1.insert(5, vec![]);
         let mut p2 = PostingList::default();
-        p2.insert(2);
-        p2.insert(4);
-        p2.insert(5);
-        p2.insert(5);
+        p2.insert(2, vec![]);
+        p2.insert(4, vec![]);
+        p2.insert(5, vec![]);
+        p2.insert(5, vec![]);
         let mut p3 = PostingList::default();
-        p3.insert(1);
-        p3.insert(2);
-        p3.insert(5);
-        p3.insert(6);
+        p3.insert(1, vec![]);
+        p3.insert(2, vec![]);
+        p3.insert(5, vec![]);
+        p3.insert(6, vec![]);
         p3.insert(7);
 
         let postings = vec![&p1, &p2, &p3];
@@ -89,5 +114,12 @@ mod tests {
         let res = merged.collect::<Vec<_>>();
 
         assert_eq!(res, vec![2, 5]);
+
+        let slices = [[1, 2, 3, 4, 5].as_slice(), &[2, 4, 5], &[1, 2, 5, 6, 7]];
+        let merged = intersect_slice_postings_iterator(slices.to_vec(), |idx| idx != 5);
+
+        let res = merged.collect::<Vec<_>>();
+
+        assert_eq!(res, vec![2]);
     }
 }
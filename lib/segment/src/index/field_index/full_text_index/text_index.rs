@@ -1,7 +1,8 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde::{Deserialize, Serialize};
@@ -12,20 +13,26 @@ use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDelet
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::index::TextIndexParams;
+use crate::data_types::text_query::TextStatistics;
 use crate::index::field_index::full_text_index::inverted_index::{
-    Document, InvertedIndex, ParsedQuery,
+    Document, DocumentTokens, InvertedIndex, ParsedQuery, PhraseQuery, TokenPosition,
 };
 use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, ValueIndexer,
 };
 use crate::telemetry::PayloadIndexTelemetry;
-use crate::types::{FieldCondition, Match, PayloadKeyType};
+use crate::types::{FieldCondition, Match, MatchPhrase, MatchText, PayloadKeyType};
+
+/// Gap between positions of tokens from different values of the same point,
+/// so that phrases don't match across values
+const VALUES_POSITION_GAP: TokenPosition = 100;
 
 pub struct FullTextIndex {
     inverted_index: InvertedIndex,
-    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
-    config: TextIndexParams,
+    /// Storage of the documents, `None` for on-disk index
+    db_wrapper: Option<DatabaseColumnScheduledDeleteWrapper>,
+    tokenizer: Tokenizer,
 }
 
 impl FullTextIndex {
@@ -37,27 +44,34 @@ impl FullTextIndex {
         bincode::deserialize(data).unwrap()
     }
 
-    fn serialize_document_tokens(&self, tokens: BTreeSet<String>) -> OperationResult<Vec<u8>> {
+    fn serialize_document_tokens(&self, tokens: DocumentTokens) -> OperationResult<Vec<u8>> {
         #[derive(Serialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            positions: Vec<Vec<TokenPosition>>,
         }
-        let doc = StoredDocument { tokens };
+        let (tokens, positions) = tokens.into_iter().unzip();
+        let doc = StoredDocument { tokens, positions };
         serde_cbor::to_vec(&doc).map_err(|e| {
             OperationError::service_error(format!("Failed to serialize document: {e}"))
         })
     }
 
-    fn deserialize_document(data: &[u8]) -> OperationResult<BTreeSet<String>> {
+    /// Returns `None` for documents stored without token positions
+    fn deserialize_document(data: &[u8]) -> OperationResult<Option<DocumentTokens>> {
         #[derive(Deserialize)]
         struct StoredDocument {
             tokens: BTreeSet<String>,
+            #[serde(default)]
+            positions: Option<Vec<Vec<TokenPosition>>>,
         }
-        serde_cbor::from_slice::<StoredDocument>(data)
-            .map_err(|e| {
-                OperationError::service_error(format!("Failed to deserialize document: {e}"))
-            })
-            .map(|doc| doc.tokens)
+        let doc = serde_cbor::from_slice::<StoredDocument>(data).map_err(|e| {
+            OperationError::service_error(format!("Failed to deserialize document: {e}"))
+        })?;
+        Ok(doc
+            .positions
+            .filter(|positions| positions.len() == doc.tokens.len())
+            .map(|positions| doc.tokens.into_iter().zip(positions).collect()))
     }
 
     fn storage_cf_name(field: &str) -> String {
@@ -77,11 +91,24 @@ impl FullTextIndex {
         ));
         FullTextIndex {
             inverted_index: InvertedIndex::new(is_appendable),
-            db_wrapper,
-            config,
+            db_wrapper: Some(db_wrapper),
+            tokenizer: Tokenizer::new(&config),
+        }
+    }
+
+    pub fn new_mmap(path: &Path, config: TextIndexParams) -> Self {
+        FullTextIndex {
+            inverted_index: InvertedIndex::new_mmap(path),
+            db_wrapper: None,
+            tokenizer: Tokenizer::new(&config),
         }
     }
 
+    /// Fill the on-disk index with the data of another index.
+    pub fn build_from(&mut self, other: &FullTextIndex) -> OperationResult<()> {
+        self.inverted_index.build_from(&other.inverted_index)
+    }
+
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
@@ -92,27 +119,58 @@ impl FullTextIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.db_wrapper.recreate_column_family()
+        match &self.db_wrapper {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     pub fn parse_query(&self, text: &str) -> ParsedQuery {
         let mut tokens = HashSet::new();
-        Tokenizer::tokenize_query(text, &self.config, |token| {
+        self.tokenizer.tokenize_query(text, |token, _| {
             tokens.insert(self.inverted_index.get_token(token));
         });
         ParsedQuery {
             tokens: tokens.into_iter().collect(),
+            phrase: None,
+        }
+    }
+
+    pub fn parse_phrase_query(&self, phrase: &str, slop: Option<u32>) -> ParsedQuery {
+        let mut tokens = vec![];
+        let mut positions = vec![];
+        self.tokenizer.tokenize_query(phrase, |token, position| {
+            tokens.push(self.inverted_index.get_token(token));
+            positions.push(position);
+        });
+        ParsedQuery {
+            tokens,
+            phrase: Some(PhraseQuery {
+                positions,
+                slop: slop.unwrap_or(0),
+            }),
+        }
+    }
+
+    /// Parse text or phrase match of the condition
+    fn parse_condition(&self, condition: &FieldCondition) -> Option<ParsedQuery> {
+        match &condition.r#match {
+            Some(Match::Text(MatchText { text })) => Some(self.parse_query(text)),
+            Some(Match::Phrase(MatchPhrase { phrase, slop })) => {
+                Some(self.parse_phrase_query(phrase, *slop))
+            }
+            _ => None,
         }
     }
 
     pub fn parse_document(&self, text: &str) -> Document {
-        let mut document_tokens = vec![];
-        Tokenizer::tokenize_doc(text, &self.config, |token| {
+        let mut document_tokens: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        self.tokenizer.tokenize_doc(text, |token, position| {
             if let Some(token_id) = self.inverted_index.get_token(token) {
-                document_tokens.push(token_id);
+                document_tokens.entry(token_id).or_default().push(position);
             }
         });
-        Document::new(document_tokens)
+        Document::with_positions(document_tokens.into_iter().collect())
     }
 
     #[cfg(test)]
@@ -132,6 +190,64 @@ impl FullTextIndex {
     pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         self.inverted_index.check_match(parsed_query, point_id)
     }
+
+    /// Unique tokens of the query text
+    fn query_tokens(&self, query: &str) -> BTreeSet<String> {
+        let mut tokens = BTreeSet::new();
+        self.tokenizer.tokenize_query(query, |token, _| {
+            tokens.insert(token.to_owned());
+        });
+        tokens
+    }
+
+    /// Add statistics of this index for the tokens of `statistics.queries`, required for BM25 scoring
+    pub fn fill_text_statistics(&self, statistics: &mut TextStatistics) {
+        statistics.points_count += self.inverted_index.points_count();
+        statistics.total_length += self.inverted_index.total_length();
+
+        let tokens: BTreeSet<_> = statistics
+            .queries
+            .iter()
+            .flat_map(|query| self.query_tokens(query))
+            .collect();
+        for token in tokens {
+            let frequency = self.inverted_index.get_token(&token).map_or(0, |token_id| {
+                self.inverted_index.document_frequency(token_id)
+            });
+            *statistics.document_frequencies.entry(token).or_default() += frequency;
+        }
+    }
+
+    /// BM25 scores of the points, which contain at least one of the query tokens.
+    ///
+    /// Both token frequency and document length are measured in token occurrences.
+    pub fn bm25_scores(
+        &self,
+        query: &str,
+        statistics: &TextStatistics,
+    ) -> HashMap<PointOffsetType, ScoreType> {
+        let mut scores: HashMap<PointOffsetType, ScoreType> = HashMap::new();
+        for token in self.query_tokens(query) {
+            let Some(token_id) = self.inverted_index.get_token(&token) else {
+                continue;
+            };
+            let idf = statistics.idf(&token);
+            for point_id in self.inverted_index.posting_iter(token_id) {
+                // Removed documents are still present in postings of immutable indexes
+                if self.inverted_index.values_is_empty(point_id) {
+                    continue;
+                }
+                let token_frequency = self
+                    .inverted_index
+                    .token_positions(token_id, point_id)
+                    .map_or(1, |positions| positions.len().max(1));
+                let document_length = self.inverted_index.document_length(point_id);
+                *scores.entry(point_id).or_default() +=
+                    statistics.bm25(idf, token_frequency, document_length);
+            }
+        }
+        scores
+    }
 }
 
 impl ValueIndexer<String> for FullTextIndex {
@@ -140,21 +256,27 @@ impl ValueIndexer<String> for FullTextIndex {
             return Ok(());
         }
 
-        let mut tokens: BTreeSet<String> = BTreeSet::new();
+        let mut tokens = DocumentTokens::new();
+        let mut value_offset: TokenPosition = 0;
 
         for value in values {
-            Tokenizer::tokenize_doc(&value, &self.config, |token| {
-                tokens.insert(token.to_owned());
+            let mut next_offset = value_offset;
+            self.tokenizer.tokenize_doc(&value, |token, position| {
+                let position = value_offset + position;
+                tokens.entry(token.to_owned()).or_default().push(position);
+                next_offset = next_offset.max(position + VALUES_POSITION_GAP);
             });
+            value_offset = next_offset;
         }
 
         let document = self.inverted_index.document_from_tokens(&tokens);
         self.inverted_index.index_document(idx, document)?;
 
-        let db_idx = Self::store_key(&idx);
-        let db_document = self.serialize_document_tokens(tokens)?;
-
-        self.db_wrapper.put(db_idx, db_document)?;
+        if let Some(db_wrapper) = &self.db_wrapper {
+            let db_idx = Self::store_key(&idx);
+            let db_document = self.serialize_document_tokens(tokens)?;
+            db_wrapper.put(db_idx, db_document)?;
+        }
 
         Ok(())
     }
@@ -168,8 +290,10 @@ impl ValueIndexer<String> for FullTextIndex {
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
         if self.inverted_index.remove_document(id) {
-            let db_doc_id = Self::store_key(&id);
-            self.db_wrapper.remove(db_doc_id)?;
+            if let Some(db_wrapper) = &self.db_wrapper {
+                let db_doc_id = Self::store_key(&id);
+                db_wrapper.remove(db_doc_id)?;
+            }
         }
         Ok(())
     }
@@ -181,35 +305,56 @@ impl PayloadFieldIndex for FullTextIndex {
     }
 
     fn load(&mut self) -> OperationResult<bool> {
-        if !self.db_wrapper.has_column_family()? {
+        let Some(db_wrapper) = &self.db_wrapper else {
+            return self.inverted_index.load_mmap();
+        };
+
+        if !db_wrapper.has_column_family()? {
             return Ok(false);
         };
 
-        let db = self.db_wrapper.lock_db();
+        let mut has_documents_without_positions = false;
+        let db = db_wrapper.lock_db();
         let i = db.iter()?.map(|(key, value)| {
             let idx = Self::restore_key(&key);
-            let tokens = Self::deserialize_document(&value)?;
+            let tokens = match Self::deserialize_document(&value)? {
+                Some(tokens) => tokens,
+                None => {
+                    has_documents_without_positions = true;
+                    DocumentTokens::new()
+                }
+            };
             Ok((idx, tokens))
         });
         self.inverted_index.build_index(i)?;
 
-        Ok(true)
+        // Index was stored by a previous version, rebuild it to get positions of tokens
+        Ok(!has_documents_without_positions)
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper.remove_column_family()
+        match &self.db_wrapper {
+            Some(db_wrapper) => db_wrapper.remove_column_family(),
+            None => self.inverted_index.clear_mmap(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        self.inverted_index.files()
     }
 
     fn flusher(&self) -> Flusher {
-        self.db_wrapper.flusher()
+        match &self.db_wrapper {
+            Some(db_wrapper) => db_wrapper.flusher(),
+            None => self.inverted_index.mmap_flusher(),
+        }
     }
 
     fn filter(
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self.inverted_index.filter(&parsed_query));
         }
         Err(OperationError::service_error("failed to filter"))
@@ -219,8 +364,7 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> OperationResult<CardinalityEstimation> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+        if let Some(parsed_query) = self.parse_condition(condition) {
             return Ok(self
                 .inverted_index
                 .estimate_cardinality(&parsed_query, condition));
@@ -275,6 +419,10 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+            on_disk: None,
         };
 
         {
@@ -360,4 +508,241 @@ mod tests {
             assert_eq!(index.count_indexed_points(), 2);
         }
     }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_phrase_matching(#[case] immutable: bool) {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("Welcome to New York, the city that never sleeps"),
+            serde_json::json!("York is a city in England, it is not that new"),
+            serde_json::json!(["The new office is in York", "New York office is closed"]),
+            serde_json::json!(["It is new", "York is old"]),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+            on_disk: None,
+        };
+
+        let phrase_request = |phrase: &str, slop: Option<u32>| {
+            FieldCondition::new_match(JsonPath::new("text"), Match::new_phrase(phrase, slop))
+        };
+        let check_index = |index: &FullTextIndex| {
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("new york", None))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0, 2]);
+
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("York, new", None))
+                .unwrap()
+                .collect();
+            assert!(search_res.is_empty());
+
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("city never", Some(1)))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0]);
+
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("new office york", Some(2)))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![2]);
+
+            // Tokens of different values are not close to each other
+            let search_res: Vec<_> = index
+                .filter(&phrase_request("new york", Some(10)))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0, 2]);
+
+            let cardinality = index
+                .estimate_cardinality(&phrase_request("new york", None))
+                .unwrap();
+            assert_eq!(cardinality.min, 0);
+            assert_eq!(cardinality.max, 4);
+
+            let parsed_query = index.parse_phrase_query("that never sleeps", None);
+            assert!(index.check_match(&parsed_query, 0));
+            assert!(!index.check_match(&parsed_query, 1));
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+            check_index(&index);
+
+            let mmap_path = temp_dir.path().join("mmap");
+            let mut mmap_index = FullTextIndex::new_mmap(&mmap_path, config.clone());
+            mmap_index.build_from(&index).unwrap();
+            check_index(&mmap_index);
+
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config, "text", !immutable);
+        assert!(index.load().unwrap());
+        check_index(&index);
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_bm25_document_length(#[case] immutable: bool) {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("fox fox fox dog"),
+            serde_json::json!("fox cat dog"),
+            serde_json::json!(["cat", "bird"]),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+            on_disk: None,
+        };
+
+        let check_index = |index: &FullTextIndex, removed: bool| {
+            let mut statistics = TextStatistics {
+                queries: vec!["dog".to_string(), "fox".to_string()],
+                ..Default::default()
+            };
+            index.fill_text_statistics(&mut statistics);
+            // Repeated tokens are counted in the document length
+            let (points_count, total_length) = if removed { (2, 7) } else { (3, 9) };
+            assert_eq!(statistics.points_count, points_count);
+            assert_eq!(statistics.total_length, total_length);
+            assert_eq!(statistics.document_frequencies["dog"], 2);
+            assert_eq!(statistics.document_frequencies["fox"], 2);
+
+            let scores = index.bm25_scores("dog", &statistics);
+            assert!(!scores.contains_key(&2));
+            // Same frequency of the token in a document with less token occurrences
+            assert!(scores[&1] > scores[&0]);
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+            check_index(&index, false);
+
+            let mmap_path = temp_dir.path().join("mmap");
+            let mut mmap_index = FullTextIndex::new_mmap(&mmap_path, config.clone());
+            mmap_index.build_from(&index).unwrap();
+            check_index(&mmap_index, false);
+            mmap_index.remove_point(2).unwrap();
+            check_index(&mmap_index, true);
+
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config, "text", !immutable);
+        assert!(index.load().unwrap());
+        check_index(&index, false);
+        index.remove_point(2).unwrap();
+        check_index(&index, true);
+    }
+
+    #[test]
+    fn test_mmap_full_text_indexing() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
+            serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
+            serde_json::json!("For the first time in a decade, technicians were not scurrying about the vitals of the giant computer"),
+            serde_json::json!("Yet now, for a day, perhaps for a week, even Multivac might celebrate the great time, and rest."),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+            on_disk: Some(true),
+        };
+        let mmap_path = temp_dir.path().join("mmap");
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+            index.recreate().unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+
+            let mut mmap_index = FullTextIndex::new_mmap(&mmap_path, config.clone());
+            assert!(!mmap_index.load().unwrap());
+            mmap_index.build_from(&index).unwrap();
+            assert_eq!(mmap_index.count_indexed_points(), payloads.len());
+
+            let search_res: Vec<_> = mmap_index
+                .filter(&filter_request("multivac"))
+                .unwrap()
+                .collect();
+            assert_eq!(search_res, vec![0, 3]);
+
+            let payload = serde_json::json!("New document");
+            assert!(mmap_index.add_point(4, &[&payload]).is_err());
+
+            mmap_index.remove_point(0).unwrap();
+            mmap_index.flusher()().unwrap();
+        }
+
+        let mut mmap_index = FullTextIndex::new_mmap(&mmap_path, config);
+        assert!(mmap_index.load().unwrap());
+        assert_eq!(mmap_index.count_indexed_points(), payloads.len() - 1);
+
+        let search_res: Vec<_> = mmap_index
+            .filter(&filter_request("multivac"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![3]);
+
+        let search_res: Vec<_> = mmap_index
+            .filter(&filter_request("giant computer"))
+            .unwrap()
+            .collect();
+        assert_eq!(search_res, vec![2]);
+
+        let parsed_query = mmap_index.parse_query("great time");
+        assert!(mmap_index.check_match(&parsed_query, 3));
+        assert!(!mmap_index.check_match(&parsed_query, 2));
+        assert!(mmap_index.values_is_empty(0));
+        assert!(!mmap_index.files().is_empty());
+
+        mmap_index.clear().unwrap();
+        assert!(!mmap_path.exists());
+    }
 }
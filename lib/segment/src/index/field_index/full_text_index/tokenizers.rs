@@ -1,6 +1,16 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
 use charabia::Tokenize;
+use rust_stemmers::{Algorithm, Stemmer};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
-use crate::data_types::index::{TextIndexParams, TokenizerType};
+use super::inverted_index::TokenPosition;
+use super::stop_words::stop_words;
+use crate::data_types::index::{
+    SnowballLanguage, StemmingAlgorithm, StopwordsInterface, TextIndexParams, TokenizerType,
+};
 
 struct WhiteSpaceTokenizer;
 
@@ -74,70 +84,226 @@ impl MultilingualTokenizer {
     }
 }
 
-pub struct Tokenizer;
+/// Splits text into tokens and normalizes them according to [`TextIndexParams`]
+///
+/// The same processing is applied to documents and queries.
+pub struct Tokenizer {
+    tokenizer_type: TokenizerType,
+    min_token_len: Option<usize>,
+    max_token_len: Option<usize>,
+    lowercase: bool,
+    ascii_folding: bool,
+    /// Normalized stop-words
+    stopwords: HashSet<String>,
+    stemmer: Option<Stemmer>,
+}
 
 impl Tokenizer {
-    fn doc_token_filter<'a, C: FnMut(&str) + 'a>(
-        config: &'a TextIndexParams,
-        mut callback: C,
-    ) -> impl FnMut(&str) + 'a {
-        move |token: &str| {
-            if config
-                .min_token_len
-                .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
-                .unwrap_or(false)
-            {
-                return;
+    pub fn new(config: &TextIndexParams) -> Self {
+        let stemmer = config.stemmer.as_ref().map(|stemmer| match stemmer {
+            StemmingAlgorithm::Snowball(params) => {
+                Stemmer::create(snowball_algorithm(params.language))
             }
-            if config
-                .max_token_len
-                .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
-                .unwrap_or(false)
-            {
-                return;
+        });
+
+        let mut tokenizer = Self {
+            tokenizer_type: config.tokenizer,
+            min_token_len: config.min_token_len,
+            max_token_len: config.max_token_len,
+            lowercase: config.lowercase.unwrap_or(true),
+            ascii_folding: config.ascii_folding.unwrap_or(false),
+            stopwords: HashSet::new(),
+            stemmer,
+        };
+
+        let mut stopwords: Vec<&str> = vec![];
+        match &config.stopwords {
+            None => {}
+            Some(StopwordsInterface::Language(language)) => {
+                stopwords.extend(stop_words(*language));
             }
-            if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
-            } else {
-                callback(token);
+            Some(StopwordsInterface::Set(set)) => {
+                for language in set.languages.iter().flatten() {
+                    stopwords.extend(stop_words(*language));
+                }
+                stopwords.extend(set.custom.iter().flatten().map(String::as_str));
             }
         }
+        tokenizer.stopwords = stopwords
+            .into_iter()
+            .map(|word| tokenizer.normalize(word).into_owned())
+            .collect();
+
+        tokenizer
+    }
+
+    /// Apply lowercase and ASCII folding, if enabled
+    fn normalize<'a>(&self, token: &'a str) -> Cow<'a, str> {
+        let mut token = Cow::Borrowed(token);
+        if self.lowercase {
+            token = Cow::Owned(token.to_lowercase());
+        }
+        if self.ascii_folding {
+            token = fold_to_ascii(token);
+        }
+        token
+    }
+
+    /// Normalize and stem the word, stop-words are filtered out
+    fn process_word<'a>(&self, word: &'a str) -> Option<Cow<'a, str>> {
+        let word = self.normalize(word);
+        if self.stopwords.contains(word.as_ref()) {
+            return None;
+        }
+        match &self.stemmer {
+            Some(stemmer) => Some(Cow::Owned(stemmer.stem(&word).into_owned())),
+            None => Some(word),
+        }
     }
 
-    pub fn tokenize_doc<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
-        match config.tokenizer {
-            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
-            TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
-            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
-            TokenizerType::Prefix => PrefixTokenizer::tokenize(
-                text,
-                config.min_token_len.unwrap_or(1),
-                config.max_token_len.unwrap_or(usize::MAX),
-                token_filter,
-            ),
+    fn is_allowed_length(&self, token: &str) -> bool {
+        if self
+            .min_token_len
+            .map(|min_len| token.len() < min_len && token.chars().count() < min_len)
+            .unwrap_or(false)
+        {
+            return false;
         }
+        if self
+            .max_token_len
+            .map(|max_len| token.len() > max_len && token.chars().count() > max_len)
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        true
+    }
+
+    /// Tokenize document text, reporting the position of each token in the text.
+    ///
+    /// Position is the index of the word the token was produced from,
+    /// so all prefixes of a word share the position of the word.
+    /// Filtered out tokens still take their position.
+    pub fn tokenize_doc<C: FnMut(&str, TokenPosition)>(&self, text: &str, callback: C) {
+        self.tokenize(text, false, callback)
     }
 
-    pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
-        match config.tokenizer {
-            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
-            TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
-            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
-            TokenizerType::Prefix => PrefixTokenizer::tokenize_query(
-                text,
-                config.max_token_len.unwrap_or(usize::MAX),
-                token_filter,
-            ),
+    /// Tokenize query text, positions are reported the same way as in [`Self::tokenize_doc`]
+    pub fn tokenize_query<C: FnMut(&str, TokenPosition)>(&self, text: &str, callback: C) {
+        self.tokenize(text, true, callback)
+    }
+
+    fn tokenize<C: FnMut(&str, TokenPosition)>(&self, text: &str, is_query: bool, mut callback: C) {
+        let mut position: TokenPosition = 0;
+        let word_callback = |word: &str| {
+            if let Some(word) = self.process_word(word) {
+                let mut token_callback = |token: &str| {
+                    if self.is_allowed_length(token) {
+                        callback(token, position);
+                    }
+                };
+                match self.tokenizer_type {
+                    TokenizerType::Prefix if is_query => PrefixTokenizer::tokenize_query(
+                        &word,
+                        self.max_token_len.unwrap_or(usize::MAX),
+                        &mut token_callback,
+                    ),
+                    TokenizerType::Prefix => PrefixTokenizer::tokenize(
+                        &word,
+                        self.min_token_len.unwrap_or(1),
+                        self.max_token_len.unwrap_or(usize::MAX),
+                        &mut token_callback,
+                    ),
+                    TokenizerType::Whitespace
+                    | TokenizerType::Word
+                    | TokenizerType::Multilingual => token_callback(&word),
+                }
+            }
+            position += 1;
+        };
+        match self.tokenizer_type {
+            TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, word_callback),
+            TokenizerType::Word | TokenizerType::Prefix => {
+                WordTokenizer::tokenize(text, word_callback)
+            }
+            TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, word_callback),
         }
     }
 }
 
+fn snowball_algorithm(language: SnowballLanguage) -> Algorithm {
+    match language {
+        SnowballLanguage::Arabic => Algorithm::Arabic,
+        SnowballLanguage::Danish => Algorithm::Danish,
+        SnowballLanguage::Dutch => Algorithm::Dutch,
+        SnowballLanguage::English => Algorithm::English,
+        SnowballLanguage::Finnish => Algorithm::Finnish,
+        SnowballLanguage::French => Algorithm::French,
+        SnowballLanguage::German => Algorithm::German,
+        SnowballLanguage::Greek => Algorithm::Greek,
+        SnowballLanguage::Hungarian => Algorithm::Hungarian,
+        SnowballLanguage::Italian => Algorithm::Italian,
+        SnowballLanguage::Norwegian => Algorithm::Norwegian,
+        SnowballLanguage::Portuguese => Algorithm::Portuguese,
+        SnowballLanguage::Romanian => Algorithm::Romanian,
+        SnowballLanguage::Russian => Algorithm::Russian,
+        SnowballLanguage::Spanish => Algorithm::Spanish,
+        SnowballLanguage::Swedish => Algorithm::Swedish,
+        SnowballLanguage::Tamil => Algorithm::Tamil,
+        SnowballLanguage::Turkish => Algorithm::Turkish,
+    }
+}
+
+/// Replace accented latin characters with their ASCII equivalents, e.g. "ação" -> "acao".
+///
+/// Characters without ASCII equivalent are kept as is.
+fn fold_to_ascii(token: Cow<str>) -> Cow<str> {
+    if token.is_ascii() {
+        return token;
+    }
+    let mut folded = String::with_capacity(token.len());
+    for c in token.chars() {
+        match c {
+            _ if c.is_ascii() => folded.push(c),
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("AE"),
+            'œ' => folded.push_str("oe"),
+            'Œ' => folded.push_str("OE"),
+            'þ' => folded.push_str("th"),
+            'Þ' => folded.push_str("TH"),
+            'ø' => folded.push('o'),
+            'Ø' => folded.push('O'),
+            'ł' => folded.push('l'),
+            'Ł' => folded.push('L'),
+            'đ' | 'ð' => folded.push('d'),
+            'Đ' | 'Ð' => folded.push('D'),
+            'ı' => folded.push('i'),
+            _ => {
+                // Decompose the character and drop diacritical marks
+                let base: String = std::iter::once(c)
+                    .nfd()
+                    .filter(|c| !is_combining_mark(*c))
+                    .collect();
+                if !base.is_empty() && base.is_ascii() {
+                    folded.push_str(&base);
+                } else {
+                    folded.push(c);
+                }
+            }
+        }
+    }
+    Cow::Owned(folded)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
-    use crate::data_types::index::TextIndexType;
+    use crate::data_types::index::{
+        Language, Snowball, SnowballParams, StopwordsSet, TextIndexType,
+    };
 
     #[test]
     fn test_whitespace_tokenizer() {
@@ -246,17 +412,18 @@ mod tests {
     fn test_tokenizer() {
         let text = "Hello, Мир!";
         let mut tokens = Vec::new();
-        Tokenizer::tokenize_doc(
-            text,
-            &TextIndexParams {
-                r#type: TextIndexType::Text,
-                tokenizer: TokenizerType::Prefix,
-                min_token_len: Some(1),
-                max_token_len: Some(4),
-                lowercase: Some(true),
-            },
-            |token| tokens.push(token.to_owned()),
-        );
+        Tokenizer::new(&TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(1),
+            max_token_len: Some(4),
+            lowercase: Some(true),
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+            on_disk: None,
+        })
+        .tokenize_doc(text, |token, _| tokens.push(token.to_owned()));
         eprintln!("tokens = {tokens:#?}");
         assert_eq!(tokens.len(), 7);
         assert_eq!(tokens.first(), Some(&"h".to_owned()));
@@ -267,4 +434,92 @@ mod tests {
         assert_eq!(tokens.get(5), Some(&"ми".to_owned()));
         assert_eq!(tokens.get(6), Some(&"мир".to_owned()));
     }
+
+    #[test]
+    fn test_tokenizer_positions() {
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(2),
+            max_token_len: Some(3),
+            lowercase: Some(true),
+            ascii_folding: None,
+            stopwords: None,
+            stemmer: None,
+            on_disk: None,
+        };
+        let tokenizer = Tokenizer::new(&config);
+
+        let mut tokens = Vec::new();
+        tokenizer.tokenize_doc("Hello, a Мир!", |token, position| {
+            tokens.push((token.to_owned(), position))
+        });
+        assert_eq!(
+            tokens,
+            vec![
+                ("he".to_owned(), 0),
+                ("hel".to_owned(), 0),
+                ("ми".to_owned(), 2),
+                ("мир".to_owned(), 2),
+            ]
+        );
+
+        let mut tokens = Vec::new();
+        tokenizer.tokenize_query("Hello, a Мир!", |token, position| {
+            tokens.push((token.to_owned(), position))
+        });
+        assert_eq!(tokens, vec![("hel".to_owned(), 0), ("мир".to_owned(), 2)]);
+    }
+
+    #[test]
+    fn test_tokenizer_normalization() {
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: Some(true),
+            ascii_folding: Some(true),
+            stopwords: Some(StopwordsInterface::Set(StopwordsSet {
+                languages: Some(BTreeSet::from([Language::English])),
+                custom: Some(BTreeSet::from(["Café".to_owned()])),
+            })),
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: Snowball::Snowball,
+                language: SnowballLanguage::English,
+            })),
+            on_disk: None,
+        };
+        let tokenizer = Tokenizer::new(&config);
+
+        let mut tokens = Vec::new();
+        tokenizer.tokenize_doc(
+            "The cafe is Opening new Zürich in Weiß",
+            |token, position| tokens.push((token.to_owned(), position)),
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                ("open".to_owned(), 3),
+                ("new".to_owned(), 4),
+                ("zurich".to_owned(), 5),
+                ("weiss".to_owned(), 7),
+            ]
+        );
+
+        // Same processing is applied to queries
+        let mut tokens = Vec::new();
+        tokenizer.tokenize_query("opens", |token, _| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["open".to_owned()]);
+    }
+
+    #[test]
+    fn test_ascii_folding() {
+        assert_eq!(fold_to_ascii("ação".into()), "acao");
+        assert_eq!(fold_to_ascii("Ærøskøbing".into()), "AEroskobing");
+        assert_eq!(fold_to_ascii("Zürich".into()), "Zurich");
+        // Non-latin characters are not changed
+        assert_eq!(fold_to_ascii("йод".into()), "йод");
+        assert_eq!(fold_to_ascii("ascii".into()), "ascii");
+    }
 }
@@ -0,0 +1,334 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::storage_version::{StorageVersion, VERSION_FILE};
+
+use super::mutable_geo_index::MutableGeoMapIndex;
+use crate::common::mmap_type::{MmapSlice, MmapType};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::geo_hash::{encode_max_precision, GeoHash, GEOHASH_MAX_LENGTH};
+use crate::index::field_index::mmap_point_to_values::{
+    combine_flushers, create_index_dir, create_mmap_slice, create_mmap_type,
+    is_current_index_version, open_mmap_slice, open_mmap_type, MmapIndexVersion, MmapPointToValues,
+};
+use crate::types::GeoPoint;
+
+const STATUS_FILE: &str = "status.dat";
+const COUNTS_PER_HASH_FILE: &str = "counts_per_hash.bin";
+const POINTS_MAP_HASHES_FILE: &str = "points_map_hashes.bin";
+const POINTS_MAP_NAME: &str = "points_map";
+const POINT_TO_VALUES_NAME: &str = "point_to_values";
+
+/// Geo-hash padded with zeros to the max length.
+/// Zero padding keeps the lexicographic order of the original hashes.
+type MmapGeoHash = [u8; GEOHASH_MAX_LENGTH];
+
+fn encode_hash(hash: &str) -> MmapGeoHash {
+    let mut result = [0; GEOHASH_MAX_LENGTH];
+    result[..hash.len()].copy_from_slice(hash.as_bytes());
+    result
+}
+
+fn hash_as_str(hash: &MmapGeoHash) -> &str {
+    let len = hash.iter().position(|&c| c == 0).unwrap_or(hash.len());
+    // Geo-hashes are ASCII only
+    std::str::from_utf8(&hash[..len]).unwrap_or_default()
+}
+
+#[repr(C)]
+struct MmapGeoIndexStatus {
+    points_count: usize,
+    points_values_count: usize,
+    max_values_per_point: usize,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Counts {
+    hash: MmapGeoHash,
+    points: u32,
+    values: u32,
+}
+
+struct Storage {
+    status: MmapType<MmapGeoIndexStatus>,
+    /// Sorted by hash
+    counts_per_hash: MmapSlice<Counts>,
+    /// Sorted hashes of `points_map`
+    points_map_hashes: MmapSlice<MmapGeoHash>,
+    /// Hash id -> points
+    points_map: MmapPointToValues<PointOffsetType>,
+    point_to_values: MmapPointToValues<GeoPoint>,
+}
+
+/// Read-only geo index, stored in memory mapped files
+///
+/// Only removal of points is supported.
+pub struct MmapGeoMapIndex {
+    path: PathBuf,
+    storage: Option<Storage>,
+}
+
+impl MmapGeoMapIndex {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+        }
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        if !is_current_index_version(&self.path)? {
+            return Ok(false);
+        }
+
+        self.storage = Some(Storage {
+            status: open_mmap_type(&self.path.join(STATUS_FILE))?,
+            counts_per_hash: open_mmap_slice(&self.path.join(COUNTS_PER_HASH_FILE))?,
+            points_map_hashes: open_mmap_slice(&self.path.join(POINTS_MAP_HASHES_FILE))?,
+            points_map: MmapPointToValues::open(&self.path, POINTS_MAP_NAME)?,
+            point_to_values: MmapPointToValues::open(&self.path, POINT_TO_VALUES_NAME)?,
+        });
+        Ok(true)
+    }
+
+    /// Write all data of the `mutable` index into this one
+    pub fn build(&mut self, mutable: &MutableGeoMapIndex) -> OperationResult<()> {
+        create_index_dir(&self.path)?;
+
+        let empty_counts = |hash: &GeoHash| Counts {
+            hash: encode_hash(hash),
+            points: 0,
+            values: 0,
+        };
+        let mut counts_per_hash: BTreeMap<&GeoHash, Counts> = Default::default();
+        for (hash, &points) in &mutable.points_per_hash {
+            counts_per_hash
+                .entry(hash)
+                .or_insert_with(|| empty_counts(hash))
+                .points = points as u32;
+        }
+        for (hash, &values) in &mutable.values_per_hash {
+            counts_per_hash
+                .entry(hash)
+                .or_insert_with(|| empty_counts(hash))
+                .values = values as u32;
+        }
+
+        let points_map: Vec<_> = mutable
+            .points_map
+            .iter()
+            .filter(|(_, points)| !points.is_empty())
+            .collect();
+        let points_map_hashes = points_map.iter().map(|(hash, _)| encode_hash(hash));
+        let postings: Vec<Vec<PointOffsetType>> = points_map
+            .iter()
+            .map(|(_, points)| points.iter().copied().collect())
+            .collect();
+
+        let status = MmapGeoIndexStatus {
+            points_count: mutable.points_count,
+            points_values_count: mutable.points_values_count,
+            max_values_per_point: mutable.max_values_per_point,
+        };
+
+        self.storage = Some(Storage {
+            status: create_mmap_type(&self.path.join(STATUS_FILE), status)?,
+            counts_per_hash: create_mmap_slice(
+                &self.path.join(COUNTS_PER_HASH_FILE),
+                counts_per_hash.into_values(),
+            )?,
+            points_map_hashes: create_mmap_slice(
+                &self.path.join(POINTS_MAP_HASHES_FILE),
+                points_map_hashes,
+            )?,
+            points_map: MmapPointToValues::create(&self.path, POINTS_MAP_NAME, &postings)?,
+            point_to_values: MmapPointToValues::create(
+                &self.path,
+                POINT_TO_VALUES_NAME,
+                &mutable.point_to_values,
+            )?,
+        });
+
+        // Version is saved last, so that incomplete index is rebuilt on load
+        MmapIndexVersion::save(&self.path)?;
+        Ok(())
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        drop(self.storage);
+        if self.path.exists() {
+            std::fs::remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![
+            self.path.join(VERSION_FILE),
+            self.path.join(STATUS_FILE),
+            self.path.join(COUNTS_PER_HASH_FILE),
+            self.path.join(POINTS_MAP_HASHES_FILE),
+        ];
+        files.extend(storage.points_map.files());
+        files.extend(storage.point_to_values.files());
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => combine_flushers(vec![
+                storage.status.flusher(),
+                storage.counts_per_hash.flusher(),
+                storage.points_map.flusher(),
+                storage.point_to_values.flusher(),
+            ]),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    fn status(&self) -> Option<&MmapGeoIndexStatus> {
+        self.storage.as_ref().map(|storage| &*storage.status)
+    }
+
+    pub fn points_count(&self) -> usize {
+        self.status().map_or(0, |status| status.points_count)
+    }
+
+    pub fn points_values_count(&self) -> usize {
+        self.status().map_or(0, |status| status.points_values_count)
+    }
+
+    pub fn max_values_per_point(&self) -> usize {
+        self.status()
+            .map_or(0, |status| status.max_values_per_point)
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
+        self.storage.as_ref()?.point_to_values.get_values(idx)
+    }
+
+    fn counts_per_hash(&self) -> &[Counts] {
+        self.storage
+            .as_ref()
+            .map(|storage| &storage.counts_per_hash[..])
+            .unwrap_or_default()
+    }
+
+    fn get_counts(&self, hash: &str) -> Option<&Counts> {
+        let counts_per_hash = self.counts_per_hash();
+        let hash = encode_hash(hash);
+        let index = counts_per_hash
+            .binary_search_by(|counts| counts.hash.cmp(&hash))
+            .ok()?;
+        Some(&counts_per_hash[index])
+    }
+
+    pub fn get_points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
+        self.counts_per_hash()
+            .iter()
+            .map(|counts| (hash_as_str(&counts.hash).into(), counts.points as usize))
+    }
+
+    pub fn get_points_of_hash(&self, hash: &GeoHash) -> usize {
+        self.get_counts(hash)
+            .map_or(0, |counts| counts.points as usize)
+    }
+
+    pub fn get_values_of_hash(&self, hash: &GeoHash) -> usize {
+        self.get_counts(hash)
+            .map_or(0, |counts| counts.values as usize)
+    }
+
+    pub fn get_stored_sub_regions(
+        &self,
+        geo: &GeoHash,
+    ) -> impl Iterator<Item = &[PointOffsetType]> + '_ {
+        let prefix = geo.to_string();
+        let encoded = encode_hash(geo);
+        let storage = self.storage.as_ref();
+        let hashes = storage
+            .map(|storage| &storage.points_map_hashes[..])
+            .unwrap_or_default();
+        let start_index = hashes.binary_search(&encoded).unwrap_or_else(|index| index);
+        (start_index..hashes.len())
+            .take_while(move |&index| hash_as_str(&hashes[index]).starts_with(prefix.as_str()))
+            .filter_map(move |index| storage?.points_map.get_values(index as PointOffsetType))
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+
+        let removed_geo_points = storage.point_to_values.remove_point(idx);
+        if removed_geo_points.is_empty() {
+            return Ok(());
+        }
+
+        storage.status.points_count -= 1;
+        storage.status.points_values_count -= removed_geo_points.len();
+        let mut removed_geo_hashes = Vec::with_capacity(removed_geo_points.len());
+
+        for removed_geo_point in removed_geo_points {
+            let removed_geo_hash: GeoHash =
+                encode_max_precision(removed_geo_point.lon, removed_geo_point.lat).unwrap();
+
+            let encoded = encode_hash(&removed_geo_hash);
+            if let Ok(index) = storage.points_map_hashes.binary_search(&encoded) {
+                storage
+                    .points_map
+                    .remove_value(index as PointOffsetType, &idx);
+            } else {
+                log::warn!(
+                    "Geo index error: no points for hash {} was found",
+                    removed_geo_hash
+                );
+            }
+
+            for i in 0..=removed_geo_hash.len() {
+                Self::decrement_counts(storage, &removed_geo_hash[0..i], |counts| {
+                    &mut counts.values
+                });
+            }
+            removed_geo_hashes.push(removed_geo_hash);
+        }
+
+        let mut seen_hashes: HashSet<&str> = Default::default();
+        for geo_hash in &removed_geo_hashes {
+            for i in 0..=geo_hash.len() {
+                let sub_geo_hash = &geo_hash[0..i];
+                if seen_hashes.insert(sub_geo_hash) {
+                    Self::decrement_counts(storage, sub_geo_hash, |counts| &mut counts.points);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn decrement_counts(
+        storage: &mut Storage,
+        hash: &str,
+        counter: impl FnOnce(&mut Counts) -> &mut u32,
+    ) {
+        let encoded = encode_hash(hash);
+        let Ok(index) = storage
+            .counts_per_hash
+            .binary_search_by(|counts| counts.hash.cmp(&encoded))
+        else {
+            debug_assert!(false, "Hash count is not found for hash: {}", hash);
+            return;
+        };
+        let count = counter(&mut storage.counts_per_hash[index]);
+        if *count > 0 {
+            *count -= 1;
+        } else {
+            debug_assert!(false, "Hash count is already empty: {}", hash);
+        }
+    }
+}
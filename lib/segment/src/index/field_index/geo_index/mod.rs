@@ -1,4 +1,5 @@
 use std::cmp::{max, min};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -9,6 +10,7 @@ use rocksdb::DB;
 use serde_json::Value;
 
 use self::immutable_geo_index::ImmutableGeoMapIndex;
+use self::mmap_geo_index::MmapGeoMapIndex;
 use self::mutable_geo_index::MutableGeoMapIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
@@ -25,6 +27,7 @@ use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{FieldCondition, GeoPoint, PayloadKeyType};
 
 pub mod immutable_geo_index;
+pub mod mmap_geo_index;
 pub mod mutable_geo_index;
 
 /// Max number of sub-regions computed for an input geo query
@@ -34,6 +37,7 @@ const GEO_QUERY_MAX_REGION: usize = 12;
 pub enum GeoMapIndex {
     Mutable(MutableGeoMapIndex),
     Immutable(ImmutableGeoMapIndex),
+    Mmap(Box<MmapGeoMapIndex>),
 }
 
 impl GeoMapIndex {
@@ -46,10 +50,15 @@ impl GeoMapIndex {
         }
     }
 
-    fn db_wrapper(&self) -> &DatabaseColumnScheduledDeleteWrapper {
+    pub fn new_mmap(path: &Path) -> Self {
+        GeoMapIndex::Mmap(Box::new(MmapGeoMapIndex::new(path)))
+    }
+
+    fn db_wrapper(&self) -> Option<&DatabaseColumnScheduledDeleteWrapper> {
         match self {
-            GeoMapIndex::Mutable(index) => index.db_wrapper(),
-            GeoMapIndex::Immutable(index) => index.db_wrapper(),
+            GeoMapIndex::Mutable(index) => Some(index.db_wrapper()),
+            GeoMapIndex::Immutable(index) => Some(index.db_wrapper()),
+            GeoMapIndex::Mmap(_) => None,
         }
     }
 
@@ -57,6 +66,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_count,
             GeoMapIndex::Immutable(index) => index.points_count,
+            GeoMapIndex::Mmap(index) => index.points_count(),
         }
     }
 
@@ -64,6 +74,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.points_values_count,
             GeoMapIndex::Immutable(index) => index.points_values_count,
+            GeoMapIndex::Mmap(index) => index.points_values_count(),
         }
     }
 
@@ -76,6 +87,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.max_values_per_point,
             GeoMapIndex::Immutable(index) => index.max_values_per_point,
+            GeoMapIndex::Mmap(index) => index.max_values_per_point(),
         }
     }
 
@@ -83,6 +95,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_points_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_points_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_points_of_hash(hash),
         }
     }
 
@@ -90,6 +103,7 @@ impl GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values_of_hash(hash),
             GeoMapIndex::Immutable(index) => index.get_values_of_hash(hash),
+            GeoMapIndex::Mmap(index) => index.get_values_of_hash(hash),
         }
     }
 
@@ -98,7 +112,27 @@ impl GeoMapIndex {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.db_wrapper().recreate_column_family()
+        match self.db_wrapper() {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
+    }
+
+    /// Fill the on-disk index with the data of another index.
+    pub fn build_from(&mut self, other: &GeoMapIndex) -> OperationResult<()> {
+        match (self, other) {
+            (GeoMapIndex::Mmap(index), GeoMapIndex::Mutable(other)) => index.build(other),
+            _ => Err(OperationError::service_error(
+                "Only on-disk geo index can be built from mutable geo index",
+            )),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            GeoMapIndex::Mutable(_) | GeoMapIndex::Immutable(_) => vec![],
+            GeoMapIndex::Mmap(index) => index.files(),
+        }
     }
 
     fn encode_db_key(value: &str, idx: PointOffsetType) -> String {
@@ -143,13 +177,18 @@ impl GeoMapIndex {
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.db_wrapper().flusher()
+        match self {
+            GeoMapIndex::Mutable(index) => index.db_wrapper().flusher(),
+            GeoMapIndex::Immutable(index) => index.db_wrapper().flusher(),
+            GeoMapIndex::Mmap(index) => index.flusher(),
+        }
     }
 
     pub fn get_values(&self, idx: PointOffsetType) -> Option<&[GeoPoint]> {
         match self {
             GeoMapIndex::Mutable(index) => index.get_values(idx),
             GeoMapIndex::Immutable(index) => index.get_values(idx),
+            GeoMapIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -231,6 +270,16 @@ impl GeoMapIndex {
                     })
                     .unique(),
             ),
+            GeoMapIndex::Mmap(index) => Box::new(
+                values
+                    .into_iter()
+                    .flat_map(|top_geo_hash| {
+                        index
+                            .get_stored_sub_regions(&top_geo_hash)
+                            .flat_map(|points| points.iter().copied())
+                    })
+                    .unique(),
+            ),
         }
     }
 
@@ -238,17 +287,23 @@ impl GeoMapIndex {
     fn get_large_hashes(
         &self,
         threshold: usize,
-    ) -> Box<dyn Iterator<Item = (&GeoHash, usize)> + '_> {
+    ) -> Box<dyn Iterator<Item = (GeoHash, usize)> + '_> {
         let filter_condition =
             |(hash, size): &(&GeoHash, usize)| *size > threshold && !hash.is_empty();
         let mut large_regions = match self {
             GeoMapIndex::Mutable(index) => index
                 .get_points_per_hash()
                 .filter(filter_condition)
+                .map(|(hash, size)| (hash.clone(), size))
                 .collect_vec(),
             GeoMapIndex::Immutable(index) => index
                 .get_points_per_hash()
                 .filter(filter_condition)
+                .map(|(hash, size)| (hash.clone(), size))
+                .collect_vec(),
+            GeoMapIndex::Mmap(index) => index
+                .get_points_per_hash()
+                .filter(|(hash, size)| *size > threshold && !hash.is_empty())
                 .collect_vec(),
         };
 
@@ -286,7 +341,7 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<GeoPoint>) -> OperationResult<()> {
         match self {
             GeoMapIndex::Mutable(index) => index.add_many_geo_points(id, &values),
-            GeoMapIndex::Immutable(_) => Err(OperationError::service_error(
+            GeoMapIndex::Immutable(_) | GeoMapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable geo index",
             )),
         }
@@ -311,6 +366,7 @@ impl ValueIndexer<GeoPoint> for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.remove_point(id),
             GeoMapIndex::Immutable(index) => index.remove_point(id),
+            GeoMapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -324,11 +380,20 @@ impl PayloadFieldIndex for GeoMapIndex {
         match self {
             GeoMapIndex::Mutable(index) => index.load(),
             GeoMapIndex::Immutable(index) => index.load(),
+            GeoMapIndex::Mmap(index) => index.load(),
         }
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper().remove_column_family()
+        match self {
+            GeoMapIndex::Mutable(index) => index.db_wrapper().remove_column_family(),
+            GeoMapIndex::Immutable(index) => index.db_wrapper().remove_column_family(),
+            GeoMapIndex::Mmap(index) => index.clear(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        GeoMapIndex::files(self)
     }
 
     fn flusher(&self) -> Flusher {
@@ -444,7 +509,7 @@ impl PayloadFieldIndex for GeoMapIndex {
                 .map(move |(geo_hash, size)| PayloadBlockCondition {
                     condition: FieldCondition::new_geo_bounding_box(
                         key.clone(),
-                        geo_hash_to_box(geo_hash),
+                        geo_hash_to_box(&geo_hash),
                     ),
                     cardinality: size,
                 }),
@@ -1215,4 +1280,67 @@ mod tests {
         // Only LOS_ANGELES is in the bounding box
         assert_eq!(point_offsets, vec![2]);
     }
+
+    #[test]
+    fn test_mmap_geo_index() {
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let index = build_random_index(1000, 5, true);
+        let mmap_path = temp_dir.path().join("mmap");
+
+        let mut mmap_index = GeoMapIndex::new_mmap(&mmap_path);
+        assert!(!mmap_index.load().unwrap());
+        mmap_index.build_from(&index).unwrap();
+
+        assert_eq!(mmap_index.points_count(), 1000);
+        assert_eq!(mmap_index.points_values_count(), 5000);
+        assert_eq!(
+            mmap_index.get_large_hashes(100).collect_vec(),
+            index.get_large_hashes(100).collect_vec(),
+        );
+
+        let geo_radius = GeoRadius {
+            center: NYC,
+            radius: 500_000.0,
+        };
+        let condition = condition_for_geo_radius("test", geo_radius);
+        let expected = index.filter(&condition).unwrap().sorted().collect_vec();
+        assert!(!expected.is_empty());
+        assert_eq!(
+            mmap_index
+                .filter(&condition)
+                .unwrap()
+                .sorted()
+                .collect_vec(),
+            expected,
+        );
+        let estimation = mmap_index.estimate_cardinality(&condition).unwrap();
+        let expected_estimation = index.estimate_cardinality(&condition).unwrap();
+        assert_eq!(estimation.min, expected_estimation.min);
+        assert_eq!(estimation.exp, expected_estimation.exp);
+        assert_eq!(estimation.max, expected_estimation.max);
+
+        let removed = expected[0];
+        mmap_index.remove_point(removed).unwrap();
+        assert_eq!(mmap_index.values_count(removed), 0);
+        mmap_index.flusher()().unwrap();
+        drop(mmap_index);
+
+        let mut mmap_index = GeoMapIndex::new_mmap(&mmap_path);
+        assert!(mmap_index.load().unwrap());
+        assert_eq!(mmap_index.points_count(), 999);
+        assert_eq!(mmap_index.points_values_count(), 4995);
+        assert_eq!(mmap_index.get_points_of_hash(&Default::default()), 999);
+        assert_eq!(
+            mmap_index
+                .filter(&condition)
+                .unwrap()
+                .sorted()
+                .collect_vec(),
+            expected[1..],
+        );
+
+        assert!(!mmap_index.files().is_empty());
+        mmap_index.clear().unwrap();
+        assert!(!mmap_path.exists());
+    }
 }
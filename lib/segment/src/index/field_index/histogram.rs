@@ -2,21 +2,26 @@ use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::Bound::{Excluded, Included, Unbounded};
 use std::ops::Bound;
+use std::path::Path;
 
+use io::file_operations::{atomic_save_json, read_json};
 use itertools::Itertools;
 use num_traits::{Num, Signed};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
+use crate::common::operation_error::OperationResult;
 use crate::index::field_index::utils::check_boundaries;
 
 const MIN_BUCKET_SIZE: usize = 10;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Counts {
     pub left: usize,
     pub right: usize,
 }
 
-#[derive(PartialEq, PartialOrd, Debug, Clone)]
+#[derive(PartialEq, PartialOrd, Debug, Clone, Serialize, Deserialize)]
 pub struct Point<T> {
     pub val: T,
     pub idx: usize,
@@ -35,7 +40,9 @@ impl<T: PartialOrd + Copy> Ord for Point<T> {
 
 /// A trait that should represent common properties of integer and floating point types.
 /// In particular, i64 and f64.
-pub trait Numericable: Num + Signed + PartialEq + PartialOrd + Copy {
+pub trait Numericable:
+    Num + Signed + PartialEq + PartialOrd + Copy + Serialize + DeserializeOwned
+{
     fn min_value() -> Self;
     fn max_value() -> Self;
     fn to_f64(self) -> f64;
@@ -103,7 +110,7 @@ impl Numericable for f64 {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Histogram<T: Numericable + PartialEq + PartialOrd + Copy> {
     max_bucket_size: usize,
     precision: f64,
@@ -111,6 +118,42 @@ pub struct Histogram<T: Numericable + PartialEq + PartialOrd + Copy> {
     borders: BTreeMap<Point<T>, Counts>,
 }
 
+/// Stored representation of the [`Histogram`], borders are kept as a list of pairs
+#[derive(Serialize, Deserialize)]
+struct StoredHistogram<T> {
+    max_bucket_size: usize,
+    precision: f64,
+    total_count: usize,
+    borders: Vec<(Point<T>, Counts)>,
+}
+
+impl<T: Numericable> Histogram<T> {
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        let stored = StoredHistogram {
+            max_bucket_size: self.max_bucket_size,
+            precision: self.precision,
+            total_count: self.total_count,
+            borders: self
+                .borders
+                .iter()
+                .map(|(point, counts)| (point.clone(), counts.clone()))
+                .collect(),
+        };
+        atomic_save_json(path, &stored)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        let stored: StoredHistogram<T> = read_json(path)?;
+        Ok(Self {
+            max_bucket_size: stored.max_bucket_size,
+            precision: stored.precision,
+            total_count: stored.total_count,
+            borders: stored.borders.into_iter().collect(),
+        })
+    }
+}
+
 impl<T: Numericable> Histogram<T> {
     pub fn new(max_bucket_size: usize, precision: f64) -> Self {
         assert!(precision < 1.0);
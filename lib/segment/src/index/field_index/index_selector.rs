@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use parking_lot::RwLock;
@@ -13,12 +14,20 @@ use crate::json_path::JsonPath;
 use crate::types::{FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams};
 
 /// Selects index types based on field type
+///
+/// Indexes of non-appendable segments are stored in memory mapped files under `path`,
+/// if it is requested by the `payload_schema`. Other indexes are stored in RocksDB.
 pub fn index_selector(
     field: &JsonPath,
     payload_schema: &PayloadFieldSchema,
     db: Arc<RwLock<DB>>,
+    path: &Path,
     is_appendable: bool,
 ) -> Vec<FieldIndex> {
+    if !is_appendable && payload_schema.is_on_disk() {
+        return mmap_index_selector(field, payload_schema, path);
+    }
+
     let field: String = field.to_string();
     let field = field.as_str();
 
@@ -62,5 +71,65 @@ pub fn index_selector(
                 NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
             )]
         }
+        PayloadSchemaParams::Uuid(_) => vec![FieldIndex::UuidMapIndex(MapIndex::new(
+            db,
+            field,
+            is_appendable,
+        ))],
+    }
+}
+
+/// Selects on-disk index types based on field type
+///
+/// Returned indexes are empty, they have to be either loaded or built from in-memory indexes.
+fn mmap_index_selector(
+    field: &JsonPath,
+    payload_schema: &PayloadFieldSchema,
+    path: &Path,
+) -> Vec<FieldIndex> {
+    let index_path = |kind: &str| -> PathBuf { path.join(format!("{}-{kind}", field.filename())) };
+
+    match payload_schema.expand().as_ref() {
+        PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(MapIndex::new_mmap(
+            &index_path("map"),
+        ))],
+        PayloadSchemaParams::Integer(integer_params) => {
+            let lookup = integer_params
+                .lookup
+                .then(|| FieldIndex::IntMapIndex(MapIndex::new_mmap(&index_path("map"))));
+            let range = integer_params.range.then(|| {
+                FieldIndex::IntIndex(NumericIndex::<IntPayloadType>::new_mmap(&index_path(
+                    "numeric",
+                )))
+            });
+            lookup.into_iter().chain(range).collect()
+        }
+        PayloadSchemaParams::Float(_) => {
+            vec![FieldIndex::FloatIndex(
+                NumericIndex::<FloatPayloadType>::new_mmap(&index_path("numeric")),
+            )]
+        }
+        PayloadSchemaParams::Geo(_) => vec![FieldIndex::GeoIndex(GeoMapIndex::new_mmap(
+            &index_path("geo"),
+        ))],
+        PayloadSchemaParams::Text(text_index_params) => {
+            vec![FieldIndex::FullTextIndex(FullTextIndex::new_mmap(
+                &index_path("fts"),
+                text_index_params.clone(),
+            ))]
+        }
+        PayloadSchemaParams::Bool(_) => {
+            vec![FieldIndex::BinaryIndex(BinaryIndex::new_mmap(&index_path(
+                "binary",
+            )))]
+        }
+        PayloadSchemaParams::Datetime(_) => {
+            vec![FieldIndex::DatetimeIndex(
+                NumericIndex::<IntPayloadType>::new_mmap(&index_path("numeric")),
+            )]
+        }
+        PayloadSchemaParams::Uuid(_) => vec![FieldIndex::UuidMapIndex(MapIndex::new_mmap(
+            &index_path("map"),
+        ))],
     }
 }
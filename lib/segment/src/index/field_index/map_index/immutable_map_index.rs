@@ -73,7 +73,8 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     /// Removes `idx` from values-to-points-container.
     /// It is implemented by shrinking the range of values-to-points by one and moving the removed element
     /// out of the range.
-    /// Previously last element is swapped with the removed one and then the range is shrank by one.
+    /// Elements after the removed one are shifted left, so that points of each value stay sorted,
+    /// and then the range is shrank by one.
     ///
     ///
     /// Example:
@@ -112,11 +113,11 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
             return;
         };
 
-        // Finds the index of `idx` in values-to-points map and swaps it with the last element.
+        // Finds the index of `idx` in values-to-points map and moves it to the end of the list.
         // So that removed element is out of the shrank range.
         if let Some(pos) = values.iter().position(|&x| x == idx) {
-            // remove `idx` from values-to-points map by swapping it with the last element
-            values.swap(pos, values.len() - 1);
+            // remove `idx` from values-to-points map, keeping the order of the remaining points
+            values[pos..].rotate_left(1);
         }
 
         if Self::shrink_value_range(value_to_points, value) {
@@ -229,6 +230,24 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         }
     }
 
+    /// Smallest range of point offsets, which contains all points with the given value
+    pub fn get_points_range<Q>(&self, value: &Q) -> Range<PointOffsetType>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let Some(range) = self.value_to_points.get(value) else {
+            return 0..0;
+        };
+        // Points of each value are sorted
+        let points = &self.value_to_points_container[range.start as usize..range.end as usize];
+        match (points.first(), points.last()) {
+            (Some(&first), Some(&last)) => first..last + 1,
+            _ => 0..0,
+        }
+    }
+
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.value_to_points.keys())
     }
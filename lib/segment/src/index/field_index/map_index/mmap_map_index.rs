@@ -0,0 +1,296 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::iter;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use common::types::PointOffsetType;
+use io::storage_version::{StorageVersion, VERSION_FILE};
+
+use super::mutable_map_index::MutableMapIndex;
+use crate::common::mmap_type::MmapType;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::mmap_point_to_values::{
+    combine_flushers, create_index_dir, create_mmap_type, is_current_index_version, open_mmap_type,
+    MmapIndexVersion, MmapPointToValues,
+};
+
+const STATUS_FILE: &str = "status.dat";
+const KEYS_NAME: &str = "keys";
+const POSTINGS_NAME: &str = "postings";
+const POINT_TO_VALUES_NAME: &str = "point_to_values";
+
+#[repr(C)]
+struct MmapMapIndexStatus {
+    indexed_points: usize,
+    values_count: usize,
+    unique_values_count: usize,
+}
+
+struct Storage {
+    status: MmapType<MmapMapIndexStatus>,
+    /// Value id -> value string, sorted by the value string
+    keys: MmapPointToValues<u8>,
+    /// Value id -> points
+    postings: MmapPointToValues<PointOffsetType>,
+    /// Point -> value ids
+    point_to_values: MmapPointToValues<u32>,
+}
+
+/// Read-only map index, stored in memory mapped files
+///
+/// Values are kept as their string representation, so that all value types share the same layout.
+/// Only removal of points is supported.
+pub struct MmapMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
+    path: PathBuf,
+    storage: Option<Storage>,
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Hash + Eq + Clone + Display + FromStr + Default> MmapMapIndex<N> {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn status_path(&self) -> PathBuf {
+        self.path.join(STATUS_FILE)
+    }
+
+    pub fn load(&mut self) -> OperationResult<bool> {
+        if !is_current_index_version(&self.path)? {
+            return Ok(false);
+        }
+
+        self.storage = Some(Storage {
+            status: open_mmap_type(&self.status_path())?,
+            keys: MmapPointToValues::open(&self.path, KEYS_NAME)?,
+            postings: MmapPointToValues::open(&self.path, POSTINGS_NAME)?,
+            point_to_values: MmapPointToValues::open(&self.path, POINT_TO_VALUES_NAME)?,
+        });
+        Ok(true)
+    }
+
+    /// Write all data of the `mutable` index into this one
+    pub fn build(&mut self, mutable: &MutableMapIndex<N>) -> OperationResult<()> {
+        create_index_dir(&self.path)?;
+
+        let mut entries: Vec<_> = mutable
+            .map
+            .iter()
+            .filter(|(_, points)| !points.is_empty())
+            .map(|(value, points)| (value.to_string(), value, points))
+            .collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        let value_ids: HashMap<&N, u32> = entries
+            .iter()
+            .enumerate()
+            .map(|(id, (_, value, _))| (*value, id as u32))
+            .collect();
+
+        let keys: Vec<&[u8]> = entries.iter().map(|(key, _, _)| key.as_bytes()).collect();
+        let postings: Vec<Vec<PointOffsetType>> = entries
+            .iter()
+            .map(|(_, _, points)| points.iter().copied().collect())
+            .collect();
+        let point_to_values: Vec<Vec<u32>> = mutable
+            .point_to_values
+            .iter()
+            .map(|values| values.iter().map(|value| value_ids[value]).collect())
+            .collect();
+
+        let status = MmapMapIndexStatus {
+            indexed_points: mutable.indexed_points,
+            values_count: mutable.values_count,
+            unique_values_count: entries.len(),
+        };
+
+        self.storage = Some(Storage {
+            status: create_mmap_type(&self.status_path(), status)?,
+            keys: MmapPointToValues::create(&self.path, KEYS_NAME, &keys)?,
+            postings: MmapPointToValues::create(&self.path, POSTINGS_NAME, &postings)?,
+            point_to_values: MmapPointToValues::create(
+                &self.path,
+                POINT_TO_VALUES_NAME,
+                &point_to_values,
+            )?,
+        });
+
+        // Version is saved last, so that incomplete index is rebuilt on load
+        MmapIndexVersion::save(&self.path)?;
+        Ok(())
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        drop(self.storage);
+        if self.path.exists() {
+            std::fs::remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![self.path.join(VERSION_FILE), self.status_path()];
+        files.extend(storage.keys.files());
+        files.extend(storage.postings.files());
+        files.extend(storage.point_to_values.files());
+        files
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => combine_flushers(vec![
+                storage.status.flusher(),
+                storage.postings.flusher(),
+                storage.point_to_values.flusher(),
+            ]),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    pub fn get_indexed_points(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.indexed_points)
+    }
+
+    pub fn get_values_count(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.values_count)
+    }
+
+    pub fn get_unique_values_count(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.unique_values_count)
+    }
+
+    /// Binary search of the value id by its string representation
+    fn find_value_id(&self, value: &str) -> Option<u32> {
+        let keys = &self.storage.as_ref()?.keys;
+        let (mut low, mut high) = (0, keys.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match keys.get_values(mid as u32)?.cmp(value.as_bytes()) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Some(mid as u32),
+            }
+        }
+        None
+    }
+
+    fn get_points(&self, value_id: u32) -> &[PointOffsetType] {
+        self.storage
+            .as_ref()
+            .and_then(|storage| storage.postings.get_values(value_id))
+            .unwrap_or_default()
+    }
+
+    fn parse_value(&self, value_id: u32) -> Option<N> {
+        let key = self.storage.as_ref()?.keys.get_values(value_id)?;
+        N::from_str(std::str::from_utf8(key).ok()?).ok()
+    }
+
+    pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
+    where
+        Q: ?Sized + Display,
+    {
+        let points = self.get_points(self.find_value_id(&value.to_string())?);
+        (!points.is_empty()).then_some(points.len())
+    }
+
+    pub fn get_iterator<Q>(&self, value: &Q) -> Box<dyn Iterator<Item = PointOffsetType> + '_>
+    where
+        Q: ?Sized + Display,
+    {
+        match self.find_value_id(&value.to_string()) {
+            Some(value_id) => Box::new(self.get_points(value_id).iter().copied()),
+            None => Box::new(iter::empty::<PointOffsetType>()),
+        }
+    }
+
+    /// Smallest range of point offsets, which contains all points with the given value
+    pub fn get_points_range<Q>(&self, value: &Q) -> Range<PointOffsetType>
+    where
+        Q: ?Sized + Display,
+    {
+        // Postings are sorted by point offset
+        let points = match self.find_value_id(&value.to_string()) {
+            Some(value_id) => self.get_points(value_id),
+            None => &[],
+        };
+        match (points.first(), points.last()) {
+            (Some(&first), Some(&last)) => first..last + 1,
+            _ => 0..0,
+        }
+    }
+
+    pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = N> + '_> {
+        let values_count = self
+            .storage
+            .as_ref()
+            .map_or(0, |storage| storage.keys.len() as u32);
+        Box::new(
+            (0..values_count)
+                .filter(|&value_id| !self.get_points(value_id).is_empty())
+                .filter_map(|value_id| self.parse_value(value_id)),
+        )
+    }
+
+    fn get_value_ids(&self, idx: PointOffsetType) -> &[u32] {
+        self.storage
+            .as_ref()
+            .and_then(|storage| storage.point_to_values.get_values(idx))
+            .unwrap_or_default()
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Box<dyn Iterator<Item = N> + '_> {
+        Box::new(
+            self.get_value_ids(idx)
+                .iter()
+                .filter_map(|&value_id| self.parse_value(value_id)),
+        )
+    }
+
+    pub fn check_values_any(&self, idx: PointOffsetType, check_fn: impl Fn(&N) -> bool) -> bool {
+        self.get_values(idx).any(|value| check_fn(&value))
+    }
+
+    pub fn values_count(&self, idx: PointOffsetType) -> usize {
+        self.get_value_ids(idx).len()
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+
+        let removed_value_ids = storage.point_to_values.remove_point(idx);
+        if removed_value_ids.is_empty() {
+            return Ok(());
+        }
+
+        storage.status.indexed_points -= 1;
+        storage.status.values_count -= removed_value_ids.len();
+        for value_id in removed_value_ids {
+            if storage.postings.remove_value(value_id, &idx) {
+                storage.status.unique_values_count -= 1;
+            }
+        }
+        Ok(())
+    }
+}
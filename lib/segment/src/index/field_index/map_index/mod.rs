@@ -1,5 +1,7 @@
 use std::fmt::Display;
 use std::hash::{BuildHasher, Hash};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -7,11 +9,13 @@ use common::types::PointOffsetType;
 use immutable_map_index::ImmutableMapIndex;
 use indexmap::IndexSet;
 use itertools::Itertools;
+use mmap_map_index::MmapMapIndex;
 use mutable_map_index::MutableMapIndex;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 use smol_str::SmolStr;
+use uuid::Uuid;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
@@ -24,15 +28,24 @@ use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, ValueVariants,
+    PayloadKeyType, UuidIntType, ValueVariants,
 };
 
 pub mod immutable_map_index;
+pub mod mmap_map_index;
 pub mod mutable_map_index;
 
+/// Parses a UUID string into its index representation.
+///
+/// Returns `None` if the string is not a valid UUID.
+pub fn uuid_to_int(uuid: &str) -> Option<UuidIntType> {
+    Uuid::parse_str(uuid).ok().map(|uuid| uuid.as_u128())
+}
+
 pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
     Mutable(MutableMapIndex<N>),
     Immutable(ImmutableMapIndex<N>),
+    Mmap(Box<MmapMapIndex<N>>),
 }
 
 impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
@@ -44,24 +57,65 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
-    fn get_db_wrapper(&self) -> &DatabaseColumnScheduledDeleteWrapper {
+    pub fn new_mmap(path: &Path) -> Self {
+        MapIndex::Mmap(Box::new(MmapMapIndex::new(path)))
+    }
+
+    fn get_db_wrapper(&self) -> Option<&DatabaseColumnScheduledDeleteWrapper> {
         match self {
-            MapIndex::Mutable(index) => index.get_db_wrapper(),
-            MapIndex::Immutable(index) => index.get_db_wrapper(),
+            MapIndex::Mutable(index) => Some(index.get_db_wrapper()),
+            MapIndex::Immutable(index) => Some(index.get_db_wrapper()),
+            MapIndex::Mmap(_) => None,
         }
     }
 
-    fn load_from_db(&mut self) -> OperationResult<bool> {
+    pub fn load(&mut self) -> OperationResult<bool> {
         match self {
             MapIndex::Mutable(index) => index.load_from_db(),
             MapIndex::Immutable(index) => index.load_from_db(),
+            MapIndex::Mmap(index) => index.load(),
+        }
+    }
+
+    /// Fill the on-disk index with the data of another index.
+    pub fn build_from(&mut self, other: &MapIndex<N>) -> OperationResult<()> {
+        match (self, other) {
+            (MapIndex::Mmap(index), MapIndex::Mutable(other)) => index.build(other),
+            _ => Err(OperationError::service_error(
+                "Only on-disk map index can be built from mutable map index",
+            )),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            MapIndex::Mutable(_) | MapIndex::Immutable(_) => vec![],
+            MapIndex::Mmap(index) => index.files(),
         }
     }
 
-    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[N]> {
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<Box<dyn Iterator<Item = N> + '_>> {
         match self {
-            MapIndex::Mutable(index) => index.get_values(idx),
-            MapIndex::Immutable(index) => index.get_values(idx),
+            MapIndex::Mutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values.iter().cloned()) as Box<dyn Iterator<Item = N>>),
+            MapIndex::Immutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values.iter().cloned()) as Box<dyn Iterator<Item = N>>),
+            MapIndex::Mmap(index) => Some(index.get_values(idx)),
+        }
+    }
+
+    /// Check if any of the values of the point satisfies `check_fn`
+    pub fn check_values_any(&self, idx: PointOffsetType, check_fn: impl Fn(&N) -> bool) -> bool {
+        match self {
+            MapIndex::Mutable(index) => index
+                .get_values(idx)
+                .is_some_and(|values| values.iter().any(check_fn)),
+            MapIndex::Immutable(index) => index
+                .get_values(idx)
+                .is_some_and(|values| values.iter().any(check_fn)),
+            MapIndex::Mmap(index) => index.check_values_any(idx, check_fn),
         }
     }
 
@@ -69,6 +123,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_indexed_points(),
             MapIndex::Immutable(index) => index.get_indexed_points(),
+            MapIndex::Mmap(index) => index.get_indexed_points(),
         }
     }
 
@@ -76,6 +131,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_values_count(),
             MapIndex::Immutable(index) => index.get_values_count(),
+            MapIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -83,18 +139,20 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         match self {
             MapIndex::Mutable(index) => index.get_unique_values_count(),
             MapIndex::Immutable(index) => index.get_unique_values_count(),
+            MapIndex::Mmap(index) => index.get_unique_values_count(),
         }
     }
 
-    fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
+    pub fn get_points_with_value_count<Q>(&self, value: &Q) -> Option<usize>
     where
         Q: ?Sized,
         N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Eq + Display,
     {
         match self {
             MapIndex::Mutable(index) => index.get_points_with_value_count(value),
             MapIndex::Immutable(index) => index.get_points_with_value_count(value),
+            MapIndex::Mmap(index) => index.get_points_with_value_count(value),
         }
     }
 
@@ -102,18 +160,36 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     where
         Q: ?Sized,
         N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Eq + Display,
     {
         match self {
             MapIndex::Mutable(index) => index.get_iterator(value),
             MapIndex::Immutable(index) => index.get_iterator(value),
+            MapIndex::Mmap(index) => index.get_iterator(value),
+        }
+    }
+
+    /// Smallest range of point offsets, which contains all points with the given value
+    ///
+    /// Range is empty if there are no such points.
+    pub fn get_points_range<Q>(&self, value: &Q) -> Range<PointOffsetType>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq + Display,
+    {
+        match self {
+            MapIndex::Mutable(index) => index.get_points_range(value),
+            MapIndex::Immutable(index) => index.get_points_range(value),
+            MapIndex::Mmap(index) => index.get_points_range(value),
         }
     }
 
-    fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
+    pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = N> + '_> {
         match self {
-            MapIndex::Mutable(index) => index.get_values_iterator(),
-            MapIndex::Immutable(index) => index.get_values_iterator(),
+            MapIndex::Mutable(index) => Box::new(index.get_values_iterator().cloned()),
+            MapIndex::Immutable(index) => Box::new(index.get_values_iterator().cloned()),
+            MapIndex::Mmap(index) => index.get_values_iterator(),
         }
     }
 
@@ -122,18 +198,33 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        match self.get_db_wrapper() {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
+    }
+
+    fn clear_storage(self) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.get_db_wrapper().recreate_column_family(),
+            MapIndex::Immutable(index) => index.get_db_wrapper().recreate_column_family(),
+            MapIndex::Mmap(index) => index.clear(),
+        }
     }
 
     fn flusher(&self) -> Flusher {
-        self.get_db_wrapper().flusher()
+        match self {
+            MapIndex::Mutable(index) => index.get_db_wrapper().flusher(),
+            MapIndex::Immutable(index) => index.get_db_wrapper().flusher(),
+            MapIndex::Mmap(index) => index.flusher(),
+        }
     }
 
     fn match_cardinality<Q>(&self, value: &Q) -> CardinalityEstimation
     where
         Q: ?Sized,
         N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Eq + Display,
     {
         let values_count = self.get_points_with_value_count(value).unwrap_or(0);
 
@@ -171,13 +262,15 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        self.get_values(point_id).map(|x| x.len()).unwrap_or(0)
+        match self {
+            MapIndex::Mutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Immutable(index) => index.get_values(point_id).map_or(0, |x| x.len()),
+            MapIndex::Mmap(index) => index.values_count(point_id),
+        }
     }
 
     pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
-        self.get_values(point_id)
-            .map(|x| x.is_empty())
-            .unwrap_or(true)
+        self.values_count(point_id) == 0
     }
 
     /// Estimates cardinality for `except` clause
@@ -194,7 +287,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         I: std::borrow::Borrow<Q>,
         Q: ?Sized,
         N: std::borrow::Borrow<Q>,
-        Q: Hash + Eq,
+        Q: Hash + Eq + Display,
     {
         // Minimal case: we exclude as many points as possible.
         // In this case, excluded points do not have any other values except excluded ones.
@@ -300,12 +393,12 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         A: BuildHasher,
         K: std::borrow::Borrow<S> + Hash + Eq,
         N: std::borrow::Borrow<S>,
-        S: ?Sized + Hash + Eq,
+        S: ?Sized + Hash + Eq + Display,
     {
         Box::new(
             self.get_values_iterator()
-                .filter(|key| !excluded.contains((*key).borrow()))
-                .flat_map(|key| self.get_iterator(key.borrow()))
+                .filter(|key| !excluded.contains(key.borrow()))
+                .flat_map(|key| self.get_iterator::<S>(key.borrow()))
                 .unique(),
         )
     }
@@ -317,11 +410,15 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
     }
 
     fn load(&mut self) -> OperationResult<bool> {
-        self.load_from_db()
+        MapIndex::load(self)
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.clear_storage()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        MapIndex::files(self)
     }
 
     fn flusher(&self) -> Flusher {
@@ -415,10 +512,13 @@ impl PayloadFieldIndex for MapIndex<SmolStr> {
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
         Box::new(
             self.get_values_iterator()
-                .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+                .map(|value| {
+                    let count = self.get_points_with_value_count(&value).unwrap_or(0);
+                    (value, count)
+                })
                 .filter(move |(_value, count)| *count > threshold)
                 .map(move |(value, count)| PayloadBlockCondition {
-                    condition: FieldCondition::new_match(key.clone(), value.to_owned().into()),
+                    condition: FieldCondition::new_match(key.clone(), value.into()),
                     cardinality: count,
                 }),
         )
@@ -431,11 +531,15 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     }
 
     fn load(&mut self) -> OperationResult<bool> {
-        self.load_from_db()
+        MapIndex::load(self)
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.clear_storage()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        MapIndex::files(self)
     }
 
     fn flusher(&self) -> Flusher {
@@ -532,10 +636,163 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
         Box::new(
             self.get_values_iterator()
-                .map(|value| (value, self.get_points_with_value_count(value).unwrap_or(0)))
+                .map(|value| {
+                    let count = self.get_points_with_value_count(&value).unwrap_or(0);
+                    (value, count)
+                })
+                .filter(move |(_value, count)| *count >= threshold)
+                .map(move |(value, count)| PayloadBlockCondition {
+                    condition: FieldCondition::new_match(key.clone(), value.into()),
+                    cardinality: count,
+                }),
+        )
+    }
+}
+
+impl PayloadFieldIndex for MapIndex<UuidIntType> {
+    fn count_indexed_points(&self) -> usize {
+        self.get_indexed_points()
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        MapIndex::load(self)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.clear_storage()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        MapIndex::files(self)
+    }
+
+    fn flusher(&self) -> Flusher {
+        MapIndex::flusher(self)
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => match uuid_to_int(keyword) {
+                Some(uuid) => Ok(self.get_iterator(&uuid)),
+                // Not a UUID, so it can't match any indexed value
+                None => Ok(Box::new(std::iter::empty())),
+            },
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => Ok(Box::new(
+                    keywords
+                        .iter()
+                        .filter_map(|keyword| uuid_to_int(keyword))
+                        .flat_map(|uuid| self.get_iterator(&uuid))
+                        .unique(),
+                )),
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(Box::new(vec![].into_iter()))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => {
+                let excluded: IndexSet<UuidIntType> = keywords
+                    .iter()
+                    .filter_map(|keyword| uuid_to_int(keyword))
+                    .collect();
+                Ok(Box::new(
+                    self.get_values_iterator()
+                        .filter(move |uuid| !excluded.contains(uuid))
+                        .flat_map(|uuid| self.get_iterator(&uuid))
+                        .unique(),
+                ))
+            }
+            _ => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.r#match {
+            Some(Match::Value(MatchValue {
+                value: ValueVariants::Keyword(keyword),
+            })) => {
+                let mut estimation = match uuid_to_int(keyword) {
+                    Some(uuid) => self.match_cardinality(&uuid),
+                    None => CardinalityEstimation::exact(0),
+                };
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                Ok(estimation)
+            }
+            Some(Match::Any(MatchAny { any: any_variant })) => match any_variant {
+                AnyVariants::Keywords(keywords) => {
+                    let estimations = keywords
+                        .iter()
+                        .filter_map(|keyword| uuid_to_int(keyword))
+                        .map(|uuid| self.match_cardinality(&uuid))
+                        .collect::<Vec<_>>();
+                    let estimation = if estimations.is_empty() {
+                        CardinalityEstimation::exact(0)
+                    } else {
+                        combine_should_estimations(&estimations, self.get_indexed_points())
+                    };
+                    Ok(estimation
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Ok(CardinalityEstimation::exact(0)
+                            .with_primary_clause(PrimaryCondition::Condition(condition.clone())))
+                    } else {
+                        Err(OperationError::service_error(
+                            "failed to estimate cardinality",
+                        ))
+                    }
+                }
+            },
+            Some(Match::Except(MatchExcept {
+                except: AnyVariants::Keywords(keywords),
+            })) => {
+                let excluded: IndexSet<UuidIntType> = keywords
+                    .iter()
+                    .filter_map(|keyword| uuid_to_int(keyword))
+                    .collect();
+                Ok(self.except_cardinality::<UuidIntType, UuidIntType>(excluded.into_iter()))
+            }
+            _ => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            self.get_values_iterator()
+                .map(|value| {
+                    let count = self.get_points_with_value_count(&value).unwrap_or(0);
+                    (value, count)
+                })
                 .filter(move |(_value, count)| *count >= threshold)
                 .map(move |(value, count)| PayloadBlockCondition {
-                    condition: FieldCondition::new_match(key.clone(), value.to_owned().into()),
+                    condition: FieldCondition::new_match(
+                        key.clone(),
+                        Uuid::from_u128(value).to_string().into(),
+                    ),
                     cardinality: count,
                 }),
         )
@@ -546,7 +803,7 @@ impl ValueIndexer<String> for MapIndex<SmolStr> {
     fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
         match self {
             MapIndex::Mutable(index) => index.add_many_to_map(id, values),
-            MapIndex::Immutable(_) => Err(OperationError::service_error(
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
             )),
         }
@@ -563,6 +820,7 @@ impl ValueIndexer<String> for MapIndex<SmolStr> {
         match self {
             MapIndex::Mutable(index) => index.remove_point(id),
             MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -575,7 +833,7 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
     ) -> OperationResult<()> {
         match self {
             MapIndex::Mutable(index) => index.add_many_to_map(id, values),
-            MapIndex::Immutable(_) => Err(OperationError::service_error(
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
             )),
         }
@@ -592,6 +850,33 @@ impl ValueIndexer<IntPayloadType> for MapIndex<IntPayloadType> {
         match self {
             MapIndex::Mutable(index) => index.remove_point(id),
             MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
+        }
+    }
+}
+
+impl ValueIndexer<UuidIntType> for MapIndex<UuidIntType> {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<UuidIntType>) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.add_many_to_map(id, values),
+            MapIndex::Immutable(_) | MapIndex::Mmap(_) => Err(OperationError::service_error(
+                "Can't add values to immutable map index",
+            )),
+        }
+    }
+
+    fn get_value(&self, value: &Value) -> Option<UuidIntType> {
+        if let Value::String(uuid) = value {
+            return uuid_to_int(uuid);
+        }
+        None
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        match self {
+            MapIndex::Mutable(index) => index.remove_point(id),
+            MapIndex::Immutable(index) => index.remove_point(id),
+            MapIndex::Mmap(index) => index.remove_point(id),
         }
     }
 }
@@ -634,15 +919,10 @@ mod tests {
     ) -> MapIndex<N> {
         let mut index =
             MapIndex::<N>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, true);
-        index.load_from_db().unwrap();
+        index.load().unwrap();
         for (idx, values) in data.iter().enumerate() {
-            let index_values: HashSet<N> = HashSet::from_iter(
-                index
-                    .get_values(idx as PointOffsetType)
-                    .unwrap()
-                    .iter()
-                    .cloned(),
-            );
+            let index_values: HashSet<N> =
+                HashSet::from_iter(index.get_values(idx as PointOffsetType).unwrap());
             let check_values: HashSet<N> = HashSet::from_iter(values.iter().cloned());
             assert_eq!(index_values, check_values);
         }
@@ -706,6 +986,120 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[test]
+    fn test_uuid_disk_map_index() {
+        let uuids = [
+            "f47ac10b-58cc-4372-a567-0e02b2c3d479",
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            "550e8400-e29b-41d4-a716-446655440000",
+        ];
+        let data: Vec<Vec<UuidIntType>> = vec![
+            vec![
+                uuid_to_int(uuids[0]).unwrap(),
+                uuid_to_int(uuids[1]).unwrap(),
+            ],
+            vec![uuid_to_int(uuids[1]).unwrap()],
+            vec![uuid_to_int(uuids[2]).unwrap()],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let index = load_map_index(&data, temp_dir.path());
+
+        let key: PayloadKeyType = FIELD_NAME.parse().unwrap();
+        let filtered = |r#match: Match| -> HashSet<PointOffsetType> {
+            let condition = FieldCondition::new_match(key.clone(), r#match);
+            index.filter(&condition).unwrap().collect()
+        };
+
+        // Uppercase is parsed into the same UUID
+        let matched = filtered(uuids[1].to_uppercase().into());
+        assert_eq!(matched, HashSet::from([0, 1]));
+
+        let matched = filtered(Match::new_any(AnyVariants::Keywords(
+            [uuids[0], uuids[2], "not-a-uuid"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        )));
+        assert_eq!(matched, HashSet::from([0, 2]));
+
+        let matched = filtered(Match::new_except(AnyVariants::Keywords(
+            [uuids[1].to_string()].into_iter().collect(),
+        )));
+        assert_eq!(matched, HashSet::from([0, 2]));
+
+        // Not a UUID, nothing matches
+        assert!(filtered("not-a-uuid".to_string().into()).is_empty());
+
+        let estimation = index
+            .estimate_cardinality(&FieldCondition::new_match(
+                key.clone(),
+                uuids[1].to_string().into(),
+            ))
+            .unwrap();
+        assert!(estimation.equals_min_exp_max(&CardinalityEstimation::exact(2)));
+    }
+
+    #[test]
+    fn test_mmap_map_index() {
+        let data: Vec<Vec<SmolStr>> = vec![
+            vec!["AABB".into(), "UUFF".into()],
+            vec!["UUFF".into()],
+            vec![],
+            vec!["IIBB".into()],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index(&data, temp_dir.path());
+        let mutable_index = load_map_index(&data, temp_dir.path());
+
+        let mmap_path = temp_dir.path().join("mmap_index");
+        let mut index = MapIndex::<SmolStr>::new_mmap(&mmap_path);
+        index.build_from(&mutable_index).unwrap();
+
+        let key: PayloadKeyType = FIELD_NAME.parse().unwrap();
+        let filtered = |index: &MapIndex<SmolStr>, r#match: Match| -> HashSet<PointOffsetType> {
+            let condition = FieldCondition::new_match(key.clone(), r#match);
+            index.filter(&condition).unwrap().collect()
+        };
+
+        assert_eq!(
+            filtered(&index, "UUFF".to_string().into()),
+            HashSet::from([0, 1])
+        );
+        assert_eq!(index.count_indexed_points(), 3);
+
+        index.remove_point(1).unwrap();
+        assert_eq!(index.values_count(1), 0);
+        assert_eq!(index.count_indexed_points(), 2);
+        assert_eq!(
+            filtered(&index, "UUFF".to_string().into()),
+            HashSet::from([0])
+        );
+        index.flusher()().unwrap();
+        drop(index);
+
+        let mut index = MapIndex::<SmolStr>::new_mmap(&mmap_path);
+        assert!(index.load().unwrap());
+        assert_eq!(
+            filtered(&index, "UUFF".to_string().into()),
+            HashSet::from([0])
+        );
+        assert_eq!(
+            filtered(
+                &index,
+                Match::new_except(AnyVariants::Keywords(
+                    ["AABB".to_string()].into_iter().collect()
+                ))
+            ),
+            HashSet::from([0, 3])
+        );
+        assert!(index.check_values_any(0, |value| value == "AABB"));
+        assert!(!index.check_values_any(1, |value| value == "UUFF"));
+        assert!(!index.files().is_empty());
+    }
+
     #[test]
     fn test_empty_index() {
         let data: Vec<Vec<String>> = vec![];
@@ -2,6 +2,7 @@ use std::collections::{BTreeSet, HashMap};
 use std::fmt::Display;
 use std::hash::Hash;
 use std::iter;
+use std::ops::Range;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -161,6 +162,23 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
             .unwrap_or_else(|| Box::new(iter::empty::<PointOffsetType>()))
     }
 
+    /// Smallest range of point offsets, which contains all points with the given value
+    pub fn get_points_range<Q>(&self, value: &Q) -> Range<PointOffsetType>
+    where
+        Q: ?Sized,
+        N: std::borrow::Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        match self
+            .map
+            .get(value)
+            .and_then(|ids| Some((ids.first()?, ids.last()?)))
+        {
+            Some((&first, &last)) => first..last + 1,
+            None => 0..0,
+        }
+    }
+
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.map.keys())
     }
@@ -0,0 +1,332 @@
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use io::storage_version::StorageVersion;
+use memmap2::MmapMut;
+use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
+
+use crate::common::mmap_type::{MmapBitSlice, MmapSlice, MmapType};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+
+/// Version of the on-disk layout, shared by all mmap payload field indexes
+pub struct MmapIndexVersion;
+
+impl StorageVersion for MmapIndexVersion {
+    fn current_raw() -> &'static str {
+        "0.2.0"
+    }
+}
+
+/// Check that the index in `path` is fully built with the current on-disk layout
+///
+/// Returns `false` if the index has to be rebuilt: either the version file is missing,
+/// because the build was interrupted, or the index was built with a different layout.
+pub fn is_current_index_version(path: &Path) -> OperationResult<bool> {
+    match MmapIndexVersion::load(path)? {
+        None => Ok(false),
+        Some(version) if version == MmapIndexVersion::current() => Ok(true),
+        Some(version) => {
+            log::warn!(
+                "Index in {} has layout version {version}, but {} is expected, rebuilding",
+                path.display(),
+                MmapIndexVersion::current(),
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Remove files of the previous index version, if any, and create an empty directory
+///
+/// Old files are removed instead of being overwritten, because they might still be mapped.
+pub fn create_index_dir(path: &Path) -> OperationResult<()> {
+    if path.exists() {
+        std::fs::remove_dir_all(path)?;
+    }
+    std::fs::create_dir_all(path)?;
+    Ok(())
+}
+
+/// Create a file of the exact size and fill it with `values`
+///
+/// `T` must be a plain `#[repr(C)]` type without heap allocations.
+pub fn create_mmap_slice<T: Clone + 'static>(
+    path: &Path,
+    values: impl ExactSizeIterator<Item = T>,
+) -> OperationResult<MmapSlice<T>> {
+    create_and_ensure_length(path, values.len() * size_of::<T>())?;
+    let mut slice = open_mmap_slice::<T>(path)?;
+    for (item, value) in slice.iter_mut().zip(values) {
+        *item = value;
+    }
+    slice.flusher()()?;
+    Ok(slice)
+}
+
+pub fn open_mmap_slice<T: 'static>(path: &Path) -> OperationResult<MmapSlice<T>> {
+    let mmap = open_write_mmap(path)?;
+    Ok(unsafe { MmapSlice::try_from(mmap)? })
+}
+
+/// Create or open a file holding a single `#[repr(C)]` value of type `T`
+pub fn create_mmap_type<T: 'static>(path: &Path, value: T) -> OperationResult<MmapType<T>> {
+    create_and_ensure_length(path, size_of::<T>())?;
+    let mut mmap_type = open_mmap_type::<T>(path)?;
+    *mmap_type = value;
+    mmap_type.flusher()()?;
+    Ok(mmap_type)
+}
+
+pub fn open_mmap_type<T: 'static>(path: &Path) -> OperationResult<MmapType<T>> {
+    let mmap: MmapMut = open_write_mmap(path)?;
+    Ok(unsafe { MmapType::try_from(mmap)? })
+}
+
+/// Create a file with a bit for each of `bits`
+pub fn create_mmap_bitslice(
+    path: &Path,
+    bits: impl ExactSizeIterator<Item = bool>,
+) -> OperationResult<MmapBitSlice> {
+    // Bit slice is stored in `usize` words, keep at least one word to avoid empty mmap
+    let words = bits.len().div_ceil(usize::BITS as usize).max(1);
+    create_and_ensure_length(path, words * size_of::<usize>())?;
+    let mut bitslice = open_mmap_bitslice(path)?;
+    for (idx, bit) in bits.enumerate() {
+        bitslice.set(idx, bit);
+    }
+    bitslice.flusher()()?;
+    Ok(bitslice)
+}
+
+pub fn open_mmap_bitslice(path: &Path) -> OperationResult<MmapBitSlice> {
+    let mmap = open_write_mmap(path)?;
+    Ok(MmapBitSlice::try_from(mmap, 0)?)
+}
+
+pub fn combine_flushers(flushers: Vec<Flusher>) -> Flusher {
+    Box::new(move || {
+        for flusher in flushers {
+            flusher()?;
+        }
+        Ok(())
+    })
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+struct MmapRange {
+    start: u32,
+    end: u32,
+}
+
+// Flatten points-to-values map stored in memory mapped files
+// It's an on-disk analogue of `ImmutablePointToValues`: ranges and values are kept in two files.
+// This structure doesn't support adding new values, only removing.
+// It's used in mmap field indices to store points-to-values maps and posting lists.
+pub struct MmapPointToValues<T: Clone + 'static> {
+    ranges_path: PathBuf,
+    values_path: PathBuf,
+    ranges: MmapSlice<MmapRange>,
+    values: MmapSlice<T>,
+}
+
+impl<T: Clone + 'static> MmapPointToValues<T> {
+    fn paths(dir: &Path, name: &str) -> (PathBuf, PathBuf) {
+        (
+            dir.join(format!("{name}_ranges.bin")),
+            dir.join(format!("{name}.bin")),
+        )
+    }
+
+    pub fn create<V: AsRef<[T]>>(dir: &Path, name: &str, src: &[V]) -> OperationResult<Self> {
+        let (ranges_path, values_path) = Self::paths(dir, name);
+
+        let mut start = 0;
+        let ranges = src.iter().map(|values| {
+            let end = start + values.as_ref().len() as u32;
+            let range = MmapRange { start, end };
+            start = end;
+            range
+        });
+        let ranges = create_mmap_slice(&ranges_path, ranges.collect::<Vec<_>>().into_iter())?;
+
+        let values_count = src.iter().map(|values| values.as_ref().len()).sum();
+        let values = src
+            .iter()
+            .flat_map(|values| values.as_ref().iter().cloned());
+        let values = create_mmap_slice(&values_path, ExactSize::new(values, values_count))?;
+
+        Ok(Self {
+            ranges_path,
+            values_path,
+            ranges,
+            values,
+        })
+    }
+
+    pub fn open(dir: &Path, name: &str) -> OperationResult<Self> {
+        let (ranges_path, values_path) = Self::paths(dir, name);
+        Ok(Self {
+            ranges: open_mmap_slice(&ranges_path)?,
+            values: open_mmap_slice(&values_path)?,
+            ranges_path,
+            values_path,
+        })
+    }
+
+    /// Number of stored lists, including removed ones
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<&[T]> {
+        let range = self.ranges.get(idx as usize)?;
+        Some(&self.values[range.start as usize..range.end as usize])
+    }
+
+    /// Offset of the list `idx` in the flattened values of all lists
+    pub fn values_offset(&self, idx: PointOffsetType) -> Option<usize> {
+        self.ranges
+            .get(idx as usize)
+            .map(|range| range.start as usize)
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) -> Vec<T> {
+        let removed = self.get_values(idx).map(<[T]>::to_vec).unwrap_or_default();
+        if let Some(range) = self.ranges.get_mut(idx as usize) {
+            range.end = range.start;
+        }
+        removed
+    }
+
+    /// Remove a single `value` from the list `idx`
+    ///
+    /// Following values are shifted left, so that the order of the list is preserved,
+    /// and the list is shrank by one.
+    /// Returns true if the list became empty.
+    pub fn remove_value(&mut self, idx: PointOffsetType, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let Some(range) = self.ranges.get_mut(idx as usize) else {
+            return false;
+        };
+        let values = &mut self.values[range.start as usize..range.end as usize];
+        if let Some(pos) = values.iter().position(|x| x == value) {
+            values[pos..].rotate_left(1);
+            range.end -= 1;
+            return range.start == range.end;
+        }
+        false
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.ranges_path.clone(), self.values_path.clone()]
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        combine_flushers(vec![self.ranges.flusher(), self.values.flusher()])
+    }
+}
+
+/// Iterator wrapper with a known length
+struct ExactSize<I> {
+    iter: I,
+    len: usize,
+}
+
+impl<I> ExactSize<I> {
+    fn new(iter: I, len: usize) -> Self {
+        Self { iter, len }
+    }
+}
+
+impl<I: Iterator> Iterator for ExactSize<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for ExactSize<I> {}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_index_version_check() {
+        let dir = Builder::new()
+            .prefix("mmap_index_version")
+            .tempdir()
+            .unwrap();
+
+        // Incomplete index
+        assert!(!is_current_index_version(dir.path()).unwrap());
+
+        MmapIndexVersion::save(dir.path()).unwrap();
+        assert!(is_current_index_version(dir.path()).unwrap());
+
+        // Index of another layout version
+        std::fs::write(dir.path().join(io::storage_version::VERSION_FILE), "0.1.0").unwrap();
+        assert!(!is_current_index_version(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn test_mmap_point_to_values_remove() {
+        let dir = Builder::new()
+            .prefix("mmap_point_to_values")
+            .tempdir()
+            .unwrap();
+        let mut values = vec![
+            vec![0, 1, 2, 3, 4],
+            vec![5, 6, 7, 8, 9],
+            vec![],
+            vec![10, 11, 12],
+            vec![13],
+        ];
+
+        let mut point_to_values = MmapPointToValues::create(dir.path(), "test", &values).unwrap();
+
+        let check = |point_to_values: &MmapPointToValues<u32>, values: &[Vec<u32>]| {
+            assert_eq!(point_to_values.len(), values.len());
+            for (idx, values) in values.iter().enumerate() {
+                assert_eq!(
+                    point_to_values.get_values(idx as PointOffsetType),
+                    Some(values.as_slice()),
+                );
+            }
+        };
+
+        check(&point_to_values, &values);
+
+        assert_eq!(point_to_values.remove_point(1), vec![5, 6, 7, 8, 9]);
+        values[1].clear();
+        check(&point_to_values, &values);
+
+        assert!(!point_to_values.remove_value(3, &10));
+        values[3] = vec![11, 12];
+        check(&point_to_values, &values);
+
+        assert!(point_to_values.remove_value(4, &13));
+        values[4].clear();
+        check(&point_to_values, &values);
+
+        point_to_values.flusher()().unwrap();
+        drop(point_to_values);
+
+        let point_to_values = MmapPointToValues::open(dir.path(), "test").unwrap();
+        check(&point_to_values, &values);
+    }
+}
@@ -23,11 +23,12 @@ pub struct ImmutableNumericIndex<T: Encodable + Numericable + Default> {
     point_to_values: ImmutablePointToValues<T>,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub(super) struct NumericIndexKey<T> {
-    key: T,
-    idx: PointOffsetType,
-    deleted: bool,
+    pub(super) key: T,
+    pub(super) idx: PointOffsetType,
+    pub(super) deleted: bool,
 }
 
 struct NumericKeySortedVec<T: Encodable + Numericable> {
@@ -36,7 +37,7 @@ struct NumericKeySortedVec<T: Encodable + Numericable> {
 }
 
 pub(super) struct NumericKeySortedVecIterator<'a, T: Encodable + Numericable> {
-    set: &'a NumericKeySortedVec<T>,
+    data: &'a [NumericIndexKey<T>],
     start_index: usize,
     end_index: usize,
 }
@@ -117,8 +118,7 @@ impl<T: Encodable + Numericable> NumericKeySortedVec<T> {
     }
 
     fn remove(&mut self, key: NumericIndexKey<T>) -> bool {
-        if let Ok(index) = self.data.binary_search(&key) {
-            self.data[index].deleted = true;
+        if remove_key(&mut self.data, &key) {
             self.deleted_count += 1;
             true
         } else {
@@ -131,19 +131,44 @@ impl<T: Encodable + Numericable> NumericKeySortedVec<T> {
         start_bound: Bound<NumericIndexKey<T>>,
         end_bound: Bound<NumericIndexKey<T>>,
     ) -> NumericKeySortedVecIterator<'_, T> {
-        let start_index = self.find_start_index(start_bound);
-        let end_index = self.find_end_index(start_index, end_bound);
-        NumericKeySortedVecIterator {
-            set: self,
+        NumericKeySortedVecIterator::new(&self.data, start_bound, end_bound)
+    }
+}
+
+/// Mark `key` as deleted in the sorted `data`. Returns false if the key is not found.
+pub(super) fn remove_key<T: Encodable + Numericable>(
+    data: &mut [NumericIndexKey<T>],
+    key: &NumericIndexKey<T>,
+) -> bool {
+    match data.binary_search(key) {
+        Ok(index) if !data[index].deleted => {
+            data[index].deleted = true;
+            true
+        }
+        _ => false,
+    }
+}
+
+impl<'a, T: Encodable + Numericable> NumericKeySortedVecIterator<'a, T> {
+    /// Iterate over non-deleted keys of the sorted `data` within the given bounds
+    pub(super) fn new(
+        data: &'a [NumericIndexKey<T>],
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> Self {
+        let start_index = Self::find_start_index(data, start_bound);
+        let end_index = Self::find_end_index(data, start_index, end_bound);
+        Self {
+            data,
             start_index,
             end_index,
         }
     }
 
-    fn find_start_index(&self, bound: Bound<NumericIndexKey<T>>) -> usize {
+    fn find_start_index(data: &[NumericIndexKey<T>], bound: Bound<NumericIndexKey<T>>) -> usize {
         match bound {
-            Bound::Included(bound) => self.data.binary_search(&bound).unwrap_or_else(|idx| idx),
-            Bound::Excluded(bound) => match self.data.binary_search(&bound) {
+            Bound::Included(bound) => data.binary_search(&bound).unwrap_or_else(|idx| idx),
+            Bound::Excluded(bound) => match data.binary_search(&bound) {
                 Ok(idx) => idx + 1,
                 Err(idx) => idx,
             },
@@ -151,21 +176,25 @@ impl<T: Encodable + Numericable> NumericKeySortedVec<T> {
         }
     }
 
-    fn find_end_index(&self, start: usize, bound: Bound<NumericIndexKey<T>>) -> usize {
-        if start >= self.data.len() {
+    fn find_end_index(
+        data: &[NumericIndexKey<T>],
+        start: usize,
+        bound: Bound<NumericIndexKey<T>>,
+    ) -> usize {
+        if start >= data.len() {
             // the range `end` should never be less than `start`
             return start;
         }
         match bound {
-            Bound::Included(bound) => match self.data[start..].binary_search(&bound) {
+            Bound::Included(bound) => match data[start..].binary_search(&bound) {
                 Ok(idx) => idx + 1 + start,
                 Err(idx) => idx + start,
             },
             Bound::Excluded(bound) => {
-                let end_bound = self.data[start..].binary_search(&bound);
+                let end_bound = data[start..].binary_search(&bound);
                 end_bound.unwrap_or_else(|idx| idx) + start
             }
-            Bound::Unbounded => self.data.len(),
+            Bound::Unbounded => data.len(),
         }
     }
 }
@@ -175,7 +204,7 @@ impl<'a, T: Encodable + Numericable> Iterator for NumericKeySortedVecIterator<'a
 
     fn next(&mut self) -> Option<Self::Item> {
         while self.start_index < self.end_index {
-            let key = self.set.data[self.start_index].clone();
+            let key = self.data[self.start_index];
             self.start_index += 1;
             if key.deleted {
                 continue;
@@ -189,7 +218,7 @@ impl<'a, T: Encodable + Numericable> Iterator for NumericKeySortedVecIterator<'a
 impl<'a, T: Encodable + Numericable> DoubleEndedIterator for NumericKeySortedVecIterator<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         while self.start_index < self.end_index {
-            let key = self.set.data[self.end_index - 1].clone();
+            let key = self.data[self.end_index - 1];
             self.end_index -= 1;
             if key.deleted {
                 continue;
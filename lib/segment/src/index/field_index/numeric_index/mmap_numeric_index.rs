@@ -0,0 +1,248 @@
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use io::storage_version::{StorageVersion, VERSION_FILE};
+
+use super::immutable_numeric_index::{remove_key, NumericIndexKey, NumericKeySortedVecIterator};
+use super::mutable_numeric_index::MutableNumericIndex;
+use super::{Encodable, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
+use crate::common::mmap_type::{MmapSlice, MmapType};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::index::field_index::histogram::{Histogram, Numericable, Point};
+use crate::index::field_index::mmap_point_to_values::{
+    combine_flushers, create_index_dir, create_mmap_slice, create_mmap_type,
+    is_current_index_version, open_mmap_slice, open_mmap_type, MmapIndexVersion, MmapPointToValues,
+};
+
+const STATUS_FILE: &str = "status.dat";
+const PAIRS_FILE: &str = "data.bin";
+const HISTOGRAM_FILE: &str = "histogram.json";
+const POINT_TO_VALUES_NAME: &str = "point_to_values";
+
+#[repr(C)]
+struct MmapNumericIndexStatus {
+    points_count: usize,
+    max_values_per_point: usize,
+    deleted_count: usize,
+}
+
+struct Storage<T: Copy + 'static> {
+    status: MmapType<MmapNumericIndexStatus>,
+    /// Sorted `(value, point)` pairs, removed pairs are marked as deleted in place
+    pairs: MmapSlice<NumericIndexKey<T>>,
+    point_to_values: MmapPointToValues<T>,
+}
+
+/// Read-only numeric index, stored in memory mapped files
+///
+/// Only removal of points is supported. The histogram is kept in RAM
+/// and saved on flush if points were removed since the last save.
+pub struct MmapNumericIndex<T: Encodable + Numericable + Default> {
+    path: PathBuf,
+    storage: Option<Storage<T>>,
+    pub(super) histogram: Histogram<T>,
+    histogram_changed: Arc<AtomicBool>,
+}
+
+impl<T: Encodable + Numericable + Default + 'static> MmapNumericIndex<T> {
+    pub(super) fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_owned(),
+            storage: None,
+            histogram: Histogram::new(HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION),
+            histogram_changed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub(super) fn load(&mut self) -> OperationResult<bool> {
+        if !is_current_index_version(&self.path)? {
+            return Ok(false);
+        }
+
+        self.histogram = Histogram::load(&self.path.join(HISTOGRAM_FILE))?;
+        self.storage = Some(Storage {
+            status: open_mmap_type(&self.path.join(STATUS_FILE))?,
+            pairs: open_mmap_slice(&self.path.join(PAIRS_FILE))?,
+            point_to_values: MmapPointToValues::open(&self.path, POINT_TO_VALUES_NAME)?,
+        });
+        Ok(true)
+    }
+
+    /// Write all data of the `mutable` index into this one
+    pub(super) fn build(&mut self, mutable: &MutableNumericIndex<T>) -> OperationResult<()> {
+        create_index_dir(&self.path)?;
+
+        // Encoded keys are sorted in the same order as decoded ones
+        let pairs = mutable.map.keys().map(|key| NumericIndexKey::decode(key));
+
+        let status = MmapNumericIndexStatus {
+            points_count: mutable.points_count,
+            max_values_per_point: mutable.max_values_per_point,
+            deleted_count: 0,
+        };
+
+        mutable.histogram.save(&self.path.join(HISTOGRAM_FILE))?;
+        self.storage = Some(Storage {
+            status: create_mmap_type(&self.path.join(STATUS_FILE), status)?,
+            pairs: create_mmap_slice(&self.path.join(PAIRS_FILE), pairs)?,
+            point_to_values: MmapPointToValues::create(
+                &self.path,
+                POINT_TO_VALUES_NAME,
+                &mutable.point_to_values,
+            )?,
+        });
+        self.histogram = Histogram::load(&self.path.join(HISTOGRAM_FILE))?;
+
+        // Version is saved last, so that incomplete index is rebuilt on load
+        MmapIndexVersion::save(&self.path)?;
+        Ok(())
+    }
+
+    pub(super) fn clear(self) -> OperationResult<()> {
+        drop(self.storage);
+        if self.path.exists() {
+            std::fs::remove_dir_all(&self.path)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn files(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.storage else {
+            return vec![];
+        };
+        let mut files = vec![
+            self.path.join(VERSION_FILE),
+            self.path.join(STATUS_FILE),
+            self.path.join(PAIRS_FILE),
+            self.path.join(HISTOGRAM_FILE),
+        ];
+        files.extend(storage.point_to_values.files());
+        files
+    }
+
+    pub(super) fn flusher(&self) -> Flusher {
+        match &self.storage {
+            Some(storage) => combine_flushers(vec![
+                storage.status.flusher(),
+                storage.pairs.flusher(),
+                storage.point_to_values.flusher(),
+                self.histogram_flusher(),
+            ]),
+            None => Box::new(|| Ok(())),
+        }
+    }
+
+    fn histogram_flusher(&self) -> Flusher {
+        if !self.histogram_changed.swap(false, Ordering::Relaxed) {
+            return Box::new(|| Ok(()));
+        }
+
+        let histogram = self.histogram.clone();
+        let histogram_changed = self.histogram_changed.clone();
+        let path = self.path.join(HISTOGRAM_FILE);
+        Box::new(move || {
+            histogram.save(&path).inspect_err(|_| {
+                // Retry on the next flush
+                histogram_changed.store(true, Ordering::Relaxed);
+            })
+        })
+    }
+
+    pub(super) fn points_count(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.points_count)
+    }
+
+    pub(super) fn max_values_per_point(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, |storage| storage.status.max_values_per_point)
+    }
+
+    pub(super) fn get_values(&self, idx: PointOffsetType) -> Option<&[T]> {
+        self.storage.as_ref()?.point_to_values.get_values(idx)
+    }
+
+    pub(super) fn get_values_count(&self) -> usize {
+        self.storage.as_ref().map_or(0, |storage| {
+            storage.pairs.len() - storage.status.deleted_count
+        })
+    }
+
+    fn pairs(&self) -> &[NumericIndexKey<T>] {
+        self.storage
+            .as_ref()
+            .map(|storage| &storage.pairs[..])
+            .unwrap_or_default()
+    }
+
+    pub(super) fn values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> impl Iterator<Item = PointOffsetType> + '_ {
+        NumericKeySortedVecIterator::new(self.pairs(), start_bound, end_bound)
+            .map(|NumericIndexKey { idx, .. }| idx)
+    }
+
+    pub(super) fn orderable_values_range(
+        &self,
+        start_bound: Bound<NumericIndexKey<T>>,
+        end_bound: Bound<NumericIndexKey<T>>,
+    ) -> impl DoubleEndedIterator<Item = (T, PointOffsetType)> + '_ {
+        NumericKeySortedVecIterator::new(self.pairs(), start_bound, end_bound)
+            .map(|NumericIndexKey { key, idx, .. }| (key, idx))
+    }
+
+    pub(super) fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(());
+        };
+
+        let removed_values = storage.point_to_values.remove_point(idx);
+        if removed_values.is_empty() {
+            return Ok(());
+        }
+
+        storage.status.points_count -= 1;
+        for value in removed_values {
+            let key = NumericIndexKey::new(value, idx);
+            if remove_key(&mut storage.pairs, &key) {
+                storage.status.deleted_count += 1;
+                let pairs: &[NumericIndexKey<T>] = &storage.pairs;
+                self.histogram.remove(
+                    &key.into(),
+                    |point| Self::get_histogram_left_neighbor(pairs, point),
+                    |point| Self::get_histogram_right_neighbor(pairs, point),
+                );
+                self.histogram_changed.store(true, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_histogram_left_neighbor(
+        pairs: &[NumericIndexKey<T>],
+        point: &Point<T>,
+    ) -> Option<Point<T>> {
+        let key: NumericIndexKey<T> = point.clone().into();
+        NumericKeySortedVecIterator::new(pairs, Bound::Unbounded, Bound::Excluded(key))
+            .next_back()
+            .map(|key| key.into())
+    }
+
+    fn get_histogram_right_neighbor(
+        pairs: &[NumericIndexKey<T>],
+        point: &Point<T>,
+    ) -> Option<Point<T>> {
+        let key: NumericIndexKey<T> = point.clone().into();
+        NumericKeySortedVecIterator::new(pairs, Bound::Excluded(key), Bound::Unbounded)
+            .next()
+            .map(|key| key.into())
+    }
+}
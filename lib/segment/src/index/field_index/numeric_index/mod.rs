@@ -1,4 +1,5 @@
 mod immutable_numeric_index;
+mod mmap_numeric_index;
 mod mutable_numeric_index;
 
 #[cfg(test)]
@@ -7,11 +8,13 @@ mod tests;
 use std::cmp::{max, min};
 use std::ops::Bound;
 use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use chrono::DateTime;
 use common::types::PointOffsetType;
+use mmap_numeric_index::MmapNumericIndex;
 use mutable_numeric_index::MutableNumericIndex;
 use parking_lot::RwLock;
 use rocksdb::DB;
@@ -141,6 +144,7 @@ impl<T: Encodable + Numericable> Range<T> {
 pub enum NumericIndex<T: Encodable + Numericable + Default> {
     Mutable(MutableNumericIndex<T>),
     Immutable(ImmutableNumericIndex<T>),
+    Mmap(Box<MmapNumericIndex<T>>),
 }
 
 impl<T: Encodable + Numericable + Default> NumericIndex<T> {
@@ -152,10 +156,15 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         }
     }
 
-    fn get_db_wrapper(&self) -> &DatabaseColumnScheduledDeleteWrapper {
+    pub fn new_mmap(path: &Path) -> Self {
+        NumericIndex::Mmap(Box::new(MmapNumericIndex::new(path)))
+    }
+
+    fn get_db_wrapper(&self) -> Option<&DatabaseColumnScheduledDeleteWrapper> {
         match self {
-            NumericIndex::Mutable(index) => index.get_db_wrapper(),
-            NumericIndex::Immutable(index) => index.get_db_wrapper(),
+            NumericIndex::Mutable(index) => Some(index.get_db_wrapper()),
+            NumericIndex::Immutable(index) => Some(index.get_db_wrapper()),
+            NumericIndex::Mmap(_) => None,
         }
     }
 
@@ -163,6 +172,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => &index.histogram,
             NumericIndex::Immutable(index) => &index.histogram,
+            NumericIndex::Mmap(index) => &index.histogram,
         }
     }
 
@@ -170,6 +180,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.points_count,
             NumericIndex::Immutable(index) => index.points_count,
+            NumericIndex::Mmap(index) => index.points_count(),
         }
     }
 
@@ -177,6 +188,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values_count(),
             NumericIndex::Immutable(index) => index.get_values_count(),
+            NumericIndex::Mmap(index) => index.get_values_count(),
         }
     }
 
@@ -185,24 +197,58 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
     }
 
     pub fn recreate(&self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        match self.get_db_wrapper() {
+            Some(db_wrapper) => db_wrapper.recreate_column_family(),
+            None => Ok(()),
+        }
     }
 
     pub fn load(&mut self) -> OperationResult<bool> {
         match self {
             NumericIndex::Mutable(index) => index.load(),
             NumericIndex::Immutable(index) => index.load(),
+            NumericIndex::Mmap(index) => index.load(),
+        }
+    }
+
+    /// Fill the on-disk index with the data of another index.
+    pub fn build_from(&mut self, other: &NumericIndex<T>) -> OperationResult<()> {
+        match (self, other) {
+            (NumericIndex::Mmap(index), NumericIndex::Mutable(other)) => index.build(other),
+            _ => Err(OperationError::service_error(
+                "Only on-disk numeric index can be built from mutable numeric index",
+            )),
+        }
+    }
+
+    fn clear_storage(self) -> OperationResult<()> {
+        match self {
+            NumericIndex::Mutable(index) => index.get_db_wrapper().recreate_column_family(),
+            NumericIndex::Immutable(index) => index.get_db_wrapper().recreate_column_family(),
+            NumericIndex::Mmap(index) => index.clear(),
+        }
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            NumericIndex::Mutable(_) | NumericIndex::Immutable(_) => vec![],
+            NumericIndex::Mmap(index) => index.files(),
         }
     }
 
     pub fn flusher(&self) -> Flusher {
-        self.get_db_wrapper().flusher()
+        match self {
+            NumericIndex::Mutable(index) => index.get_db_wrapper().flusher(),
+            NumericIndex::Immutable(index) => index.get_db_wrapper().flusher(),
+            NumericIndex::Mmap(index) => index.flusher(),
+        }
     }
 
     pub fn remove_point(&mut self, idx: PointOffsetType) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.remove_point(idx),
             NumericIndex::Immutable(index) => index.remove_point(idx),
+            NumericIndex::Mmap(index) => index.remove_point(idx),
         }
     }
 
@@ -210,6 +256,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.get_values(idx),
             NumericIndex::Immutable(index) => index.get_values(idx),
+            NumericIndex::Mmap(index) => index.get_values(idx),
         }
     }
 
@@ -222,6 +269,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         match self {
             NumericIndex::Mutable(index) => index.max_values_per_point,
             NumericIndex::Immutable(index) => index.max_values_per_point,
+            NumericIndex::Mmap(index) => index.max_values_per_point(),
         }
     }
 
@@ -323,7 +371,11 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.get_db_wrapper().recreate_column_family()
+        self.clear_storage()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        NumericIndex::files(self)
     }
 
     fn flusher(&self) -> Flusher {
@@ -360,6 +412,7 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
                 Box::new(index.values_range(start_bound, end_bound))
             }
             NumericIndex::Immutable(index) => Box::new(index.values_range(start_bound, end_bound)),
+            NumericIndex::Mmap(index) => Box::new(index.values_range(start_bound, end_bound)),
         })
     }
 
@@ -459,9 +512,9 @@ impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -484,9 +537,9 @@ impl ValueIndexer<DateTimePayloadType> for NumericIndex<IntPayloadType> {
             NumericIndex::Mutable(index) => {
                 index.add_many_to_list(id, values.into_iter().map(|x| x.timestamp()))
             }
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -507,9 +560,9 @@ impl ValueIndexer<FloatPayloadType> for NumericIndex<FloatPayloadType> {
     ) -> OperationResult<()> {
         match self {
             NumericIndex::Mutable(index) => index.add_many_to_list(id, values),
-            NumericIndex::Immutable(_) => Err(OperationError::service_error(
-                "Can't add values to immutable numeric index",
-            )),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => Err(
+                OperationError::service_error("Can't add values to immutable numeric index"),
+            ),
         }
     }
 
@@ -553,6 +606,9 @@ where
             NumericIndex::Immutable(index) => {
                 Box::new(index.orderable_values_range(start_bound, end_bound))
             }
+            NumericIndex::Mmap(index) => {
+                Box::new(index.orderable_values_range(start_bound, end_bound))
+            }
         }
     }
 }
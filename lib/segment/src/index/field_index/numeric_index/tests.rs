@@ -35,7 +35,9 @@ fn random_index(
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(i as PointOffsetType, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         }
     }
 
@@ -43,7 +45,7 @@ fn random_index(
 
     // if immutable, we have to reload the index
     if immutable {
-        let db_ref = index.get_db_wrapper().get_database();
+        let db_ref = index.get_db_wrapper().unwrap().get_database();
         let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
         new_index.load().unwrap();
         (temp_dir, new_index)
@@ -214,14 +216,16 @@ fn test_payload_blocks_small(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
     let index = if immutable {
-        let db_ref = index.get_db_wrapper().get_database();
+        let db_ref = index.get_db_wrapper().unwrap().get_database();
         let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
         new_index.load().unwrap();
         new_index
@@ -260,12 +264,14 @@ fn test_numeric_index_load_from_disk(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
 
-    let db_ref = index.get_db_wrapper().get_database();
+    let db_ref = index.get_db_wrapper().unwrap().get_database();
     let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, !immutable);
     new_index.load().unwrap();
 
@@ -306,14 +312,16 @@ fn test_numeric_index(#[case] immutable: bool) {
             NumericIndex::Mutable(index) => index
                 .add_many_to_list(idx as PointOffsetType + 1, values)
                 .unwrap(),
-            NumericIndex::Immutable(_) => unreachable!("index is mutable"),
+            NumericIndex::Immutable(_) | NumericIndex::Mmap(_) => {
+                unreachable!("index is mutable")
+            }
         });
 
     index.flusher()().unwrap();
 
     // if immutable, we have to reload the index
     let index = if immutable {
-        let db_ref = index.get_db_wrapper().get_database();
+        let db_ref = index.get_db_wrapper().unwrap().get_database();
         let mut new_index: NumericIndex<f64> = NumericIndex::new(db_ref, COLUMN_NAME, false);
         new_index.load().unwrap();
         new_index
@@ -414,3 +422,67 @@ fn test_empty_cardinality(#[case] immutable: bool) {
         },
     );
 }
+
+#[test]
+fn test_mmap_numeric_index() {
+    let (temp_dir, index) = random_index(1000, 2, false);
+    let mmap_path = temp_dir.path().join("mmap");
+
+    let mut mmap_index: NumericIndex<f64> = NumericIndex::new_mmap(&mmap_path);
+    assert!(!mmap_index.load().unwrap());
+    mmap_index.build_from(&index).unwrap();
+
+    let query = Range {
+        lt: Some(60.0),
+        gt: None,
+        gte: Some(10.0),
+        lte: None,
+    };
+    let condition = FieldCondition::new_range(JsonPath::new("unused"), query.clone());
+    let expected = index.filter(&condition).unwrap().sorted().collect_vec();
+    assert_eq!(
+        mmap_index
+            .filter(&condition)
+            .unwrap()
+            .sorted()
+            .collect_vec(),
+        expected,
+    );
+    cardinality_request(&mmap_index, query.clone());
+
+    for idx in 0..1000 {
+        assert_eq!(mmap_index.get_values(idx), index.get_values(idx));
+    }
+
+    mmap_index.remove_point(42).unwrap();
+    assert_eq!(mmap_index.get_values(42), Some(&[][..]));
+    assert_eq!(mmap_index.get_points_count(), 999);
+    mmap_index.flusher()().unwrap();
+    drop(mmap_index);
+
+    let mut mmap_index: NumericIndex<f64> = NumericIndex::new_mmap(&mmap_path);
+    assert!(mmap_index.load().unwrap());
+    assert_eq!(mmap_index.get_points_count(), 999);
+    assert_eq!(mmap_index.get_values_count(), 1998);
+    let filtered = mmap_index.filter(&condition).unwrap().collect_vec();
+    assert!(!filtered.contains(&42));
+    assert!(filtered.iter().all(|idx| expected.contains(idx)));
+    assert_eq!(mmap_index.get_histogram().get_total_count(), 1998);
+
+    // Histogram must be persisted after removals
+    for idx in 100..600 {
+        mmap_index.remove_point(idx).unwrap();
+    }
+    mmap_index.flusher()().unwrap();
+    drop(mmap_index);
+
+    let mut mmap_index: NumericIndex<f64> = NumericIndex::new_mmap(&mmap_path);
+    assert!(mmap_index.load().unwrap());
+    assert_eq!(mmap_index.get_values_count(), 998);
+    assert_eq!(mmap_index.get_histogram().get_total_count(), 998);
+    cardinality_request(&mmap_index, query);
+
+    assert!(!mmap_index.files().is_empty());
+    mmap_index.clear().unwrap();
+    assert!(!mmap_path.exists());
+}
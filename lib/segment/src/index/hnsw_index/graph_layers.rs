@@ -89,6 +89,60 @@ pub trait GraphLayersBase {
         }
     }
 
+    /// Greedy search for closest points within a single graph layer, ACORN-style.
+    ///
+    /// Neighbors which don't match the filter are not scored, but their own neighbors are
+    /// considered instead. So the search can step over filtered-out points, which would
+    /// otherwise disconnect the graph under restrictive filters.
+    fn _search_on_level_acorn(
+        &self,
+        searcher: &mut SearchContext,
+        level: usize,
+        visited_list: &mut VisitedListHandle,
+        points_scorer: &mut FilteredScorer,
+    ) {
+        let limit = self.get_m(level);
+        let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut hop_ids: Vec<PointOffsetType> = Vec::with_capacity(limit);
+
+        while let Some(candidate) = searcher.candidates.pop() {
+            if candidate.score < searcher.lower_bound() {
+                break;
+            }
+
+            points_ids.clear();
+            hop_ids.clear();
+            self.links_map(candidate.idx, level, |link| {
+                if visited_list.check(link) {
+                    return;
+                }
+                if points_scorer.check_filter(link) {
+                    points_ids.push(link);
+                } else {
+                    hop_ids.push(link);
+                }
+            });
+
+            // Direct neighbors go first, fill up the rest with second hop neighbors
+            for &hop in &hop_ids {
+                if points_ids.len() >= limit {
+                    break;
+                }
+                self.links_map(hop, level, |link| {
+                    if !visited_list.check(link) && !points_ids.contains(&link) {
+                        points_ids.push(link);
+                    }
+                });
+            }
+
+            let scores = points_scorer.score_points(&mut points_ids, limit);
+            scores.iter().copied().for_each(|score_point| {
+                searcher.process_candidate(score_point);
+                visited_list.check_and_update_visited(score_point.idx);
+            });
+        }
+    }
+
     fn search_on_level(
         &self,
         level_entry: ScoredPointOffset,
@@ -104,6 +158,21 @@ pub trait GraphLayersBase {
         search_context.nearest
     }
 
+    fn search_on_level_acorn(
+        &self,
+        level_entry: ScoredPointOffset,
+        level: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        let mut visited_list = self.get_visited_list_from_pool();
+        visited_list.check_and_update_visited(level_entry.idx);
+        let mut search_context = SearchContext::new(level_entry, ef);
+
+        self._search_on_level_acorn(&mut search_context, level, &mut visited_list, points_scorer);
+        search_context.nearest
+    }
+
     /// Greedy searches for entry point of level `target_level`.
     /// Beam size is 1.
     fn search_entry(
@@ -153,9 +222,7 @@ impl<TGraphLinks: GraphLinks> GraphLayersBase for GraphLayers<TGraphLinks> {
     where
         F: FnMut(PointOffsetType),
     {
-        for link in self.links.links(point_id, level) {
-            f(*link);
-        }
+        self.links.links(point_id, level).for_each(f);
     }
 
     fn get_m(&self, level: usize) -> usize {
@@ -201,11 +268,34 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
     }
 
     pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_impl(top, ef, points_scorer, custom_entry_points, false)
+    }
+
+    /// Same as [`GraphLayers::search`], but explores two-hop neighbors on the zero level when
+    /// direct neighbors don't match the filter
+    pub fn search_acorn(
+        &self,
+        top: usize,
+        ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_impl(top, ef, points_scorer, custom_entry_points, true)
+    }
+
+    fn search_impl(
         &self,
         top: usize,
         ef: usize,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
+        acorn: bool,
     ) -> Vec<ScoredPointOffset> {
         let Some(entry_point) = self.get_entry_point(&points_scorer, custom_entry_points) else {
             return Vec::default();
@@ -217,7 +307,12 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             0,
             &mut points_scorer,
         );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
+        let ef = max(top, ef);
+        let nearest = if acorn {
+            self.search_on_level_acorn(zero_level_entry, 0, ef, &mut points_scorer)
+        } else {
+            self.search_on_level(zero_level_entry, 0, ef, &mut points_scorer)
+        };
         nearest.into_iter().take(top).collect_vec()
     }
 
@@ -14,7 +14,7 @@ use super::graph_links::GraphLinks;
 use crate::common::operation_error::OperationResult;
 use crate::index::hnsw_index::entry_points::EntryPoints;
 use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
-use crate::index::hnsw_index::graph_links::GraphLinksConverter;
+use crate::index::hnsw_index::graph_links::{GraphLinksConverter, GraphLinksFormat};
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
@@ -78,6 +78,7 @@ impl GraphLayersBuilder {
     pub fn into_graph_layers<TGraphLinks: GraphLinks>(
         self,
         path: Option<&Path>,
+        format: GraphLinksFormat,
     ) -> OperationResult<GraphLayers<TGraphLinks>> {
         let unlocker_links_layers = self
             .links_layers
@@ -85,7 +86,8 @@ impl GraphLayersBuilder {
             .map(|l| l.into_iter().map(|l| l.into_inner()).collect())
             .collect();
 
-        let mut links_converter = GraphLinksConverter::new(unlocker_links_layers);
+        let mut links_converter =
+            GraphLinksConverter::new(unlocker_links_layers).with_format(format);
         if let Some(path) = path {
             links_converter.save_as(path)?;
         }
@@ -195,6 +197,68 @@ impl GraphLayersBuilder {
         self.links_layers.len()
     }
 
+    /// Whether the point is already linked into the graph
+    pub fn is_ready(&self, point_id: PointOffsetType) -> bool {
+        self.ready_list.read()[point_id as usize]
+    }
+
+    /// Seed the graph with the links of an already built graph.
+    ///
+    /// Points of the old graph are moved to their new ids and keep their levels,
+    /// points without a new id are dropped together with the links to them.
+    ///
+    /// Returns ids of seeded points which lost some of their neighbors. They keep their
+    /// remaining links, but are not marked as ready and should be linked again to repair the graph.
+    pub fn seed_from<TGraphLinks: GraphLinks>(
+        &mut self,
+        old_graph: &GraphLayers<TGraphLinks>,
+        old_to_new: &[Option<PointOffsetType>],
+    ) -> Vec<PointOffsetType> {
+        let mut damaged_points = Vec::new();
+        let mut old_links = Vec::new();
+
+        for (old_id, new_id) in old_to_new.iter().enumerate() {
+            let Some(new_id) = *new_id else {
+                continue;
+            };
+            let old_id = old_id as PointOffsetType;
+            if old_id as usize >= old_graph.num_points() {
+                continue;
+            }
+
+            let level = old_graph.point_level(old_id);
+            self.set_levels(new_id, level);
+
+            let mut is_damaged = false;
+            for curr_level in 0..=level {
+                old_links.clear();
+                old_graph.links_map(old_id, curr_level, |link| old_links.push(link));
+
+                let links = self.links_layers[new_id as usize][curr_level].get_mut();
+                links.clear();
+                for &old_link in &old_links {
+                    match old_to_new.get(old_link as usize).copied().flatten() {
+                        Some(new_link) => links.push(new_link),
+                        None => is_damaged = true,
+                    }
+                }
+                is_damaged |= curr_level == 0 && links.is_empty();
+            }
+            if is_damaged {
+                damaged_points.push(new_id);
+                continue;
+            }
+
+            self.ready_list.get_mut().set(new_id as usize, true);
+            self.entry_points
+                .get_mut()
+                .new_point(new_id, level, |_| true);
+        }
+
+        damaged_points.sort_unstable();
+        damaged_points
+    }
+
     /// Generate random level for a new point, according to geometric distribution
     pub fn get_random_layer<R>(&self, rng: &mut R) -> usize
     where
@@ -646,7 +710,7 @@ mod tests {
         }
 
         let graph = graph_layers_builder
-            .into_graph_layers::<GraphLinksRam>(None)
+            .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
             .unwrap();
 
         let fake_filter_context = FakeFilterContext {};
@@ -681,10 +745,13 @@ mod tests {
         assert_eq!(orig_len, builder_len);
 
         for idx in 0..builder_len {
-            let links_orig = &graph_layers_orig.links.links(idx as PointOffsetType, 0);
+            let links_orig = graph_layers_orig
+                .links
+                .links(idx as PointOffsetType, 0)
+                .collect::<Vec<_>>();
             let links_builder = graph_layers_builder.links_layers[idx][0].read();
             let link_container_from_builder = links_builder.iter().copied().collect::<Vec<_>>();
-            assert_eq!(links_orig, &link_container_from_builder);
+            assert_eq!(links_orig, link_container_from_builder);
         }
 
         let main_entry = graph_layers_builder
@@ -729,7 +796,7 @@ mod tests {
         }
 
         let graph = graph_layers_builder
-            .into_graph_layers::<GraphLinksRam>(None)
+            .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
             .unwrap();
 
         let fake_filter_context = FakeFilterContext {};
@@ -765,7 +832,7 @@ mod tests {
             graph_layers_builder.link_new_point(idx, scorer);
         }
         let graph_layers = graph_layers_builder
-            .into_graph_layers::<GraphLinksRam>(None)
+            .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
             .unwrap();
 
         let num_points = graph_layers.links.num_points();
@@ -779,7 +846,7 @@ mod tests {
 
         let layers910 = graph_layers.links.point_level(910);
         let links910 = (0..layers910 + 1)
-            .map(|i| graph_layers.links.links(910, i).to_vec())
+            .map(|i| graph_layers.links.links(910, i).collect::<Vec<_>>())
             .collect::<Vec<_>>();
         eprintln!("graph_layers.links_layers[910] = {links910:#?}",);
 
@@ -11,6 +11,7 @@ use memory::{madvise, mmap_ops};
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::vector_utils::TrySetCapacityExact;
+use crate::index::hnsw_index::links_compression::{compress_links, CompressedLinksIterator};
 
 pub const MMAP_PANIC_MESSAGE: &str = "Mmap links are not loaded";
 
@@ -44,8 +45,76 @@ reindex:           142350  142350 142350 142350  (same for each level)
 
 for lvl > 0:
 links offset = level_offsets[level] + offsets[reindex[point_id]]
+
+In compressed format `flatten` is a byte array of compressed links, see `links_compression`,
+and `offsets` are byte offsets in it.
 */
 
+/// Format of links in the graph links file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GraphLinksFormat {
+    /// Links are stored as plain arrays of point ids
+    #[default]
+    Plain,
+    /// Links are sorted, delta-encoded and bit-packed
+    Compressed,
+}
+
+impl GraphLinksFormat {
+    /// Files saved before the format version was introduced have zero in its place
+    fn version(self) -> u64 {
+        match self {
+            GraphLinksFormat::Plain => 0,
+            GraphLinksFormat::Compressed => 1,
+        }
+    }
+
+    fn from_version(version: u64) -> OperationResult<Self> {
+        match version {
+            0 => Ok(GraphLinksFormat::Plain),
+            1 => Ok(GraphLinksFormat::Compressed),
+            _ => Err(OperationError::service_error(format!(
+                "Unsupported HNSW links format version: {version}"
+            ))),
+        }
+    }
+
+    /// Size in bytes of a single unit of `total_links_len`
+    fn links_unit_size(self) -> usize {
+        match self {
+            GraphLinksFormat::Plain => size_of::<PointOffsetType>(),
+            GraphLinksFormat::Compressed => size_of::<u8>(),
+        }
+    }
+}
+
+/// Links of a single point on a single level
+#[derive(Debug, Clone)]
+pub enum LinksIterator<'a> {
+    Plain(std::slice::Iter<'a, PointOffsetType>),
+    Compressed(CompressedLinksIterator<'a>),
+}
+
+impl<'a> Iterator for LinksIterator<'a> {
+    type Item = PointOffsetType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            LinksIterator::Plain(iter) => iter.next().copied(),
+            LinksIterator::Compressed(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            LinksIterator::Plain(iter) => iter.size_hint(),
+            LinksIterator::Compressed(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for LinksIterator<'a> {}
+
 #[derive(Debug, Default)]
 struct GraphLinksFileHeader {
     pub point_count: u64,
@@ -53,6 +122,7 @@ struct GraphLinksFileHeader {
     pub total_links_len: u64,
     pub total_offsets_len: u64,
     pub offsets_padding: u64,
+    pub version: u64,
 }
 
 fn get_reindex_slice<'a>(
@@ -70,6 +140,10 @@ fn get_links_slice<'a>(data: &'a [u8], header: &'a GraphLinksFileHeader) -> &'a
     mmap_ops::transmute_from_u8_to_slice(links_byte_slice)
 }
 
+fn get_compressed_links_slice<'a>(data: &'a [u8], header: &'a GraphLinksFileHeader) -> &'a [u8] {
+    &data[header.get_links_range()]
+}
+
 fn get_offsets_iter<'a>(
     data: &'a [u8],
     header: &'a GraphLinksFileHeader,
@@ -96,23 +170,28 @@ impl GraphLinksFileHeader {
         levels_count: usize,
         total_links_len: usize,
         total_offsets_len: usize,
+        format: GraphLinksFormat,
     ) -> GraphLinksFileHeader {
-        let offsets_padding = if (point_count + total_links_len) % 2 == 0 {
-            0
-        } else {
-            4
-        };
-        GraphLinksFileHeader {
+        let mut header = GraphLinksFileHeader {
             point_count: point_count as u64,
             levels_count: levels_count as u64,
             total_links_len: total_links_len as u64,
             total_offsets_len: total_offsets_len as u64,
-            offsets_padding,
-        }
+            offsets_padding: 0,
+            version: format.version(),
+        };
+        // offsets are u64, align them to 8 bytes
+        let links_end = header.get_links_range().end;
+        header.offsets_padding = (links_end.next_multiple_of(size_of::<u64>()) - links_end) as u64;
+        header
     }
 
     pub fn raw_size() -> usize {
-        size_of::<u64>() * 5
+        size_of::<u64>() * 6
+    }
+
+    pub fn format(&self) -> OperationResult<GraphLinksFormat> {
+        GraphLinksFormat::from_version(self.version)
     }
 
     pub fn serialize_bytes_to(&self, raw_data: &mut [u8]) {
@@ -123,6 +202,7 @@ impl GraphLinksFileHeader {
         arr[2] = self.total_links_len;
         arr[3] = self.total_offsets_len;
         arr[4] = self.offsets_padding;
+        arr[5] = self.version;
     }
 
     pub fn deserialize_bytes_from(raw_data: &[u8]) -> GraphLinksFileHeader {
@@ -134,6 +214,7 @@ impl GraphLinksFileHeader {
             total_links_len: arr[2],
             total_offsets_len: arr[3],
             offsets_padding: arr[4],
+            version: arr[5],
         }
     }
 
@@ -155,7 +236,11 @@ impl GraphLinksFileHeader {
 
     pub fn get_links_range(&self) -> Range<usize> {
         let start = self.get_reindex_range().end;
-        start..start + self.total_links_len as usize * size_of::<PointOffsetType>()
+        // Unknown version is reported on load, it can't get here
+        let unit_size = GraphLinksFormat::from_version(self.version)
+            .unwrap_or_default()
+            .links_unit_size();
+        start..start + self.total_links_len as usize * unit_size
     }
 
     pub fn get_offsets_range(&self) -> Range<usize> {
@@ -170,6 +255,7 @@ pub struct GraphLinksConverter {
     back_index: Vec<usize>,
     total_links_len: usize,
     total_offsets_len: usize,
+    format: GraphLinksFormat,
     path: Option<PathBuf>,
 }
 
@@ -182,6 +268,7 @@ impl GraphLinksConverter {
                 back_index: Vec::new(),
                 total_links_len: 0,
                 total_offsets_len: 1,
+                format: GraphLinksFormat::Plain,
                 path: None,
             };
         }
@@ -214,16 +301,39 @@ impl GraphLinksConverter {
             back_index,
             total_links_len,
             total_offsets_len,
+            format: GraphLinksFormat::Plain,
             path: None,
         }
     }
 
+    /// Set format of links to serialize
+    pub fn with_format(mut self, format: GraphLinksFormat) -> Self {
+        self.format = format;
+        self.total_links_len = match format {
+            GraphLinksFormat::Plain => self.edges.iter().flatten().map(Vec::len).sum(),
+            GraphLinksFormat::Compressed => {
+                let mut buffer = Vec::new();
+                self.edges
+                    .iter()
+                    .flatten()
+                    .map(|links| {
+                        buffer.clear();
+                        compress_links(links, &mut buffer);
+                        buffer.len()
+                    })
+                    .sum()
+            }
+        };
+        self
+    }
+
     fn get_header(&self) -> GraphLinksFileHeader {
         GraphLinksFileHeader::new(
             self.reindex.len(),
             self.get_levels_count(),
             self.total_links_len,
             self.total_offsets_len,
+            self.format,
         )
     }
 
@@ -255,20 +365,32 @@ impl GraphLinksConverter {
                 .as_mut()
                 .split_at_mut(links_range.len());
             let offsets_mmap = &mut offsets_with_padding_mmap[header.offsets_padding as _..];
-            let links_mmap: &mut [PointOffsetType] =
-                mmap_ops::transmute_from_u8_to_mut_slice(links_mmap);
             let offsets_mmap: &mut [u64] = mmap_ops::transmute_from_u8_to_mut_slice(offsets_mmap);
             offsets_mmap[0] = 0;
 
+            let unit_size = self.format.links_unit_size();
+            let mut compressed_links = Vec::new();
+            // position in bytes
             let mut links_pos = 0;
             let mut offsets_pos = 1;
             for level in 0..header_levels_count {
                 level_offsets.push(offsets_pos as u64 - 1);
                 self.iterate_level_points(level, |_, links| {
-                    links_mmap[links_pos..links_pos + links.len()].copy_from_slice(links);
-                    links_pos += links.len();
-
-                    offsets_mmap[offsets_pos] = links_pos as u64;
+                    let links_bytes = match self.format {
+                        GraphLinksFormat::Plain => {
+                            mmap_ops::transmute_to_u8_slice(links.as_slice())
+                        }
+                        GraphLinksFormat::Compressed => {
+                            compressed_links.clear();
+                            compress_links(links, &mut compressed_links);
+                            compressed_links.as_slice()
+                        }
+                    };
+                    links_mmap[links_pos..links_pos + links_bytes.len()]
+                        .copy_from_slice(links_bytes);
+                    links_pos += links_bytes.len();
+
+                    offsets_mmap[offsets_pos] = (links_pos / unit_size) as u64;
                     offsets_pos += 1;
                 });
             }
@@ -344,7 +466,7 @@ pub trait GraphLinks: Default {
 
     fn levels_count(&self) -> usize;
 
-    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType];
+    fn get_links(&self, range: Range<usize>) -> LinksIterator<'_>;
 
     fn get_links_range(&self, idx: usize) -> Range<usize>;
 
@@ -354,7 +476,7 @@ pub trait GraphLinks: Default {
 
     fn num_points(&self) -> usize;
 
-    fn links(&self, point_id: PointOffsetType, level: usize) -> &[PointOffsetType] {
+    fn links(&self, point_id: PointOffsetType, level: usize) -> LinksIterator<'_> {
         if level == 0 {
             let links_range = self.get_links_range(point_id as usize);
             self.get_links(links_range)
@@ -400,10 +522,22 @@ pub trait GraphLinks: Default {
     }
 }
 
+#[derive(Debug)]
+enum GraphLinksRamData {
+    Plain(Vec<PointOffsetType>),
+    Compressed(Vec<u8>),
+}
+
+impl Default for GraphLinksRamData {
+    fn default() -> Self {
+        GraphLinksRamData::Plain(Vec::new())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct GraphLinksRam {
     // all flattened links of all levels
-    links: Vec<PointOffsetType>,
+    links: GraphLinksRamData,
     // all ranges in `links`. each range is `links[offsets[i]..offsets[i+1]]`
     // ranges are sorted by level
     offsets: Vec<u64>,
@@ -417,14 +551,26 @@ impl GraphLinksRam {
     pub fn load_from_memory(data: &[u8]) -> OperationResult<Self> {
         let header = GraphLinksFileHeader::deserialize_bytes_from(data);
 
-        let mut links: Vec<PointOffsetType> = Vec::new();
         let mut offsets: Vec<u64> = Vec::new();
         let mut level_offsets: Vec<u64> = Vec::new();
         let mut reindex: Vec<PointOffsetType> = Vec::new();
 
-        let link_slice = get_links_slice(data, &header);
-        links.try_set_capacity_exact(link_slice.len())?;
-        links.extend_from_slice(link_slice);
+        let links = match header.format()? {
+            GraphLinksFormat::Plain => {
+                let link_slice = get_links_slice(data, &header);
+                let mut links: Vec<PointOffsetType> = Vec::new();
+                links.try_set_capacity_exact(link_slice.len())?;
+                links.extend_from_slice(link_slice);
+                GraphLinksRamData::Plain(links)
+            }
+            GraphLinksFormat::Compressed => {
+                let link_slice = get_compressed_links_slice(data, &header);
+                let mut links: Vec<u8> = Vec::new();
+                links.try_set_capacity_exact(link_slice.len())?;
+                links.extend_from_slice(link_slice);
+                GraphLinksRamData::Compressed(links)
+            }
+        };
 
         offsets.try_set_capacity_exact(header.get_offsets_range().len() / size_of::<u64>())?;
         offsets.extend(get_offsets_iter(data, &header));
@@ -477,8 +623,13 @@ impl GraphLinks for GraphLinksRam {
         self.level_offsets.len()
     }
 
-    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType] {
-        &self.links[range]
+    fn get_links(&self, range: Range<usize>) -> LinksIterator<'_> {
+        match &self.links {
+            GraphLinksRamData::Plain(links) => LinksIterator::Plain(links[range].iter()),
+            GraphLinksRamData::Compressed(links) => {
+                LinksIterator::Compressed(CompressedLinksIterator::new(&links[range]))
+            }
+        }
     }
 
     fn get_links_range(&self, idx: usize) -> Range<usize> {
@@ -504,6 +655,7 @@ impl GraphLinks for GraphLinksRam {
 pub struct GraphLinksMmap {
     mmap: Option<Arc<Mmap>>,
     header: GraphLinksFileHeader,
+    format: GraphLinksFormat,
     level_offsets: Vec<u64>,
 }
 
@@ -524,6 +676,14 @@ impl GraphLinksMmap {
         }
     }
 
+    fn get_compressed_links_slice(&self) -> &[u8] {
+        if let Some(mmap) = &self.mmap {
+            get_compressed_links_slice(mmap, &self.header)
+        } else {
+            panic!("{}", MMAP_PANIC_MESSAGE);
+        }
+    }
+
     fn get_links_offset(offsets_data: &[u8], idx: usize) -> usize {
         let begin = mem::size_of::<u64>() * idx;
         let end = begin + mem::size_of::<u64>();
@@ -550,11 +710,13 @@ impl GraphLinks for GraphLinksMmap {
         madvise::madvise(&mmap, madvise::get_global())?;
 
         let header = GraphLinksFileHeader::deserialize_bytes_from(&mmap);
+        let format = header.format()?;
         let level_offsets = get_level_offsets(&mmap, &header).to_vec();
 
         Ok(Self {
             mmap: Some(Arc::new(mmap)),
             header,
+            format,
             level_offsets,
         })
     }
@@ -577,8 +739,13 @@ impl GraphLinks for GraphLinksMmap {
         self.level_offsets.len()
     }
 
-    fn get_links(&self, range: Range<usize>) -> &[PointOffsetType] {
-        &self.get_links_slice()[range]
+    fn get_links(&self, range: Range<usize>) -> LinksIterator<'_> {
+        match self.format {
+            GraphLinksFormat::Plain => LinksIterator::Plain(self.get_links_slice()[range].iter()),
+            GraphLinksFormat::Compressed => LinksIterator::Compressed(
+                CompressedLinksIterator::new(&self.get_compressed_links_slice()[range]),
+            ),
+        }
     }
 
     fn get_links_range(&self, idx: usize) -> Range<usize> {
@@ -619,7 +786,7 @@ mod tests {
             let mut layers = Vec::new();
             let num_levels = links.point_level(i as PointOffsetType) + 1;
             for level in 0..num_levels {
-                let links = links.links(i as PointOffsetType, level).to_vec();
+                let links = links.links(i as PointOffsetType, level).collect::<Vec<_>>();
                 layers.push(links);
             }
             result.push(layers);
@@ -648,18 +815,26 @@ mod tests {
     }
 
     /// Test that random links can be saved by `GraphLinksConverter` and loaded correctly by a GraphLinks impl.
-    fn test_save_load<A>(points_count: usize, max_levels_count: usize)
+    fn test_save_load<A>(points_count: usize, max_levels_count: usize, format: GraphLinksFormat)
     where
         A: GraphLinks,
     {
         let path = Builder::new().prefix("graph_dir").tempdir().unwrap();
         let links_file = path.path().join("links.bin");
-        let links = random_links(points_count, max_levels_count);
+        let mut links = random_links(points_count, max_levels_count);
         {
-            let mut links_converter = GraphLinksConverter::new(links.clone());
+            let mut links_converter = GraphLinksConverter::new(links.clone()).with_format(format);
             links_converter.save_as(&links_file).unwrap();
         }
         let cmp_links = to_vec(&A::load_from_file(&links_file).unwrap());
+
+        // Compressed links are decoded in sorted order
+        if format == GraphLinksFormat::Compressed {
+            links
+                .iter_mut()
+                .flatten()
+                .for_each(|links| links.sort_unstable());
+        }
         assert_eq!(links, cmp_links);
     }
 
@@ -731,7 +906,20 @@ mod tests {
 
     #[test]
     fn test_graph_links_mmap_ram_compatibility() {
-        test_save_load::<GraphLinksRam>(1000, 10);
-        test_save_load::<GraphLinksMmap>(1000, 10);
+        test_save_load::<GraphLinksRam>(1000, 10, GraphLinksFormat::Plain);
+        test_save_load::<GraphLinksMmap>(1000, 10, GraphLinksFormat::Plain);
+    }
+
+    #[test]
+    fn test_graph_links_compressed() {
+        test_save_load::<GraphLinksRam>(1000, 10, GraphLinksFormat::Compressed);
+        test_save_load::<GraphLinksMmap>(1000, 10, GraphLinksFormat::Compressed);
+
+        let links = random_links(1000, 10);
+        let plain_size = GraphLinksConverter::new(links.clone()).data_size();
+        let compressed_size = GraphLinksConverter::new(links)
+            .with_format(GraphLinksFormat::Compressed)
+            .data_size();
+        assert!(compressed_size < plain_size);
     }
 }
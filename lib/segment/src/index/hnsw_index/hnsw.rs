@@ -18,7 +18,7 @@ use rand::thread_rng;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
-use super::graph_links::{GraphLinks, GraphLinksMmap};
+use super::graph_links::{GraphLinks, GraphLinksFormat, GraphLinksMmap};
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
@@ -27,16 +27,18 @@ use crate::common::BYTES_IN_KB;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::CardinalityEstimation;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::old_index::OldIndexCandidate;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
-use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::sample_estimation::{sample_check_cardinality, sample_estimate_selectivity};
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
@@ -46,7 +48,8 @@ use crate::types::{
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::DiscoveryQuery;
 use crate::vector_storage::{
-    new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+    new_stoppable_raw_scorer, new_truncated_raw_scorer, scoring_dimensions, RawScorer,
+    VectorStorage, VectorStorageEnum, DEFAULT_STOPPED,
 };
 
 const HNSW_USE_HEURISTIC: bool = true;
@@ -67,6 +70,8 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: GraphLayers<TGraphLinks>,
+    /// Number of leading dimensions to use, if this index is a truncated view of another vector
+    truncate_to: Option<usize>,
     searches_telemetry: HNSWSearchesTelemetry,
 }
 
@@ -87,6 +92,10 @@ pub struct HnswIndexOpenArgs<'a> {
     pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     pub hnsw_config: HnswConfig,
+    /// Build and search over the leading dimensions only, for truncated vector views
+    pub truncate_to: Option<usize>,
+    /// Existing index to reuse the graph of, if the index has to be built
+    pub old_index: Option<OldIndexCandidate>,
     pub permit: Option<Arc<CpuPermit>>,
     pub stopped: &'a AtomicBool,
 }
@@ -100,6 +109,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             quantized_vectors,
             payload_index,
             hnsw_config,
+            truncate_to,
+            old_index,
             permit,
             stopped,
         } = args;
@@ -158,6 +169,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 &quantized_vectors.borrow(),
                 &payload_index.borrow(),
                 hnsw_config,
+                truncate_to,
+                old_index,
                 num_cpus,
                 stopped,
             )?;
@@ -176,6 +189,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            truncate_to,
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -187,7 +201,6 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         })
     }
 
-    #[cfg(test)]
     pub(super) fn graph(&self) -> &GraphLayers<TGraphLinks> {
         &self.graph
     }
@@ -204,6 +217,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         quantized_vectors: &Option<QuantizedVectors>,
         payload_index: &StructPayloadIndex,
         hnsw_config: HnswConfig,
+        truncate_to: Option<usize>,
+        old_index: Option<OldIndexCandidate>,
         num_cpus: usize,
         stopped: &AtomicBool,
     ) -> OperationResult<(HnswGraphConfig, GraphLayers<TGraphLinks>)> {
@@ -276,8 +291,38 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             })
             .build()?;
 
+        // Reuse the graph of an old index, so only new points and the points which lost
+        // their neighbors have to be linked
+        let damaged_points = match old_index.filter(|_| config.m > 0) {
+            Some(old_index) => {
+                let old_to_new = old_index.old_to_new(deleted_bitslice);
+                let damaged_points = match &*old_index.index.borrow() {
+                    VectorIndexEnum::HnswRam(index) => {
+                        graph_layers_builder.seed_from(index.graph(), &old_to_new)
+                    }
+                    VectorIndexEnum::HnswMmap(index) => {
+                        graph_layers_builder.seed_from(index.graph(), &old_to_new)
+                    }
+                    _ => Vec::new(),
+                };
+                debug!(
+                    "reusing HNSW graph for {} vectors, {} to repair",
+                    old_to_new.iter().flatten().count(),
+                    damaged_points.len(),
+                );
+                damaged_points
+            }
+            None => Vec::new(),
+        };
+
         for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
             check_process_stopped(stopped)?;
+            // Seeded points keep their levels
+            if graph_layers_builder.is_ready(vector_id)
+                || damaged_points.binary_search(&vector_id).is_ok()
+            {
+                continue;
+            }
             let level = graph_layers_builder.get_random_layer(&mut rng);
             graph_layers_builder.set_levels(vector_id, level);
         }
@@ -285,7 +330,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let mut indexed_vectors = 0;
 
         if config.m > 0 {
-            let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
+            let all_ids: Vec<_> = id_tracker.iter_ids_excluding(deleted_bitslice).collect();
+            indexed_vectors = all_ids.len();
+
+            // Only new and damaged points are not ready after seeding
+            let mut ids_iterator = all_ids
+                .into_iter()
+                .filter(|&vector_id| !graph_layers_builder.is_ready(vector_id));
 
             let first_few_ids: Vec<_> = ids_iterator
                 .by_ref()
@@ -293,8 +344,6 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 .collect();
             let ids: Vec<_> = ids_iterator.collect();
 
-            indexed_vectors = ids.len() + first_few_ids.len();
-
             let insert_point = |vector_id| {
                 check_process_stopped(stopped)?;
                 let vector = vector_storage.get_vector(vector_id);
@@ -307,7 +356,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                         stopped,
                     )
                 } else {
-                    new_raw_scorer(vector, vector_storage, id_tracker.deleted_point_bitslice())
+                    new_truncated_raw_scorer(
+                        vector,
+                        vector_storage,
+                        truncate_to,
+                        id_tracker.deleted_point_bitslice(),
+                        &DEFAULT_STOPPED,
+                    )
                 }?;
                 let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
 
@@ -374,6 +429,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                         id_tracker,
                         vector_storage,
                         quantized_vectors,
+                        truncate_to,
                         payload_index,
                         &pool,
                         stopped,
@@ -396,15 +452,20 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         config.indexed_vector_count.replace(indexed_vectors);
 
+        let links_format = if hnsw_config.compressed_links.unwrap_or_default() {
+            GraphLinksFormat::Compressed
+        } else {
+            GraphLinksFormat::Plain
+        };
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(path);
         let graph: GraphLayers<TGraphLinks> =
-            graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
+            graph_layers_builder.into_graph_layers(Some(&graph_links_path), links_format)?;
 
         #[cfg(debug_assertions)]
         {
             for (idx, deleted) in deleted_bitslice.iter().enumerate() {
                 if *deleted {
-                    debug_assert!(graph.links.links(idx as PointOffsetType, 0).is_empty());
+                    debug_assert_eq!(graph.links.links(idx as PointOffsetType, 0).len(), 0);
                 }
             }
         }
@@ -418,6 +479,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         id_tracker: &IdTrackerSS,
         vector_storage: &VectorStorageEnum,
         quantized_vectors: &Option<QuantizedVectors>,
+        truncate_to: Option<usize>,
         payload_index: &StructPayloadIndex,
         pool: &ThreadPool,
         stopped: &AtomicBool,
@@ -458,7 +520,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                     deleted_bitslice,
                     stopped,
                 ),
-                None => new_raw_scorer(vector, vector_storage, id_tracker.deleted_point_bitslice()),
+                None => new_truncated_raw_scorer(
+                    vector,
+                    vector_storage,
+                    truncate_to,
+                    id_tracker.deleted_point_bitslice(),
+                    &DEFAULT_STOPPED,
+                ),
             }?;
             let block_condition_checker = BuildConditionChecker {
                 filter_list: block_filter_list,
@@ -501,6 +569,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         top: usize,
         params: Option<&SearchParams>,
         custom_entry_points: Option<&[PointOffsetType]>,
+        use_acorn: bool,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let ef = params
@@ -524,6 +593,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             quantized_vectors.as_ref(),
             deleted_points,
             params,
+            scoring_dimensions(self.truncate_to, params),
             &is_stopped,
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
@@ -531,9 +601,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let filter_context = filter.map(|f| payload_index.filter_context(f));
         let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
 
-        let search_result =
+        let search_result = if use_acorn {
+            self.graph
+                .search_acorn(oversampled_top, ef, points_scorer, custom_entry_points)
+        } else {
             self.graph
-                .search(oversampled_top, ef, points_scorer, custom_entry_points);
+                .search(oversampled_top, ef, points_scorer, custom_entry_points)
+        };
         self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
     }
 
@@ -543,6 +617,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        use_acorn: bool,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         vectors
@@ -553,11 +628,18 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                     filter,
                     top,
                     params,
+                    use_acorn,
+                    vector_query_context,
+                ),
+                other => self.search_with_graph(
+                    other,
+                    filter,
+                    top,
+                    params,
+                    None,
+                    use_acorn,
                     vector_query_context,
                 ),
-                other => {
-                    self.search_with_graph(other, filter, top, params, None, vector_query_context)
-                }
             })
             .collect()
     }
@@ -586,6 +668,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             quantized_vectors.as_ref(),
             deleted_points,
             params,
+            scoring_dimensions(self.truncate_to, params),
             &is_stopped,
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
@@ -621,6 +704,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        use_acorn: bool,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         // Stage 1: Find best entry points using Context search
@@ -635,6 +719,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 DISCOVERY_ENTRY_POINT_COUNT,
                 params,
                 None,
+                use_acorn,
                 vector_query_context,
             )
             .map(|search_result| search_result.iter().map(|x| x.idx).collect())?;
@@ -648,10 +733,67 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             top,
             params,
             Some(&custom_entry_points),
+            use_acorn,
             vector_query_context,
         )
     }
 
+    /// Decide whether to use ACORN for a filtered graph search.
+    ///
+    /// ACORN is used if the graph has no payload links for the filter and the filter
+    /// selectivity is below the configured maximum, unless it is disabled in the search params.
+    ///
+    /// Selectivity is taken from the cardinality estimation if it is exact, and sampled
+    /// otherwise, e.g. for conditions on fields without a payload index.
+    fn use_acorn(
+        &self,
+        params: Option<&SearchParams>,
+        filter: &Filter,
+        query_cardinality: &CardinalityEstimation,
+        available_vector_count: usize,
+    ) -> bool {
+        let acorn = params.and_then(|params| params.acorn).unwrap_or_default();
+        if !acorn.enable || available_vector_count == 0 || self.has_payload_links(filter) {
+            return false;
+        }
+
+        let selectivity = if query_cardinality.min == query_cardinality.max {
+            query_cardinality.exp as f64 / available_vector_count as f64
+        } else {
+            let payload_index = self.payload_index.borrow();
+            let filter_context = payload_index.filter_context(filter);
+            let id_tracker = self.id_tracker.borrow();
+            let vector_storage = self.vector_storage.borrow();
+            sample_estimate_selectivity(
+                id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                |idx| filter_context.check(idx),
+            )
+        };
+        acorn.applies_to(selectivity)
+    }
+
+    /// Whether the graph has additional payload links for the points matching the filter,
+    /// i.e. one of the `must` conditions is on an indexed field, and payload links are built.
+    ///
+    /// The regular graph search stays navigable within such filters.
+    fn has_payload_links(&self, filter: &Filter) -> bool {
+        if self.config.payload_m.unwrap_or(self.config.m) == 0 {
+            return false;
+        }
+
+        let payload_index = self.payload_index.borrow();
+        filter
+            .must
+            .iter()
+            .flatten()
+            .any(|condition| match condition {
+                Field(field_condition) => payload_index
+                    .field_indexes
+                    .contains_key(&field_condition.key),
+                _ => false,
+            })
+    }
+
     fn is_quantized_search(
         quantized_storage: Option<&QuantizedVectors>,
         params: Option<&SearchParams>,
@@ -660,7 +802,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .and_then(|p| p.quantization)
             .map(|q| q.ignore)
             .unwrap_or(default_quantization_ignore_value());
-        quantized_storage.is_some() && !ignore_quantization
+        // Quantized vectors always cover all dimensions
+        let truncated = params.and_then(|p| p.dimensions).is_some();
+        quantized_storage.is_some() && !ignore_quantization && !truncated
     }
 
     fn construct_search_scorer<'a>(
@@ -669,6 +813,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         quantized_storage: Option<&'a QuantizedVectors>,
         deleted_points: &'a BitSlice,
         params: Option<&SearchParams>,
+        dimensions: Option<usize>,
         is_stopped: &'a AtomicBool,
     ) -> OperationResult<Box<dyn RawScorer + 'a>> {
         let quantization_enabled = Self::is_quantized_search(quantized_storage, params);
@@ -679,9 +824,10 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 vector_storage.deleted_vector_bitslice(),
                 is_stopped,
             ),
-            _ => new_stoppable_raw_scorer(
+            _ => new_truncated_raw_scorer(
                 vector.to_owned(),
                 vector_storage,
+                dimensions,
                 deleted_points,
                 is_stopped,
             ),
@@ -794,13 +940,15 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         .unwrap_or(id_tracker.deleted_point_bitslice());
 
                     let is_stopped = query_context.is_stopped();
+                    let dimensions = scoring_dimensions(self.truncate_to, params);
 
                     vectors
                         .iter()
                         .map(|&vector| {
-                            new_stoppable_raw_scorer(
+                            new_truncated_raw_scorer(
                                 vector.to_owned(),
                                 &vector_storage,
+                                dimensions,
                                 deleted_points,
                                 &is_stopped,
                             )
@@ -810,7 +958,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
-                    self.search_vectors_with_graph(vectors, None, top, params, query_context)
+                    self.search_vectors_with_graph(vectors, None, top, params, false, query_context)
                 }
             }
             Some(query_filter) => {
@@ -868,11 +1016,18 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    let use_acorn = self.use_acorn(
+                        params,
+                        query_filter,
+                        &query_cardinality,
+                        available_vector_count,
+                    );
                     return self.search_vectors_with_graph(
                         vectors,
                         filter,
                         top,
                         params,
+                        use_acorn,
                         query_context,
                     );
                 }
@@ -890,7 +1045,20 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    self.search_vectors_with_graph(vectors, filter, top, params, query_context)
+                    let use_acorn = self.use_acorn(
+                        params,
+                        query_filter,
+                        &query_cardinality,
+                        available_vector_count,
+                    );
+                    self.search_vectors_with_graph(
+                        vectors,
+                        filter,
+                        top,
+                        params,
+                        use_acorn,
+                        query_context,
+                    )
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
@@ -0,0 +1,191 @@
+use common::types::PointOffsetType;
+
+/*
+Compressed links of a single point on a single level.
+
+Links are sorted and stored as differences between neighbouring ids,
+packed with the minimal number of bits required for the largest difference:
+
+    links:   [17, 3, 9, 12]
+    sorted:  [3, 9, 12, 17]
+    deltas:      [6, 3, 5]  -> 3 bits each
+
+    ┌───────────┬───────────┬──────────┬─────────────────┐
+    │ count: 4  │ first: 3  │ bits: 3  │ 110 011 101 ... │
+    │ (varint)  │ (varint)  │ (u8)     │ (packed deltas) │
+    └───────────┴───────────┴──────────┴─────────────────┘
+
+Empty list is stored as a single zero count byte.
+*/
+
+fn write_varint(mut value: u32, output: &mut Vec<u8>) {
+    while value >= 0x80 {
+        output.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn read_varint(data: &[u8]) -> (u32, &[u8]) {
+    let mut value = 0;
+    let mut shift = 0;
+    for (i, &byte) in data.iter().enumerate() {
+        value |= u32::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return (value, &data[i + 1..]);
+        }
+        shift += 7;
+    }
+    (value, &[])
+}
+
+/// Append compressed `links` to `output`.
+///
+/// Order of links is not preserved, they are decoded in ascending order.
+pub fn compress_links(links: &[PointOffsetType], output: &mut Vec<u8>) {
+    write_varint(links.len() as u32, output);
+
+    let mut sorted = links.to_vec();
+    sorted.sort_unstable();
+
+    let Some(&first) = sorted.first() else {
+        return;
+    };
+    write_varint(first, output);
+
+    let max_delta = sorted.windows(2).map(|w| w[1] - w[0]).max().unwrap_or(0);
+    let bits = u32::BITS - max_delta.leading_zeros();
+    output.push(bits as u8);
+
+    let mut buffer = 0u64;
+    let mut buffer_len = 0;
+    for delta in sorted.windows(2).map(|w| w[1] - w[0]) {
+        buffer |= u64::from(delta) << buffer_len;
+        buffer_len += bits;
+        while buffer_len >= 8 {
+            output.push(buffer as u8);
+            buffer >>= 8;
+            buffer_len -= 8;
+        }
+    }
+    if buffer_len > 0 {
+        output.push(buffer as u8);
+    }
+}
+
+/// Decodes links compressed by [`compress_links`] on the fly
+#[derive(Debug, Clone)]
+pub struct CompressedLinksIterator<'a> {
+    data: &'a [u8],
+    bits: u32,
+    buffer: u64,
+    buffer_len: u32,
+    current: PointOffsetType,
+    remaining: usize,
+    started: bool,
+}
+
+impl<'a> CompressedLinksIterator<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        let (count, data) = read_varint(data);
+        let (first, data, bits) = if count == 0 {
+            (0, data, 0)
+        } else {
+            let (first, data) = read_varint(data);
+            (first, &data[1..], u32::from(data[0]))
+        };
+
+        Self {
+            data,
+            bits,
+            buffer: 0,
+            buffer_len: 0,
+            current: first,
+            remaining: count as usize,
+            started: false,
+        }
+    }
+
+    fn read_delta(&mut self) -> PointOffsetType {
+        while self.buffer_len < self.bits {
+            self.buffer |= u64::from(self.data[0]) << self.buffer_len;
+            self.data = &self.data[1..];
+            self.buffer_len += 8;
+        }
+        let delta = self.buffer & ((1u64 << self.bits) - 1);
+        self.buffer >>= self.bits;
+        self.buffer_len -= self.bits;
+        delta as PointOffsetType
+    }
+}
+
+impl<'a> Iterator for CompressedLinksIterator<'a> {
+    type Item = PointOffsetType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        if self.started {
+            self.current += self.read_delta();
+        } else {
+            self.started = true;
+        }
+        Some(self.current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for CompressedLinksIterator<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    fn check_roundtrip(links: &[PointOffsetType]) {
+        let mut data = Vec::new();
+        compress_links(links, &mut data);
+
+        let mut expected = links.to_vec();
+        expected.sort_unstable();
+
+        let iter = CompressedLinksIterator::new(&data);
+        assert_eq!(iter.len(), links.len());
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_compress_links() {
+        check_roundtrip(&[]);
+        check_roundtrip(&[0]);
+        check_roundtrip(&[PointOffsetType::MAX]);
+        check_roundtrip(&[5, 5, 5]);
+        check_roundtrip(&[17, 3, 9, 12]);
+        check_roundtrip(&[0, PointOffsetType::MAX, 1 << 20]);
+
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let count = rng.gen_range(0..64);
+            let max_id = rng.gen_range(1..=PointOffsetType::MAX);
+            let links: Vec<PointOffsetType> =
+                (0..count).map(|_| rng.gen_range(0..max_id)).collect();
+            check_roundtrip(&links);
+        }
+    }
+
+    #[test]
+    fn test_compressed_links_size() {
+        // Links with close ids should take much less than 4 bytes per link
+        let links: Vec<PointOffsetType> = (0..32).map(|i| 100_000 + i * 7).collect();
+        let mut data = Vec::new();
+        compress_links(&links, &mut data);
+        assert!(data.len() < links.len() * std::mem::size_of::<PointOffsetType>() / 2);
+    }
+}
@@ -8,6 +8,8 @@ pub mod graph_layers;
 pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
+pub mod links_compression;
+pub mod old_index;
 pub mod point_scorer;
 mod search_context;
 
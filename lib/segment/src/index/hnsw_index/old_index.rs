@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+
+use crate::index::VectorIndexEnum;
+use crate::types::{Indexes, VectorDataConfig};
+
+/// HNSW index of a source segment, which graph can be reused to build the index of a new segment
+pub struct OldIndexCandidate {
+    pub index: Arc<AtomicRefCell<VectorIndexEnum>>,
+    /// Internal id in the new segment for each internal id of the old index, `None` if the
+    /// point is not moved into the new segment
+    pub old_to_new: Vec<Option<PointOffsetType>>,
+}
+
+impl OldIndexCandidate {
+    /// Number of points of the old graph which are moved into the new segment
+    pub fn reused_points(&self) -> usize {
+        self.old_to_new.iter().flatten().count()
+    }
+
+    /// Mapping of old ids to new ones, excluding points deleted in the new segment
+    pub fn old_to_new(&self, deleted_vectors: &BitSlice) -> Vec<Option<PointOffsetType>> {
+        self.old_to_new
+            .iter()
+            .map(|new_id| {
+                new_id.filter(|&new_id| {
+                    !deleted_vectors
+                        .get(new_id as usize)
+                        .map(|deleted| *deleted)
+                        .unwrap_or(false)
+                })
+            })
+            .collect()
+    }
+
+    /// Whether a graph built for `old_config` is valid for `new_config`
+    ///
+    /// Graph must be built over the same vectors with the same distance and HNSW params.
+    /// Quantization and storage types don't affect the graph.
+    pub fn is_compatible(old_config: &VectorDataConfig, new_config: &VectorDataConfig) -> bool {
+        let (Indexes::Hnsw(old_hnsw), Indexes::Hnsw(new_hnsw)) =
+            (&old_config.index, &new_config.index)
+        else {
+            return false;
+        };
+        old_hnsw.m == new_hnsw.m
+            && old_hnsw.ef_construct == new_hnsw.ef_construct
+            && old_config.size == new_config.size
+            && old_config.distance == new_config.distance
+            && old_config.datatype == new_config.datatype
+            && old_config.multivector_config == new_config.multivector_config
+            && old_config.truncated_view == new_config.truncated_view
+    }
+}
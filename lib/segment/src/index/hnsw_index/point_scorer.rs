@@ -28,6 +28,12 @@ impl<'a> FilteredScorer<'a> {
         }
     }
 
+    /// Check only the filter condition, without checking for deleted vectors
+    pub fn check_filter(&self, point_id: PointOffsetType) -> bool {
+        self.filter_context
+            .map_or(true, |filter_context| filter_context.check(point_id))
+    }
+
     /// Method filters and calculates scores for the given slice of points IDs
     ///
     /// For performance reasons this function mutates input values.
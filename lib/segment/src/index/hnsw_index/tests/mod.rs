@@ -1,12 +1,14 @@
+mod test_acorn_search;
 mod test_compact_graph_layer;
 mod test_graph_connectivity;
+mod test_graph_seeding;
 
 use std::path::Path;
 
 use common::types::PointOffsetType;
 use rand::Rng;
 
-use super::graph_links::GraphLinksRam;
+use super::graph_links::{GraphLinksFormat, GraphLinksRam};
 use crate::data_types::vectors::VectorElementType;
 use crate::fixtures::index_fixtures::{FakeFilterContext, TestRawScorerProducer};
 use crate::index::hnsw_index::graph_layers::GraphLayers;
@@ -66,6 +68,8 @@ where
 
     (
         vector_holder,
-        graph_layers_builder.into_graph_layers(links_path).unwrap(),
+        graph_layers_builder
+            .into_graph_layers(links_path, GraphLinksFormat::Plain)
+            .unwrap(),
     )
 }
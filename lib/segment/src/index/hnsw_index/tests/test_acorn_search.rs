@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use common::types::{PointOffsetType, ScoredPointOffset};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::fixtures::index_fixtures::{random_vector, ModuloFilterContext};
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+use crate::payload_storage::FilterContext;
+use crate::spaces::simple::CosineMetric;
+
+fn ids(result: &[ScoredPointOffset]) -> HashSet<PointOffsetType> {
+    result.iter().map(|scored| scored.idx).collect()
+}
+
+#[test]
+fn test_acorn_search_restrictive_filter() {
+    let num_vectors = 2_000;
+    let dim = 16;
+    let m = 8;
+    let top = 10;
+    let ef = 32;
+    let num_queries = 20;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let (vector_holder, graph_layers) =
+        create_graph_layer_fixture::<CosineMetric, _>(num_vectors, m, dim, true, &mut rng, None);
+
+    // Only 2% of points match, graph without payload links is disconnected for them
+    let filter_context = ModuloFilterContext::new(50);
+    let filtered_points: Vec<PointOffsetType> = (0..num_vectors as PointOffsetType)
+        .filter(|&idx| filter_context.check(idx))
+        .collect();
+
+    let mut hnsw_hits = 0;
+    let mut acorn_hits = 0;
+
+    for _ in 0..num_queries {
+        let query = random_vector(&mut rng, dim);
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+
+        let mut exact_scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let mut exact = exact_scorer
+            .score_points(&mut filtered_points.clone(), 0)
+            .to_vec();
+        exact.sort_by(|a, b| b.score.total_cmp(&a.score));
+        exact.truncate(top);
+        let exact = ids(&exact);
+
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let hnsw_result = graph_layers.search(top, ef, scorer, None);
+
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&filter_context));
+        let acorn_result = graph_layers.search_acorn(top, ef, scorer, None);
+
+        assert!(acorn_result
+            .iter()
+            .all(|scored| filter_context.check(scored.idx)));
+
+        hnsw_hits += ids(&hnsw_result).intersection(&exact).count();
+        acorn_hits += ids(&acorn_result).intersection(&exact).count();
+    }
+
+    assert!(
+        acorn_hits > hnsw_hits,
+        "ACORN must improve recall: {acorn_hits} vs {hnsw_hits}",
+    );
+}
@@ -8,7 +8,7 @@ use rand::SeedableRng;
 use crate::fixtures::index_fixtures::random_vector;
 use crate::index::hnsw_index::graph_layers::GraphLayersBase;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
-use crate::index::hnsw_index::graph_links::GraphLinksRam;
+use crate::index::hnsw_index::graph_links::{GraphLinksFormat, GraphLinksRam};
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::tests::create_graph_layer_builder_fixture;
 use crate::spaces::simple::CosineMetric;
@@ -67,7 +67,7 @@ fn test_compact_graph_layers() {
         .collect_vec();
 
     let graph_layers = graph_layers_builder
-        .into_graph_layers::<GraphLinksRam>(None)
+        .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
         .unwrap();
 
     let results = queries
@@ -46,6 +46,7 @@ fn test_graph_connectivity() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -71,6 +72,7 @@ fn test_graph_connectivity() {
         max_indexing_threads: 4,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -85,6 +87,8 @@ fn test_graph_connectivity() {
         quantized_vectors: Default::default(),
         payload_index: payload_index_ptr.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -98,7 +102,7 @@ fn test_graph_connectivity() {
             .links
             .links(point_id as PointOffsetType, 0);
         for link in links {
-            reverse_links[*link as usize].push(point_id);
+            reverse_links[link as usize].push(point_id);
         }
     }
 
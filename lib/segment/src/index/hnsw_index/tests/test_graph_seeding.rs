@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use common::types::{PointOffsetType, ScoredPointOffset};
+use itertools::Itertools;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+use crate::fixtures::index_fixtures::{random_vector, FakeFilterContext};
+use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksFormat, GraphLinksRam};
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+use crate::spaces::simple::CosineMetric;
+
+fn ids(result: &[ScoredPointOffset]) -> HashSet<PointOffsetType> {
+    result.iter().map(|scored| scored.idx).collect()
+}
+
+#[test]
+/// Check that a graph seeded from an old one, with some points dropped, is repaired and
+/// gives results as good as the old graph
+fn test_seed_graph_from_old_graph() {
+    let num_vectors = 1000;
+    let num_queries = 100;
+    let m = 8;
+    let dim = 8;
+    let top = 5;
+    let ef = 64;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let (vector_holder, old_graph) =
+        create_graph_layer_fixture::<CosineMetric, _>(num_vectors, m, dim, true, &mut rng, None);
+
+    // Drop every 10th point, keep ids of the others
+    let old_to_new = (0..num_vectors as PointOffsetType)
+        .map(|idx| (idx % 10 != 0).then_some(idx))
+        .collect_vec();
+    let kept_points = old_to_new.iter().flatten().copied().collect_vec();
+
+    let mut builder = GraphLayersBuilder::new(num_vectors, m, m * 2, 16, 10, true);
+    let damaged_points = builder.seed_from(&old_graph, &old_to_new);
+    assert!(!damaged_points.is_empty());
+    assert!(damaged_points.iter().all(|&idx| !builder.is_ready(idx)));
+
+    for &idx in &damaged_points {
+        let fake_filter_context = FakeFilterContext {};
+        let vector = vector_holder.vectors.get(idx).to_vec();
+        let raw_scorer = vector_holder.get_raw_scorer(vector).unwrap();
+        let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+        builder.link_new_point(idx, scorer);
+    }
+
+    assert!(kept_points.iter().all(|&idx| builder.is_ready(idx)));
+
+    let graph = builder
+        .into_graph_layers::<GraphLinksRam>(None, GraphLinksFormat::Plain)
+        .unwrap();
+
+    // Dropped points must not be reachable
+    for &idx in &kept_points {
+        for level in 0..=graph.point_level(idx) {
+            assert!(graph.links.links(idx, level).all(|link| link % 10 != 0));
+        }
+    }
+
+    let mut old_hits = 0;
+    let mut new_hits = 0;
+    for _ in 0..num_queries {
+        let query = random_vector(&mut rng, dim);
+        let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+
+        let mut scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
+        let exact = scorer
+            .score_points(&mut kept_points.clone(), 0)
+            .iter()
+            .copied()
+            .sorted_by(|a, b| b.score.total_cmp(&a.score))
+            .take(top)
+            .collect_vec();
+        let exact = ids(&exact);
+
+        // Old graph still contains dropped points, exclude them as deleted ones would be
+        let old_result = old_graph
+            .search(
+                top * 2,
+                ef,
+                FilteredScorer::new(raw_scorer.as_ref(), None),
+                None,
+            )
+            .into_iter()
+            .filter(|scored| scored.idx % 10 != 0)
+            .take(top)
+            .collect_vec();
+        let new_result = graph.search(
+            top,
+            ef,
+            FilteredScorer::new(raw_scorer.as_ref(), None),
+            None,
+        );
+
+        assert!(new_result.iter().all(|scored| scored.idx % 10 != 0));
+
+        old_hits += ids(&old_result).intersection(&exact).count();
+        new_hits += ids(&new_result).intersection(&exact).count();
+    }
+
+    let max_hits = num_queries * top;
+    assert!(
+        new_hits as f64 >= old_hits as f64 - 0.05 * max_hits as f64,
+        "recall of seeded graph is too low: {new_hits} vs {old_hits} of {max_hits}",
+    );
+}
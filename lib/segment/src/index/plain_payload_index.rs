@@ -27,7 +27,9 @@ use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
     SearchParams,
 };
-use crate::vector_storage::{new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum};
+use crate::vector_storage::{
+    new_truncated_raw_scorer, scoring_dimensions, VectorStorage, VectorStorageEnum,
+};
 
 /// Implementation of `PayloadIndex` which does not really indexes anything.
 ///
@@ -208,6 +210,8 @@ pub struct PlainIndex {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Number of leading dimensions to use, if this index is a truncated view of another vector
+    truncate_to: Option<usize>,
     filtered_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
     unfiltered_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
 }
@@ -217,11 +221,13 @@ impl PlainIndex {
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
         vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
         payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+        truncate_to: Option<usize>,
     ) -> PlainIndex {
         PlainIndex {
             id_tracker,
             vector_storage,
             payload_index,
+            truncate_to,
             filtered_searches_telemetry: OperationDurationsAggregator::new(),
             unfiltered_searches_telemetry: OperationDurationsAggregator::new(),
         }
@@ -274,6 +280,7 @@ impl VectorIndex for PlainIndex {
         }
 
         let is_stopped = query_context.is_stopped();
+        let dimensions = scoring_dimensions(self.truncate_to, params);
 
         match filter {
             Some(filter) => {
@@ -288,9 +295,10 @@ impl VectorIndex for PlainIndex {
                 vectors
                     .iter()
                     .map(|&vector| {
-                        new_stoppable_raw_scorer(
+                        new_truncated_raw_scorer(
                             vector.to_owned(),
                             &vector_storage,
+                            dimensions,
                             deleted_points,
                             &is_stopped,
                         )
@@ -310,9 +318,10 @@ impl VectorIndex for PlainIndex {
                 vectors
                     .iter()
                     .map(|&vector| {
-                        new_stoppable_raw_scorer(
+                        new_truncated_raw_scorer(
                             vector.to_owned(),
                             &vector_storage,
+                            dimensions,
                             deleted_points,
                             &is_stopped,
                         )
@@ -357,6 +366,11 @@ impl VectorIndex for PlainIndex {
         id: PointOffsetType,
         vector: Option<VectorRef>,
     ) -> OperationResult<()> {
+        if self.truncate_to.is_some() {
+            // Truncated view shares the storage of its source vector, which is updated there
+            return Ok(());
+        }
+
         let mut vector_storage = self.vector_storage.borrow_mut();
 
         if let Some(vector) = vector {
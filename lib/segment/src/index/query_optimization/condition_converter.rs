@@ -5,6 +5,7 @@ use serde_json::Value;
 
 use crate::common::utils::IndexesMap;
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::map_index::uuid_to_int;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
@@ -15,8 +16,8 @@ use crate::payload_storage::query_checker::{
 };
 use crate::types::{
     AnyVariants, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
-    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText, MatchValue,
-    OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
+    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchPhrase, MatchText,
+    MatchValue, OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
 };
 
 pub fn condition_converter<'a>(
@@ -278,16 +279,18 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         }) => match (value_variant, index) {
             (ValueVariants::Keyword(keyword), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index
-                        .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|k| k == &keyword))
+                    index.check_values_any(point_id, |k| k == &keyword)
+                }))
+            }
+            (ValueVariants::Keyword(keyword), FieldIndex::UuidMapIndex(index)) => {
+                let uuid = uuid_to_int(&keyword);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    uuid.is_some_and(|uuid| index.check_values_any(point_id, |u| u == &uuid))
                 }))
             }
             (ValueVariants::Integer(value), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index
-                        .get_values(point_id)
-                        .map_or(false, |values| values.iter().any(|i| i == &value))
+                    index.check_values_any(point_id, |i| i == &value)
                 }))
             }
             (ValueVariants::Bool(is_true), FieldIndex::BinaryIndex(index)) => {
@@ -310,29 +313,40 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
             }
             _ => None,
         },
+        Match::Phrase(MatchPhrase { phrase, slop }) => match index {
+            FieldIndex::FullTextIndex(full_text_index) => {
+                let parsed_query = full_text_index.parse_phrase_query(&phrase, slop);
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    full_text_index.check_match(&parsed_query, point_id)
+                }))
+            }
+            _ => None,
+        },
         Match::Any(MatchAny { any }) => match (any, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
-                        if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values
-                                .iter()
-                                .any(|k| list.iter().any(|s| s.as_str() == k.as_ref()))
-                        } else {
-                            values.iter().any(|k| list.contains(k.as_str()))
-                        }
-                    })
+                    if list.len() < INDEXSET_ITER_THRESHOLD {
+                        index.check_values_any(point_id, |k| {
+                            list.iter().any(|s| s.as_str() == k.as_ref())
+                        })
+                    } else {
+                        index.check_values_any(point_id, |k| list.contains(k.as_str()))
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                let uuids: HashSet<_> = list.iter().filter_map(|k| uuid_to_int(k)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.check_values_any(point_id, |u| uuids.contains(u))
                 }))
             }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
-                        if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values.iter().any(|i| list.iter().any(|k| k == i))
-                        } else {
-                            values.iter().any(|i| list.contains(i))
-                        }
-                    })
+                    if list.len() < INDEXSET_ITER_THRESHOLD {
+                        index.check_values_any(point_id, |i| list.iter().any(|k| k == i))
+                    } else {
+                        index.check_values_any(point_id, |i| list.contains(i))
+                    }
                 }))
             }
             _ => None,
@@ -340,26 +354,28 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         Match::Except(MatchExcept { except }) => match (except, index) {
             (AnyVariants::Keywords(list), FieldIndex::KeywordIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
-                        if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values
-                                .iter()
-                                .any(|k| !list.iter().any(|s| s.as_str() == k.as_ref()))
-                        } else {
-                            values.iter().any(|k| !list.contains(k.as_str()))
-                        }
-                    })
+                    if list.len() < INDEXSET_ITER_THRESHOLD {
+                        index.check_values_any(point_id, |k| {
+                            !list.iter().any(|s| s.as_str() == k.as_ref())
+                        })
+                    } else {
+                        index.check_values_any(point_id, |k| !list.contains(k.as_str()))
+                    }
+                }))
+            }
+            (AnyVariants::Keywords(list), FieldIndex::UuidMapIndex(index)) => {
+                let uuids: HashSet<_> = list.iter().filter_map(|k| uuid_to_int(k)).collect();
+                Some(Box::new(move |point_id: PointOffsetType| {
+                    index.check_values_any(point_id, |u| !uuids.contains(u))
                 }))
             }
             (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
                 Some(Box::new(move |point_id: PointOffsetType| {
-                    index.get_values(point_id).map_or(false, |values| {
-                        if list.len() < INDEXSET_ITER_THRESHOLD {
-                            values.iter().any(|i| !list.iter().any(|k| k == i))
-                        } else {
-                            values.iter().any(|i| !list.contains(i))
-                        }
-                    })
+                    if list.len() < INDEXSET_ITER_THRESHOLD {
+                        index.check_values_any(point_id, |i| !list.iter().any(|k| k == i))
+                    } else {
+                        index.check_values_any(point_id, |i| !list.contains(i))
+                    }
                 }))
             }
             (_, index) => Some(Box::new(|point_id: PointOffsetType| {
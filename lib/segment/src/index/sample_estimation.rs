@@ -63,6 +63,23 @@ pub fn sample_check_cardinality(
     exp > threshold as i64
 }
 
+/// Estimates the fraction of points matching the `checker` on a sample of points
+pub fn sample_estimate_selectivity(
+    sample_points: impl Iterator<Item = PointOffsetType>,
+    checker: impl Fn(PointOffsetType) -> bool,
+) -> f64 {
+    let mut matched_points = 0;
+    let mut total_checked = 0;
+    for idx in sample_points.take(MAX_ESTIMATED_POINTS) {
+        matched_points += checker(idx) as usize;
+        total_checked += 1;
+    }
+    if total_checked == 0 {
+        return 0.0;
+    }
+    matched_points as f64 / total_checked as f64
+}
+
 #[cfg(test)]
 mod tests {
     use rand::rngs::StdRng;
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use common::types::PointOffsetType;
 
 use crate::common::utils::IndexesMap;
@@ -11,6 +13,8 @@ use crate::types::{Condition, Filter};
 
 pub struct StructFilterContext<'a> {
     optimized_filter: OptimizedFilter<'a>,
+    /// All matching points are known to be within this range
+    points_range: Option<Range<PointOffsetType>>,
 }
 
 impl<'a> StructFilterContext<'a> {
@@ -21,6 +25,7 @@ impl<'a> StructFilterContext<'a> {
         field_indexes: &'a IndexesMap,
         estimator: &F,
         total: usize,
+        points_range: Option<Range<PointOffsetType>>,
     ) -> Self
     where
         F: Fn(&Condition) -> CardinalityEstimation,
@@ -34,12 +39,20 @@ impl<'a> StructFilterContext<'a> {
             total,
         );
 
-        Self { optimized_filter }
+        Self {
+            optimized_filter,
+            points_range,
+        }
     }
 }
 
 impl<'a> FilterContext for StructFilterContext<'a> {
     fn check(&self, point_id: PointOffsetType) -> bool {
+        if let Some(points_range) = &self.points_range {
+            if !points_range.contains(&point_id) {
+                return false;
+            }
+        }
         check_optimized_filter(&self.optimized_filter, point_id)
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fs::create_dir_all;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -36,6 +36,21 @@ use crate::types::{
     PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
 };
 
+/// Principal range is used to select candidate points, if it is expected to select
+/// at most this many times more points than the regular query plan
+const PRINCIPAL_RANGE_MAX_OVERHEAD: usize = 2;
+
+/// Iterate over available points, only within `points_range` if it is given
+fn iter_points_in_range(
+    id_tracker: &IdTrackerSS,
+    points_range: Option<Range<PointOffsetType>>,
+) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+    match points_range {
+        Some(range) => Box::new(range.filter(move |&id| !id_tracker.is_deleted_point(id))),
+        None => id_tracker.iter_ids(),
+    }
+}
+
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 #[derive(Debug)]
 pub struct StructPayloadIndex {
@@ -51,6 +66,7 @@ pub struct StructPayloadIndex {
     /// Used to select unique point ids
     visited_pool: VisitedPool,
     db: Arc<RwLock<DB>>,
+    is_appendable: bool,
 }
 
 impl StructPayloadIndex {
@@ -88,6 +104,98 @@ impl StructPayloadIndex {
         indexes
     }
 
+    /// Range condition over the principal field, which all the matching points must satisfy,
+    /// together with its cardinality estimation
+    fn principal_range_condition<'a>(
+        &self,
+        query: &'a Filter,
+    ) -> Option<(&'a FieldCondition, CardinalityEstimation)> {
+        query
+            .must
+            .as_ref()?
+            .iter()
+            .find_map(|condition| match condition {
+                Condition::Field(field_condition)
+                    if field_condition.range.is_some()
+                        && self
+                            .config
+                            .indexed_fields
+                            .get(&field_condition.key)
+                            .is_some_and(|payload_schema| payload_schema.is_principal()) =>
+                {
+                    let estimation = self.estimate_field_condition(field_condition, None)?;
+                    Some((field_condition, estimation))
+                }
+                _ => None,
+            })
+    }
+
+    /// Expected number of points to read, if the query is resolved with `primary_clauses`
+    fn primary_clauses_cost(&self, primary_clauses: &[PrimaryCondition]) -> usize {
+        let available_points = self.available_point_count();
+        if primary_clauses.is_empty() {
+            // Full scan
+            return available_points;
+        }
+        primary_clauses
+            .iter()
+            .map(|clause| match clause {
+                PrimaryCondition::Condition(field_condition) => self
+                    .estimate_field_condition(field_condition, None)
+                    .map_or(available_points, |estimation| estimation.exp),
+                PrimaryCondition::Ids(ids) => ids.len(),
+                PrimaryCondition::IsEmpty(_) | PrimaryCondition::IsNull(_) => available_points,
+            })
+            .sum()
+    }
+
+    /// Select conditions, which are used to iterate over candidate points of the `query`
+    pub fn query_primary_clauses(&self, query: &Filter) -> Vec<PrimaryCondition> {
+        let primary_clauses = self.estimate_cardinality(query).primary_clauses;
+
+        // Points are ordered by the principal field after optimization,
+        // so reading its range first keeps storage access sequential.
+        // It is only worth it, if the range doesn't select many more points than the cheapest plan.
+        match self.principal_range_condition(query) {
+            Some((principal_condition, principal_cardinality))
+                if principal_cardinality.exp
+                    <= self
+                        .primary_clauses_cost(&primary_clauses)
+                        .saturating_mul(PRINCIPAL_RANGE_MAX_OVERHEAD) =>
+            {
+                vec![PrimaryCondition::Condition(principal_condition.clone())]
+            }
+            _ => primary_clauses,
+        }
+    }
+
+    /// Range of point offsets, which contains all points of the tenant selected by `query`
+    ///
+    /// Points of the same tenant are stored next to each other after optimization,
+    /// so in optimized segments the range only covers the points of this tenant.
+    pub fn tenant_points_range(&self, query: &Filter) -> Option<Range<PointOffsetType>> {
+        query
+            .must
+            .as_ref()?
+            .iter()
+            .filter_map(|condition| match condition {
+                Condition::Field(field_condition)
+                    if self
+                        .config
+                        .indexed_fields
+                        .get(&field_condition.key)
+                        .is_some_and(|payload_schema| payload_schema.is_tenant()) =>
+                {
+                    self.field_indexes
+                        .get(&field_condition.key)?
+                        .iter()
+                        .find_map(|index| index.get_points_range(field_condition))
+                }
+                _ => None,
+            })
+            .reduce(|a, b| a.start.max(b.start)..a.end.min(b.end))
+    }
+
     fn config_path(&self) -> PathBuf {
         PayloadConfig::get_config_path(&self.path)
     }
@@ -97,11 +205,11 @@ impl StructPayloadIndex {
         self.config.save(&config_path)
     }
 
-    fn load_all_fields(&mut self, is_appendable: bool) -> OperationResult<()> {
+    fn load_all_fields(&mut self) -> OperationResult<()> {
         let mut field_indexes: IndexesMap = Default::default();
 
         for (field, payload_schema) in &self.config.indexed_fields {
-            let field_index = self.load_from_db(field, payload_schema.to_owned(), is_appendable)?;
+            let field_index = self.load_from_db(field, payload_schema.to_owned())?;
             field_indexes.insert(field.clone(), field_index);
         }
         self.field_indexes = field_indexes;
@@ -112,9 +220,14 @@ impl StructPayloadIndex {
         &self,
         field: PayloadKeyTypeRef,
         payload_schema: PayloadFieldSchema,
-        is_appendable: bool,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let mut indexes = index_selector(field, &payload_schema, self.db.clone(), is_appendable);
+        let mut indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &self.path,
+            self.is_appendable,
+        );
 
         let mut is_loaded = true;
         for ref mut index in indexes.iter_mut() {
@@ -157,6 +270,7 @@ impl StructPayloadIndex {
             path: path.to_owned(),
             visited_pool: Default::default(),
             db,
+            is_appendable,
         };
 
         if !index.config_path().exists() {
@@ -164,7 +278,7 @@ impl StructPayloadIndex {
             index.save_config()?;
         }
 
-        index.load_all_fields(is_appendable)?;
+        index.load_all_fields()?;
 
         Ok(index)
     }
@@ -175,7 +289,8 @@ impl StructPayloadIndex {
         payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         let payload_storage = self.payload.borrow();
-        let mut field_indexes = index_selector(field, &payload_schema, self.db.clone(), true);
+        let mut field_indexes =
+            index_selector(field, &payload_schema, self.db.clone(), &self.path, true);
         for index in &field_indexes {
             index.recreate()?;
         }
@@ -187,7 +302,25 @@ impl StructPayloadIndex {
             }
             Ok(true)
         })?;
-        Ok(field_indexes)
+
+        if self.is_appendable || !payload_schema.is_on_disk() {
+            return Ok(field_indexes);
+        }
+
+        // Move built in-memory indexes into memory mapped files
+        let mut mmap_indexes = index_selector(
+            field,
+            &payload_schema,
+            self.db.clone(),
+            &self.path,
+            self.is_appendable,
+        );
+        for (mmap_index, field_index) in mmap_indexes.iter_mut().zip(field_indexes) {
+            mmap_index.build_from(&field_index)?;
+            mmap_index.flusher()()?;
+            field_index.clear()?;
+        }
+        Ok(mmap_indexes)
     }
 
     fn build_and_save(
@@ -218,6 +351,7 @@ impl StructPayloadIndex {
             &self.field_indexes,
             &estimator,
             self.available_point_count(),
+            self.tenant_points_range(filter),
         )
     }
 
@@ -398,11 +532,14 @@ impl PayloadIndex for StructPayloadIndex {
     fn query_points(&self, query: &Filter) -> Vec<PointOffsetType> {
         // Assume query is already estimated to be small enough so we can iterate over all matched ids
 
-        let query_cardinality = self.estimate_cardinality(query);
+        let primary_clauses = self.query_primary_clauses(query);
 
-        if query_cardinality.primary_clauses.is_empty() {
+        // Only points within the tenant range can match the query
+        let tenant_range = self.tenant_points_range(query);
+
+        if primary_clauses.is_empty() {
             let id_tracker = self.id_tracker.borrow();
-            let full_scan_iterator = id_tracker.iter_ids();
+            let full_scan_iterator = iter_points_in_range(&id_tracker, tenant_range);
 
             let struct_filtered_context = self.struct_filtered_context(query);
             // Worst case: query expected to return few matches, but index can't be used
@@ -420,19 +557,20 @@ impl PayloadIndex for StructPayloadIndex {
                 .visited_pool
                 .get(points_iterator_ref.total_point_count());
 
-            let preselected: Vec<PointOffsetType> = query_cardinality
-                .primary_clauses
+            let candidates = || iter_points_in_range(&points_iterator_ref, tenant_range.clone());
+
+            let preselected: Vec<PointOffsetType> = primary_clauses
                 .iter()
                 .flat_map(|clause| {
                     match clause {
                         PrimaryCondition::Condition(field_condition) => {
                             self.query_field(field_condition).unwrap_or_else(
-                                || points_iterator_ref.iter_ids(), /* index is not built */
+                                candidates, /* index is not built */
                             )
                         }
                         PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
-                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
-                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
+                        PrimaryCondition::IsEmpty(_) => candidates(), /* there are no fast index for IsEmpty */
+                        PrimaryCondition::IsNull(_) => candidates(),  /* no fast index for IsNull too */
                     }
                 })
                 .filter(|&id| !visited_list.check_and_update_visited(id))
@@ -572,6 +710,13 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        for field_indexes in self.field_indexes.values() {
+            for index in field_indexes {
+                files.extend(index.files());
+            }
+        }
+        files.extend(self.payload.borrow().files());
+        files
     }
 }
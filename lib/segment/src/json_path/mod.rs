@@ -183,6 +183,26 @@ impl JsonPath {
         self.first_key == pattern.first_key && pattern.rest.starts_with(&self.rest)
     }
 
+    /// Filename-safe representation of the path, e.g. to name on-disk index files.
+    ///
+    /// Unsafe characters are replaced, so a hash of the full path is appended to keep
+    /// distinct paths apart.
+    pub fn filename(&self) -> String {
+        let path = self.to_string();
+        let sanitized: String = path
+            .chars()
+            .take(32)
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{sanitized}-{:016x}", seahash::hash(path.as_bytes()))
+    }
+
     pub fn extend_or_new(base: Option<&Self>, other: &Self) -> Self {
         base.map_or_else(|| other.clone(), |base| base.extend(other))
     }
@@ -519,6 +539,15 @@ mod tests {
         assert!(!JsonPath::new("a.x").is_affected_by_value_set(&json(r#"{"b": {"x": 1}}"#), None));
     }
 
+    #[test]
+    fn test_filename() {
+        let filename = JsonPath::new("a.\"b/../c\"[].d").filename();
+        assert!(filename.starts_with("a_"));
+        assert!(!filename.contains(['/', '.', '"']));
+        assert_ne!(filename, JsonPath::new("a.b.c").filename());
+        assert_eq!(filename, JsonPath::new("a.\"b/../c\"[].d").filename());
+    }
+
     #[test]
     fn test_is_affected_by_value_remove() {
         assert!(JsonPath::new("a").is_affected_by_value_remove(&JsonPath::new("a")));
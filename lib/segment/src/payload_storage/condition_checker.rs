@@ -6,7 +6,7 @@ use serde_json::Value;
 
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
+    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchPhrase, MatchText, MatchValue, Range,
     RangeInterface, ValueVariants, ValuesCount,
 };
 
@@ -91,6 +91,10 @@ impl ValueChecker for Match {
                 Value::String(stored) => stored.contains(text),
                 _ => false,
             },
+            Match::Phrase(MatchPhrase { phrase, slop }) => match payload {
+                Value::String(stored) => check_phrase(stored, phrase, slop.unwrap_or(0)),
+                _ => false,
+            },
             Match::Any(MatchAny { any }) => match (payload, any) {
                 (Value::String(stored), AnyVariants::Keywords(list)) => {
                     if list.len() < INDEXSET_ITER_THRESHOLD {
@@ -212,6 +216,40 @@ impl ValueChecker for GeoPolygon {
     }
 }
 
+/// Check that words of the `phrase` appear in the `text` in the same order,
+/// with at most `slop` other words in between.
+///
+/// Words are compared as is, without normalization of the full-text index.
+fn check_phrase(text: &str, phrase: &str, slop: u32) -> bool {
+    let split_words = |text: &str| -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_owned)
+            .collect()
+    };
+    let text_words = split_words(text);
+    let phrase_words = split_words(phrase);
+    let Some((first_word, next_words)) = phrase_words.split_first() else {
+        return false;
+    };
+    let max_span = next_words.len() + slop as usize;
+
+    text_words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| *word == first_word)
+        .any(|(start, _)| {
+            let mut current = start;
+            for word in next_words {
+                match text_words[current + 1..].iter().position(|w| w == word) {
+                    Some(offset) => current += offset + 1,
+                    None => return false,
+                }
+            }
+            current - start <= max_span
+        })
+}
+
 impl ValueChecker for ValuesCount {
     fn check_match(&self, payload: &Value) -> bool {
         self.check_count(payload)
@@ -261,6 +299,18 @@ mod tests {
         assert!(!miss_geo_query.check(&berlin_and_moscow));
     }
 
+    #[test]
+    fn test_phrase_matching() {
+        let text = json!("Welcome to New York, the city that never sleeps");
+
+        assert!(Match::new_phrase("New York", None).check(&text));
+        assert!(Match::new_phrase("city never sleeps", Some(1)).check(&text));
+        assert!(!Match::new_phrase("city never sleeps", None).check(&text));
+        assert!(!Match::new_phrase("York New", Some(10)).check(&text));
+        assert!(!Match::new_phrase("new york", None).check(&text));
+        assert!(!Match::new_phrase("", None).check(&text));
+    }
+
     #[test]
     fn test_value_count() {
         let countries = json!([
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use common::types::PointOffsetType;
 use serde_json::Value;
@@ -68,6 +69,10 @@ impl PayloadStorage for InMemoryPayloadStorage {
     fn flusher(&self) -> Flusher {
         Box::new(|| Ok(()))
     }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
 }
 
 #[cfg(test)]
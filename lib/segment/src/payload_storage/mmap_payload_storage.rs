@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use crate::common::blob_store::{Blob, BlobStore};
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::json_path::JsonPath;
+use crate::payload_storage::PayloadStorage;
+use crate::types::Payload;
+
+pub const PAYLOAD_STORAGE_PATH: &str = "payload_storage";
+
+impl Blob for Payload {
+    fn to_bytes(&self) -> Vec<u8> {
+        serde_cbor::to_vec(self).unwrap()
+    }
+
+    fn from_bytes(data: &[u8]) -> OperationResult<Self> {
+        Ok(serde_cbor::from_slice(data)?)
+    }
+}
+
+/// Payload storage, which keeps serialized payloads in memory mapped blob storage
+#[derive(Debug)]
+pub struct MmapPayloadStorage {
+    storage: BlobStore<Payload>,
+}
+
+impl MmapPayloadStorage {
+    /// Open storage in the segment directory
+    ///
+    /// If `populate` is set, all payloads are loaded into the page cache.
+    pub fn open_or_create(segment_path: &Path, populate: bool) -> OperationResult<Self> {
+        let storage = BlobStore::open(&segment_path.join(PAYLOAD_STORAGE_PATH))?;
+        if populate {
+            storage.populate();
+        }
+        Ok(Self { storage })
+    }
+
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        self.storage.get(point_id)
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        self.storage
+            .iter(|point_id, payload| callback(point_id, &payload))
+    }
+}
+
+impl PayloadStorage for MmapPayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.storage.put(point_id, payload)
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self.read_payload(point_id)? {
+            Some(mut point_payload) => {
+                point_payload.merge(payload);
+                self.storage.put(point_id, &point_payload)
+            }
+            None => self.storage.put(point_id, payload),
+        }
+    }
+
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: &JsonPath,
+    ) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_by_key(payload, key)?;
+        self.storage.put(point_id, &point_payload)
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.storage.put(point_id, &payload)?;
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        self.storage.delete(point_id)?;
+        Ok(payload)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        self.storage.clear()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.storage.flusher()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        self.storage.files()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_payload_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let payload: Payload = serde_json::from_str(
+            r#"{
+                "name": "John Doe",
+                "age": 52,
+                "location": {
+                    "city": "Melbourne",
+                    "geo": { "lon": 144.9631, "lat": 37.8136 }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        {
+            let mut storage = MmapPayloadStorage::open_or_create(dir.path(), false).unwrap();
+            storage.assign_all(100, &payload).unwrap();
+            storage.assign_all(101, &payload).unwrap();
+
+            let partial_payload: Payload = serde_json::from_str(r#"{ "age": 53 }"#).unwrap();
+            storage.assign(100, &partial_payload).unwrap();
+            storage.delete(100, &JsonPath::new("location.geo")).unwrap();
+            assert_eq!(storage.drop(101).unwrap(), Some(payload.clone()));
+            storage.flusher()().unwrap();
+        }
+
+        let mut storage = MmapPayloadStorage::open_or_create(dir.path(), true).unwrap();
+        let res = storage.payload(100).unwrap();
+        assert_eq!(res.0["age"], 53);
+        assert_eq!(
+            res.0["location"],
+            serde_json::json!({ "city": "Melbourne" })
+        );
+        assert_eq!(storage.payload(101).unwrap(), Payload::default());
+
+        storage.wipe().unwrap();
+        assert_eq!(storage.payload(100).unwrap(), Payload::default());
+        storage.assign(100, &payload).unwrap();
+        assert_eq!(storage.payload(100).unwrap(), payload);
+    }
+}
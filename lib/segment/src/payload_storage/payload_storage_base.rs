@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
@@ -41,6 +43,9 @@ pub trait PayloadStorage {
 
     /// Return function that forces persistence of current storage state.
     fn flusher(&self) -> Flusher;
+
+    /// Files of the storage, which are not part of the segment database
+    fn files(&self) -> Vec<PathBuf>;
 }
 
 pub trait ConditionChecker {
@@ -16,7 +16,7 @@ use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
     Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
-    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
+    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType, PointIdType,
 };
 
 fn check_condition<F>(checker: &F, condition: &Condition) -> bool
@@ -157,6 +157,37 @@ where
     check_filter(&checker, query)
 }
 
+/// Checks the filter against a standalone payload of a point, without relying on segment internals.
+///
+/// Conditions are checked against the payload values only, field indexes are not used.
+pub fn check_point_payload(point_id: PointIdType, payload: &Payload, query: &Filter) -> bool {
+    let checker = |condition: &Condition| match condition {
+        Condition::Field(field_condition) => {
+            check_field_condition(field_condition, payload, &IndexesMap::new())
+        }
+        Condition::IsEmpty(is_empty) => check_is_empty_condition(is_empty, payload),
+        Condition::IsNull(is_null) => check_is_null_condition(is_null, payload),
+        Condition::HasId(has_id) => has_id.has_id.contains(&point_id),
+        Condition::Nested(nested) => payload
+            .get_value(&nested.array_key())
+            .iter()
+            .filter_map(|value| value.as_object())
+            .any(|object| {
+                check_payload(
+                    Box::new(|| OwnedPayloadRef::from(object)),
+                    None,
+                    &nested.nested.filter,
+                    0,
+                    &IndexesMap::new(),
+                )
+            }),
+        Condition::Resharding(cond) => cond.check(point_id),
+        Condition::Filter(_) => unreachable!(),
+    };
+
+    check_filter(&checker, query)
+}
+
 pub fn check_is_empty_condition(
     is_empty: &IsEmptyCondition,
     payload: &impl PayloadContainer,
@@ -268,6 +299,10 @@ impl ConditionChecker for SimpleConditionChecker {
                                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                                 .map(|x| x.into())
                         }
+                        PayloadStorageEnum::MmapPayloadStorage(s) => s
+                            .read_payload(point_id)
+                            .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                            .map(|x| x.into()),
                     };
 
                     payload_ref_cell
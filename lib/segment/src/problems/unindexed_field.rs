@@ -201,13 +201,17 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     if let Some(r#match) = r#match {
         inferred.push(match r#match {
             Match::Value(match_value) => infer_schema_from_match_value(match_value),
-            Match::Text(_match_text) => {
+            Match::Text(_) | Match::Phrase(_) => {
                 PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(TextIndexParams {
                     r#type: TextIndexType::Text,
                     tokenizer: TokenizerType::default(),
                     min_token_len: None,
                     max_token_len: None,
                     lowercase: None,
+                    ascii_folding: None,
+                    stopwords: None,
+                    stemmer: None,
+                    on_disk: None,
                 }))
             }
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
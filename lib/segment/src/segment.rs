@@ -1,14 +1,15 @@
-use std::cmp::max;
-use std::collections::{HashMap, HashSet};
+use std::cmp::{max, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fmt;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitVec;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
 use io::file_operations::{atomic_save_json, read_json};
 use io::storage_version::{StorageVersion, VERSION_FILE};
 use itertools::Either;
@@ -22,11 +23,17 @@ use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
+use crate::common::sampling::{sample_key, SampleKey};
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
-use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
+use crate::common::{
+    check_named_vectors, check_query_vectors, check_stopped, check_vector_name,
+    check_vector_writable,
+};
+use crate::data_types::facets::{FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
+use crate::data_types::text_query::{TextQuery, TextStatistics};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
@@ -38,9 +45,10 @@ use crate::json_path::JsonPath;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    Condition, FieldCondition, Filter, Payload, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentState, SegmentType, SeqNumberType, VectorDataInfo,
+    WithPayload, WithVector,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
@@ -466,7 +474,15 @@ impl Segment {
                     ),
                 })
             } else {
-                Ok(Some(vector_storage.get_vector(point_offset).to_owned()))
+                let mut vector: Vector = vector_storage.get_vector(point_offset).to_owned();
+                // Truncated view shares the full-length vectors of its source
+                if let (Some(dim), Vector::Dense(dense)) = (
+                    self.segment_config.vector_data[vector_name].truncate_to(),
+                    &mut vector,
+                ) {
+                    dense.truncate(dim);
+                }
+                Ok(Some(vector))
             }
         } else {
             Ok(None)
@@ -479,6 +495,9 @@ impl Segment {
     ) -> OperationResult<NamedVectors> {
         let mut vectors = NamedVectors::default();
         for (vector_name, vector_data) in &self.vector_data {
+            if self.is_truncated_view(vector_name) {
+                continue;
+            }
             let is_vector_deleted = vector_data
                 .vector_storage
                 .borrow()
@@ -495,6 +514,14 @@ impl Segment {
         Ok(vectors)
     }
 
+    /// Whether the named vector is a truncated view over another vector's storage
+    pub(crate) fn is_truncated_view(&self, vector_name: &str) -> bool {
+        self.segment_config
+            .vector_data
+            .get(vector_name)
+            .is_some_and(|config| config.truncated_view.is_some())
+    }
+
     /// Retrieve payload by internal ID
     #[inline]
     fn payload_by_offset(&self, point_offset: PointOffsetType) -> OperationResult<Payload> {
@@ -830,6 +857,42 @@ impl Segment {
             .collect()
     }
 
+    pub fn filtered_read_by_index_random(
+        &self,
+        limit: usize,
+        condition: &Filter,
+        seed: u64,
+    ) -> Vec<SampleKey> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let keys = payload_index
+            .query_points(condition)
+            .into_iter()
+            .filter_map(|internal_id| id_tracker.external_id(internal_id))
+            .map(|external_id| sample_key(seed, external_id));
+        peek_top_smallest_iterable(keys, limit)
+    }
+
+    pub fn filtered_read_by_random_stream(
+        &self,
+        limit: usize,
+        condition: Option<&Filter>,
+        seed: u64,
+    ) -> Vec<SampleKey> {
+        let payload_index = self.payload_index.borrow();
+        let filter_context = condition.map(|condition| payload_index.filter_context(condition));
+        let id_tracker = self.id_tracker.borrow();
+        let keys = id_tracker
+            .iter_from(None)
+            .filter(move |(_, internal_id)| match &filter_context {
+                Some(filter_context) => filter_context.check(*internal_id),
+                None => true,
+            })
+            .map(|(external_id, _)| sample_key(seed, external_id));
+        peek_top_smallest_iterable(keys, limit)
+    }
+
     pub fn filtered_read_by_value_stream(
         &self,
         order_by: &OrderBy,
@@ -1225,6 +1288,7 @@ impl SegmentEntry for Segment {
         vector_name: &str,
     ) -> OperationResult<bool> {
         check_vector_name(vector_name, &self.segment_config)?;
+        check_vector_writable(vector_name, &self.segment_config)?;
         let internal_id = self.id_tracker.borrow().internal_id(point_id);
         match internal_id {
             None => Err(OperationError::PointIdError {
@@ -1392,6 +1456,146 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        seed: u64,
+    ) -> Vec<SampleKey> {
+        match filter {
+            // Whole sample is drawn from all matching points, so no limit for the estimation
+            Some(condition) if self.should_pre_filter(condition, None) => {
+                self.filtered_read_by_index_random(limit, condition, seed)
+            }
+            _ => self.filtered_read_by_random_stream(limit, filter, seed),
+        }
+    }
+
+    fn read_text_scored<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        query: &'a TextQuery,
+        query_context: SegmentQueryContext,
+    ) -> OperationResult<Vec<(ScoreType, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let text_index = payload_index
+            .field_indexes
+            .get(&query.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_full_text()))
+            .ok_or_else(|| OperationError::MissingFullTextIndexForQuery {
+                key: query.key.to_string(),
+            })?;
+
+        let scores = match query_context.get_text_statistics(&query.key) {
+            Some(statistics) => text_index.bm25_scores(&query.query, statistics),
+            None => {
+                let mut statistics = TextStatistics {
+                    queries: vec![query.query.clone()],
+                    ..Default::default()
+                };
+                text_index.fill_text_statistics(&mut statistics);
+                text_index.bm25_scores(&query.query, &statistics)
+            }
+        };
+
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let scored_iterator = scores
+            .into_iter()
+            .filter(|(internal_id, _)| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(*internal_id))
+            })
+            .map(|(idx, score)| ScoredPointOffset { idx, score });
+
+        let id_tracker = self.id_tracker.borrow();
+        let top = peek_top_largest_iterable(scored_iterator, limit)
+            .into_iter()
+            .filter_map(|scored| {
+                id_tracker
+                    .external_id(scored.idx)
+                    .map(|external_id| (scored.score, external_id))
+            })
+            .collect();
+        Ok(top)
+    }
+
+    fn facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, usize>> {
+        let payload_index = self.payload_index.borrow();
+        let facet_index = payload_index
+            .field_indexes
+            .get(&request.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_facet_index()))
+            .ok_or_else(|| OperationError::MissingMapIndexForFacet {
+                key: request.key.to_string(),
+            })?;
+
+        let mut counts = HashMap::new();
+        match &request.filter {
+            // Postings of deleted points are removed from the index, so their sizes are exact
+            None => {
+                counts.extend(
+                    facet_index
+                        .iter_values_with_counts()
+                        .filter(|(_, count)| *count > 0),
+                );
+            }
+            Some(filter) if request.exact => {
+                for (i, point_id) in payload_index.query_points(filter).into_iter().enumerate() {
+                    if i % 1024 == 0 {
+                        check_stopped(is_stopped)?;
+                    }
+                    for value in facet_index.get_point_values(point_id) {
+                        *counts.entry(value).or_insert(0) += 1;
+                    }
+                }
+            }
+            Some(filter) => {
+                let mut values: Vec<_> = facet_index
+                    .iter_values_with_counts()
+                    .filter(|(_, count)| *count > 0)
+                    .collect();
+                values.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+
+                // Estimation for a value can't exceed its unfiltered count,
+                // so stop as soon as the rest of values can't get into the top
+                let mut top_estimations = BinaryHeap::with_capacity(request.limit + 1);
+                for (value, values_count) in values {
+                    check_stopped(is_stopped)?;
+                    if top_estimations.len() >= request.limit
+                        && top_estimations
+                            .peek()
+                            .map_or(true, |Reverse(min)| values_count <= *min)
+                    {
+                        break;
+                    }
+
+                    let value_condition = Condition::Field(FieldCondition::new_match(
+                        request.key.clone(),
+                        value.clone().into(),
+                    ));
+                    let value_filter = filter.merge(&Filter::new_must(value_condition));
+                    let estimation = payload_index.estimate_cardinality(&value_filter).exp;
+                    if estimation == 0 {
+                        continue;
+                    }
+
+                    counts.insert(value, estimation);
+                    top_estimations.push(Reverse(estimation));
+                    if top_estimations.len() > request.limit {
+                        top_estimations.pop();
+                    }
+                }
+            }
+        }
+        Ok(counts)
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -1476,6 +1680,7 @@ impl SegmentEntry for Segment {
                         0
                     },
                     num_deleted_vectors: vector_storage.deleted_vector_count(),
+                    pooling: vector_storage.multi_vector_pooling_info(),
                 };
                 (key.to_string(), vector_data_info)
             })
@@ -1778,7 +1983,7 @@ impl SegmentEntry for Segment {
 
         let files = Path::new(SNAPSHOT_PATH).join(SNAPSHOT_FILES_PATH);
 
-        for vector_data in self.vector_data.values() {
+        for (vector_name, vector_data) in &self.vector_data {
             for file in vector_data.vector_index.borrow().files() {
                 utils::tar::append_file_relative_to_base(
                     &mut builder,
@@ -1788,7 +1993,13 @@ impl SegmentEntry for Segment {
                 )?;
             }
 
-            for file in vector_data.vector_storage.borrow().files() {
+            // Storage of a truncated view is archived with its source vector
+            let storage_files = if self.is_truncated_view(vector_name) {
+                Vec::new()
+            } else {
+                vector_data.vector_storage.borrow().files()
+            };
+            for file in storage_files {
                 utils::tar::append_file_relative_to_base(
                     &mut builder,
                     &self.current_path,
@@ -1809,6 +2020,15 @@ impl SegmentEntry for Segment {
             }
         }
 
+        for file in self.id_tracker.borrow().files() {
+            utils::tar::append_file_relative_to_base(
+                &mut builder,
+                &self.current_path,
+                &file,
+                &files,
+            )?;
+        }
+
         for file in self.payload_index.borrow().files() {
             utils::tar::append_file_relative_to_base(
                 &mut builder,
@@ -1873,6 +2093,17 @@ impl SegmentEntry for Segment {
                 vector_data.vector_index.borrow().fill_idf_statistics(idf);
             }
         }
+
+        let payload_index = self.payload_index.borrow();
+        for (key, statistics) in query_context.mut_text_statistics().iter_mut() {
+            let text_index = payload_index
+                .field_indexes
+                .get(key)
+                .and_then(|indexes| indexes.iter().find_map(|index| index.as_full_text()));
+            if let Some(text_index) = text_index {
+                text_index.fill_text_statistics(statistics);
+            }
+        }
     }
 }
 
@@ -1912,6 +2143,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1986,6 +2218,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2078,6 +2311,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2174,6 +2408,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2208,6 +2443,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2303,6 +2539,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -2358,6 +2595,7 @@ mod tests {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
                 (
@@ -2370,6 +2608,7 @@ mod tests {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
             ]),
@@ -2475,6 +2714,7 @@ mod tests {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
                 (
@@ -2487,6 +2727,7 @@ mod tests {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
             ]),
@@ -2649,6 +2890,7 @@ mod tests {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -1,12 +1,18 @@
 use std::cmp;
+use std::collections::hash_map::{DefaultHasher, Entry};
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
 use io::storage_version::StorageVersion;
+use serde_json::Value;
 
 use super::{
     create_id_tracker, create_payload_storage, create_sparse_vector_index,
@@ -18,15 +24,17 @@ use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::index::hnsw_index::old_index::OldIndexCandidate;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndexOpenArgs;
 use crate::index::struct_payload_index::StructPayloadIndex;
-use crate::index::PayloadIndex;
+use crate::index::{PayloadIndex, VectorIndexEnum};
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::load_segment;
 use crate::types::{
-    PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
+    DateTimePayloadType, Payload, PayloadContainer, PayloadFieldSchema, PayloadKeyType,
+    PayloadSchemaType, PointIdType, SegmentConfig, SegmentState, SeqNumberType,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
@@ -44,6 +52,8 @@ pub struct SegmentBuilder {
     // Path to the temporary segment directory
     temp_path: PathBuf,
     indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    // HNSW indices of source segments to reuse the graphs of, by vector name
+    old_indices: HashMap<String, OldIndexCandidate>,
 }
 
 impl SegmentBuilder {
@@ -60,13 +70,18 @@ impl SegmentBuilder {
 
         let database = open_segment_db(&temp_path, segment_config)?;
 
-        let id_tracker = create_id_tracker(database.clone())?;
+        let id_tracker = create_id_tracker(database.clone(), &temp_path)?;
 
-        let payload_storage = create_payload_storage(database.clone(), segment_config)?;
+        let payload_storage = create_payload_storage(database.clone(), segment_config, &temp_path)?;
 
         let mut vector_storages = HashMap::new();
 
         for (vector_name, vector_config) in &segment_config.vector_data {
+            // Truncated views share the storage of their source vector
+            if vector_config.truncated_view.is_some() {
+                continue;
+            }
+
             let vector_storage_path = get_vector_storage_path(&temp_path, vector_name);
             let vector_storage = open_vector_storage(
                 &database,
@@ -84,8 +99,13 @@ impl SegmentBuilder {
             // `_sparse_vector_config` should be used, once we are able to initialize storage with
             // different datatypes
 
-            let vector_storage =
-                create_sparse_vector_storage(database.clone(), vector_name, &stopped)?;
+            let vector_storage_path = get_vector_storage_path(&temp_path, vector_name);
+            let vector_storage = create_sparse_vector_storage(
+                database.clone(),
+                &vector_storage_path,
+                vector_name,
+                &stopped,
+            )?;
             vector_storages.insert(vector_name.to_owned(), vector_storage);
         }
 
@@ -101,6 +121,7 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            old_indices: Default::default(),
         })
     }
 
@@ -124,39 +145,174 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update_from(&mut self, other: &Segment, stopped: &AtomicBool) -> OperationResult<bool> {
-        self.version = cmp::max(self.version, other.version());
+        self.update(&[other], stopped)
+    }
 
-        let other_id_tracker = other.id_tracker.borrow();
-        let other_vector_storages: HashMap<_, _> = other
-            .vector_data
+    /// Update current segment builder with all (not deleted) vectors and payload from `segments`
+    ///
+    /// If some of the indexed fields are marked as tenant fields, points are reordered,
+    /// so that points with the same tenant value are stored next to each other.
+    /// If there is a principal field, points are additionally sorted by its value.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - segments to add into construction
+    ///
+    /// # Result
+    ///
+    /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
+    ///
+    pub fn update(&mut self, segments: &[&Segment], stopped: &AtomicBool) -> OperationResult<bool> {
+        for segment in segments {
+            self.version = cmp::max(self.version, segment.version());
+
+            for (field, payload_schema) in segment.payload_index.borrow().indexed_fields() {
+                self.indexed_fields.insert(field, payload_schema);
+            }
+        }
+
+        let other_id_trackers: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.id_tracker.borrow())
+            .collect();
+        let other_vector_storages: Vec<HashMap<_, _>> = segments
             .iter()
-            .map(|(vector_name, vector_data)| {
-                (vector_name.to_owned(), vector_data.vector_storage.borrow())
+            .map(|segment| {
+                segment
+                    .vector_data
+                    .iter()
+                    .filter(|(vector_name, _)| !segment.is_truncated_view(vector_name))
+                    .map(|(vector_name, vector_data)| {
+                        (vector_name.to_owned(), vector_data.vector_storage.borrow())
+                    })
+                    .collect()
             })
             .collect();
-        let other_payload_index = other.payload_index.borrow();
+        let other_payload_indices: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.payload_index.borrow())
+            .collect();
 
-        let id_tracker = &mut self.id_tracker;
+        for other_vector_storages in &other_vector_storages {
+            if self.vector_storages.len() != other_vector_storages.len() {
+                return Err(OperationError::service_error(
+                    format!("Self and other segments have different vector names count. Self count: {}, other count: {}", self.vector_storages.len(), other_vector_storages.len()),
+                ));
+            }
+        }
+
+        // Take the latest version of each point across all the source segments
+        let mut latest_points: HashMap<PointIdType, PointData> = HashMap::new();
+        for (segment_index, other_id_tracker) in other_id_trackers.iter().enumerate() {
+            for internal_id in other_id_tracker.iter_ids() {
+                check_process_stopped(stopped)?;
 
-        if self.vector_storages.len() != other_vector_storages.len() {
-            return Err(OperationError::service_error(
-                format!("Self and other segments have different vector names count. Self count: {}, other count: {}", self.vector_storages.len(), other_vector_storages.len()),
-            ));
+                let Some(external_id) = other_id_tracker.external_id(internal_id) else {
+                    log::warn!("Cannot find external id for internal id {internal_id}, skipping");
+                    continue;
+                };
+
+                let version = other_id_tracker
+                    .internal_version(internal_id)
+                    .unwrap_or_else(|| {
+                        log::debug!(
+                            "Internal version not found for internal id {internal_id}, using 0"
+                        );
+                        0
+                    });
+
+                let point = PointData {
+                    external_id,
+                    segment_index,
+                    internal_id,
+                    version,
+                    ordering: 0,
+                    principal_ordering: 0,
+                };
+
+                match latest_points.entry(external_id) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(point);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        if entry.get().version < version {
+                            entry.insert(point);
+                        }
+                    }
+                }
+            }
         }
 
+        let mut points: Vec<_> = latest_points.into_values().collect();
+
+        let mut tenant_fields: Vec<_> = self
+            .indexed_fields
+            .iter()
+            .filter(|(_, payload_schema)| payload_schema.is_tenant())
+            .map(|(field, _)| field.clone())
+            .collect();
+        tenant_fields.sort_by_cached_key(|field| field.to_string());
+
+        // Only one field can define the order of points, pick it deterministically
+        let principal_field = self
+            .indexed_fields
+            .iter()
+            .filter(|(_, payload_schema)| payload_schema.is_principal())
+            .map(|(field, payload_schema)| (field.clone(), payload_schema.expand().kind()))
+            .min_by_key(|(field, _)| field.to_string());
+
+        if !tenant_fields.is_empty() || principal_field.is_some() {
+            for point in &mut points {
+                check_process_stopped(stopped)?;
+                let payload =
+                    other_payload_indices[point.segment_index].payload(point.internal_id)?;
+                point.ordering = tenant_ordering(&payload, &tenant_fields);
+                if let Some((field, field_type)) = &principal_field {
+                    point.principal_ordering = principal_ordering(&payload, field, *field_type);
+                }
+            }
+        }
+
+        // Group points of the same tenant together and order them by the principal field,
+        // keep original order otherwise
+        points.sort_unstable_by_key(|point| {
+            (
+                point.ordering,
+                point.principal_ordering,
+                point.segment_index,
+                point.internal_id,
+            )
+        });
+
         let mut new_internal_range = None;
         for (vector_name, vector_storage) in &mut self.vector_storages {
             check_process_stopped(stopped)?;
-            let other_vector_storage = other_vector_storages.get(vector_name).ok_or_else(|| {
-                OperationError::service_error(format!(
-                    "Cannot update from other segment because if missing vector name {vector_name}"
-                ))
-            })?;
-            let internal_range = vector_storage.update_from(
-                other_vector_storage,
-                &mut other_id_tracker.iter_ids(),
-                stopped,
-            )?;
+
+            let mut internal_range: Option<Range<PointOffsetType>> = None;
+            for chunk in points.chunk_by(|a, b| a.segment_index == b.segment_index) {
+                let segment_index = chunk[0].segment_index;
+                let other_vector_storage = other_vector_storages[segment_index]
+                    .get(vector_name)
+                    .ok_or_else(|| {
+                        OperationError::service_error(format!(
+                            "Cannot update from other segment because if missing vector name {vector_name}"
+                        ))
+                    })?;
+                let chunk_range = vector_storage.update_from(
+                    other_vector_storage,
+                    &mut chunk.iter().map(|point| point.internal_id),
+                    stopped,
+                )?;
+                internal_range = Some(match internal_range {
+                    Some(internal_range) => internal_range.start..chunk_range.end,
+                    None => chunk_range,
+                });
+            }
+            let Some(internal_range) = internal_range else {
+                // Nothing to add
+                continue;
+            };
+
             match new_internal_range.clone() {
                 Some(new_internal_range) => {
                     if new_internal_range != internal_range {
@@ -169,37 +325,28 @@ impl SegmentBuilder {
             }
         }
 
+        if let Some(new_internal_range) = &new_internal_range {
+            self.update_old_indices(segments, &points, new_internal_range.start);
+        }
+
+        let id_tracker = &mut self.id_tracker;
+
         if let Some(new_internal_range) = new_internal_range {
-            let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
+            let internal_id_iter = new_internal_range.zip(points.iter());
 
-            for (new_internal_id, old_internal_id) in internal_id_iter {
+            for (new_internal_id, point) in internal_id_iter {
                 check_process_stopped(stopped)?;
 
-                let external_id =
-                    if let Some(external_id) = other_id_tracker.external_id(old_internal_id) {
-                        external_id
-                    } else {
-                        log::warn!(
-                            "Cannot find external id for internal id {old_internal_id}, skipping"
-                        );
-                        continue;
-                    };
-
-                let other_version = other_id_tracker
-                    .internal_version(old_internal_id)
-                    .unwrap_or_else(|| {
-                        log::debug!(
-                            "Internal version not found for internal id {old_internal_id}, using 0"
-                        );
-                        0
-                    });
+                let other_payload_index = &other_payload_indices[point.segment_index];
+                let external_id = point.external_id;
+                let other_version = point.version;
 
                 match id_tracker.internal_id(external_id) {
                     None => {
                         // New point, just insert
                         id_tracker.set_link(external_id, new_internal_id)?;
                         id_tracker.set_internal_version(new_internal_id, other_version)?;
-                        let other_payload = other_payload_index.payload(old_internal_id)?;
+                        let other_payload = other_payload_index.payload(point.internal_id)?;
                         // Propagate payload to new segment
                         if !other_payload.is_empty() {
                             self.payload_storage
@@ -217,7 +364,7 @@ impl SegmentBuilder {
                             id_tracker.set_link(external_id, new_internal_id)?;
                             id_tracker.set_internal_version(new_internal_id, other_version)?;
                             self.payload_storage.drop(existing_internal_id)?;
-                            let other_payload = other_payload_index.payload(old_internal_id)?;
+                            let other_payload = other_payload_index.payload(point.internal_id)?;
                             // Propagate payload to new segment
                             if !other_payload.is_empty() {
                                 self.payload_storage
@@ -237,16 +384,68 @@ impl SegmentBuilder {
             }
         }
 
-        for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
-            self.indexed_fields.insert(field, payload_schema);
-        }
-
         id_tracker.mapping_flusher()()?;
         id_tracker.versions_flusher()()?;
 
         Ok(true)
     }
 
+    /// Pick HNSW indices of source segments, which graphs can be reused for the new segment.
+    ///
+    /// Only one graph can be reused per vector, so the one covering most points is kept.
+    /// Points of the source segments are placed in the order of `points`, starting from
+    /// `first_internal_id`.
+    fn update_old_indices(
+        &mut self,
+        segments: &[&Segment],
+        points: &[PointData],
+        first_internal_id: PointOffsetType,
+    ) {
+        for (vector_name, vector_config) in &self.segment_config.vector_data {
+            for (segment_index, segment) in segments.iter().enumerate() {
+                let Some(old_vector_config) = segment.config().vector_data.get(vector_name) else {
+                    continue;
+                };
+                if !OldIndexCandidate::is_compatible(old_vector_config, vector_config) {
+                    continue;
+                }
+
+                let Some(vector_data) = segment.vector_data.get(vector_name) else {
+                    continue;
+                };
+                let is_hnsw = matches!(
+                    &*vector_data.vector_index.borrow(),
+                    VectorIndexEnum::HnswRam(_) | VectorIndexEnum::HnswMmap(_),
+                );
+                if !is_hnsw {
+                    continue;
+                }
+
+                let total_vector_count = vector_data.vector_storage.borrow().total_vector_count();
+                let mut old_to_new = vec![None; total_vector_count];
+                for (offset, point) in points.iter().enumerate() {
+                    if point.segment_index == segment_index {
+                        if let Some(new_id) = old_to_new.get_mut(point.internal_id as usize) {
+                            *new_id = Some(first_internal_id + offset as PointOffsetType);
+                        }
+                    }
+                }
+
+                let candidate = OldIndexCandidate {
+                    index: vector_data.vector_index.clone(),
+                    old_to_new,
+                };
+                let reused_points = self
+                    .old_indices
+                    .get(vector_name)
+                    .map_or(0, |old_index| old_index.reused_points());
+                if candidate.reused_points() > reused_points {
+                    self.old_indices.insert(vector_name.clone(), candidate);
+                }
+            }
+        }
+    }
+
     pub fn build(self, permit: CpuPermit, stopped: &AtomicBool) -> Result<Segment, OperationError> {
         let (temp_path, destination_path) = {
             let SegmentBuilder {
@@ -258,6 +457,7 @@ impl SegmentBuilder {
                 destination_path,
                 temp_path,
                 indexed_fields,
+                mut old_indices,
             } = self;
 
             let appendable_flag = segment_config.is_appendable();
@@ -297,18 +497,34 @@ impl SegmentBuilder {
                 stopped,
             )?;
 
-            for (vector_name, vector_config) in &segment_config.vector_data {
+            // Build truncated views last, they reuse the storage of their source vector
+            let mut dense_vector_configs: Vec<_> = segment_config.vector_data.iter().collect();
+            dense_vector_configs
+                .sort_by_key(|(_, vector_config)| vector_config.truncated_view.is_some());
+
+            let mut vector_storage_arcs = HashMap::new();
+
+            for (vector_name, vector_config) in dense_vector_configs {
                 let vector_index_path = get_vector_index_path(&temp_path, vector_name);
 
-                let Some(vector_storage) = vector_storages.remove(vector_name) else {
-                    return Err(OperationError::service_error(format!(
+                let storage_name = vector_config
+                    .truncated_view
+                    .as_ref()
+                    .map_or(vector_name, |view| &view.source);
+
+                let vector_storage_arc =
+                    if let Some(vector_storage) = vector_storages.remove(vector_name) {
+                        vector_storage.flusher()()?;
+                        let vector_storage_arc = Arc::new(AtomicRefCell::new(vector_storage));
+                        vector_storage_arcs.insert(vector_name.clone(), vector_storage_arc.clone());
+                        vector_storage_arc
+                    } else if let Some(vector_storage_arc) = vector_storage_arcs.get(storage_name) {
+                        vector_storage_arc.clone()
+                    } else {
+                        return Err(OperationError::service_error(format!(
                         "Vector storage for vector name {vector_name} not found on segment build"
                     )));
-                };
-
-                vector_storage.flusher()()?;
-
-                let vector_storage_arc = Arc::new(AtomicRefCell::new(vector_storage));
+                    };
 
                 let quantized_vectors = quantized_vectors.remove(vector_name);
                 let quantized_vectors_arc = Arc::new(AtomicRefCell::new(quantized_vectors));
@@ -320,6 +536,7 @@ impl SegmentBuilder {
                     vector_storage_arc,
                     payload_index_arc.clone(),
                     quantized_vectors_arc,
+                    old_indices.remove(vector_name),
                     Some(permit.clone()),
                     stopped,
                 )?;
@@ -431,3 +648,73 @@ impl SegmentBuilder {
         Ok(quantized_vectors_map)
     }
 }
+
+/// Point selected to be moved into the new segment
+struct PointData {
+    external_id: PointIdType,
+    /// Index of the source segment
+    segment_index: usize,
+    /// Internal id in the source segment
+    internal_id: PointOffsetType,
+    version: SeqNumberType,
+    /// Points are placed into the new segment in ascending order of this key
+    ordering: u64,
+    /// Order of points within the same `ordering` key
+    principal_ordering: u64,
+}
+
+/// Key, which is equal for the points with the same values of all tenant fields
+fn tenant_ordering(payload: &Payload, tenant_fields: &[PayloadKeyType]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for field in tenant_fields {
+        let value = payload.get_value(field).first().map(|value| match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        });
+        value.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Order-preserving mapping of the principal field value into `u64`
+///
+/// Points without a value of the field are placed last.
+fn principal_ordering(
+    payload: &Payload,
+    principal_field: &PayloadKeyType,
+    field_type: PayloadSchemaType,
+) -> u64 {
+    let values = payload.get_value(principal_field);
+    let Some(value) = values.first() else {
+        return u64::MAX;
+    };
+
+    let ordering = match field_type {
+        PayloadSchemaType::Integer => value.as_i64().map(int_ordering),
+        PayloadSchemaType::Float => value.as_f64().map(float_ordering),
+        PayloadSchemaType::Datetime => value
+            .as_str()
+            .and_then(|value| DateTimePayloadType::from_str(value).ok())
+            .map(|datetime| int_ordering(datetime.timestamp())),
+        PayloadSchemaType::Keyword
+        | PayloadSchemaType::Geo
+        | PayloadSchemaType::Text
+        | PayloadSchemaType::Bool
+        | PayloadSchemaType::Uuid => None,
+    };
+
+    ordering.unwrap_or(u64::MAX)
+}
+
+fn int_ordering(value: i64) -> u64 {
+    (value as u64) ^ (1 << 63)
+}
+
+fn float_ordering(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if value.is_sign_negative() {
+        !bits
+    } else {
+        bits ^ (1 << 63)
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
 use io::storage_version::StorageVersion;
 use log::info;
 use parking_lot::{Mutex, RwLock};
@@ -15,11 +16,15 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
-use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
-use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
+use crate::common::rocksdb_wrapper::{
+    open_db, DatabaseColumnWrapper, DB_MAPPING_CF, DB_PAYLOAD_CF, DB_VECTOR_CF, DB_VERSIONS_CF,
+};
+use crate::data_types::vectors::{PackedBits, DEFAULT_VECTOR_NAME};
+use crate::id_tracker::mutable_id_tracker::MutableIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerEnum, IdTrackerSS};
 use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::index::hnsw_index::old_index::OldIndexCandidate;
 use crate::index::plain_payload_index::PlainIndex;
 use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::{
@@ -27,24 +32,29 @@ use crate::index::sparse_index::sparse_vector_index::{
 };
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::mmap_payload_storage::MmapPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
-use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
+use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
 use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
     VectorDataConfig, VectorStorageDatatype, VectorStorageType,
 };
 use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::{
-    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
-    open_appendable_memmap_vector_storage_half,
+    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_bit,
+    open_appendable_memmap_vector_storage_byte, open_appendable_memmap_vector_storage_half,
 };
 use crate::vector_storage::dense::memmap_dense_vector_storage::{
-    open_memmap_vector_storage, open_memmap_vector_storage_byte, open_memmap_vector_storage_half,
+    open_memmap_vector_storage, open_memmap_vector_storage_bit, open_memmap_vector_storage_byte,
+    open_memmap_vector_storage_half,
 };
 use crate::vector_storage::dense::simple_dense_vector_storage::{
-    open_simple_dense_byte_vector_storage, open_simple_dense_half_vector_storage,
-    open_simple_dense_vector_storage,
+    open_simple_dense_bit_vector_storage, open_simple_dense_byte_vector_storage,
+    open_simple_dense_half_vector_storage, open_simple_dense_vector_storage,
+};
+use crate::vector_storage::mmap_sparse_vector_storage::{
+    open_mmap_sparse_vector_storage, MmapSparseVectorStorage,
 };
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::{
     open_appendable_memmap_multi_vector_storage, open_appendable_memmap_multi_vector_storage_byte,
@@ -85,6 +95,12 @@ pub fn get_vector_index_path(segment_path: &Path, vector_name: &str) -> PathBuf
     segment_path.join(get_vector_name_with_prefix(VECTOR_INDEX_PATH, vector_name))
 }
 
+fn bit_multivector_error() -> OperationError {
+    OperationError::ValidationError {
+        description: "Bit datatype is not supported for multivectors".to_string(),
+    }
+}
+
 pub(crate) fn open_vector_storage(
     database: &Arc<RwLock<DB>>,
     vector_config: &VectorDataConfig,
@@ -94,6 +110,18 @@ pub(crate) fn open_vector_storage(
 ) -> OperationResult<VectorStorageEnum> {
     let storage_element_type = vector_config.datatype.unwrap_or_default();
 
+    if storage_element_type == VectorStorageDatatype::Bit
+        && vector_config.size % PackedBits::BITS != 0
+    {
+        return Err(OperationError::ValidationError {
+            description: format!(
+                "Vector size must be a multiple of {} for bit datatype, got {}",
+                PackedBits::BITS,
+                vector_config.size,
+            ),
+        });
+    }
+
     match vector_config.storage_type {
         // In memory
         VectorStorageType::Memory => {
@@ -125,6 +153,7 @@ pub(crate) fn open_vector_storage(
                         *multi_vec_config,
                         stopped,
                     ),
+                    VectorStorageDatatype::Bit => Err(bit_multivector_error()),
                 }
             } else {
                 match storage_element_type {
@@ -149,6 +178,13 @@ pub(crate) fn open_vector_storage(
                         vector_config.distance,
                         stopped,
                     ),
+                    VectorStorageDatatype::Bit => open_simple_dense_bit_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                        stopped,
+                    ),
                 }
             }
         }
@@ -179,6 +215,7 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Bit => Err(bit_multivector_error()),
                 }
             } else {
                 match storage_element_type {
@@ -197,6 +234,11 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Bit => open_memmap_vector_storage_bit(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                 }
             }
         }
@@ -226,6 +268,7 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Bit => Err(bit_multivector_error()),
                 }
             } else {
                 match storage_element_type {
@@ -244,6 +287,11 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Bit => open_appendable_memmap_vector_storage_bit(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                 }
             }
         }
@@ -269,27 +317,106 @@ pub(crate) fn open_segment_db(
         .map_err(|err| OperationError::service_error(format!("RocksDB open error: {err}")))
 }
 
+/// Check whether the column of the segment database has any stored records
+fn rocksdb_column_has_data(database: &Arc<RwLock<DB>>, column_name: &str) -> OperationResult<bool> {
+    let db_wrapper = DatabaseColumnWrapper::new(database.clone(), column_name);
+    if !db_wrapper.has_column_family()? {
+        return Ok(false);
+    }
+    let has_data = db_wrapper.lock_db().iter()?.next().is_some();
+    Ok(has_data)
+}
+
+/// Payload storage is always created in the mmap format.
+///
+/// Payloads of segments created with RocksDB are migrated on load. RocksDB column is cleared only
+/// after the new storage is fully persisted, so an interrupted migration is simply restarted.
 pub(crate) fn create_payload_storage(
     database: Arc<RwLock<DB>>,
     config: &SegmentConfig,
+    segment_path: &Path,
 ) -> OperationResult<PayloadStorageEnum> {
-    let payload_storage = match config.payload_storage_type {
-        PayloadStorageType::InMemory => {
-            PayloadStorageEnum::from(SimplePayloadStorage::open(database)?)
-        }
-        PayloadStorageType::OnDisk => {
-            PayloadStorageEnum::from(OnDiskPayloadStorage::open(database)?)
-        }
+    let populate = match config.payload_storage_type {
+        PayloadStorageType::InMemory => true,
+        PayloadStorageType::OnDisk => false,
     };
-    Ok(payload_storage)
+    if rocksdb_column_has_data(&database, DB_PAYLOAD_CF)? {
+        migrate_rocksdb_payload_storage(database, segment_path)?;
+    }
+    let payload_storage = MmapPayloadStorage::open_or_create(segment_path, populate)?;
+    Ok(PayloadStorageEnum::from(payload_storage))
 }
 
-pub(crate) fn create_id_tracker(database: Arc<RwLock<DB>>) -> OperationResult<IdTrackerEnum> {
-    Ok(IdTrackerEnum::MutableIdTracker(SimpleIdTracker::open(
-        database,
+fn migrate_rocksdb_payload_storage(
+    database: Arc<RwLock<DB>>,
+    segment_path: &Path,
+) -> OperationResult<()> {
+    info!(
+        "Migrating payload storage of segment {} from RocksDB",
+        segment_path.display(),
+    );
+
+    let mut payload_storage = MmapPayloadStorage::open_or_create(segment_path, false)?;
+    // Drop leftovers of an interrupted migration
+    payload_storage.wipe()?;
+
+    let old_payload_storage = OnDiskPayloadStorage::open(database.clone())?;
+    old_payload_storage.iter(|point_id, payload| {
+        payload_storage.assign_all(point_id, payload)?;
+        Ok(true)
+    })?;
+    payload_storage.flusher()()?;
+    drop(old_payload_storage);
+
+    DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF).recreate_column_family()?;
+    Ok(())
+}
+
+/// Id tracker is always created in the file based format.
+///
+/// Mappings of segments created with RocksDB are migrated on load, see [`create_payload_storage`].
+pub(crate) fn create_id_tracker(
+    database: Arc<RwLock<DB>>,
+    segment_path: &Path,
+) -> OperationResult<IdTrackerEnum> {
+    if rocksdb_column_has_data(&database, DB_MAPPING_CF)? {
+        migrate_rocksdb_id_tracker(database, segment_path)?;
+    }
+    Ok(IdTrackerEnum::MutableIdTracker(MutableIdTracker::open(
+        segment_path,
     )?))
 }
 
+fn migrate_rocksdb_id_tracker(
+    database: Arc<RwLock<DB>>,
+    segment_path: &Path,
+) -> OperationResult<()> {
+    info!(
+        "Migrating id tracker of segment {} from RocksDB",
+        segment_path.display(),
+    );
+
+    // Drop leftovers of an interrupted migration
+    MutableIdTracker::clear_storage(segment_path)?;
+    let mut id_tracker = MutableIdTracker::open(segment_path)?;
+
+    let old_id_tracker = SimpleIdTracker::open(database.clone())?;
+    for (external_id, internal_id) in old_id_tracker.iter_from(None) {
+        id_tracker.set_link(external_id, internal_id)?;
+        if let Some(version) = old_id_tracker.internal_version(internal_id) {
+            id_tracker.set_internal_version(internal_id, version)?;
+        }
+    }
+    id_tracker.mapping_flusher()()?;
+    id_tracker.versions_flusher()()?;
+    drop(old_id_tracker);
+
+    // Mappings go first: without them, leftover versions are never read
+    DatabaseColumnWrapper::new(database.clone(), DB_MAPPING_CF).recreate_column_family()?;
+    DatabaseColumnWrapper::new(database, DB_VERSIONS_CF).recreate_column_family()?;
+    Ok(())
+}
+
 pub(crate) fn get_payload_index_path(segment_path: &Path) -> PathBuf {
     segment_path.join(PAYLOAD_INDEX_PATH)
 }
@@ -302,6 +429,7 @@ pub(crate) fn create_vector_index(
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    old_index: Option<OldIndexCandidate>,
     permit: Option<Arc<CpuPermit>>,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorIndexEnum> {
@@ -310,6 +438,7 @@ pub(crate) fn create_vector_index(
             id_tracker.clone(),
             vector_storage.clone(),
             payload_index.clone(),
+            vector_config.truncate_to(),
         )),
         Indexes::Hnsw(vector_hnsw_config) => {
             let args = HnswIndexOpenArgs {
@@ -319,6 +448,8 @@ pub(crate) fn create_vector_index(
                 quantized_vectors: quantized_vectors.clone(),
                 payload_index: payload_index.clone(),
                 hnsw_config: vector_hnsw_config.clone(),
+                truncate_to: vector_config.truncate_to(),
+                old_index,
                 permit,
                 stopped,
             };
@@ -348,7 +479,8 @@ pub(crate) fn create_sparse_vector_index(
         args.config.datatype.unwrap_or_default(),
         sparse_vector_index::USE_COMPRESSED,
     ) {
-        (_, a @ (VectorStorageDatatype::Float16 | VectorStorageDatatype::Uint8), false) => {
+        (_, a @ VectorStorageDatatype::Bit, _)
+        | (_, a @ (VectorStorageDatatype::Float16 | VectorStorageDatatype::Uint8), false) => {
             Err(OperationError::ValidationError {
                 description: format!("{:?} datatype is not supported", a),
             })?
@@ -390,13 +522,61 @@ pub(crate) fn create_sparse_vector_index(
     Ok(vector_index)
 }
 
+/// Sparse vector storage is always created in the mmap format.
+///
+/// Vectors of segments created with RocksDB are migrated on load, see [`create_payload_storage`].
 pub(crate) fn create_sparse_vector_storage(
     database: Arc<RwLock<DB>>,
+    vector_storage_path: &Path,
     vector_name: &str,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
-    open_simple_sparse_vector_storage(database, &db_column_name, stopped)
+    if rocksdb_column_has_data(&database, &db_column_name)? {
+        migrate_rocksdb_sparse_vector_storage(
+            database,
+            &db_column_name,
+            vector_storage_path,
+            stopped,
+        )?;
+    }
+    open_mmap_sparse_vector_storage(vector_storage_path, stopped)
+}
+
+fn migrate_rocksdb_sparse_vector_storage(
+    database: Arc<RwLock<DB>>,
+    db_column_name: &str,
+    vector_storage_path: &Path,
+    stopped: &AtomicBool,
+) -> OperationResult<()> {
+    info!(
+        "Migrating sparse vector storage {} from RocksDB",
+        vector_storage_path.display(),
+    );
+
+    // Drop leftovers of an interrupted migration
+    if vector_storage_path.exists() {
+        std::fs::remove_dir_all(vector_storage_path)?;
+    }
+    let mut vector_storage = MmapSparseVectorStorage::open(vector_storage_path, stopped)?;
+
+    let old_vector_storage =
+        open_simple_sparse_vector_storage(database.clone(), db_column_name, stopped)?;
+    for key in 0..old_vector_storage.total_vector_count() as PointOffsetType {
+        check_process_stopped(stopped)?;
+        let Some(vector) = old_vector_storage.get_vector_opt(key) else {
+            continue;
+        };
+        vector_storage.insert_vector(key, vector.as_vec_ref())?;
+        if old_vector_storage.is_deleted_vector(key) {
+            vector_storage.delete_vector(key)?;
+        }
+    }
+    vector_storage.flusher()()?;
+    drop(old_vector_storage);
+
+    DatabaseColumnWrapper::new(database, db_column_name).recreate_column_family()?;
+    Ok(())
 }
 
 fn create_segment(
@@ -406,11 +586,15 @@ fn create_segment(
     stopped: &AtomicBool,
 ) -> OperationResult<Segment> {
     let database = open_segment_db(segment_path, config)?;
-    let payload_storage = sp(create_payload_storage(database.clone(), config)?);
+    let payload_storage = sp(create_payload_storage(
+        database.clone(),
+        config,
+        segment_path,
+    )?);
 
     let appendable_flag = config.is_appendable();
 
-    let id_tracker = sp(create_id_tracker(database.clone())?);
+    let id_tracker = sp(create_id_tracker(database.clone(), segment_path)?);
 
     let payload_index_path = get_payload_index_path(segment_path);
     let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
@@ -420,19 +604,34 @@ fn create_segment(
         appendable_flag,
     )?);
 
-    let mut vector_data = HashMap::new();
-    for (vector_name, vector_config) in &config.vector_data {
+    // Open truncated views last, they share the storage of their source vector
+    let mut dense_vector_configs: Vec<_> = config.vector_data.iter().collect();
+    dense_vector_configs.sort_by_key(|(_, vector_config)| vector_config.truncated_view.is_some());
+
+    let mut vector_data: HashMap<String, VectorData> = HashMap::new();
+    for (vector_name, vector_config) in dense_vector_configs {
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
-        // Select suitable vector storage type based on configuration
-        let vector_storage = Arc::new(AtomicRefCell::new(open_vector_storage(
-            &database,
-            vector_config,
-            stopped,
-            &vector_storage_path,
-            vector_name,
-        )?));
+        let vector_storage = match &vector_config.truncated_view {
+            Some(view) => vector_data
+                .get(&view.source)
+                .map(|source| source.vector_storage.clone())
+                .ok_or_else(|| {
+                    OperationError::service_error(format!(
+                        "Source vector `{}` of truncated view `{vector_name}` not found",
+                        view.source,
+                    ))
+                })?,
+            // Select suitable vector storage type based on configuration
+            None => Arc::new(AtomicRefCell::new(open_vector_storage(
+                &database,
+                vector_config,
+                stopped,
+                &vector_storage_path,
+                vector_name,
+            )?)),
+        };
 
         // Warn when number of points between ID tracker and storage differs
         let point_count = id_tracker.borrow().total_point_count();
@@ -444,18 +643,22 @@ fn create_segment(
             );
         }
 
-        let quantized_vectors = sp(if config.quantization_config(vector_name).is_some() {
-            let quantized_data_path = vector_storage_path;
-            if QuantizedVectors::config_exists(&quantized_data_path) {
-                let quantized_vectors =
-                    QuantizedVectors::load(&vector_storage.borrow(), &quantized_data_path)?;
-                Some(quantized_vectors)
+        let quantized_vectors = sp(
+            if config.quantization_config(vector_name).is_some()
+                && vector_config.truncated_view.is_none()
+            {
+                let quantized_data_path = vector_storage_path;
+                if QuantizedVectors::config_exists(&quantized_data_path) {
+                    let quantized_vectors =
+                        QuantizedVectors::load(&vector_storage.borrow(), &quantized_data_path)?;
+                    Some(quantized_vectors)
+                } else {
+                    None
+                }
             } else {
                 None
-            }
-        } else {
-            None
-        });
+            },
+        );
 
         let vector_index: Arc<AtomicRefCell<VectorIndexEnum>> = sp(create_vector_index(
             vector_config,
@@ -465,6 +668,7 @@ fn create_segment(
             payload_index.clone(),
             quantized_vectors.clone(),
             None,
+            None,
             stopped,
         )?);
 
@@ -486,6 +690,7 @@ fn create_segment(
 
         let vector_storage = sp(create_sparse_vector_storage(
             database.clone(),
+            &vector_storage_path,
             vector_name,
             stopped,
         )?);
@@ -31,6 +31,7 @@ pub fn build_simple_segment(
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    truncated_view: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -57,6 +58,7 @@ pub fn build_multivec_segment(
             quantization_config: None,
             multivector_config: None,
             datatype: None,
+            truncated_view: None,
         },
     );
     vectors_config.insert(
@@ -69,6 +71,7 @@ pub fn build_multivec_segment(
             quantization_config: None,
             multivector_config: None,
             datatype: None,
+            truncated_view: None,
         },
     );
 
@@ -0,0 +1,76 @@
+use common::types::ScoreType;
+
+use super::simple_dot::count_bits;
+use crate::data_types::vectors::{DenseVector, VectorElementTypeBit};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::CosineMetric;
+use crate::types::Distance;
+
+/// Binary vectors can't be normalized in advance, so the norms are computed on the fly
+impl Metric<VectorElementTypeBit> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+        cosine_similarity_bits(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn cosine_similarity_bits(
+    v1: &[VectorElementTypeBit],
+    v2: &[VectorElementTypeBit],
+) -> ScoreType {
+    let common = count_bits(v1, v2, |a, b| a & b);
+    let norms = count_bits(v1, v1, |a, _| a) * count_bits(v2, v2, |a, _| a);
+    if norms == 0 {
+        return 0.0;
+    }
+    common as ScoreType / (norms as ScoreType).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::vectors::PackedBits;
+    use crate::spaces::simple::{
+        DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+    };
+
+    #[test]
+    fn test_bit_metrics() {
+        let v1 = [
+            1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        ];
+        let v2 = [
+            1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+        ];
+        let (b1, b2) = (PackedBits::pack(&v1), PackedBits::pack(&v2));
+        assert_eq!(b1.len(), 2);
+        assert_eq!(PackedBits::unpack(&b1), v1);
+
+        fn score<M: Metric<VectorElementTypeBit>>(v1: &[PackedBits], v2: &[PackedBits]) -> f32 {
+            M::similarity(v1, v2)
+        }
+        let dot = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+        let common = dot(&v1, &v2);
+        let differing = v1.iter().zip(&v2).filter(|(x, y)| x != y).count() as f32;
+
+        assert_eq!(score::<DotProductMetric>(&b1, &b2), common);
+        assert_eq!(
+            score::<CosineMetric>(&b1, &b2),
+            common / (dot(&v1, &v1) * dot(&v2, &v2)).sqrt(),
+        );
+        assert_eq!(score::<EuclidMetric>(&b1, &b2), -differing);
+        assert_eq!(score::<ManhattanMetric>(&b1, &b2), -differing);
+        assert_eq!(score::<HammingMetric>(&b1, &b2), -differing);
+        assert_eq!(
+            score::<JaccardMetric>(&b1, &b2),
+            common / (common + differing),
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, PackedBits, VectorElementTypeBit};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::DotProductMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBit> for DotProductMetric {
+    fn distance() -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+        dot_similarity_bits(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+/// Number of bits set in both vectors
+pub fn dot_similarity_bits(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+    count_bits(v1, v2, |a, b| a & b) as ScoreType
+}
+
+/// Number of set bits of a bitwise operation over two vectors
+pub(super) fn count_bits(
+    v1: &[VectorElementTypeBit],
+    v2: &[VectorElementTypeBit],
+    op: impl Fn(u8, u8) -> u8,
+) -> u32 {
+    PackedBits::as_bytes(v1)
+        .iter()
+        .zip(PackedBits::as_bytes(v2))
+        .map(|(&a, &b)| op(a, b).count_ones())
+        .sum()
+}
@@ -0,0 +1,25 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{
+    DenseVector, PackedBits, VectorElementTypeBit, VectorElementTypeByte,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::HammingMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBit> for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+        <HammingMetric as Metric<VectorElementTypeByte>>::similarity(
+            PackedBits::as_bytes(v1),
+            PackedBits::as_bytes(v2),
+        )
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
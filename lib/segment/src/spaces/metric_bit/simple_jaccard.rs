@@ -0,0 +1,25 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{
+    DenseVector, PackedBits, VectorElementTypeBit, VectorElementTypeByte,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::JaccardMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBit> for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+        <JaccardMetric as Metric<VectorElementTypeByte>>::similarity(
+            PackedBits::as_bytes(v1),
+            PackedBits::as_bytes(v2),
+        )
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
@@ -0,0 +1,26 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{
+    DenseVector, PackedBits, VectorElementTypeBit, VectorElementTypeByte,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{HammingMetric, ManhattanMetric};
+use crate::types::Distance;
+
+/// Manhattan distance between binary vectors is the number of differing bits
+impl Metric<VectorElementTypeBit> for ManhattanMetric {
+    fn distance() -> Distance {
+        Distance::Manhattan
+    }
+
+    fn similarity(v1: &[VectorElementTypeBit], v2: &[VectorElementTypeBit]) -> ScoreType {
+        <HammingMetric as Metric<VectorElementTypeByte>>::similarity(
+            PackedBits::as_bytes(v1),
+            PackedBits::as_bytes(v2),
+        )
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
@@ -0,0 +1,30 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeHalf};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{bit_packed_preprocess, HammingMetric};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeHalf> for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        hamming_similarity_half(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        bit_packed_preprocess(vector)
+    }
+}
+
+pub fn hamming_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(a, b)| ((a.to_f32() as u8) ^ (b.to_f32() as u8)).count_ones())
+        .sum::<u32>() as ScoreType)
+}
@@ -0,0 +1,31 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeHalf};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{bit_packed_preprocess, jaccard_score, JaccardMetric};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeHalf> for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementTypeHalf], v2: &[VectorElementTypeHalf]) -> ScoreType {
+        jaccard_similarity_half(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        bit_packed_preprocess(vector)
+    }
+}
+
+pub fn jaccard_similarity_half(
+    v1: &[VectorElementTypeHalf],
+    v2: &[VectorElementTypeHalf],
+) -> ScoreType {
+    let (intersection, union) = v1.iter().zip(v2).fold((0, 0), |(i, u), (a, b)| {
+        let (a, b) = (a.to_f32() as u8, b.to_f32() as u8);
+        (i + (a & b).count_ones(), u + (a | b).count_ones())
+    });
+    jaccard_score(intersection, union)
+}
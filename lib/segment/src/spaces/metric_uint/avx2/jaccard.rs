@@ -0,0 +1,81 @@
+use std::arch::x86_64::*;
+
+use crate::spaces::metric_uint::avx2::hamming::popcount_epi8_avx;
+use crate::spaces::simple::jaccard_score;
+use crate::spaces::simple_avx::hsum256_ps_avx;
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "avx2")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn avx_jaccard_similarity_bytes(v1: &[u8], v2: &[u8]) -> f32 {
+    debug_assert!(v1.len() == v2.len());
+    debug_assert!(is_x86_feature_detected!("avx"));
+    debug_assert!(is_x86_feature_detected!("avx2"));
+
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+
+    let zero = _mm256_setzero_si256();
+    // sum accumulators for 8x32 bit integers
+    let mut intersection_acc = _mm256_setzero_si256();
+    let mut union_acc = _mm256_setzero_si256();
+    let len = v1.len();
+    for _ in 0..len / 32 {
+        // load 32 bytes
+        let p1 = _mm256_loadu_si256(ptr1 as *const __m256i);
+        let p2 = _mm256_loadu_si256(ptr2 as *const __m256i);
+        ptr1 = ptr1.add(32);
+        ptr2 = ptr2.add(32);
+
+        let intersection = popcount_epi8_avx(_mm256_and_si256(p1, p2));
+        let union = popcount_epi8_avx(_mm256_or_si256(p1, p2));
+        intersection_acc = _mm256_add_epi32(intersection_acc, _mm256_sad_epu8(intersection, zero));
+        union_acc = _mm256_add_epi32(union_acc, _mm256_sad_epu8(union, zero));
+    }
+
+    let mut intersection = hsum256_ps_avx(_mm256_cvtepi32_ps(intersection_acc)) as u32;
+    let mut union = hsum256_ps_avx(_mm256_cvtepi32_ps(union_acc)) as u32;
+
+    for _ in 0..len % 32 {
+        intersection += (*ptr1 & *ptr2).count_ones();
+        union += (*ptr1 | *ptr2).count_ones();
+        ptr1 = ptr1.add(1);
+        ptr2 = ptr2.add(1);
+    }
+
+    jaccard_score(intersection, union)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::metric_uint::simple_jaccard::jaccard_similarity_bytes;
+
+    #[test]
+    fn test_spaces_avx() {
+        if is_x86_feature_detected!("avx") && is_x86_feature_detected!("avx2") {
+            let v1: Vec<u8> = vec![
+                255, 255, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255,
+                0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255, 0, 1, 2, 3,
+                4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255, 0, 1, 2, 3, 4, 5, 6, 7,
+                8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+                11, 12, 13, 14, 15, 16, 17,
+            ];
+            let v2: Vec<u8> = vec![
+                255, 255, 0, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241,
+                240, 239, 238, 255, 255, 255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245,
+                244, 243, 242, 241, 240, 239, 238, 255, 255, 255, 254, 253, 252, 251, 250, 249,
+                248, 247, 246, 245, 244, 243, 242, 241, 240, 239, 238, 255, 255, 255, 254, 253,
+                252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241, 240, 239, 238, 255,
+                255, 255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241,
+                240, 239, 238,
+            ];
+
+            let jaccard_simd = unsafe { avx_jaccard_similarity_bytes(&v1, &v2) };
+            let jaccard = jaccard_similarity_bytes(&v1, &v2);
+            assert_eq!(jaccard_simd, jaccard);
+        } else {
+            println!("avx test skipped");
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeByte};
+use crate::spaces::metric::Metric;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::metric_uint::avx2::hamming::avx_hamming_similarity_bytes;
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::spaces::metric_uint::neon::hamming::neon_hamming_similarity_bytes;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::spaces::metric_uint::sse2::hamming::sse_hamming_similarity_bytes;
+#[cfg(target_arch = "x86_64")]
+use crate::spaces::simple::MIN_DIM_SIZE_AVX;
+use crate::spaces::simple::{bit_packed_preprocess, HammingMetric, MIN_DIM_SIZE_SIMD};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeByte> for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementTypeByte], v2: &[VectorElementTypeByte]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx")
+                && is_x86_feature_detected!("avx2")
+                && v1.len() >= MIN_DIM_SIZE_AVX
+            {
+                return unsafe { avx_hamming_similarity_bytes(v1, v2) };
+            }
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse")
+                && is_x86_feature_detected!("sse2")
+                && is_x86_feature_detected!("ssse3")
+                && v1.len() >= MIN_DIM_SIZE_SIMD
+            {
+                return unsafe { sse_hamming_similarity_bytes(v1, v2) };
+            }
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") && v1.len() >= MIN_DIM_SIZE_SIMD {
+                return unsafe { neon_hamming_similarity_bytes(v1, v2) };
+            }
+        }
+
+        hamming_similarity_bytes(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        bit_packed_preprocess(vector)
+    }
+}
+
+pub fn hamming_similarity_bytes(
+    v1: &[VectorElementTypeByte],
+    v2: &[VectorElementTypeByte],
+) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum::<u32>() as ScoreType)
+}
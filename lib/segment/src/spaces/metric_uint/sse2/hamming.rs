@@ -0,0 +1,97 @@
+use std::arch::x86_64::*;
+
+use crate::spaces::simple_sse::hsum128_ps_sse;
+
+/// Counts set bits in each of packed 8-bit integers using nibble lookup table
+#[target_feature(enable = "sse2")]
+#[target_feature(enable = "ssse3")]
+pub(crate) unsafe fn popcount_epi8_sse(v: __m128i) -> __m128i {
+    let lookup = _mm_setr_epi8(0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4);
+    let low_mask = _mm_set1_epi8(0x0f);
+    let low = _mm_and_si128(v, low_mask);
+    let high = _mm_and_si128(_mm_srli_epi16(v, 4), low_mask);
+    _mm_add_epi8(
+        _mm_shuffle_epi8(lookup, low),
+        _mm_shuffle_epi8(lookup, high),
+    )
+}
+
+#[target_feature(enable = "sse")]
+#[target_feature(enable = "sse2")]
+#[target_feature(enable = "ssse3")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn sse_hamming_similarity_bytes(v1: &[u8], v2: &[u8]) -> f32 {
+    debug_assert!(v1.len() == v2.len());
+    debug_assert!(is_x86_feature_detected!("sse"));
+    debug_assert!(is_x86_feature_detected!("sse2"));
+    debug_assert!(is_x86_feature_detected!("ssse3"));
+
+    let mut ptr1: *const u8 = v1.as_ptr();
+    let mut ptr2: *const u8 = v2.as_ptr();
+
+    let zero = _mm_setzero_si128();
+    // sum accumulator for 4x32 bit integers
+    let mut acc = _mm_setzero_si128();
+    let len = v1.len();
+    for _ in 0..len / 16 {
+        // load 16 bytes
+        let p1 = _mm_loadu_si128(ptr1 as *const __m128i);
+        let p2 = _mm_loadu_si128(ptr2 as *const __m128i);
+        ptr1 = ptr1.add(16);
+        ptr2 = ptr2.add(16);
+
+        // count differing bits per byte and sum them horizontally into accumulator
+        let bits = popcount_epi8_sse(_mm_xor_si128(p1, p2));
+        acc = _mm_add_epi32(acc, _mm_sad_epu8(bits, zero));
+    }
+
+    // convert 4x32 bit integers into 4x32 bit floats and calculate horizontal sum
+    let mut score = hsum128_ps_sse(_mm_cvtepi32_ps(acc));
+
+    let remainder = len % 16;
+    if remainder != 0 {
+        let mut remainder_score = 0;
+        for _ in 0..remainder {
+            remainder_score += (*ptr1 ^ *ptr2).count_ones();
+            ptr1 = ptr1.add(1);
+            ptr2 = ptr2.add(1);
+        }
+        score += remainder_score as f32;
+    }
+
+    -score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::metric_uint::simple_hamming::hamming_similarity_bytes;
+
+    #[test]
+    fn test_spaces_sse() {
+        if is_x86_feature_detected!("sse2") && is_x86_feature_detected!("ssse3") {
+            let v1: Vec<u8> = vec![
+                255, 255, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255,
+                0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255, 0, 1, 2, 3,
+                4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255, 0, 1, 2, 3, 4, 5, 6, 7,
+                8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 255, 255, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10,
+                11, 12, 13, 14, 15, 16, 17,
+            ];
+            let v2: Vec<u8> = vec![
+                255, 255, 0, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241,
+                240, 239, 238, 255, 255, 255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245,
+                244, 243, 242, 241, 240, 239, 238, 255, 255, 255, 254, 253, 252, 251, 250, 249,
+                248, 247, 246, 245, 244, 243, 242, 241, 240, 239, 238, 255, 255, 255, 254, 253,
+                252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241, 240, 239, 238, 255,
+                255, 255, 254, 253, 252, 251, 250, 249, 248, 247, 246, 245, 244, 243, 242, 241,
+                240, 239, 238,
+            ];
+
+            let hamming_simd = unsafe { sse_hamming_similarity_bytes(&v1, &v2) };
+            let hamming = hamming_similarity_bytes(&v1, &v2);
+            assert_eq!(hamming_simd, hamming);
+        } else {
+            println!("sse test skipped");
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod cosine;
 pub mod dot;
 pub mod euclid;
+pub mod hamming;
+pub mod jaccard;
 pub mod manhattan;
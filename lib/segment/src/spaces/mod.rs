@@ -8,6 +8,7 @@ pub mod simple_sse;
 #[cfg(target_arch = "x86_64")]
 pub mod simple_avx;
 
+pub mod metric_bit;
 pub mod metric_f16;
 pub mod metric_uint;
 
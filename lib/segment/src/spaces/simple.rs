@@ -33,6 +33,14 @@ pub struct EuclidMetric;
 #[derive(Clone)]
 pub struct ManhattanMetric;
 
+/// Number of differing bits between bit-packed vectors, each element holds 8 bits.
+#[derive(Clone)]
+pub struct HammingMetric;
+
+/// Ratio of common set bits to all set bits of bit-packed vectors, also known as Tanimoto.
+#[derive(Clone)]
+pub struct JaccardMetric;
+
 impl Metric<VectorElementType> for EuclidMetric {
     fn distance() -> Distance {
         Distance::Euclid
@@ -121,6 +129,46 @@ impl MetricPostProcessing for ManhattanMetric {
     }
 }
 
+impl Metric<VectorElementType> for HammingMetric {
+    fn distance() -> Distance {
+        Distance::Hamming
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        hamming_similarity(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        bit_packed_preprocess(vector)
+    }
+}
+
+impl MetricPostProcessing for HammingMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score.abs()
+    }
+}
+
+impl Metric<VectorElementType> for JaccardMetric {
+    fn distance() -> Distance {
+        Distance::Jaccard
+    }
+
+    fn similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        jaccard_similarity(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        bit_packed_preprocess(vector)
+    }
+}
+
+impl MetricPostProcessing for JaccardMetric {
+    fn postprocess(score: ScoreType) -> ScoreType {
+        score
+    }
+}
+
 impl Metric<VectorElementType> for DotProductMetric {
     fn distance() -> Distance {
         Distance::Dot
@@ -225,6 +273,37 @@ pub fn manhattan_similarity(v1: &[VectorElementType], v2: &[VectorElementType])
         .sum::<ScoreType>()
 }
 
+/// Round elements of a bit-packed vector to whole bytes
+pub fn bit_packed_preprocess(vector: DenseVector) -> DenseVector {
+    vector
+        .into_iter()
+        .map(|x| x.round().clamp(0.0, u8::MAX as VectorElementType))
+        .collect()
+}
+
+pub fn hamming_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    -(v1.iter()
+        .zip(v2)
+        .map(|(a, b)| ((*a as u8) ^ (*b as u8)).count_ones())
+        .sum::<u32>() as ScoreType)
+}
+
+pub fn jaccard_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let (intersection, union) = v1.iter().zip(v2).fold((0, 0), |(i, u), (a, b)| {
+        let (a, b) = (*a as u8, *b as u8);
+        (i + (a & b).count_ones(), u + (a | b).count_ones())
+    });
+    jaccard_score(intersection, union)
+}
+
+/// Jaccard similarity from bit counts, two empty vectors are considered identical
+pub fn jaccard_score(intersection: u32, union: u32) -> ScoreType {
+    if union == 0 {
+        return 1.0;
+    }
+    intersection as ScoreType / union as ScoreType
+}
+
 pub fn cosine_preprocess(vector: DenseVector) -> DenseVector {
     let mut length: f32 = vector.iter().map(|x| x * x).sum();
     if is_length_zero_or_normalized(length) {
@@ -244,6 +323,34 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_bit_packed_similarity() {
+        let v1 = <HammingMetric as Metric<VectorElementType>>::preprocess(vec![
+            0b1100_1010 as f32,
+            255.3,
+            0.0,
+            -3.0,
+        ]);
+        let v2 = <JaccardMetric as Metric<VectorElementType>>::preprocess(vec![
+            0b0100_1011 as f32,
+            0.0,
+            0.0,
+            300.0,
+        ]);
+        assert_eq!(v1, vec![202.0, 255.0, 0.0, 0.0]);
+        assert_eq!(v2, vec![75.0, 0.0, 0.0, 255.0]);
+
+        let hamming = <HammingMetric as Metric<VectorElementType>>::similarity(&v1, &v2);
+        assert_eq!(hamming, -18.0);
+        assert_eq!(HammingMetric::postprocess(hamming), 18.0);
+
+        let jaccard = <JaccardMetric as Metric<VectorElementType>>::similarity(&v1, &v2);
+        assert_eq!(jaccard, 3.0 / 21.0);
+        let empty = vec![0.0, 0.0];
+        let jaccard = <JaccardMetric as Metric<VectorElementType>>::similarity(&empty, &empty);
+        assert_eq!(jaccard, 1.0);
+    }
+
     #[test]
     fn test_cosine_preprocessing() {
         let res = <CosineMetric as Metric<VectorElementType>>::preprocess(vec![0.0, 0.0, 0.0, 0.0]);
@@ -4,8 +4,8 @@ use serde::Serialize;
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_time_statistics::OperationDurationStatistics;
 use crate::types::{
-    PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig, VectorDataConfig,
-    VectorDataInfo,
+    MultiVectorPoolingInfo, PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig,
+    TruncatedViewConfig, VectorDataConfig, VectorDataInfo,
 };
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -100,6 +100,10 @@ impl Anonymize for VectorDataInfo {
             num_vectors: self.num_vectors.anonymize(),
             num_indexed_vectors: self.num_indexed_vectors.anonymize(),
             num_deleted_vectors: self.num_deleted_vectors.anonymize(),
+            pooling: self.pooling.map(|pooling| MultiVectorPoolingInfo {
+                num_original_vectors: pooling.num_original_vectors.anonymize(),
+                num_pooled_vectors: pooling.num_pooled_vectors.anonymize(),
+            }),
         }
     }
 }
@@ -134,6 +138,12 @@ impl Anonymize for VectorDataConfig {
             quantization_config: self.quantization_config.clone(),
             multivector_config: self.multivector_config,
             datatype: self.datatype,
+            truncated_view: self
+                .truncated_view
+                .as_ref()
+                .map(|view| TruncatedViewConfig {
+                    source: view.source.anonymize(),
+                }),
         }
     }
 }
@@ -4,6 +4,7 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
@@ -28,15 +29,17 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
 use crate::data_types::index::{
     BoolIndexParams, DatetimeIndexParams, FloatIndexParams, GeoIndexParams, IntegerIndexParams,
-    KeywordIndexParams, TextIndexParams,
+    KeywordIndexParams, TextIndexParams, UuidIndexParams,
 };
 use crate::data_types::order_by::OrderValue;
-use crate::data_types::vectors::VectorStructInternal;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorStructInternal};
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
 use crate::json_path::JsonPath;
-use crate::spaces::metric::MetricPostProcessing;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::metric::{Metric, MetricPostProcessing};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 
 pub type PayloadKeyType = JsonPath;
 pub type PayloadKeyTypeRef<'a> = &'a JsonPath;
@@ -48,6 +51,10 @@ pub type FloatPayloadType = f64;
 pub type IntPayloadType = i64;
 /// Type of datetime point payload
 pub type DateTimePayloadType = DateTimeWrapper;
+/// Type of UUID point payload, as it comes in the payload
+pub type UuidPayloadType = String;
+/// Type of UUID point payload, as it is stored in the index
+pub type UuidIntType = u128;
 
 /// Wraps `DateTime<Utc>` to allow more flexible deserialization
 #[derive(Clone, Copy, Serialize, JsonSchema, Debug, PartialEq, PartialOrd)]
@@ -175,6 +182,10 @@ pub enum Distance {
     Dot,
     // <https://simple.wikipedia.org/wiki/Manhattan_distance>
     Manhattan,
+    // <https://en.wikipedia.org/wiki/Hamming_distance>
+    Hamming,
+    // <https://en.wikipedia.org/wiki/Jaccard_index>
+    Jaccard,
 }
 
 impl Distance {
@@ -184,13 +195,41 @@ impl Distance {
             Distance::Euclid => EuclidMetric::postprocess(score),
             Distance::Dot => DotProductMetric::postprocess(score),
             Distance::Manhattan => ManhattanMetric::postprocess(score),
+            Distance::Hamming => HammingMetric::postprocess(score),
+            Distance::Jaccard => JaccardMetric::postprocess(score),
+        }
+    }
+
+    /// Transformation of a dense vector applied before comparing it, like normalization for cosine
+    pub fn preprocess_vector(&self, vector: DenseVector) -> DenseVector {
+        match self {
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Manhattan => {
+                <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
+            }
+            Distance::Hamming => <HammingMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Jaccard => <JaccardMetric as Metric<VectorElementType>>::preprocess(vector),
+        }
+    }
+
+    /// Raw similarity of two preprocessed dense vectors. Greater the value - closer the vectors
+    pub fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        match self {
+            Distance::Cosine => CosineMetric::similarity(v1, v2),
+            Distance::Euclid => EuclidMetric::similarity(v1, v2),
+            Distance::Dot => DotProductMetric::similarity(v1, v2),
+            Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+            Distance::Hamming => HammingMetric::similarity(v1, v2),
+            Distance::Jaccard => JaccardMetric::similarity(v1, v2),
         }
     }
 
     pub fn distance_order(&self) -> Order {
         match self {
-            Distance::Cosine | Distance::Dot => Order::LargeBetter,
-            Distance::Euclid | Distance::Manhattan => Order::SmallBetter,
+            Distance::Cosine | Distance::Dot | Distance::Jaccard => Order::LargeBetter,
+            Distance::Euclid | Distance::Manhattan | Distance::Hamming => Order::SmallBetter,
         }
     }
 
@@ -300,6 +339,42 @@ pub struct VectorDataInfo {
     pub num_vectors: usize,
     pub num_indexed_vectors: usize,
     pub num_deleted_vectors: usize,
+    /// Present if multivectors of this vector are pooled at storage time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pooling: Option<MultiVectorPoolingInfo>,
+}
+
+/// Number of multivector token vectors before and after storage-time pooling
+#[derive(Debug, Default, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MultiVectorPoolingInfo {
+    pub num_original_vectors: usize,
+    pub num_pooled_vectors: usize,
+}
+
+impl MultiVectorPoolingInfo {
+    /// Fraction of token vectors kept after pooling
+    pub fn pooling_ratio(&self) -> f32 {
+        if self.num_original_vectors == 0 {
+            1.0
+        } else {
+            self.num_pooled_vectors as f32 / self.num_original_vectors as f32
+        }
+    }
+}
+
+impl std::ops::AddAssign for MultiVectorPoolingInfo {
+    fn add_assign(&mut self, other: Self) {
+        self.num_original_vectors += other.num_original_vectors;
+        self.num_pooled_vectors += other.num_pooled_vectors;
+    }
+}
+
+impl std::ops::SubAssign for MultiVectorPoolingInfo {
+    fn sub_assign(&mut self, other: Self) {
+        self.num_original_vectors -= other.num_original_vectors;
+        self.num_pooled_vectors -= other.num_pooled_vectors;
+    }
 }
 
 /// Aggregated information about segment
@@ -352,6 +427,55 @@ pub const fn default_quantization_oversampling_value() -> Option<f64> {
     None
 }
 
+/// ACORN-style filtered search parameters.
+///
+/// With ACORN, neighbors of the graph which don't match the filter are not scored,
+/// but their own neighbors are explored instead. This keeps the graph navigable under
+/// restrictive filters, which are not covered by a payload index.
+///
+/// ACORN is picked automatically for such filters with selectivity below `max_selectivity`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct AcornSearchParams {
+    /// If false, ACORN is never used and filtered searches always use regular graph search.
+    /// Default is true.
+    #[serde(default = "default_acorn_enable")]
+    pub enable: bool,
+
+    /// Maximum selectivity of the filter, i.e. the estimated fraction of points matching it,
+    /// for which ACORN is used. Less restrictive filters use regular graph search.
+    /// Default is 0.4.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub max_selectivity: Option<f64>,
+}
+
+pub const DEFAULT_ACORN_MAX_SELECTIVITY: f64 = 0.4;
+
+pub const fn default_acorn_enable() -> bool {
+    true
+}
+
+impl Default for AcornSearchParams {
+    fn default() -> Self {
+        Self {
+            enable: default_acorn_enable(),
+            max_selectivity: None,
+        }
+    }
+}
+
+impl AcornSearchParams {
+    /// Whether ACORN should be used for a filter with the given selectivity
+    pub fn applies_to(&self, selectivity: f64) -> bool {
+        self.enable
+            && selectivity
+                <= self
+                    .max_selectivity
+                    .unwrap_or(DEFAULT_ACORN_MAX_SELECTIVITY)
+    }
+}
+
 /// Additional parameters of the search
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -374,6 +498,19 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// If set, only the first `dimensions` of dense vectors are used for scoring.
+    /// Useful with Matryoshka embeddings to make a fast first stage search, which is then rescored
+    /// with all dimensions. Quantized vectors are not used if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub dimensions: Option<usize>,
+
+    /// ACORN-style search for restrictive filters.
+    /// If not set, ACORN is used with default parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub acorn: Option<AcornSearchParams>,
 }
 
 /// Collection default values
@@ -474,6 +611,10 @@ pub struct HnswConfig {
     /// Custom M param for hnsw graph built for payload index. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Store links of HNSW graph compressed. Makes the graph several times smaller at the cost
+    /// of slightly slower search. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub compressed_links: Option<bool>,
 }
 
 impl HnswConfig {
@@ -494,6 +635,9 @@ impl HnswConfig {
             // this to just reload the collection rather than optimizing it again as a whole just
             // to flip this flag
             || self.on_disk != other.on_disk
+            // Rebuild converts links of existing graphs into the requested format
+            || self.compressed_links.unwrap_or_default()
+                != other.compressed_links.unwrap_or_default()
     }
 }
 
@@ -516,6 +660,7 @@ pub enum CompressionRatio {
 pub enum ScalarType {
     #[default]
     Int8,
+    Int4,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
@@ -523,6 +668,7 @@ pub enum ScalarType {
 pub struct ScalarQuantizationConfig {
     /// Type of quantization to use
     /// If `int8` - 8 bit quantization will be used
+    /// If `int4` - 4 bit quantization will be used, two values are packed into a byte
     pub r#type: ScalarType,
     /// Quantile for quantization. Expected value range in [0.5, 1.0]. If not set - use the whole range of values
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -585,11 +731,70 @@ impl std::hash::Hash for ScalarQuantizationConfig {
 
 impl Eq for ScalarQuantizationConfig {}
 
+/// Number of bits used to encode each dimension of a vector in binary quantization
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryQuantizationEncoding {
+    /// Single bit per dimension, the sign of the value
+    #[default]
+    OneBit,
+    /// Two bits per dimension for the half of dimensions with the highest variance,
+    /// one bit per dimension for the rest
+    OneAndHalfBits,
+    /// Two bits per dimension
+    TwoBits,
+}
+
+/// Precision of the query vector when scoring against binary quantized vectors
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryQuantizationQueryEncoding {
+    /// Query is encoded the same way as the stored vectors
+    #[default]
+    Binary,
+    /// Query is kept in 4-bit scalar precision (asymmetric scoring)
+    #[serde(rename = "scalar4bits")]
+    Scalar4Bits,
+    /// Query is kept in 8-bit scalar precision (asymmetric scoring)
+    #[serde(rename = "scalar8bits")]
+    Scalar8Bits,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub struct BinaryQuantizationConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// Number of bits per dimension of the stored vectors. Default: `one_bit`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<BinaryQuantizationEncoding>,
+    /// Precision of the query vector. Higher precision gives better recall at the cost of
+    /// slower scoring. Default: `binary`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_encoding: Option<BinaryQuantizationQueryEncoding>,
+}
+
+impl BinaryQuantizationConfig {
+    pub fn encoding(&self) -> BinaryQuantizationEncoding {
+        self.encoding.unwrap_or_default()
+    }
+
+    pub fn query_encoding(&self) -> BinaryQuantizationQueryEncoding {
+        self.query_encoding.unwrap_or_default()
+    }
+
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Returns true only if both conditions are met:
+    /// - this configuration does not match `other`
+    /// - to effectively change the configuration, a quantization rebuild is required
+    ///
+    /// Unset encodings are equal to the explicit defaults.
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self.always_ram != other.always_ram
+            || self.encoding() != other.encoding()
+            || self.query_encoding() != other.query_encoding()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
@@ -613,7 +818,12 @@ impl QuantizationConfig {
     /// - this configuration does not match `other`
     /// - to effectively change the configuration, a quantization rebuild is required
     pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
-        self != other
+        match (self, other) {
+            (QuantizationConfig::Binary(this), QuantizationConfig::Binary(other)) => {
+                this.binary.mismatch_requires_rebuild(&other.binary)
+            }
+            _ => self != other,
+        }
     }
 }
 
@@ -656,6 +866,7 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            compressed_links: None,
         }
     }
 }
@@ -784,6 +995,8 @@ pub enum VectorStorageDatatype {
     Float16,
     // Unsigned 8-bit integer
     Uint8,
+    // Single bit, packed 8 per byte
+    Bit,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
@@ -791,13 +1004,46 @@ pub enum VectorStorageDatatype {
 pub struct MultiVectorConfig {
     /// How to compare multivector points
     pub comparator: MultiVectorComparator,
+    /// Reduce the number of vectors of each multivector at storage time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pooling: Option<MultiVectorPooling>,
+}
+
+/// Storage-time pooling of multivectors.
+///
+/// Similar vectors of a multivector are merged with hierarchical clustering and replaced with
+/// their mean, so that fewer vectors have to be stored and scored.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorPooling {
+    /// Keep at most this many vectors per point
+    TargetCount(NonZeroUsize),
+    /// Divide the number of vectors per point by this factor, rounding up
+    PoolFactor(NonZeroUsize),
+}
+
+impl MultiVectorPooling {
+    /// Number of vectors to keep for a multivector of `count` vectors
+    pub fn target_count(&self, count: usize) -> usize {
+        match self {
+            Self::TargetCount(target) => count.min(target.get()),
+            Self::PoolFactor(factor) => count.div_ceil(factor.get()),
+        }
+    }
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum MultiVectorComparator {
+    /// Sum over query vectors of the best match similarity
     #[default]
     MaxSim,
+    /// Best match similarity of the worst matching query vector
+    MinSim,
+    /// Mean over query vectors of the best match similarity
+    AvgSim,
+    /// Symmetric Chamfer similarity: mean of best match similarities in both directions
+    Chamfer,
 }
 
 impl VectorStorageType {
@@ -810,6 +1056,15 @@ impl VectorStorageType {
     }
 }
 
+/// Truncated view over the leading dimensions of another dense vector, e.g. of Matryoshka
+/// embeddings. The view has its own index, but shares the storage with the source vector.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct TruncatedViewConfig {
+    /// Name of the vector to take the leading dimensions from
+    pub source: String,
+}
+
 /// Config of single vector data storage
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -830,9 +1085,17 @@ pub struct VectorDataConfig {
     /// Vector specific configuration to set specific storage element type
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub datatype: Option<VectorStorageDatatype>,
+    /// If set, this vector is a view over the first `size` dimensions of another vector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_view: Option<TruncatedViewConfig>,
 }
 
 impl VectorDataConfig {
+    /// Number of leading dimensions of the source vector, if this is a truncated view
+    pub fn truncate_to(&self) -> Option<usize> {
+        self.truncated_view.as_ref().map(|_| self.size)
+    }
+
     /// Whether this vector data can be appended to
     ///
     /// This requires an index and storage type that both support appending.
@@ -880,6 +1143,7 @@ pub struct SegmentState {
 /// Geo point payload schema
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
 #[serde(try_from = "GeoPointShadow")]
+#[repr(C)]
 pub struct GeoPoint {
     pub lon: f64,
     pub lat: f64,
@@ -926,6 +1190,11 @@ impl GeoPoint {
         Self::validate(lon, lat)?;
         Ok(GeoPoint { lon, lat })
     }
+
+    /// Great-circle distance to the other point, in meters
+    pub fn haversine_distance(&self, other: &GeoPoint) -> f64 {
+        Point::new(self.lon, self.lat).haversine_distance(&Point::new(other.lon, other.lat))
+    }
 }
 
 impl TryFrom<GeoPointShadow> for GeoPoint {
@@ -1103,6 +1372,7 @@ pub enum PayloadSchemaType {
     Text,
     Bool,
     Datetime,
+    Uuid,
 }
 
 impl PayloadSchemaType {
@@ -1120,6 +1390,7 @@ impl PayloadSchemaType {
             Self::Text => PayloadSchemaParams::Text(TextIndexParams::default()),
             Self::Bool => PayloadSchemaParams::Bool(BoolIndexParams::default()),
             Self::Datetime => PayloadSchemaParams::Datetime(DatetimeIndexParams::default()),
+            Self::Uuid => PayloadSchemaParams::Uuid(UuidIndexParams::default()),
         }
     }
 }
@@ -1135,6 +1406,7 @@ pub enum PayloadSchemaParams {
     Text(TextIndexParams),
     Bool(BoolIndexParams),
     Datetime(DatetimeIndexParams),
+    Uuid(UuidIndexParams),
 }
 
 impl PayloadSchemaParams {
@@ -1152,8 +1424,52 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Text(_) => PayloadSchemaType::Text,
             PayloadSchemaParams::Bool(_) => PayloadSchemaType::Bool,
             PayloadSchemaParams::Datetime(_) => PayloadSchemaType::Datetime,
+            PayloadSchemaParams::Uuid(_) => PayloadSchemaType::Uuid,
+        }
+    }
+
+    /// Whether points should be grouped by the values of this field on optimization
+    pub fn is_tenant(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Keyword(keyword) => keyword.is_tenant.unwrap_or_default(),
+            PayloadSchemaParams::Uuid(uuid) => uuid.is_tenant.unwrap_or_default(),
+            PayloadSchemaParams::Integer(_)
+            | PayloadSchemaParams::Float(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Text(_)
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::Datetime(_) => false,
         }
     }
+
+    /// Whether points should be ordered by the values of this field on optimization
+    pub fn is_principal(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Integer(integer) => integer.is_principal.unwrap_or_default(),
+            PayloadSchemaParams::Float(float) => float.is_principal.unwrap_or_default(),
+            PayloadSchemaParams::Datetime(datetime) => datetime.is_principal.unwrap_or_default(),
+            PayloadSchemaParams::Keyword(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Text(_)
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::Uuid(_) => false,
+        }
+    }
+
+    /// Whether the index should be stored on disk instead of RAM
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Keyword(keyword) => keyword.on_disk,
+            PayloadSchemaParams::Integer(integer) => integer.on_disk,
+            PayloadSchemaParams::Float(float) => float.on_disk,
+            PayloadSchemaParams::Geo(geo) => geo.on_disk,
+            PayloadSchemaParams::Text(text) => text.on_disk,
+            PayloadSchemaParams::Bool(boolean) => boolean.on_disk,
+            PayloadSchemaParams::Datetime(datetime) => datetime.on_disk,
+            PayloadSchemaParams::Uuid(uuid) => uuid.on_disk,
+        }
+        .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
@@ -1178,6 +1494,27 @@ impl PayloadFieldSchema {
             PayloadFieldSchema::FieldParams(field_params) => field_params.name(),
         }
     }
+
+    pub fn is_tenant(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(field_params) => field_params.is_tenant(),
+        }
+    }
+
+    pub fn is_principal(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(field_params) => field_params.is_principal(),
+        }
+    }
+
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(field_params) => field_params.is_on_disk(),
+        }
+    }
 }
 
 impl From<PayloadSchemaType> for PayloadFieldSchema {
@@ -1294,6 +1631,18 @@ impl From<String> for MatchText {
     }
 }
 
+/// Full-text phrase match of the strings.
+/// Tokens of the phrase should appear in the same order, as in the phrase.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPhrase {
+    pub phrase: String,
+    /// Number of additional tokens allowed between the tokens of the phrase.
+    /// Default: 0 - exact phrase match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slop: Option<u32>,
+}
+
 /// Exact match on any of the given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1314,6 +1663,7 @@ pub struct MatchExcept {
 pub enum MatchInterface {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -1324,6 +1674,7 @@ pub enum MatchInterface {
 pub enum Match {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
     Any(MatchAny),
     Except(MatchExcept),
 }
@@ -1337,6 +1688,13 @@ impl Match {
         Self::Text(MatchText { text: text.into() })
     }
 
+    pub fn new_phrase(phrase: &str, slop: Option<u32>) -> Self {
+        Self::Phrase(MatchPhrase {
+            phrase: phrase.into(),
+            slop,
+        })
+    }
+
     pub fn new_any(any: AnyVariants) -> Self {
         Self::Any(MatchAny { any })
     }
@@ -1357,6 +1715,10 @@ impl From<MatchInterface> for Match {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
             MatchInterface::Text(text) => Self::Text(MatchText { text: text.text }),
+            MatchInterface::Phrase(phrase) => Self::Phrase(MatchPhrase {
+                phrase: phrase.phrase,
+                slop: phrase.slop,
+            }),
             MatchInterface::Any(any) => Self::Any(MatchAny { any: any.any }),
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
@@ -1579,8 +1941,7 @@ pub struct GeoRadius {
 
 impl GeoRadius {
     pub fn check_point(&self, point: &GeoPoint) -> bool {
-        let query_center = Point::new(self.center.lon, self.center.lat);
-        query_center.haversine_distance(&Point::new(point.lon, point.lat)) < self.radius
+        self.center.haversine_distance(point) < self.radius
     }
 }
 
@@ -2575,6 +2936,21 @@ mod tests {
         assert!(filter.is_err())
     }
 
+    #[test]
+    fn test_acorn_enabled_by_default() {
+        let acorn: AcornSearchParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(acorn, AcornSearchParams::default());
+        assert!(acorn.applies_to(0.1));
+        assert!(!acorn.applies_to(0.9));
+
+        let acorn: AcornSearchParams = serde_json::from_str(r#"{"enable": false}"#).unwrap();
+        assert!(!acorn.applies_to(0.1));
+
+        let acorn: AcornSearchParams =
+            serde_json::from_str(r#"{"max_selectivity": 0.05}"#).unwrap();
+        assert!(!acorn.applies_to(0.1));
+    }
+
     #[test]
     fn test_parse_match_query() {
         let query = r#"
@@ -3457,6 +3833,52 @@ mod tests {
         });
         assert_eq!(payload, expected.into());
     }
+
+    #[test]
+    fn test_binary_quantization_mismatch_requires_rebuild() {
+        let one_bit = QuantizationConfig::from(BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: None,
+            query_encoding: None,
+        });
+        let explicit_one_bit = QuantizationConfig::from(BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: Some(BinaryQuantizationEncoding::OneBit),
+            query_encoding: Some(BinaryQuantizationQueryEncoding::Binary),
+        });
+        let two_bits = QuantizationConfig::from(BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: Some(BinaryQuantizationEncoding::TwoBits),
+            query_encoding: None,
+        });
+        let asymmetric = QuantizationConfig::from(BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: None,
+            query_encoding: Some(BinaryQuantizationQueryEncoding::Scalar8Bits),
+        });
+
+        assert!(!one_bit.mismatch_requires_rebuild(&explicit_one_bit));
+        assert!(one_bit.mismatch_requires_rebuild(&two_bits));
+        assert!(one_bit.mismatch_requires_rebuild(&asymmetric));
+        assert!(two_bits.mismatch_requires_rebuild(&asymmetric));
+    }
+
+    #[test]
+    fn test_compressed_links_mismatch_requires_rebuild() {
+        let default = HnswConfig::default();
+        let plain = HnswConfig {
+            compressed_links: Some(false),
+            ..HnswConfig::default()
+        };
+        let compressed = HnswConfig {
+            compressed_links: Some(true),
+            ..HnswConfig::default()
+        };
+
+        assert!(!default.mismatch_requires_rebuild(&plain));
+        assert!(default.mismatch_requires_rebuild(&compressed));
+        assert!(compressed.mismatch_requires_rebuild(&plain));
+    }
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Debug, Clone, PartialEq, Eq, Hash)]
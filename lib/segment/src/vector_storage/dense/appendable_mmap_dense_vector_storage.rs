@@ -11,7 +11,7 @@ use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::vectors::{PackedBits, VectorElementType, VectorElementTypeBit, VectorRef};
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::dense::dynamic_mmap_flags::DynamicMmapFlags;
@@ -51,6 +51,23 @@ pub fn open_appendable_memmap_vector_storage_byte(
     )))
 }
 
+/// Bit vectors are packed 8 dimensions per byte, `dim` is the number of bits
+pub fn open_appendable_memmap_vector_storage_bit(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_appendable_memmap_vector_storage_impl::<VectorElementTypeBit>(
+        path,
+        dim.div_ceil(PackedBits::BITS),
+        distance,
+    )?;
+
+    Ok(VectorStorageEnum::DenseAppendableMemmapBit(Box::new(
+        storage,
+    )))
+}
+
 pub fn open_appendable_memmap_vector_storage_half(
     path: &Path,
     dim: usize,
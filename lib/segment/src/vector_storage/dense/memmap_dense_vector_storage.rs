@@ -13,7 +13,7 @@ use crate::common::operation_error::{check_process_stopped, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::vectors::{PackedBits, VectorElementType, VectorElementTypeBit, VectorRef};
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::common::get_async_scorer;
 use crate::vector_storage::dense::mmap_dense_vectors::MmapDenseVectors;
@@ -60,6 +60,21 @@ pub fn open_memmap_vector_storage_byte(
     Ok(VectorStorageEnum::DenseMemmapByte(storage))
 }
 
+/// Bit vectors are packed 8 dimensions per byte, `dim` is the number of bits
+pub fn open_memmap_vector_storage_bit(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_memmap_vector_storage_with_async_io_impl::<VectorElementTypeBit>(
+        path,
+        dim.div_ceil(PackedBits::BITS),
+        distance,
+        get_async_scorer(),
+    )?;
+    Ok(VectorStorageEnum::DenseMemmapBit(storage))
+}
+
 pub fn open_memmap_vector_storage_half(
     path: &Path,
     dim: usize,
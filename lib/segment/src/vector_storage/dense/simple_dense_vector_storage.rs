@@ -15,7 +15,7 @@ use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::data_types::vectors::{PackedBits, VectorElementType, VectorElementTypeBit, VectorRef};
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
@@ -122,6 +122,25 @@ pub fn open_simple_dense_byte_vector_storage(
     Ok(VectorStorageEnum::DenseSimpleByte(storage))
 }
 
+/// Bit vectors are packed 8 dimensions per byte, `dim` is the number of bits
+pub fn open_simple_dense_bit_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_simple_dense_vector_storage_impl::<VectorElementTypeBit>(
+        database,
+        database_column_name,
+        dim.div_ceil(PackedBits::BITS),
+        distance,
+        stopped,
+    )?;
+
+    Ok(VectorStorageEnum::DenseSimpleBit(storage))
+}
+
 pub fn open_simple_dense_half_vector_storage(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
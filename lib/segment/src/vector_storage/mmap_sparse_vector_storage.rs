@@ -0,0 +1,275 @@
+use std::fs::create_dir_all;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use sparse::common::sparse_vector::SparseVector;
+
+use super::simple_sparse_vector_storage::SPARSE_VECTOR_DISTANCE;
+use super::SparseVectorStorage;
+use crate::common::blob_store::{Blob, BlobStore};
+use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::VectorRef;
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::dense::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+const STORAGE_DIR_PATH: &str = "storage";
+const DELETED_DIR_PATH: &str = "deleted";
+
+impl Blob for SparseVector {
+    fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap()
+    }
+
+    fn from_bytes(data: &[u8]) -> OperationResult<Self> {
+        bincode::deserialize(data).map_err(|_| {
+            OperationError::service_error("Cannot deserialize sparse vector from storage")
+        })
+    }
+}
+
+/// Sparse vector storage, which keeps vectors in memory mapped blob storage
+///
+/// Deleted vectors are kept in the storage, only their deleted flag is set.
+#[derive(Debug)]
+pub struct MmapSparseVectorStorage {
+    storage: BlobStore<SparseVector>,
+    /// Deleted flags, its length defines the total number of vectors
+    deleted: DynamicMmapFlags,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+    /// Total number of non-zero elements in all available vectors. Used to estimate average vector size.
+    total_sparse_size: usize,
+}
+
+pub fn open_mmap_sparse_vector_storage(
+    path: &Path,
+    stopped: &AtomicBool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = MmapSparseVectorStorage::open(path, stopped)?;
+    Ok(VectorStorageEnum::SparseMmap(storage))
+}
+
+impl MmapSparseVectorStorage {
+    pub fn open(path: &Path, stopped: &AtomicBool) -> OperationResult<Self> {
+        create_dir_all(path)?;
+
+        let storage = BlobStore::open(&path.join(STORAGE_DIR_PATH))?;
+        let mut deleted = DynamicMmapFlags::open(&path.join(DELETED_DIR_PATH))?;
+        let deleted_count = deleted.count_flags();
+
+        // Vectors may have been flushed without their deleted flags
+        if deleted.len() < storage.max_point_offset() {
+            deleted.set_len(storage.max_point_offset())?;
+        }
+
+        let mut total_sparse_size = 0;
+        storage.iter(|key, vector: SparseVector| {
+            if !deleted.get(key) {
+                total_sparse_size += vector.values.len();
+            }
+            check_process_stopped(stopped)?;
+            Ok(true)
+        })?;
+
+        Ok(Self {
+            storage,
+            deleted,
+            deleted_count,
+            total_sparse_size,
+        })
+    }
+
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if key as usize >= self.deleted.len() {
+            return false;
+        }
+        let was_deleted = self.deleted.set(key, deleted);
+        if was_deleted != deleted {
+            if !was_deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count = self.deleted_count.saturating_sub(1);
+            }
+        }
+        was_deleted
+    }
+
+    fn stored_size(&self, key: PointOffsetType) -> OperationResult<usize> {
+        Ok(self
+            .storage
+            .get(key)?
+            .map_or(0, |vector| vector.values.len()))
+    }
+
+    fn put(
+        &mut self,
+        key: PointOffsetType,
+        deleted: bool,
+        vector: &SparseVector,
+    ) -> OperationResult<()> {
+        if key as usize >= self.deleted.len() {
+            self.deleted.set_len(key as usize + 1)?;
+        } else if !self.deleted.get(key) {
+            let previous_size = self.stored_size(key)?;
+            self.total_sparse_size = self.total_sparse_size.saturating_sub(previous_size);
+        }
+
+        self.storage.put(key, vector)?;
+        self.set_deleted(key, deleted);
+        if !deleted {
+            self.total_sparse_size += vector.values.len();
+        }
+        Ok(())
+    }
+}
+
+impl SparseVectorStorage for MmapSparseVectorStorage {
+    fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector> {
+        self.storage.get(key)?.ok_or_else(|| {
+            OperationError::service_error(format!("Sparse vector {key} not found in storage"))
+        })
+    }
+}
+
+impl VectorStorage for MmapSparseVectorStorage {
+    fn distance(&self) -> Distance {
+        SPARSE_VECTOR_DISTANCE
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Float32
+    }
+
+    fn is_on_disk(&self) -> bool {
+        true
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.deleted.len()
+    }
+
+    fn available_size_in_bytes(&self) -> usize {
+        self.total_sparse_size * (std::mem::size_of::<f32>() + std::mem::size_of::<u32>())
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> CowVector {
+        let vector = self.get_vector_opt(key);
+        debug_assert!(vector.is_some());
+        vector.unwrap_or_else(CowVector::default_sparse)
+    }
+
+    /// Get vector by key, if it exists.
+    ///
+    /// ignore any error
+    fn get_vector_opt(&self, key: PointOffsetType) -> Option<CowVector> {
+        self.get_sparse(key).ok().map(CowVector::from)
+    }
+
+    fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
+        let vector: &SparseVector = vector.try_into()?;
+        debug_assert!(vector.is_sorted());
+        self.put(key, false, vector)
+    }
+
+    fn update_from(
+        &mut self,
+        other: &VectorStorageEnum,
+        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.total_vector_count() as PointOffsetType;
+        for point_id in other_ids {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector = other.get_vector(point_id);
+            let other_vector = other_vector.as_vec_ref().try_into()?;
+            let other_deleted = other.is_deleted_vector(point_id);
+            let new_id = self.total_vector_count() as PointOffsetType;
+            self.put(new_id, other_deleted, other_vector)?;
+        }
+        Ok(start_index..self.total_vector_count() as PointOffsetType)
+    }
+
+    fn flusher(&self) -> Flusher {
+        let storage_flusher = self.storage.flusher();
+        let deleted_flusher = self.deleted.flusher();
+        Box::new(move || {
+            storage_flusher()?;
+            deleted_flusher()?;
+            Ok(())
+        })
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.storage.files();
+        files.extend(self.deleted.files());
+        files
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        let is_deleted = !self.set_deleted(key, true);
+        if is_deleted {
+            let size = self.stored_size(key)?;
+            self.total_sparse_size = self.total_sparse_size.saturating_sub(size);
+        }
+        Ok(is_deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.get_bitslice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_mmap_sparse_vector_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let stopped = AtomicBool::new(false);
+        let vector_a = SparseVector::new(vec![1, 5, 10], vec![0.1, 0.5, 1.0]).unwrap();
+        let vector_b = SparseVector::new(vec![2, 3], vec![0.2, 0.3]).unwrap();
+
+        {
+            let mut storage = MmapSparseVectorStorage::open(dir.path(), &stopped).unwrap();
+            storage.insert_vector(0, (&vector_a).into()).unwrap();
+            storage.insert_vector(2, (&vector_b).into()).unwrap();
+            storage.insert_vector(3, (&vector_a).into()).unwrap();
+            assert!(storage.delete_vector(3).unwrap());
+            assert!(!storage.delete_vector(3).unwrap());
+            storage.flusher()().unwrap();
+        }
+
+        let storage = MmapSparseVectorStorage::open(dir.path(), &stopped).unwrap();
+        assert_eq!(storage.total_vector_count(), 4);
+        assert_eq!(storage.deleted_vector_count(), 1);
+        assert!(storage.is_deleted_vector(3));
+        assert!(!storage.is_deleted_vector(2));
+        assert_eq!(storage.get_sparse(0).unwrap(), vector_a);
+        assert_eq!(storage.get_sparse(2).unwrap(), vector_b);
+        assert!(storage.get_vector_opt(1).is_none());
+        assert_eq!(
+            storage.available_size_in_bytes(),
+            5 * (std::mem::size_of::<f32>() + std::mem::size_of::<u32>()),
+        );
+    }
+}
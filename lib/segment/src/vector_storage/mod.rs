@@ -16,6 +16,7 @@ mod async_io_mock;
 mod bitvec;
 pub mod common;
 pub mod dense;
+pub mod mmap_sparse_vector_storage;
 pub mod multi_dense;
 pub mod query;
 mod query_scorer;
@@ -5,20 +5,25 @@ use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
+use memory::mmap_ops::{create_and_ensure_length, open_write_mmap};
 
+use crate::common::mmap_type::MmapType;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::Flusher;
 use crate::data_types::named_vectors::{CowMultiVector, CowVector};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{TypedMultiDenseVectorRef, VectorElementType, VectorRef};
-use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
+use crate::types::{Distance, MultiVectorConfig, MultiVectorPoolingInfo, VectorStorageDatatype};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::dense::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::vector_storage::multi_dense::pooling::pool_multi_vector;
 use crate::vector_storage::{MultiVectorStorage, VectorStorage, VectorStorageEnum};
 
 const VECTORS_DIR_PATH: &str = "vectors";
 const OFFSETS_DIR_PATH: &str = "offsets";
 const DELETED_DIR_PATH: &str = "deleted";
+const ORIGINAL_COUNTS_DIR_PATH: &str = "original_counts";
+const POOLING_STATUS_FILE: &str = "pooling_status.dat";
 
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 struct MultivectorMmapOffset {
@@ -27,11 +32,24 @@ struct MultivectorMmapOffset {
     capacity: PointOffsetType,
 }
 
+/// Vector counts of all not deleted points, persisted to avoid scanning the storage
+#[repr(C)]
+#[derive(Debug, Default)]
+struct PoolingStatus {
+    num_original_vectors: usize,
+    num_pooled_vectors: usize,
+}
+
 #[derive(Debug)]
 pub struct AppendableMmapMultiDenseVectorStorage<T: PrimitiveVectorElement> {
     vectors: ChunkedMmapVectors<T>,
     offsets: ChunkedMmapVectors<MultivectorMmapOffset>,
     deleted: DynamicMmapFlags,
+    /// Number of vectors of each point before pooling, only present if pooling is configured
+    original_counts: Option<ChunkedMmapVectors<PointOffsetType>>,
+    /// Only present if pooling is configured
+    pooling_status: Option<MmapType<PoolingStatus>>,
+    path: PathBuf,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
     deleted_count: usize,
@@ -101,14 +119,47 @@ pub fn open_appendable_memmap_multi_vector_storage_impl<T: PrimitiveVectorElemen
     let deleted: DynamicMmapFlags = DynamicMmapFlags::open(&deleted_path)?;
     let deleted_count = deleted.count_flags();
 
-    Ok(AppendableMmapMultiDenseVectorStorage {
+    let original_counts = multi_vector_config
+        .pooling
+        .map(|_| ChunkedMmapVectors::open(&path.join(ORIGINAL_COUNTS_DIR_PATH), 1))
+        .transpose()?;
+
+    let mut storage = AppendableMmapMultiDenseVectorStorage {
         vectors,
         offsets,
         deleted,
+        original_counts,
+        pooling_status: None,
+        path: path.to_path_buf(),
         distance,
         multi_vector_config,
         deleted_count,
-    })
+    };
+
+    if storage.multi_vector_config.pooling.is_some() {
+        let status_path = path.join(POOLING_STATUS_FILE);
+        let is_new = !status_path.exists();
+        if is_new {
+            create_and_ensure_length(&status_path, std::mem::size_of::<PoolingStatus>())?;
+        }
+        let status_mmap = open_write_mmap(&status_path)?;
+        let mut pooling_status: MmapType<PoolingStatus> =
+            unsafe { MmapType::try_from(status_mmap)? };
+        if is_new {
+            // Storage might be created before the status was persisted, count it once
+            let mut pooling_info = MultiVectorPoolingInfo::default();
+            for key in 0..storage.total_vector_count() as PointOffsetType {
+                pooling_info += storage.point_pooling_info(key);
+            }
+            *pooling_status = PoolingStatus {
+                num_original_vectors: pooling_info.num_original_vectors,
+                num_pooled_vectors: pooling_info.num_pooled_vectors,
+            };
+        }
+        storage.pooling_status = Some(pooling_status);
+    }
+
+    Ok(storage)
 }
 
 impl<T: PrimitiveVectorElement> AppendableMmapMultiDenseVectorStorage<T> {
@@ -130,6 +181,93 @@ impl<T: PrimitiveVectorElement> AppendableMmapMultiDenseVectorStorage<T> {
         }
         Ok(previous)
     }
+
+    /// Vector counts of the point, zero if it is deleted or absent, or pooling is not configured
+    fn point_pooling_info(&self, key: PointOffsetType) -> MultiVectorPoolingInfo {
+        if self.multi_vector_config.pooling.is_none() || self.is_deleted_vector(key) {
+            return MultiVectorPoolingInfo::default();
+        }
+        match self.get_multi_opt(key) {
+            Some(multi_vector) => MultiVectorPoolingInfo {
+                num_original_vectors: self.original_vectors_count(key),
+                num_pooled_vectors: multi_vector.vectors_count(),
+            },
+            None => MultiVectorPoolingInfo::default(),
+        }
+    }
+
+    /// Replace `removed` counts of the point in the persisted status with the current ones
+    fn update_pooling_status(&mut self, key: PointOffsetType, removed: MultiVectorPoolingInfo) {
+        if self.pooling_status.is_none() {
+            return;
+        }
+        let added = self.point_pooling_info(key);
+        if let Some(pooling_status) = &mut self.pooling_status {
+            pooling_status.num_original_vectors = pooling_status.num_original_vectors
+                + added.num_original_vectors
+                - removed.num_original_vectors;
+            pooling_status.num_pooled_vectors = pooling_status.num_pooled_vectors
+                + added.num_pooled_vectors
+                - removed.num_pooled_vectors;
+        }
+    }
+
+    fn insert_vector_impl(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        original_vectors_count: usize,
+    ) -> OperationResult<()> {
+        let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
+        let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
+        let multi_vector = multi_vector.as_vec_ref();
+        assert_eq!(multi_vector.dim, self.vectors.dim());
+        let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
+        let chunk_size = self.vectors.get_chunk_size_in_bytes();
+        if multivector_size_in_bytes >= chunk_size {
+            return Err(OperationError::service_error(format!("Cannot insert multi vector of size {multivector_size_in_bytes} to the mmap vector storage. It's too large, maximum size is {chunk_size}.")));
+        }
+
+        let removed_pooling_info = self.point_pooling_info(key);
+
+        let mut offset = self
+            .offsets
+            .get(key as usize)
+            .map(|x| x.first().copied().unwrap_or_default())
+            .unwrap_or_default();
+
+        if multi_vector.vectors_count() > offset.capacity as usize {
+            // append vector to the end
+            let mut new_key = self.vectors.len();
+            let chunk_left_keys = self.vectors.get_remaining_chunk_keys(new_key);
+            if multi_vector.vectors_count() > chunk_left_keys {
+                new_key += chunk_left_keys;
+            }
+
+            offset = MultivectorMmapOffset {
+                offset: new_key as PointOffsetType,
+                count: multi_vector.vectors_count() as PointOffsetType,
+                capacity: multi_vector.vectors_count() as PointOffsetType,
+            };
+        } else {
+            // use existing place to insert vector
+            offset.count = multi_vector.vectors_count() as PointOffsetType;
+        }
+
+        self.vectors.insert_many(
+            offset.offset,
+            multi_vector.flattened_vectors,
+            multi_vector.vectors_count(),
+        )?;
+        self.offsets.insert(key as usize, &[offset])?;
+        if let Some(original_counts) = &mut self.original_counts {
+            original_counts.insert(key as usize, &[original_vectors_count as PointOffsetType])?;
+        }
+        self.set_deleted(key, false)?;
+        self.update_pooling_status(key, removed_pooling_info);
+
+        Ok(())
+    }
 }
 
 impl<T: PrimitiveVectorElement> MultiVectorStorage<T> for AppendableMmapMultiDenseVectorStorage<T> {
@@ -167,6 +305,29 @@ impl<T: PrimitiveVectorElement> MultiVectorStorage<T> for AppendableMmapMultiDen
     fn multi_vector_config(&self) -> &MultiVectorConfig {
         &self.multi_vector_config
     }
+
+    fn original_vectors_count(&self, key: PointOffsetType) -> usize {
+        let original_count = self
+            .original_counts
+            .as_ref()
+            .and_then(|original_counts| original_counts.get(key as usize))
+            .and_then(|original_count| original_count.first().copied());
+        match original_count {
+            Some(original_count) => original_count as usize,
+            None => self
+                .get_multi_opt(key)
+                .map_or(0, |multi_vector| multi_vector.vectors_count()),
+        }
+    }
+
+    fn pooling_info(&self) -> Option<MultiVectorPoolingInfo> {
+        self.pooling_status
+            .as_ref()
+            .map(|pooling_status| MultiVectorPoolingInfo {
+                num_original_vectors: pooling_status.num_original_vectors,
+                num_pooled_vectors: pooling_status.num_pooled_vectors,
+            })
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVectorStorage<T> {
@@ -210,48 +371,13 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
-        let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
-        let multi_vector = multi_vector.as_vec_ref();
-        assert_eq!(multi_vector.dim, self.vectors.dim());
-        let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
-        let chunk_size = self.vectors.get_chunk_size_in_bytes();
-        if multivector_size_in_bytes >= chunk_size {
-            return Err(OperationError::service_error(format!("Cannot insert multi vector of size {multivector_size_in_bytes} to the mmap vector storage. It's too large, maximum size is {chunk_size}.")));
-        }
-
-        let mut offset = self
-            .offsets
-            .get(key as usize)
-            .map(|x| x.first().copied().unwrap_or_default())
-            .unwrap_or_default();
-
-        if multi_vector.vectors_count() > offset.capacity as usize {
-            // append vector to the end
-            let mut new_key = self.vectors.len();
-            let chunk_left_keys = self.vectors.get_remaining_chunk_keys(new_key);
-            if multi_vector.vectors_count() > chunk_left_keys {
-                new_key += chunk_left_keys;
-            }
-
-            offset = MultivectorMmapOffset {
-                offset: new_key as PointOffsetType,
-                count: multi_vector.vectors_count() as PointOffsetType,
-                capacity: multi_vector.vectors_count() as PointOffsetType,
-            };
-        } else {
-            // use existing place to insert vector
-            offset.count = multi_vector.vectors_count() as PointOffsetType;
-        }
-
-        self.vectors.insert_many(
-            offset.offset,
-            multi_vector.flattened_vectors,
-            multi_vector.vectors_count(),
-        )?;
-        self.offsets.insert(key as usize, &[offset])?;
-        self.set_deleted(key, false)?;
-
-        Ok(())
+        let original_vectors_count = multi_vector.vectors_count();
+        let pooled = self
+            .multi_vector_config
+            .pooling
+            .and_then(|pooling| pool_multi_vector(multi_vector, pooling, self.distance));
+        let multi_vector = pooled.as_ref().map_or(multi_vector, Into::into);
+        self.insert_vector_impl(key, multi_vector.into(), original_vectors_count)
     }
 
     fn update_from(
@@ -263,13 +389,19 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
         let start_index = self.offsets.len() as PointOffsetType;
         for point_id in other_ids {
             check_process_stopped(stopped)?;
-            // Do not perform preprocessing - vectors should be already processed
+            // Do not perform preprocessing or pooling - vectors should be already processed
             let other_deleted = other.is_deleted_vector(point_id);
             let other_vector = other.get_vector(point_id);
-            let other_vector: VectorRef = other_vector.as_vec_ref();
+            let other_vector: TypedMultiDenseVectorRef<VectorElementType> =
+                other_vector.as_vec_ref().try_into()?;
+            let original_vectors_count = other
+                .original_vectors_count(point_id)
+                .unwrap_or(other_vector.vectors_count());
             let new_id = self.offsets.len() as PointOffsetType;
-            self.insert_vector(new_id, other_vector)?;
-            self.set_deleted(new_id, other_deleted)?;
+            self.insert_vector_impl(new_id, other_vector.into(), original_vectors_count)?;
+            if other_deleted {
+                self.delete_vector(new_id)?;
+            }
         }
         let end_index = self.offsets.len() as PointOffsetType;
         Ok(start_index..end_index)
@@ -280,10 +412,24 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
             let vectors_flusher = self.vectors.flusher();
             let offsets_flusher = self.offsets.flusher();
             let deleted_flusher = self.deleted.flusher();
+            let original_counts_flusher = self
+                .original_counts
+                .as_ref()
+                .map(|original_counts| original_counts.flusher());
+            let pooling_status_flusher = self
+                .pooling_status
+                .as_ref()
+                .map(|pooling_status| pooling_status.flusher());
             move || {
                 vectors_flusher()?;
                 offsets_flusher()?;
                 deleted_flusher()?;
+                if let Some(original_counts_flusher) = original_counts_flusher {
+                    original_counts_flusher()?;
+                }
+                if let Some(pooling_status_flusher) = pooling_status_flusher {
+                    pooling_status_flusher()?;
+                }
                 Ok(())
             }
         })
@@ -293,11 +439,20 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
         let mut files = self.vectors.files();
         files.extend(self.offsets.files());
         files.extend(self.deleted.files());
+        if let Some(original_counts) = &self.original_counts {
+            files.extend(original_counts.files());
+        }
+        if self.pooling_status.is_some() {
+            files.push(self.path.join(POOLING_STATUS_FILE));
+        }
         files
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
-        self.set_deleted(key, true)
+        let removed_pooling_info = self.point_pooling_info(key);
+        let previous = self.set_deleted(key, true)?;
+        self.update_pooling_status(key, removed_pooling_info);
+        Ok(previous)
     }
 
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
@@ -0,0 +1,199 @@
+//! Storage-time pooling of multivectors.
+//!
+//! Token vectors of a point are merged with agglomerative hierarchical clustering using
+//! average linkage: the two most similar clusters are merged until the target number of clusters
+//! is reached. Each cluster is then replaced with the mean of its vectors.
+//!
+//! The most similar pair is taken from a priority queue of candidate pairs, so pooling of `n`
+//! vectors takes `O(n^2 log n)` time.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use common::types::ScoreType;
+use ordered_float::OrderedFloat;
+
+use crate::data_types::vectors::{
+    MultiDenseVectorInternal, TypedMultiDenseVectorRef, VectorElementType,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
+use crate::types::{Distance, MultiVectorPooling};
+
+/// Pool vectors of a multivector according to `pooling`.
+///
+/// Returns `None` if the multivector already has no more vectors than the target.
+pub fn pool_multi_vector(
+    multi_vector: TypedMultiDenseVectorRef<VectorElementType>,
+    pooling: MultiVectorPooling,
+    distance: Distance,
+) -> Option<MultiDenseVectorInternal> {
+    match distance {
+        Distance::Cosine => pool_multi_vector_impl::<CosineMetric>(multi_vector, pooling),
+        Distance::Euclid => pool_multi_vector_impl::<EuclidMetric>(multi_vector, pooling),
+        Distance::Dot => pool_multi_vector_impl::<DotProductMetric>(multi_vector, pooling),
+        Distance::Manhattan => pool_multi_vector_impl::<ManhattanMetric>(multi_vector, pooling),
+        Distance::Hamming => pool_multi_vector_impl::<HammingMetric>(multi_vector, pooling),
+        Distance::Jaccard => pool_multi_vector_impl::<JaccardMetric>(multi_vector, pooling),
+    }
+}
+
+fn pool_multi_vector_impl<TMetric: Metric<VectorElementType>>(
+    multi_vector: TypedMultiDenseVectorRef<VectorElementType>,
+    pooling: MultiVectorPooling,
+) -> Option<MultiDenseVectorInternal> {
+    let count = multi_vector.vectors_count();
+    let target = pooling.target_count(count).max(1);
+    if target >= count {
+        return None;
+    }
+
+    let dim = multi_vector.dim;
+    let vectors: Vec<&[VectorElementType]> = multi_vector.multi_vectors().collect();
+
+    // Pairwise similarities between clusters, only `i < j` part is used
+    let mut similarities = vec![ScoreType::NEG_INFINITY; count * count];
+    let pair_index = |a: usize, b: usize| a.min(b) * count + a.max(b);
+
+    // Max-heap of candidate pairs `(similarity, first, second, first size, second size)`,
+    // ties are resolved to the first pair. Candidates are not removed on merge, instead they are
+    // skipped once the size of any of the clusters has changed.
+    let mut candidates = BinaryHeap::with_capacity(count * (count - 1) / 2);
+    for i in 0..count {
+        for j in i + 1..count {
+            let similarity = TMetric::similarity(vectors[i], vectors[j]);
+            similarities[i * count + j] = similarity;
+            candidates.push((OrderedFloat(similarity), Reverse(i), Reverse(j), 1, 1));
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = (0..count).map(|i| vec![i]).collect();
+    let mut clusters_count = count;
+
+    while clusters_count > target {
+        let Some((_, Reverse(a), Reverse(b), size_a, size_b)) = candidates.pop() else {
+            break;
+        };
+        if clusters[a].len() != size_a || clusters[b].len() != size_b {
+            continue;
+        }
+
+        let merged = std::mem::take(&mut clusters[b]);
+        clusters[a].extend(merged);
+        clusters_count -= 1;
+        let merged_size = clusters[a].len();
+
+        // Average linkage: similarity to the merged cluster is the size-weighted mean
+        for other in 0..count {
+            let other_size = clusters[other].len();
+            if other == a || other_size == 0 {
+                continue;
+            }
+            let merged_similarity = (size_a as ScoreType * similarities[pair_index(a, other)]
+                + size_b as ScoreType * similarities[pair_index(b, other)])
+                / merged_size as ScoreType;
+            similarities[pair_index(a, other)] = merged_similarity;
+
+            let (first, second, first_size, second_size) = if a < other {
+                (a, other, merged_size, other_size)
+            } else {
+                (other, a, other_size, merged_size)
+            };
+            candidates.push((
+                OrderedFloat(merged_similarity),
+                Reverse(first),
+                Reverse(second),
+                first_size,
+                second_size,
+            ));
+        }
+    }
+
+    // Replace each cluster with the mean of its vectors, keeping the order of first tokens
+    let mut flattened_vectors = Vec::with_capacity(target * dim);
+    for members in clusters.iter().filter(|members| !members.is_empty()) {
+        let mut mean = vec![0.0; dim];
+        for &member in members {
+            for (sum, value) in mean.iter_mut().zip(vectors[member]) {
+                *sum += value;
+            }
+        }
+        for value in &mut mean {
+            *value /= members.len() as VectorElementType;
+        }
+        flattened_vectors.extend(TMetric::preprocess(mean));
+    }
+
+    Some(MultiDenseVectorInternal::new(flattened_vectors, dim))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[test]
+    fn test_pooling_merges_similar_vectors() {
+        let flattened_vectors = vec![
+            1.0, 0.0, //
+            0.0, 1.0, //
+            0.9, 0.1, //
+            0.1, 0.9, //
+            1.0, 0.05,
+        ];
+        let multi_vector = TypedMultiDenseVectorRef {
+            flattened_vectors: &flattened_vectors,
+            dim: 2,
+        };
+
+        let pooling = MultiVectorPooling::TargetCount(NonZeroUsize::new(2).unwrap());
+        let pooled = pool_multi_vector(multi_vector, pooling, Distance::Euclid).unwrap();
+        assert_eq!(pooled.vectors_count(), 2);
+        let pooled: Vec<&[f32]> = pooled.multi_vectors().collect();
+        assert!((pooled[0][0] - (1.0 + 0.9 + 1.0) / 3.0).abs() < 1e-6);
+        assert!((pooled[1][1] - (1.0 + 0.9) / 2.0).abs() < 1e-6);
+
+        let pooling = MultiVectorPooling::PoolFactor(NonZeroUsize::new(2).unwrap());
+        let pooled = pool_multi_vector(multi_vector, pooling, Distance::Cosine).unwrap();
+        assert_eq!(pooled.vectors_count(), 3);
+        for vector in pooled.multi_vectors() {
+            let norm: f32 = vector.iter().map(|x| x * x).sum();
+            assert!((norm - 1.0).abs() < 1e-5);
+        }
+
+        let pooling = MultiVectorPooling::TargetCount(NonZeroUsize::new(5).unwrap());
+        assert!(pool_multi_vector(multi_vector, pooling, Distance::Dot).is_none());
+    }
+
+    #[test]
+    fn test_pooling_many_vectors() {
+        // Interleaved tokens of 3 separated groups, with a small noise
+        let count = 600;
+        let flattened_vectors: Vec<f32> = (0..count)
+            .flat_map(|i| {
+                let noise = (i * 7 % 11) as f32 / 1100.0;
+                let mut vector = vec![noise; 3];
+                vector[i % 3] += 1.0;
+                vector
+            })
+            .collect();
+        let multi_vector = TypedMultiDenseVectorRef {
+            flattened_vectors: &flattened_vectors,
+            dim: 3,
+        };
+
+        let pooling = MultiVectorPooling::TargetCount(NonZeroUsize::new(3).unwrap());
+        let pooled = pool_multi_vector(multi_vector, pooling, Distance::Euclid).unwrap();
+        assert_eq!(pooled.vectors_count(), 3);
+        // Each group is merged into a single vector, in the order of first tokens
+        for (group, vector) in pooled.multi_vectors().enumerate() {
+            for (dim, value) in vector.iter().enumerate() {
+                let expected = if dim == group { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 0.02);
+            }
+        }
+    }
+}
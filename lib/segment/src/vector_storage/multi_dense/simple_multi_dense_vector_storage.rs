@@ -16,10 +16,11 @@ use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     TypedMultiDenseVector, TypedMultiDenseVectorRef, VectorElementType, VectorRef,
 };
-use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
+use crate::types::{Distance, MultiVectorConfig, MultiVectorPoolingInfo, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::chunked_vectors::{ChunkedVectors, CHUNK_SIZE};
 use crate::vector_storage::common::StoredRecord;
+use crate::vector_storage::multi_dense::pooling::pool_multi_vector;
 use crate::vector_storage::{MultiVectorStorage, VectorStorage, VectorStorageEnum};
 
 type StoredMultiDenseVector<T> = StoredRecord<TypedMultiDenseVector<T>>;
@@ -31,6 +32,8 @@ struct MultiVectorMetadata {
     start: PointOffsetType,
     inner_vectors_count: usize,
     inner_vector_capacity: usize,
+    /// Number of vectors before storage-time pooling
+    original_vectors_count: usize,
 }
 
 /// In-memory vector storage with on-update persistence using `store`
@@ -46,6 +49,8 @@ pub struct SimpleMultiDenseVectorStorage<T: PrimitiveVectorElement> {
     deleted: BitVec,
     /// Current number of deleted vectors.
     deleted_count: usize,
+    /// Vector counts of all not deleted points, present if pooling is configured
+    pooling_info: Option<MultiVectorPoolingInfo>,
 }
 
 impl<T: fmt::Debug + PrimitiveVectorElement> fmt::Debug for SimpleMultiDenseVectorStorage<T> {
@@ -58,6 +63,7 @@ impl<T: fmt::Debug + PrimitiveVectorElement> fmt::Debug for SimpleMultiDenseVect
             .field("vectors_metadata", &self.vectors_metadata)
             .field("db_wrapper", &self.db_wrapper)
             .field("deleted_count", &self.deleted_count)
+            .field("pooling_info", &self.pooling_info)
             .finish_non_exhaustive()
     }
 }
@@ -130,18 +136,39 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     let mut vectors = ChunkedVectors::new(dim);
     let mut vectors_metadata = Vec::<MultiVectorMetadata>::new();
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
+    let mut pooling_info = multi_vector_config
+        .pooling
+        .map(|_| MultiVectorPoolingInfo::default());
     let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
     db_wrapper.lock_db().iter()?;
     for (key, value) in db_wrapper.lock_db().iter()? {
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
-        let stored_record: StoredMultiDenseVector<T> = bincode::deserialize(&value)
-            .map_err(|_| OperationError::service_error("cannot deserialize record from db"))?;
+        // Pooled records are followed by the number of vectors before pooling
+        let (stored_record, original_vectors_count) =
+            match bincode::deserialize::<(StoredMultiDenseVector<T>, u32)>(&value) {
+                Ok((stored_record, original_vectors_count)) => {
+                    (stored_record, original_vectors_count as usize)
+                }
+                Err(_) => {
+                    let stored_record: StoredMultiDenseVector<T> = bincode::deserialize(&value)
+                        .map_err(|_| {
+                            OperationError::service_error("cannot deserialize record from db")
+                        })?;
+                    let original_vectors_count = stored_record.vector.vectors_count();
+                    (stored_record, original_vectors_count)
+                }
+            };
 
         // Propagate deleted flag
         if stored_record.deleted {
             bitvec_set_deleted(&mut deleted, point_id, true);
             deleted_count += 1;
+        } else if let Some(pooling_info) = &mut pooling_info {
+            *pooling_info += MultiVectorPoolingInfo {
+                num_original_vectors: original_vectors_count,
+                num_pooled_vectors: stored_record.vector.vectors_count(),
+            };
         }
         let point_id_usize = point_id as usize;
         if point_id_usize >= vectors_metadata.len() {
@@ -151,6 +178,7 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
         let metadata = &mut vectors_metadata[point_id_usize];
         metadata.inner_vectors_count = stored_record.vector.vectors_count();
         metadata.inner_vector_capacity = metadata.inner_vectors_count;
+        metadata.original_vectors_count = original_vectors_count;
         metadata.id = point_id;
 
         metadata.start = vectors.len() as PointOffsetType;
@@ -176,6 +204,7 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
         db_wrapper,
         deleted,
         deleted_count,
+        pooling_info,
     })
 }
 
@@ -197,11 +226,34 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
         was_deleted
     }
 
+    /// Vector counts of the point, zero if it is deleted or absent, or pooling is not configured
+    fn point_pooling_info(&self, key: PointOffsetType) -> MultiVectorPoolingInfo {
+        match self.vectors_metadata.get(key as usize) {
+            Some(metadata) if self.pooling_info.is_some() && !self.is_deleted_vector(key) => {
+                MultiVectorPoolingInfo {
+                    num_original_vectors: metadata.original_vectors_count,
+                    num_pooled_vectors: metadata.inner_vectors_count,
+                }
+            }
+            _ => MultiVectorPoolingInfo::default(),
+        }
+    }
+
+    /// Replace `removed` counts of the point in the totals with the current ones
+    fn update_pooling_info(&mut self, key: PointOffsetType, removed: MultiVectorPoolingInfo) {
+        let added = self.point_pooling_info(key);
+        if let Some(pooling_info) = &mut self.pooling_info {
+            *pooling_info -= removed;
+            *pooling_info += added;
+        }
+    }
+
     fn update_stored(
         &mut self,
         key: PointOffsetType,
         deleted: bool,
         vector: Option<TypedMultiDenseVectorRef<T>>,
+        original_vectors_count: usize,
     ) -> OperationResult<()> {
         let mut record = StoredMultiDenseVector {
             deleted,
@@ -216,11 +268,14 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
                 .extend_from_slice(vector.flattened_vectors);
         }
 
-        // Store updated record
-        self.db_wrapper.put(
-            bincode::serialize(&key).unwrap(),
-            bincode::serialize(&record).unwrap(),
-        )?;
+        // Store updated record, appending the original vectors count only if it was pooled
+        let value = if original_vectors_count != record.vector.vectors_count() {
+            bincode::serialize(&(&record, original_vectors_count as u32)).unwrap()
+        } else {
+            bincode::serialize(&record).unwrap()
+        };
+        self.db_wrapper
+            .put(bincode::serialize(&key).unwrap(), value)?;
 
         Ok(())
     }
@@ -229,6 +284,7 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
         &mut self,
         key: PointOffsetType,
         vector: VectorRef,
+        original_vectors_count: usize,
         is_deleted: bool,
     ) -> OperationResult<()> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
@@ -240,6 +296,7 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
             return Err(OperationError::service_error(format!("Cannot insert multi vector of size {multivector_size_in_bytes} to the vector storage. It's too large, maximum size is {CHUNK_SIZE}.")));
         }
 
+        let removed_pooling_info = self.point_pooling_info(key);
         let key_usize = key as usize;
         if key_usize >= self.vectors_metadata.len() {
             self.vectors_metadata
@@ -248,6 +305,7 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
         let metadata = &mut self.vectors_metadata[key_usize];
         metadata.id = key;
         metadata.inner_vectors_count = multi_vector.vectors_count();
+        metadata.original_vectors_count = original_vectors_count;
 
         if multi_vector.vectors_count() > metadata.inner_vector_capacity {
             metadata.inner_vector_capacity = metadata.inner_vectors_count;
@@ -270,7 +328,8 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
         }
 
         self.set_deleted(key, is_deleted);
-        self.update_stored(key, is_deleted, Some(multi_vector))?;
+        self.update_pooling_info(key, removed_pooling_info);
+        self.update_stored(key, is_deleted, Some(multi_vector), original_vectors_count)?;
         Ok(())
     }
 }
@@ -309,6 +368,16 @@ impl<T: PrimitiveVectorElement> MultiVectorStorage<T> for SimpleMultiDenseVector
     fn multi_vector_config(&self) -> &MultiVectorConfig {
         &self.multi_vector_config
     }
+
+    fn original_vectors_count(&self, key: PointOffsetType) -> usize {
+        self.vectors_metadata
+            .get(key as usize)
+            .map_or(0, |metadata| metadata.original_vectors_count)
+    }
+
+    fn pooling_info(&self) -> Option<MultiVectorPoolingInfo> {
+        self.pooling_info
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for SimpleMultiDenseVectorStorage<T> {
@@ -351,7 +420,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleMultiDenseVectorStorage<
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        self.insert_vector_impl(key, vector, false)
+        let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
+        let original_vectors_count = multi_vector.vectors_count();
+        let pooled = self
+            .multi_vector_config
+            .pooling
+            .and_then(|pooling| pool_multi_vector(multi_vector, pooling, self.distance));
+        let multi_vector = pooled.as_ref().map_or(multi_vector, Into::into);
+        self.insert_vector_impl(key, multi_vector.into(), original_vectors_count, false)
     }
 
     fn update_from(
@@ -363,12 +439,21 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleMultiDenseVectorStorage<
         let start_index = self.vectors_metadata.len() as PointOffsetType;
         for point_id in other_ids {
             check_process_stopped(stopped)?;
-            // Do not perform preprocessing - vectors should be already processed
+            // Do not perform preprocessing or pooling - vectors should be already processed
             let other_deleted = other.is_deleted_vector(point_id);
             let other_vector = other.get_vector(point_id);
-            let other_vector: VectorRef = other_vector.as_vec_ref();
+            let other_vector: TypedMultiDenseVectorRef<VectorElementType> =
+                other_vector.as_vec_ref().try_into()?;
+            let original_vectors_count = other
+                .original_vectors_count(point_id)
+                .unwrap_or(other_vector.vectors_count());
             let new_id = self.vectors_metadata.len() as PointOffsetType;
-            self.insert_vector_impl(new_id, other_vector, other_deleted)?;
+            self.insert_vector_impl(
+                new_id,
+                other_vector.into(),
+                original_vectors_count,
+                other_deleted,
+            )?;
         }
         let end_index = self.vectors_metadata.len() as PointOffsetType;
         Ok(start_index..end_index)
@@ -383,9 +468,12 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleMultiDenseVectorStorage<
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        let removed_pooling_info = self.point_pooling_info(key);
         let is_deleted = !self.set_deleted(key, true);
         if is_deleted {
-            self.update_stored(key, true, None)?;
+            self.update_pooling_info(key, removed_pooling_info);
+            let original_vectors_count = self.original_vectors_count(key);
+            self.update_stored(key, true, None, original_vectors_count)?;
         }
         Ok(is_deleted)
     }
@@ -1,6 +1,8 @@
+pub mod quantized_bit_planes;
 mod quantized_custom_query_scorer;
 mod quantized_mmap_storage;
 pub mod quantized_multivector_storage;
 mod quantized_query_scorer;
+pub mod quantized_scalar_u4;
 mod quantized_scorer_builder;
 pub mod quantized_vectors;
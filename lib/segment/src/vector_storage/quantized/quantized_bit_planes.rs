@@ -0,0 +1,722 @@
+//! Binary quantization with more than one bit per dimension and asymmetric query scoring.
+//!
+//! Every dimension is centered by its mean and encoded into a 2-bit code with thresholds at
+//! `-t, 0, t`, where `t` depends on the deviation of the dimension. The code is stored as two
+//! bit planes: the high bit (sign) and the low bit. Dimensions encoded with a single bit only
+//! store the high plane, their low bit is implicitly the inverse of the high bit. This way each
+//! dimension is reconstructed as a linear combination of bits, and all scores can be computed
+//! with popcounts over the planes.
+//!
+//! | Encoding          | Dimensions with two bits                         |
+//! |-------------------|--------------------------------------------------|
+//! | `one_bit`         | none                                             |
+//! | `one_and_half_bits` | half of dimensions with the highest variance   |
+//! | `two_bits`        | all                                              |
+//!
+//! The query is either encoded the same way as the stored vectors (symmetric scoring), or it is
+//! quantized to 4/8-bit scalars and decomposed into bit planes (asymmetric scoring).
+
+use std::path::Path;
+
+use common::types::{PointOffsetType, ScoreType};
+use quantization::{
+    DistanceType, EncodedStorage, EncodedStorageBuilder, EncodedVectors, EncodingError,
+    VectorParameters,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding};
+
+const BITS_IN_WORD: usize = u64::BITS as usize;
+const WORD_SIZE: usize = std::mem::size_of::<u64>();
+
+/// Per-vector header: dot product with the means, squared norm and bit-independent part of the
+/// dot product of the reconstructed levels
+const HEADER_SIZE: usize = 3 * std::mem::size_of::<f32>();
+
+/// Low bit threshold, in deviations of the dimension.
+/// Optimal for 4-level quantization of normally distributed values.
+const LOW_BIT_THRESHOLD: f32 = 0.98;
+
+pub struct EncodedVectorsBitPlanes<TStorage: EncodedStorage> {
+    encoded_vectors: TStorage,
+    metadata: Metadata,
+    layout: Layout,
+    means_squared_norm: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    vector_parameters: VectorParameters,
+    encoding: BinaryQuantizationEncoding,
+    query_encoding: BinaryQuantizationQueryEncoding,
+    /// Per-dimension mean, subtracted before encoding
+    means: Vec<f32>,
+    /// Per-dimension threshold of the low bit
+    thresholds: Vec<f32>,
+    /// Order of dimensions in the bit planes. Dimensions with two bits go first
+    permutation: Vec<u32>,
+    /// Scale of the reconstructed levels
+    scale: f32,
+}
+
+/// Sizes of the bit planes, derived from the metadata
+#[derive(Clone, Copy)]
+struct Layout {
+    dim: usize,
+    /// Number of dimensions encoded with two bits, either all or a multiple of the word size
+    two_bits_dim: usize,
+    /// Words in the high bit plane
+    high_words: usize,
+    /// Words in the low bit plane
+    low_words: usize,
+}
+
+impl Layout {
+    fn new(dim: usize, encoding: BinaryQuantizationEncoding) -> Self {
+        let two_bits_dim = match encoding {
+            BinaryQuantizationEncoding::OneBit => 0,
+            BinaryQuantizationEncoding::OneAndHalfBits => {
+                (dim / 2).next_multiple_of(BITS_IN_WORD).min(dim)
+            }
+            BinaryQuantizationEncoding::TwoBits => dim,
+        };
+        Self {
+            dim,
+            two_bits_dim,
+            high_words: dim.div_ceil(BITS_IN_WORD),
+            low_words: two_bits_dim.div_ceil(BITS_IN_WORD),
+        }
+    }
+
+    fn quantized_vector_size(&self) -> usize {
+        HEADER_SIZE + (self.high_words + self.low_words) * WORD_SIZE
+    }
+
+    /// Bit-independent part of the dot product of two vectors
+    fn constant_dot(&self) -> f32 {
+        let one_bit_dim = self.dim - self.two_bits_dim;
+        2.25 * self.two_bits_dim as f32 + 0.25 * one_bit_dim as f32
+    }
+}
+
+/// View of an encoded vector
+#[derive(Clone, Copy)]
+struct BitPlanes<'a> {
+    data: &'a [u8],
+    layout: &'a Layout,
+}
+
+pub enum EncodedBitPlanesQuery {
+    /// Query encoded the same way as the stored vectors
+    Binary(Vec<u8>),
+    /// Query quantized to scalars, decomposed into bit planes
+    Scalar(ScalarQuery),
+}
+
+pub struct ScalarQuery {
+    /// Bit planes of the quantized values, from the least significant
+    planes: Vec<Vec<u64>>,
+    /// Value of the zero level
+    offset: f32,
+    /// Distance between levels
+    step: f32,
+    /// Score part which doesn't depend on the stored vector
+    constant: f32,
+}
+
+impl<TStorage: EncodedStorage> EncodedVectorsBitPlanes<TStorage> {
+    pub fn encode<'a>(
+        orig_data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<TStorage>,
+        vector_parameters: &VectorParameters,
+        encoding: BinaryQuantizationEncoding,
+        query_encoding: BinaryQuantizationQueryEncoding,
+        stop_condition: impl Fn() -> bool,
+    ) -> Result<Self, EncodingError> {
+        let dim = vector_parameters.dim;
+
+        // First pass: per-dimension statistics
+        let mut sums = vec![0.0f64; dim];
+        let mut squared_sums = vec![0.0f64; dim];
+        let mut count = 0usize;
+        for vector in orig_data.clone() {
+            if stop_condition() {
+                return Err(EncodingError::Stopped);
+            }
+            for (i, &value) in vector.as_ref().iter().enumerate() {
+                sums[i] += f64::from(value);
+                squared_sums[i] += f64::from(value) * f64::from(value);
+            }
+            count += 1;
+        }
+
+        let count = count.max(1) as f64;
+        let means: Vec<f32> = sums.iter().map(|sum| (sum / count) as f32).collect();
+        let deviations: Vec<f32> = sums
+            .iter()
+            .zip(&squared_sums)
+            .map(|(sum, squared_sum)| {
+                let mean = sum / count;
+                (squared_sum / count - mean * mean).max(0.0).sqrt() as f32
+            })
+            .collect();
+        let thresholds = deviations
+            .iter()
+            .map(|deviation| deviation * LOW_BIT_THRESHOLD)
+            .collect();
+
+        // Dimensions with the highest variance get the second bit
+        let mut permutation: Vec<u32> = (0..dim as u32).collect();
+        if encoding == BinaryQuantizationEncoding::OneAndHalfBits {
+            permutation.sort_by(|&a, &b| deviations[b as usize].total_cmp(&deviations[a as usize]));
+        }
+
+        let metadata = Metadata {
+            vector_parameters: vector_parameters.clone(),
+            encoding,
+            query_encoding,
+            means,
+            thresholds,
+            permutation,
+            scale: 1.0,
+        };
+        let layout = Layout::new(dim, encoding);
+
+        // Second pass: encode, and fit the scale of levels with least squares
+        let mut values_dot_levels = 0.0f64;
+        let mut levels_squared = 0.0f64;
+        for vector in orig_data {
+            if stop_condition() {
+                return Err(EncodingError::Stopped);
+            }
+            let vector = vector.as_ref();
+            let encoded = metadata.encode_vector(&layout, vector);
+            let planes = BitPlanes::new(&encoded, &layout);
+            for (i, &d) in metadata.permutation.iter().enumerate() {
+                let d = d as usize;
+                values_dot_levels +=
+                    f64::from(vector[d] - metadata.means[d]) * f64::from(planes.level(i));
+            }
+            levels_squared += f64::from(planes.squared_norm());
+            storage_builder.push_vector_data(&encoded);
+        }
+
+        let mut result = Self::new(storage_builder.build(), metadata, layout);
+        if levels_squared > 0.0 && values_dot_levels > 0.0 {
+            result.metadata.scale = (values_dot_levels / levels_squared) as f32;
+        }
+        Ok(result)
+    }
+
+    fn new(encoded_vectors: TStorage, metadata: Metadata, layout: Layout) -> Self {
+        let means_squared_norm = metadata.means.iter().map(|m| m * m).sum();
+        Self {
+            encoded_vectors,
+            metadata,
+            layout,
+            means_squared_norm,
+        }
+    }
+
+    pub fn get_quantized_vector_size(
+        vector_parameters: &VectorParameters,
+        encoding: BinaryQuantizationEncoding,
+    ) -> usize {
+        Layout::new(vector_parameters.dim, encoding).quantized_vector_size()
+    }
+
+    fn get_planes(&self, i: PointOffsetType) -> BitPlanes {
+        let data = self
+            .encoded_vectors
+            .get_vector_data(i as usize, self.layout.quantized_vector_size());
+        BitPlanes::new(data, &self.layout)
+    }
+
+    fn score_symmetric(&self, a: BitPlanes, b: BitPlanes) -> ScoreType {
+        let levels_dot = a.levels_dot(b);
+        let scale = self.metadata.scale;
+        let score = match self.metadata.vector_parameters.distance_type {
+            DistanceType::Dot => {
+                self.means_squared_norm
+                    + scale * (a.mean_dot() + b.mean_dot())
+                    + scale * scale * levels_dot
+            }
+            // Manhattan distance is approximated with the euclidean one
+            DistanceType::L1 | DistanceType::L2 => {
+                scale * scale * (a.squared_norm() + b.squared_norm() - 2.0 * levels_dot)
+            }
+        };
+        self.postprocess(score)
+    }
+
+    fn score_scalar(&self, query: &ScalarQuery, b: BitPlanes) -> ScoreType {
+        let query_dot_levels = query.dot_levels(b);
+        let scale = self.metadata.scale;
+        let score = match self.metadata.vector_parameters.distance_type {
+            DistanceType::Dot => query.constant + scale * query_dot_levels,
+            DistanceType::L1 | DistanceType::L2 => {
+                query.constant - 2.0 * scale * query_dot_levels + scale * scale * b.squared_norm()
+            }
+        };
+        self.postprocess(score)
+    }
+
+    fn postprocess(&self, score: f32) -> ScoreType {
+        if self.metadata.vector_parameters.invert {
+            -score
+        } else {
+            score
+        }
+    }
+
+    fn encode_scalar_query(&self, query: &[f32], bits: usize) -> ScalarQuery {
+        let Self {
+            metadata, layout, ..
+        } = self;
+
+        // Euclidean distance is computed against the centered query
+        let centered = match metadata.vector_parameters.distance_type {
+            DistanceType::Dot => false,
+            DistanceType::L1 | DistanceType::L2 => true,
+        };
+        let permuted: Vec<f32> = metadata
+            .permutation
+            .iter()
+            .map(|&d| {
+                let d = d as usize;
+                if centered {
+                    query[d] - metadata.means[d]
+                } else {
+                    query[d]
+                }
+            })
+            .collect();
+
+        let constant = if centered {
+            permuted.iter().map(|v| v * v).sum()
+        } else {
+            query.iter().zip(&metadata.means).map(|(q, m)| q * m).sum()
+        };
+        // Bit-independent part of the levels, `-1.5` for two bits and `-0.5` for one bit
+        let levels_offset: f32 = permuted[..layout.two_bits_dim].iter().sum::<f32>() * -1.5
+            + permuted[layout.two_bits_dim..].iter().sum::<f32>() * -0.5;
+        let scale = match metadata.vector_parameters.distance_type {
+            DistanceType::Dot => metadata.scale,
+            DistanceType::L1 | DistanceType::L2 => -2.0 * metadata.scale,
+        };
+
+        let min = permuted.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = permuted.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let levels = ((1u32 << bits) - 1) as f32;
+        let step = if max > min { (max - min) / levels } else { 0.0 };
+
+        let mut planes = vec![vec![0u64; layout.high_words]; bits];
+        for (i, &value) in permuted.iter().enumerate() {
+            let quantized = if step > 0.0 {
+                ((value - min) / step).round() as u32
+            } else {
+                0
+            };
+            for (bit, plane) in planes.iter_mut().enumerate() {
+                if quantized & (1 << bit) != 0 {
+                    plane[i / BITS_IN_WORD] |= 1 << (i % BITS_IN_WORD);
+                }
+            }
+        }
+
+        ScalarQuery {
+            planes,
+            offset: min,
+            step,
+            constant: constant + scale * levels_offset,
+        }
+    }
+}
+
+impl Metadata {
+    fn encode_vector(&self, layout: &Layout, vector: &[f32]) -> Vec<u8> {
+        let mut high = vec![0u64; layout.high_words];
+        let mut low = vec![0u64; layout.low_words];
+        let mut mean_dot = 0.0;
+        let mut squared_norm = 0.0;
+        let mut levels_offset = 0.0;
+        for (i, &d) in self.permutation.iter().enumerate() {
+            let d = d as usize;
+            let value = vector[d] - self.means[d];
+            let threshold = self.thresholds[d];
+            let high_bit = value > 0.0;
+            if high_bit {
+                high[i / BITS_IN_WORD] |= 1 << (i % BITS_IN_WORD);
+            }
+            let level = if i < layout.two_bits_dim {
+                let low_bit = value > threshold || (value > -threshold && !high_bit);
+                if low_bit {
+                    low[i / BITS_IN_WORD] |= 1 << (i % BITS_IN_WORD);
+                }
+                let bits = 2.0 * f32::from(u8::from(high_bit)) + f32::from(u8::from(low_bit));
+                levels_offset += -1.5 * bits;
+                bits - 1.5
+            } else {
+                let bits = f32::from(u8::from(high_bit));
+                levels_offset += -0.5 * bits;
+                bits - 0.5
+            };
+            mean_dot += self.means[d] * level;
+            squared_norm += level * level;
+        }
+
+        let mut encoded = Vec::with_capacity(layout.quantized_vector_size());
+        encoded.extend_from_slice(&f32::to_le_bytes(mean_dot));
+        encoded.extend_from_slice(&f32::to_le_bytes(squared_norm));
+        encoded.extend_from_slice(&f32::to_le_bytes(levels_offset));
+        for word in high.iter().chain(&low) {
+            encoded.extend_from_slice(&word.to_le_bytes());
+        }
+        encoded
+    }
+}
+
+impl<'a> BitPlanes<'a> {
+    fn new(data: &'a [u8], layout: &'a Layout) -> Self {
+        debug_assert_eq!(data.len(), layout.quantized_vector_size());
+        Self { data, layout }
+    }
+
+    #[inline]
+    fn read_f32(&self, offset: usize) -> f32 {
+        f32::from_le_bytes(self.data[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// Dot product of the reconstructed levels with the means
+    fn mean_dot(&self) -> f32 {
+        self.read_f32(0)
+    }
+
+    /// Squared norm of the reconstructed levels
+    fn squared_norm(&self) -> f32 {
+        self.read_f32(4)
+    }
+
+    /// Sum of bits weighted with the bit-independent parts of the levels
+    fn levels_offset(&self) -> f32 {
+        self.read_f32(8)
+    }
+
+    #[inline]
+    fn word(&self, w: usize) -> u64 {
+        let offset = HEADER_SIZE + w * WORD_SIZE;
+        u64::from_le_bytes(self.data[offset..offset + WORD_SIZE].try_into().unwrap())
+    }
+
+    #[inline]
+    fn high(&self, w: usize) -> u64 {
+        self.word(w)
+    }
+
+    #[inline]
+    fn low(&self, w: usize) -> u64 {
+        self.word(self.layout.high_words + w)
+    }
+
+    /// Reconstructed level of the `i`-th dimension in the planes order
+    fn level(&self, i: usize) -> f32 {
+        let mask = 1 << (i % BITS_IN_WORD);
+        let high = f32::from(u8::from(self.high(i / BITS_IN_WORD) & mask != 0));
+        if i < self.layout.two_bits_dim {
+            let low = f32::from(u8::from(self.low(i / BITS_IN_WORD) & mask != 0));
+            2.0 * high + low - 1.5
+        } else {
+            high - 0.5
+        }
+    }
+
+    /// Dot product of the reconstructed levels of two vectors
+    fn levels_dot(&self, other: BitPlanes) -> f32 {
+        let layout = self.layout;
+        let mut bits_dot = 0u32;
+        for w in 0..layout.low_words {
+            let (h_a, l_a) = (self.high(w), self.low(w));
+            let (h_b, l_b) = (other.high(w), other.low(w));
+            bits_dot += 4 * (h_a & h_b).count_ones()
+                + 2 * (h_a & l_b).count_ones()
+                + 2 * (l_a & h_b).count_ones()
+                + (l_a & l_b).count_ones();
+        }
+        for w in layout.low_words..layout.high_words {
+            bits_dot += (self.high(w) & other.high(w)).count_ones();
+        }
+        bits_dot as f32 + self.levels_offset() + other.levels_offset() + layout.constant_dot()
+    }
+}
+
+impl ScalarQuery {
+    /// Weighted sum of query values over the set bits of the plane word
+    #[inline]
+    fn masked_sum(&self, word: u64, w: usize) -> (u32, u32) {
+        let weighted = self
+            .planes
+            .iter()
+            .enumerate()
+            .map(|(bit, plane)| (plane[w] & word).count_ones() << bit)
+            .sum();
+        (word.count_ones(), weighted)
+    }
+
+    /// Dot product of the query with the bit-dependent part of the reconstructed levels
+    fn dot_levels(&self, vector: BitPlanes) -> f32 {
+        let layout = vector.layout;
+        let mut bits_count = 0u32;
+        let mut weighted = 0u32;
+        for w in 0..layout.low_words {
+            let (high_count, high_weighted) = self.masked_sum(vector.high(w), w);
+            let (low_count, low_weighted) = self.masked_sum(vector.low(w), w);
+            bits_count += 2 * high_count + low_count;
+            weighted += 2 * high_weighted + low_weighted;
+        }
+        for w in layout.low_words..layout.high_words {
+            let (high_count, high_weighted) = self.masked_sum(vector.high(w), w);
+            bits_count += high_count;
+            weighted += high_weighted;
+        }
+        self.offset * bits_count as f32 + self.step * weighted as f32
+    }
+}
+
+impl<TStorage: EncodedStorage> EncodedVectors<EncodedBitPlanesQuery>
+    for EncodedVectorsBitPlanes<TStorage>
+{
+    fn save(&self, data_path: &Path, meta_path: &Path) -> std::io::Result<()> {
+        meta_path.parent().map(std::fs::create_dir_all);
+        let metadata_bytes = serde_json::to_vec(&self.metadata)?;
+        std::fs::write(meta_path, metadata_bytes)?;
+
+        data_path.parent().map(std::fs::create_dir_all);
+        self.encoded_vectors.save_to_file(data_path)?;
+        Ok(())
+    }
+
+    fn load(
+        data_path: &Path,
+        meta_path: &Path,
+        vector_parameters: &VectorParameters,
+    ) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(meta_path)?;
+        let metadata: Metadata = serde_json::from_str(&contents)?;
+        let layout = Layout::new(vector_parameters.dim, metadata.encoding);
+        let encoded_vectors = TStorage::from_file(
+            data_path,
+            layout.quantized_vector_size(),
+            vector_parameters.count,
+        )?;
+        Ok(Self::new(encoded_vectors, metadata, layout))
+    }
+
+    fn encode_query(&self, query: &[f32]) -> EncodedBitPlanesQuery {
+        match self.metadata.query_encoding {
+            BinaryQuantizationQueryEncoding::Binary => {
+                EncodedBitPlanesQuery::Binary(self.metadata.encode_vector(&self.layout, query))
+            }
+            BinaryQuantizationQueryEncoding::Scalar4Bits => {
+                EncodedBitPlanesQuery::Scalar(self.encode_scalar_query(query, 4))
+            }
+            BinaryQuantizationQueryEncoding::Scalar8Bits => {
+                EncodedBitPlanesQuery::Scalar(self.encode_scalar_query(query, 8))
+            }
+        }
+    }
+
+    fn score_point(&self, query: &EncodedBitPlanesQuery, i: PointOffsetType) -> ScoreType {
+        let vector = self.get_planes(i);
+        match query {
+            EncodedBitPlanesQuery::Binary(query) => {
+                self.score_symmetric(BitPlanes::new(query, &self.layout), vector)
+            }
+            EncodedBitPlanesQuery::Scalar(query) => self.score_scalar(query, vector),
+        }
+    }
+
+    fn score_internal(&self, i: PointOffsetType, j: PointOffsetType) -> ScoreType {
+        self.score_symmetric(self.get_planes(i), self.get_planes(j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    use super::*;
+    use crate::vector_storage::chunked_vectors::ChunkedVectors;
+
+    fn encode(
+        vectors: &[Vec<f32>],
+        distance_type: DistanceType,
+        encoding: BinaryQuantizationEncoding,
+        query_encoding: BinaryQuantizationQueryEncoding,
+    ) -> EncodedVectorsBitPlanes<ChunkedVectors<u8>> {
+        let vector_parameters = VectorParameters {
+            dim: vectors[0].len(),
+            count: vectors.len(),
+            invert: distance_type != DistanceType::Dot,
+            distance_type,
+        };
+        let size = EncodedVectorsBitPlanes::<ChunkedVectors<u8>>::get_quantized_vector_size(
+            &vector_parameters,
+            encoding,
+        );
+        EncodedVectorsBitPlanes::encode(
+            vectors.iter(),
+            ChunkedVectors::<u8>::new(size),
+            &vector_parameters,
+            encoding,
+            query_encoding,
+            || false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_levels_match_popcount_scores() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 150;
+        let vectors: Vec<Vec<f32>> = (0..20)
+            .map(|_| (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+
+        for encoding in [
+            BinaryQuantizationEncoding::OneBit,
+            BinaryQuantizationEncoding::OneAndHalfBits,
+            BinaryQuantizationEncoding::TwoBits,
+        ] {
+            let encoded = encode(
+                &vectors,
+                DistanceType::Dot,
+                encoding,
+                BinaryQuantizationQueryEncoding::Binary,
+            );
+            let a = encoded.get_planes(0);
+            let b = encoded.get_planes(1);
+
+            let expected: f32 = (0..dim).map(|i| a.level(i) * b.level(i)).sum();
+            assert!((a.levels_dot(b) - expected).abs() < 1e-3);
+
+            let expected_norm: f32 = (0..dim).map(|i| a.level(i).powi(2)).sum();
+            assert!((a.squared_norm() - expected_norm).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_save_load() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let vectors: Vec<Vec<f32>> = (0..10)
+            .map(|_| (0..70).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+        let encoded = encode(
+            &vectors,
+            DistanceType::L2,
+            BinaryQuantizationEncoding::OneAndHalfBits,
+            BinaryQuantizationQueryEncoding::Scalar8Bits,
+        );
+
+        let dir = tempfile::Builder::new()
+            .prefix("bit_planes")
+            .tempdir()
+            .unwrap();
+        let data_path = dir.path().join("data");
+        let meta_path = dir.path().join("meta");
+        encoded.save(&data_path, &meta_path).unwrap();
+        let loaded = EncodedVectorsBitPlanes::<ChunkedVectors<u8>>::load(
+            &data_path,
+            &meta_path,
+            &encoded.metadata.vector_parameters,
+        )
+        .unwrap();
+
+        let query = loaded.encode_query(&vectors[3]);
+        for i in 0..vectors.len() as PointOffsetType {
+            assert_eq!(
+                loaded.score_point(&query, i),
+                encoded.score_point(&encoded.encode_query(&vectors[3]), i),
+            );
+        }
+    }
+
+    /// Share of the true top-`k` found among the top `4 * k` quantized scores
+    fn recall(
+        encoded: &EncodedVectorsBitPlanes<ChunkedVectors<u8>>,
+        vectors: &[Vec<f32>],
+        queries: &[Vec<f32>],
+        k: usize,
+    ) -> f32 {
+        let top = |scores: Vec<f32>, limit: usize| -> Vec<usize> {
+            let mut ids: Vec<usize> = (0..scores.len()).collect();
+            ids.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+            ids.truncate(limit);
+            ids
+        };
+        let mut found = 0;
+        for query in queries {
+            let raw_scores = vectors
+                .iter()
+                .map(|v| v.iter().zip(query).map(|(a, b)| a * b).sum())
+                .collect();
+            let encoded_query = encoded.encode_query(query);
+            let quantized_scores = (0..vectors.len() as PointOffsetType)
+                .map(|i| encoded.score_point(&encoded_query, i))
+                .collect();
+            let candidates = top(quantized_scores, 4 * k);
+            found += top(raw_scores, k)
+                .iter()
+                .filter(|id| candidates.contains(id))
+                .count();
+        }
+        found as f32 / (k * queries.len()) as f32
+    }
+
+    #[test]
+    fn test_recall_against_raw_scores() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 128;
+        let mut random_vector =
+            || -> Vec<f32> { (0..dim).map(|_| rng.sample(StandardNormal)).collect() };
+        let vectors: Vec<Vec<f32>> = (0..500).map(|_| random_vector()).collect();
+        let queries: Vec<Vec<f32>> = (0..20).map(|_| random_vector()).collect();
+
+        for (encoding, query_encoding, min_recall) in [
+            (
+                BinaryQuantizationEncoding::OneBit,
+                BinaryQuantizationQueryEncoding::Binary,
+                0.3,
+            ),
+            (
+                BinaryQuantizationEncoding::OneBit,
+                BinaryQuantizationQueryEncoding::Scalar8Bits,
+                0.55,
+            ),
+            (
+                BinaryQuantizationEncoding::OneAndHalfBits,
+                BinaryQuantizationQueryEncoding::Scalar4Bits,
+                0.65,
+            ),
+            (
+                BinaryQuantizationEncoding::TwoBits,
+                BinaryQuantizationQueryEncoding::Binary,
+                0.7,
+            ),
+            (
+                BinaryQuantizationEncoding::TwoBits,
+                BinaryQuantizationQueryEncoding::Scalar8Bits,
+                0.85,
+            ),
+        ] {
+            let encoded = encode(&vectors, DistanceType::Dot, encoding, query_encoding);
+            let recall = recall(&encoded, &vectors, &queries, 10);
+            assert!(
+                recall >= min_recall,
+                "{encoding:?} with {query_encoding:?} query: recall {recall} < {min_recall}",
+            );
+        }
+    }
+}
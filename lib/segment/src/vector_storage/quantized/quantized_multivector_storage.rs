@@ -12,6 +12,7 @@ use crate::common::mmap_type::MmapSlice;
 use crate::common::operation_error::OperationResult;
 use crate::data_types::vectors::{TypedMultiDenseVectorRef, VectorElementType};
 use crate::types::{MultiVectorComparator, MultiVectorConfig};
+use crate::vector_storage::query_scorer::score_multi_similarity;
 
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
 pub struct MultivectorOffset {
@@ -235,12 +236,38 @@ where
     fn score_point(&self, query: &Vec<TEncodedQuery>, i: PointOffsetType) -> ScoreType {
         match self.multi_vector_config.comparator {
             MultiVectorComparator::MaxSim => self.score_point_max_similarity(query, i),
+            comparator @ (MultiVectorComparator::MinSim
+            | MultiVectorComparator::AvgSim
+            | MultiVectorComparator::Chamfer) => {
+                let offset = self.offsets.get_offset(i);
+                score_multi_similarity(comparator, query.len(), offset.count as usize, |a, b| {
+                    self.quantized_storage
+                        .score_point(&query[a], offset.start + b as PointOffsetType)
+                })
+            }
         }
     }
 
     fn score_internal(&self, i: PointOffsetType, j: PointOffsetType) -> ScoreType {
         match self.multi_vector_config.comparator {
             MultiVectorComparator::MaxSim => self.score_internal_max_similarity(i, j),
+            comparator @ (MultiVectorComparator::MinSim
+            | MultiVectorComparator::AvgSim
+            | MultiVectorComparator::Chamfer) => {
+                let offset_a = self.offsets.get_offset(i);
+                let offset_b = self.offsets.get_offset(j);
+                score_multi_similarity(
+                    comparator,
+                    offset_a.count as usize,
+                    offset_b.count as usize,
+                    |a, b| {
+                        self.quantized_storage.score_internal(
+                            offset_a.start + a as PointOffsetType,
+                            offset_b.start + b as PointOffsetType,
+                        )
+                    },
+                )
+            }
         }
     }
 }
@@ -0,0 +1,413 @@
+//! Scalar quantization into 4-bit integers.
+//!
+//! Values are calibrated the same way as in 8-bit scalar quantization: the range of values
+//! (optionally limited by a quantile) is split into 16 levels. Two levels are packed into a byte,
+//! the even dimension in the low nibble and the odd one in the high nibble.
+//!
+//! Each vector is prefixed with a correction term, so that the score of two vectors only
+//! requires a dot product (or a sum of absolute differences) of the packed levels.
+
+use std::path::Path;
+
+use common::types::{PointOffsetType, ScoreType};
+use quantization::{
+    DistanceType, EncodedStorage, EncodedStorageBuilder, EncodedVectors, EncodingError,
+    VectorParameters,
+};
+use serde::{Deserialize, Serialize};
+
+/// Maximal quantized level
+const MAX_LEVEL: f32 = 15.0;
+
+/// Per-vector correction term, see [`Metadata::correction`]
+const HEADER_SIZE: usize = std::mem::size_of::<f32>();
+
+/// Maximal number of values used to estimate quantiles
+const QUANTILE_SAMPLE_SIZE: usize = 100_000;
+
+/// Bytes processed with a 16-bit accumulator.
+/// Each byte adds at most `2 * 15 * 15 = 450`, so the block sum fits into `u16`.
+const BLOCK_SIZE: usize = 64;
+
+pub struct EncodedVectorsU4<TStorage: EncodedStorage> {
+    encoded_vectors: TStorage,
+    metadata: Metadata,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    vector_parameters: VectorParameters,
+    /// Value of the zero level
+    offset: f32,
+    /// Distance between levels
+    multiplier: f32,
+}
+
+pub struct EncodedQueryU4 {
+    encoded: Vec<u8>,
+}
+
+impl<TStorage: EncodedStorage> EncodedVectorsU4<TStorage> {
+    pub fn encode<'a>(
+        orig_data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<TStorage>,
+        vector_parameters: &VectorParameters,
+        quantile: Option<f32>,
+        stop_condition: impl Fn() -> bool,
+    ) -> Result<Self, EncodingError> {
+        let (min, max) = Self::find_min_max(orig_data.clone(), vector_parameters, quantile)?;
+        let multiplier = if max > min {
+            (max - min) / MAX_LEVEL
+        } else {
+            1.0
+        };
+        let metadata = Metadata {
+            vector_parameters: vector_parameters.clone(),
+            offset: min,
+            multiplier,
+        };
+
+        for vector in orig_data {
+            if stop_condition() {
+                return Err(EncodingError::Stopped);
+            }
+            storage_builder.push_vector_data(&metadata.encode_vector(vector.as_ref()));
+        }
+
+        Ok(Self {
+            encoded_vectors: storage_builder.build(),
+            metadata,
+        })
+    }
+
+    pub fn get_quantized_vector_size(vector_parameters: &VectorParameters) -> usize {
+        HEADER_SIZE + vector_parameters.dim.div_ceil(2)
+    }
+
+    /// Range of values to quantize, limited by the `quantile` if set
+    fn find_min_max<'a>(
+        orig_data: impl Iterator<Item = impl AsRef<[f32]> + 'a>,
+        vector_parameters: &VectorParameters,
+        quantile: Option<f32>,
+    ) -> Result<(f32, f32), EncodingError> {
+        let Some(quantile) = quantile else {
+            let (min, max) = orig_data
+                .flat_map(|vector| vector.as_ref().to_vec())
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                });
+            return Ok(if min <= max { (min, max) } else { (0.0, 0.0) });
+        };
+
+        if !(0.5..=1.0).contains(&quantile) {
+            return Err(EncodingError::ArgumentsError(format!(
+                "Quantile {quantile} is out of range [0.5, 1.0]",
+            )));
+        }
+
+        // Take evenly spaced vectors to bound the sample size
+        let vectors_in_sample = (QUANTILE_SAMPLE_SIZE / vector_parameters.dim.max(1)).max(1);
+        let step = vector_parameters.count.div_ceil(vectors_in_sample).max(1);
+        let mut sample: Vec<f32> = orig_data
+            .step_by(step)
+            .flat_map(|vector| vector.as_ref().to_vec())
+            .filter(|value| value.is_finite())
+            .collect();
+        if sample.is_empty() {
+            return Ok((0.0, 0.0));
+        }
+        sample.sort_unstable_by(f32::total_cmp);
+
+        let cut = ((sample.len() - 1) as f32 * (1.0 - quantile) / 2.0) as usize;
+        Ok((sample[cut], sample[sample.len() - 1 - cut]))
+    }
+
+    fn get_encoded(&self, i: PointOffsetType) -> &[u8] {
+        self.encoded_vectors.get_vector_data(
+            i as usize,
+            Self::get_quantized_vector_size(&self.metadata.vector_parameters),
+        )
+    }
+
+    fn score_encoded(&self, a: &[u8], b: &[u8]) -> ScoreType {
+        let Metadata {
+            vector_parameters,
+            multiplier,
+            ..
+        } = &self.metadata;
+        let (correction_a, levels_a) = split_encoded(a);
+        let (correction_b, levels_b) = split_encoded(b);
+        let score = match vector_parameters.distance_type {
+            DistanceType::Dot => {
+                correction_a + correction_b + multiplier * multiplier * dot_u4(levels_a, levels_b)
+            }
+            DistanceType::L1 => multiplier * abs_diff_u4(levels_a, levels_b),
+            DistanceType::L2 => {
+                correction_a + correction_b
+                    - 2.0 * multiplier * multiplier * dot_u4(levels_a, levels_b)
+            }
+        };
+        if vector_parameters.invert {
+            -score
+        } else {
+            score
+        }
+    }
+}
+
+impl Metadata {
+    fn quantize(&self, value: f32) -> u8 {
+        ((value - self.offset) / self.multiplier)
+            .round()
+            .clamp(0.0, MAX_LEVEL) as u8
+    }
+
+    fn encode_vector(&self, vector: &[f32]) -> Vec<u8> {
+        let mut encoded = vec![0u8; HEADER_SIZE + vector.len().div_ceil(2)];
+        let mut levels_sum = 0u32;
+        let mut levels_squared_sum = 0u32;
+        for (i, &value) in vector.iter().enumerate() {
+            let level = self.quantize(value);
+            levels_sum += u32::from(level);
+            levels_squared_sum += u32::from(level) * u32::from(level);
+            encoded[HEADER_SIZE + i / 2] |= level << (4 * (i % 2));
+        }
+
+        let correction = self.correction(levels_sum as f32, levels_squared_sum as f32);
+        encoded[..HEADER_SIZE].copy_from_slice(&correction.to_le_bytes());
+        encoded
+    }
+
+    /// Score part which depends only on one of the vectors.
+    ///
+    /// For dot product `a * b = dim * o^2 + o * m * (sum(a) + sum(b)) + m^2 * sum(a * b)`,
+    /// the correction is half of the constant plus the own sum term.
+    /// For euclidean distance `|a - b|^2 = m^2 * (sum(a^2) + sum(b^2) - 2 * sum(a * b))`,
+    /// the correction is the own squared norm.
+    fn correction(&self, levels_sum: f32, levels_squared_sum: f32) -> f32 {
+        let Self {
+            vector_parameters,
+            offset,
+            multiplier,
+        } = self;
+        match vector_parameters.distance_type {
+            DistanceType::Dot => {
+                vector_parameters.dim as f32 * offset * offset / 2.0
+                    + offset * multiplier * levels_sum
+            }
+            DistanceType::L1 => 0.0,
+            DistanceType::L2 => multiplier * multiplier * levels_squared_sum,
+        }
+    }
+}
+
+fn split_encoded(encoded: &[u8]) -> (f32, &[u8]) {
+    let (header, levels) = encoded.split_at(HEADER_SIZE);
+    (f32::from_le_bytes(header.try_into().unwrap()), levels)
+}
+
+/// Dot product of packed 4-bit levels.
+///
+/// Written over fixed-size blocks with narrow accumulators, so that it is auto-vectorized.
+fn dot_u4(a: &[u8], b: &[u8]) -> f32 {
+    debug_assert_eq!(a.len(), b.len());
+    let block_dot = |a: &[u8], b: &[u8]| -> u32 {
+        let sum: u16 = a
+            .iter()
+            .zip(b)
+            .map(|(&a, &b)| {
+                u16::from(a & 0x0F) * u16::from(b & 0x0F) + u16::from(a >> 4) * u16::from(b >> 4)
+            })
+            .sum();
+        u32::from(sum)
+    };
+    let mut result = 0u32;
+    let mut blocks_a = a.chunks_exact(BLOCK_SIZE);
+    let mut blocks_b = b.chunks_exact(BLOCK_SIZE);
+    for (block_a, block_b) in (&mut blocks_a).zip(&mut blocks_b) {
+        result += block_dot(block_a, block_b);
+    }
+    result += block_dot(blocks_a.remainder(), blocks_b.remainder());
+    result as f32
+}
+
+/// Sum of absolute differences of packed 4-bit levels
+fn abs_diff_u4(a: &[u8], b: &[u8]) -> f32 {
+    debug_assert_eq!(a.len(), b.len());
+    let block_diff = |a: &[u8], b: &[u8]| -> u32 {
+        let sum: u16 = a
+            .iter()
+            .zip(b)
+            .map(|(&a, &b)| {
+                u16::from((a & 0x0F).abs_diff(b & 0x0F)) + u16::from((a >> 4).abs_diff(b >> 4))
+            })
+            .sum();
+        u32::from(sum)
+    };
+    let mut result = 0u32;
+    let mut blocks_a = a.chunks_exact(BLOCK_SIZE);
+    let mut blocks_b = b.chunks_exact(BLOCK_SIZE);
+    for (block_a, block_b) in (&mut blocks_a).zip(&mut blocks_b) {
+        result += block_diff(block_a, block_b);
+    }
+    result += block_diff(blocks_a.remainder(), blocks_b.remainder());
+    result as f32
+}
+
+impl<TStorage: EncodedStorage> EncodedVectors<EncodedQueryU4> for EncodedVectorsU4<TStorage> {
+    fn save(&self, data_path: &Path, meta_path: &Path) -> std::io::Result<()> {
+        meta_path.parent().map(std::fs::create_dir_all);
+        let metadata_bytes = serde_json::to_vec(&self.metadata)?;
+        std::fs::write(meta_path, metadata_bytes)?;
+
+        data_path.parent().map(std::fs::create_dir_all);
+        self.encoded_vectors.save_to_file(data_path)?;
+        Ok(())
+    }
+
+    fn load(
+        data_path: &Path,
+        meta_path: &Path,
+        vector_parameters: &VectorParameters,
+    ) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(meta_path)?;
+        let metadata: Metadata = serde_json::from_str(&contents)?;
+        let encoded_vectors = TStorage::from_file(
+            data_path,
+            Self::get_quantized_vector_size(vector_parameters),
+            vector_parameters.count,
+        )?;
+        Ok(Self {
+            encoded_vectors,
+            metadata,
+        })
+    }
+
+    fn encode_query(&self, query: &[f32]) -> EncodedQueryU4 {
+        EncodedQueryU4 {
+            encoded: self.metadata.encode_vector(query),
+        }
+    }
+
+    fn score_point(&self, query: &EncodedQueryU4, i: PointOffsetType) -> ScoreType {
+        self.score_encoded(&query.encoded, self.get_encoded(i))
+    }
+
+    fn score_internal(&self, i: PointOffsetType, j: PointOffsetType) -> ScoreType {
+        self.score_encoded(self.get_encoded(i), self.get_encoded(j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::vector_storage::chunked_vectors::ChunkedVectors;
+
+    fn encode(
+        vectors: &[Vec<f32>],
+        distance_type: DistanceType,
+        quantile: Option<f32>,
+    ) -> EncodedVectorsU4<ChunkedVectors<u8>> {
+        let vector_parameters = VectorParameters {
+            dim: vectors[0].len(),
+            count: vectors.len(),
+            invert: distance_type != DistanceType::Dot,
+            distance_type,
+        };
+        let size =
+            EncodedVectorsU4::<ChunkedVectors<u8>>::get_quantized_vector_size(&vector_parameters);
+        EncodedVectorsU4::encode(
+            vectors.iter(),
+            ChunkedVectors::<u8>::new(size),
+            &vector_parameters,
+            quantile,
+            || false,
+        )
+        .unwrap()
+    }
+
+    fn raw_score(a: &[f32], b: &[f32], distance_type: DistanceType) -> f32 {
+        let pairs = a.iter().zip(b);
+        match distance_type {
+            DistanceType::Dot => pairs.map(|(a, b)| a * b).sum(),
+            DistanceType::L1 => -pairs.map(|(a, b)| (a - b).abs()).sum::<f32>(),
+            DistanceType::L2 => -pairs.map(|(a, b)| (a - b) * (a - b)).sum::<f32>(),
+        }
+    }
+
+    #[test]
+    fn test_scores_close_to_raw() {
+        let mut rng = StdRng::seed_from_u64(42);
+        // Odd dimension to check the last half-filled byte
+        let dim = 131;
+        let vectors: Vec<Vec<f32>> = (0..50)
+            .map(|_| (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+
+        for (distance_type, max_error) in [
+            (DistanceType::Dot, 1.5),
+            (DistanceType::L1, 3.0),
+            (DistanceType::L2, 5.0),
+        ] {
+            let encoded = encode(&vectors, distance_type, None);
+            let query = encoded.encode_query(&vectors[0]);
+            for (i, vector) in vectors.iter().enumerate() {
+                let expected = raw_score(&vectors[0], vector, distance_type);
+                let score = encoded.score_point(&query, i as PointOffsetType);
+                assert!(
+                    (score - expected).abs() < max_error,
+                    "{distance_type:?}: {score} vs {expected}",
+                );
+                let internal = encoded.score_internal(0, i as PointOffsetType);
+                assert!((internal - score).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantile_clamps_outliers() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut vectors: Vec<Vec<f32>> = (0..100)
+            .map(|_| (0..16).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+        vectors[0][0] = 1000.0;
+
+        let encoded = encode(&vectors, DistanceType::Dot, Some(0.99));
+        assert!(encoded.metadata.offset >= -1.0);
+        assert!(encoded.metadata.offset + MAX_LEVEL * encoded.metadata.multiplier <= 1.0);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let vectors: Vec<Vec<f32>> = (0..10)
+            .map(|_| (0..32).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+        let encoded = encode(&vectors, DistanceType::L2, Some(0.95));
+
+        let dir = tempfile::Builder::new()
+            .prefix("scalar_u4")
+            .tempdir()
+            .unwrap();
+        let data_path = dir.path().join("data");
+        let meta_path = dir.path().join("meta");
+        encoded.save(&data_path, &meta_path).unwrap();
+        let loaded = EncodedVectorsU4::<ChunkedVectors<u8>>::load(
+            &data_path,
+            &meta_path,
+            &encoded.metadata.vector_parameters,
+        )
+        .unwrap();
+
+        let query = loaded.encode_query(&vectors[3]);
+        for i in 0..vectors.len() as PointOffsetType {
+            assert_eq!(
+                loaded.score_point(&query, i),
+                encoded.score_point(&encoded.encode_query(&vectors[3]), i),
+            );
+        }
+    }
+}
@@ -6,14 +6,16 @@ use quantization::EncodedVectors;
 use super::quantized_custom_query_scorer::QuantizedCustomQueryScorer;
 use super::quantized_query_scorer::QuantizedQueryScorer;
 use super::quantized_vectors::QuantizedVectorStorage;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
     VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
 use crate::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
 use crate::vector_storage::{raw_scorer_from_query_scorer, RawScorer};
@@ -62,6 +64,8 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementType, ManhattanMetric>()
                 }
+                Distance::Hamming => self.build_with_metric::<VectorElementType, HammingMetric>(),
+                Distance::Jaccard => self.build_with_metric::<VectorElementType, JaccardMetric>(),
             },
             VectorStorageDatatype::Uint8 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeByte, CosineMetric>(),
@@ -72,6 +76,12 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementTypeByte, ManhattanMetric>()
                 }
+                Distance::Hamming => {
+                    self.build_with_metric::<VectorElementTypeByte, HammingMetric>()
+                }
+                Distance::Jaccard => {
+                    self.build_with_metric::<VectorElementTypeByte, JaccardMetric>()
+                }
             },
             VectorStorageDatatype::Float16 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeHalf, CosineMetric>(),
@@ -82,7 +92,16 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 Distance::Manhattan => {
                     self.build_with_metric::<VectorElementTypeHalf, ManhattanMetric>()
                 }
+                Distance::Hamming => {
+                    self.build_with_metric::<VectorElementTypeHalf, HammingMetric>()
+                }
+                Distance::Jaccard => {
+                    self.build_with_metric::<VectorElementTypeHalf, JaccardMetric>()
+                }
             },
+            VectorStorageDatatype::Bit => Err(OperationError::service_error(
+                "Quantization is not supported for bit vectors",
+            )),
         }
     }
 
@@ -98,6 +117,12 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::ScalarMmap(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric, _>(storage)
             }
+            QuantizedVectorStorage::ScalarU4Ram(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::ScalarU4Mmap(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
             QuantizedVectorStorage::PQRam(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric, _>(storage)
             }
@@ -110,12 +135,24 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::BinaryMmap(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric, _>(storage)
             }
+            QuantizedVectorStorage::BitPlanesRam(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::BitPlanesMmap(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
             QuantizedVectorStorage::ScalarRamMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
             }
             QuantizedVectorStorage::ScalarMmapMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
             }
+            QuantizedVectorStorage::ScalarU4RamMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::ScalarU4MmapMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
             QuantizedVectorStorage::PQRamMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
             }
@@ -128,6 +165,12 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::BinaryMmapMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
             }
+            QuantizedVectorStorage::BitPlanesRamMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
+            QuantizedVectorStorage::BitPlanesMmapMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric, _>(storage)
+            }
         }
     }
 
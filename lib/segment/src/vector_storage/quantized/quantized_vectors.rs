@@ -11,19 +11,22 @@ use quantization::{
 };
 use serde::{Deserialize, Serialize};
 
+use super::quantized_bit_planes::{EncodedBitPlanesQuery, EncodedVectorsBitPlanes};
 use super::quantized_multivector_storage::{
     create_offsets_file_from_iter, MultivectorOffset, MultivectorOffsetsStorage,
     MultivectorOffsetsStorageMmap, QuantizedMultivectorStorage,
 };
+use super::quantized_scalar_u4::{EncodedQueryU4, EncodedVectorsU4};
 use super::quantized_scorer_builder::QuantizedScorerBuilder;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::vector_utils::TrySetCapacityExact;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{QueryVector, VectorElementType};
 use crate::types::{
-    BinaryQuantization, BinaryQuantizationConfig, CompressionRatio, Distance, MultiVectorConfig,
+    BinaryQuantization, BinaryQuantizationConfig, BinaryQuantizationEncoding,
+    BinaryQuantizationQueryEncoding, CompressionRatio, Distance, MultiVectorConfig,
     ProductQuantization, ProductQuantizationConfig, QuantizationConfig, ScalarQuantization,
-    ScalarQuantizationConfig, VectorStorageDatatype,
+    ScalarQuantizationConfig, ScalarType, VectorStorageDatatype,
 };
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
 use crate::vector_storage::quantized::quantized_mmap_storage::{
@@ -64,6 +67,18 @@ type ScalarMmapMulti = QuantizedMultivectorStorage<
     MultivectorOffsetsStorageMmap,
 >;
 
+type ScalarU4RamMulti = QuantizedMultivectorStorage<
+    EncodedQueryU4,
+    EncodedVectorsU4<ChunkedVectors<u8>>,
+    Vec<MultivectorOffset>,
+>;
+
+type ScalarU4MmapMulti = QuantizedMultivectorStorage<
+    EncodedQueryU4,
+    EncodedVectorsU4<QuantizedMmapStorage>,
+    MultivectorOffsetsStorageMmap,
+>;
+
 type PQRamMulti = QuantizedMultivectorStorage<
     EncodedQueryPQ,
     EncodedVectorsPQ<ChunkedVectors<u8>>,
@@ -88,19 +103,39 @@ type BinaryMmapMulti = QuantizedMultivectorStorage<
     MultivectorOffsetsStorageMmap,
 >;
 
+type BitPlanesRamMulti = QuantizedMultivectorStorage<
+    EncodedBitPlanesQuery,
+    EncodedVectorsBitPlanes<ChunkedVectors<u8>>,
+    Vec<MultivectorOffset>,
+>;
+
+type BitPlanesMmapMulti = QuantizedMultivectorStorage<
+    EncodedBitPlanesQuery,
+    EncodedVectorsBitPlanes<QuantizedMmapStorage>,
+    MultivectorOffsetsStorageMmap,
+>;
+
 pub enum QuantizedVectorStorage {
     ScalarRam(EncodedVectorsU8<ChunkedVectors<u8>>),
     ScalarMmap(EncodedVectorsU8<QuantizedMmapStorage>),
+    ScalarU4Ram(EncodedVectorsU4<ChunkedVectors<u8>>),
+    ScalarU4Mmap(EncodedVectorsU4<QuantizedMmapStorage>),
     PQRam(EncodedVectorsPQ<ChunkedVectors<u8>>),
     PQMmap(EncodedVectorsPQ<QuantizedMmapStorage>),
     BinaryRam(EncodedVectorsBin<u128, ChunkedVectors<u8>>),
     BinaryMmap(EncodedVectorsBin<u128, QuantizedMmapStorage>),
+    BitPlanesRam(EncodedVectorsBitPlanes<ChunkedVectors<u8>>),
+    BitPlanesMmap(EncodedVectorsBitPlanes<QuantizedMmapStorage>),
     ScalarRamMulti(ScalarRamMulti),
     ScalarMmapMulti(ScalarMmapMulti),
+    ScalarU4RamMulti(ScalarU4RamMulti),
+    ScalarU4MmapMulti(ScalarU4MmapMulti),
     PQRamMulti(PQRamMulti),
     PQMmapMulti(PQMmapMulti),
     BinaryRamMulti(BinaryRamMulti),
     BinaryMmapMulti(BinaryMmapMulti),
+    BitPlanesRamMulti(BitPlanesRamMulti),
+    BitPlanesMmapMulti(BitPlanesMmapMulti),
 }
 
 impl fmt::Debug for QuantizedVectorStorage {
@@ -122,7 +157,10 @@ impl QuantizedVectors {
     pub fn default_rescoring(&self) -> bool {
         matches!(
             self.storage_impl,
-            QuantizedVectorStorage::BinaryRam(_) | QuantizedVectorStorage::BinaryMmap(_)
+            QuantizedVectorStorage::BinaryRam(_)
+                | QuantizedVectorStorage::BinaryMmap(_)
+                | QuantizedVectorStorage::BitPlanesRam(_)
+                | QuantizedVectorStorage::BitPlanesMmap(_)
         )
     }
 
@@ -130,16 +168,24 @@ impl QuantizedVectors {
         match self.storage_impl {
             QuantizedVectorStorage::ScalarRam(_) => false,
             QuantizedVectorStorage::ScalarMmap(_) => false,
+            QuantizedVectorStorage::ScalarU4Ram(_) => false,
+            QuantizedVectorStorage::ScalarU4Mmap(_) => false,
             QuantizedVectorStorage::PQRam(_) => false,
             QuantizedVectorStorage::PQMmap(_) => false,
             QuantizedVectorStorage::BinaryRam(_) => false,
             QuantizedVectorStorage::BinaryMmap(_) => false,
+            QuantizedVectorStorage::BitPlanesRam(_) => false,
+            QuantizedVectorStorage::BitPlanesMmap(_) => false,
             QuantizedVectorStorage::ScalarRamMulti(_) => true,
             QuantizedVectorStorage::ScalarMmapMulti(_) => true,
+            QuantizedVectorStorage::ScalarU4RamMulti(_) => true,
+            QuantizedVectorStorage::ScalarU4MmapMulti(_) => true,
             QuantizedVectorStorage::PQRamMulti(_) => true,
             QuantizedVectorStorage::PQMmapMulti(_) => true,
             QuantizedVectorStorage::BinaryRamMulti(_) => true,
             QuantizedVectorStorage::BinaryMmapMulti(_) => true,
+            QuantizedVectorStorage::BitPlanesRamMulti(_) => true,
+            QuantizedVectorStorage::BitPlanesMmapMulti(_) => true,
         }
     }
 
@@ -170,16 +216,32 @@ impl QuantizedVectors {
         match &self.storage_impl {
             QuantizedVectorStorage::ScalarRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::ScalarMmap(storage) => storage.save(&data_path, &meta_path)?,
+            QuantizedVectorStorage::ScalarU4Ram(storage) => storage.save(&data_path, &meta_path)?,
+            QuantizedVectorStorage::ScalarU4Mmap(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
             QuantizedVectorStorage::PQRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::PQMmap(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::BinaryRam(storage) => storage.save(&data_path, &meta_path)?,
             QuantizedVectorStorage::BinaryMmap(storage) => storage.save(&data_path, &meta_path)?,
+            QuantizedVectorStorage::BitPlanesRam(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
+            QuantizedVectorStorage::BitPlanesMmap(storage) => {
+                storage.save(&data_path, &meta_path)?
+            }
             QuantizedVectorStorage::ScalarRamMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
             QuantizedVectorStorage::ScalarMmapMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
+            QuantizedVectorStorage::ScalarU4RamMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
+            QuantizedVectorStorage::ScalarU4MmapMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
             QuantizedVectorStorage::PQRamMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
@@ -192,6 +254,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::BinaryMmapMulti(storage) => {
                 storage.save_multi(&data_path, &meta_path, &offsets_path)?
             }
+            QuantizedVectorStorage::BitPlanesRamMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
+            QuantizedVectorStorage::BitPlanesMmapMulti(storage) => {
+                storage.save_multi(&data_path, &meta_path, &offsets_path)?
+            }
         };
         Ok(())
     }
@@ -218,6 +286,13 @@ impl QuantizedVectors {
         max_threads: usize,
         stopped: &AtomicBool,
     ) -> OperationResult<Self> {
+        let distance = vector_storage.distance();
+        if matches!(distance, Distance::Hamming | Distance::Jaccard) {
+            return Err(OperationError::ValidationError {
+                description: format!("Quantization is not supported for {distance:?} distance"),
+            });
+        }
+
         match vector_storage {
             VectorStorageEnum::DenseSimple(v) => {
                 Self::create_impl(v, quantization_config, path, max_threads, stopped)
@@ -246,7 +321,15 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseSimpleBit(_)
+            | VectorStorageEnum::DenseMemmapBit(_)
+            | VectorStorageEnum::DenseAppendableMemmapBit(_) => {
+                Err(OperationError::ValidationError {
+                    description: "Quantization is not supported for bit vectors".to_string(),
+                })
+            }
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
+            VectorStorageEnum::SparseMmap(_) => Err(OperationError::WrongSparse),
             VectorStorageEnum::MultiDenseSimple(v) => {
                 Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
             }
@@ -455,6 +538,31 @@ impl QuantizedVectors {
         {
             let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
             match &config.quantization_config {
+                QuantizationConfig::Scalar(ScalarQuantization { scalar })
+                    if scalar.r#type == ScalarType::Int4 =>
+                {
+                    if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::ScalarU4RamMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    } else {
+                        QuantizedVectorStorage::ScalarU4MmapMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    }
+                }
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
                     if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::ScalarRamMulti(
@@ -499,6 +607,31 @@ impl QuantizedVectors {
                         )
                     }
                 }
+                QuantizationConfig::Binary(BinaryQuantization { binary })
+                    if Self::is_bit_planes(binary) =>
+                {
+                    if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::BitPlanesRamMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    } else {
+                        QuantizedVectorStorage::BitPlanesMmapMulti(
+                            QuantizedMultivectorStorage::load_multi(
+                                &data_path,
+                                &meta_path,
+                                &offsets_path,
+                                &config.vector_parameters,
+                                multivector_config,
+                            )?,
+                        )
+                    }
+                }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::BinaryRamMulti(
@@ -525,6 +658,23 @@ impl QuantizedVectors {
             }
         } else {
             match &config.quantization_config {
+                QuantizationConfig::Scalar(ScalarQuantization { scalar })
+                    if scalar.r#type == ScalarType::Int4 =>
+                {
+                    if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::ScalarU4Ram(EncodedVectorsU4::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    } else {
+                        QuantizedVectorStorage::ScalarU4Mmap(EncodedVectorsU4::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    }
+                }
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
                     if Self::is_ram(scalar.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::ScalarRam(EncodedVectorsU8::load(
@@ -555,6 +705,23 @@ impl QuantizedVectors {
                         )?)
                     }
                 }
+                QuantizationConfig::Binary(BinaryQuantization { binary })
+                    if Self::is_bit_planes(binary) =>
+                {
+                    if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
+                        QuantizedVectorStorage::BitPlanesRam(EncodedVectorsBitPlanes::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    } else {
+                        QuantizedVectorStorage::BitPlanesMmap(EncodedVectorsBitPlanes::load(
+                            &data_path,
+                            &meta_path,
+                            &config.vector_parameters,
+                        )?)
+                    }
+                }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     if Self::is_ram(binary.always_ram, on_disk_vector_storage) {
                         QuantizedVectorStorage::BinaryRam(EncodedVectorsBin::load(
@@ -590,6 +757,16 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if scalar_config.r#type == ScalarType::Int4 {
+            return Self::create_scalar_u4(
+                vectors,
+                vector_parameters,
+                scalar_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
         let quantized_vector_size =
             EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
         let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
@@ -633,6 +810,18 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if scalar_config.r#type == ScalarType::Int4 {
+            return Self::create_scalar_u4_multi(
+                vectors,
+                offsets,
+                vector_parameters,
+                scalar_config,
+                multi_vector_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
         let quantized_vector_size =
             EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
         let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
@@ -681,6 +870,107 @@ impl QuantizedVectors {
         }
     }
 
+    fn create_scalar_u4<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        vector_parameters: &quantization::VectorParameters,
+        scalar_config: &ScalarQuantizationConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let quantized_vector_size =
+            EncodedVectorsU4::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
+        let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            Ok(QuantizedVectorStorage::ScalarU4Ram(
+                EncodedVectorsU4::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    scalar_config.quantile,
+                    || stopped.load(Ordering::Relaxed),
+                )?,
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::ScalarU4Mmap(
+                EncodedVectorsU4::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    scalar_config.quantile,
+                    || stopped.load(Ordering::Relaxed),
+                )?,
+            ))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_scalar_u4_multi<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        offsets: impl Iterator<Item = MultivectorOffset>,
+        vector_parameters: &quantization::VectorParameters,
+        scalar_config: &ScalarQuantizationConfig,
+        multi_vector_config: MultiVectorConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let quantized_vector_size =
+            EncodedVectorsU4::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
+        let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            let quantized_storage = EncodedVectorsU4::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                scalar_config.quantile,
+                || stopped.load(Ordering::Relaxed),
+            )?;
+            Ok(QuantizedVectorStorage::ScalarU4RamMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    offsets.collect(),
+                    multi_vector_config,
+                ),
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            let quantized_storage = EncodedVectorsU4::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                scalar_config.quantile,
+                || stopped.load(Ordering::Relaxed),
+            )?;
+            let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
+            create_offsets_file_from_iter(&offsets_path, vector_parameters.count, offsets)?;
+            Ok(QuantizedVectorStorage::ScalarU4MmapMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    MultivectorOffsetsStorage::load(&offsets_path)?,
+                    multi_vector_config,
+                ),
+            ))
+        }
+    }
+
     fn create_pq<'a>(
         vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone + Send,
         vector_parameters: &quantization::VectorParameters,
@@ -800,6 +1090,16 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if Self::is_bit_planes(binary_config) {
+            return Self::create_bit_planes(
+                vectors,
+                vector_parameters,
+                binary_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
         let quantized_vector_size =
             EncodedVectorsBin::<u128, QuantizedMmapStorage>::get_quantized_vector_size_from_params(
                 vector_parameters,
@@ -839,6 +1139,18 @@ impl QuantizedVectors {
         on_disk_vector_storage: bool,
         stopped: &AtomicBool,
     ) -> OperationResult<QuantizedVectorStorage> {
+        if Self::is_bit_planes(binary_config) {
+            return Self::create_bit_planes_multi(
+                vectors,
+                offsets,
+                vector_parameters,
+                binary_config,
+                multi_vector_config,
+                path,
+                on_disk_vector_storage,
+                stopped,
+            );
+        }
         let quantized_vector_size =
             EncodedVectorsBin::<u8, QuantizedMmapStorage>::get_quantized_vector_size_from_params(
                 vector_parameters,
@@ -883,6 +1195,128 @@ impl QuantizedVectors {
         }
     }
 
+    fn create_bit_planes<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        vector_parameters: &quantization::VectorParameters,
+        binary_config: &BinaryQuantizationConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let encoding = binary_config.encoding();
+        let query_encoding = binary_config.query_encoding();
+        let quantized_vector_size =
+            EncodedVectorsBitPlanes::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                encoding,
+            );
+        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            Ok(QuantizedVectorStorage::BitPlanesRam(
+                EncodedVectorsBitPlanes::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    encoding,
+                    query_encoding,
+                    || stopped.load(Ordering::Relaxed),
+                )?,
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::BitPlanesMmap(
+                EncodedVectorsBitPlanes::encode(
+                    vectors,
+                    storage_builder,
+                    vector_parameters,
+                    encoding,
+                    query_encoding,
+                    || stopped.load(Ordering::Relaxed),
+                )?,
+            ))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_bit_planes_multi<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
+        offsets: impl Iterator<Item = MultivectorOffset>,
+        vector_parameters: &quantization::VectorParameters,
+        binary_config: &BinaryQuantizationConfig,
+        multi_vector_config: MultiVectorConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        let encoding = binary_config.encoding();
+        let query_encoding = binary_config.query_encoding();
+        let quantized_vector_size =
+            EncodedVectorsBitPlanes::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                encoding,
+            );
+        let in_ram = Self::is_ram(binary_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let mut storage_builder = ChunkedVectors::<u8>::new(quantized_vector_size);
+            storage_builder.try_set_capacity_exact(vector_parameters.count)?;
+            let quantized_storage = EncodedVectorsBitPlanes::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                encoding,
+                query_encoding,
+                || stopped.load(Ordering::Relaxed),
+            )?;
+            Ok(QuantizedVectorStorage::BitPlanesRamMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    offsets.collect(),
+                    multi_vector_config,
+                ),
+            ))
+        } else {
+            let mmap_data_path = path.join(QUANTIZED_DATA_PATH);
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                mmap_data_path.as_path(),
+                vector_parameters.count,
+                quantized_vector_size,
+            )?;
+            let quantized_storage = EncodedVectorsBitPlanes::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                encoding,
+                query_encoding,
+                || stopped.load(Ordering::Relaxed),
+            )?;
+            let offsets_path = path.join(QUANTIZED_OFFSETS_PATH);
+            create_offsets_file_from_iter(&offsets_path, vector_parameters.count, offsets)?;
+            Ok(QuantizedVectorStorage::BitPlanesMmapMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    MultivectorOffsetsStorage::load(&offsets_path)?,
+                    multi_vector_config,
+                ),
+            ))
+        }
+    }
+
+    /// Plain 1-bit encoding with binary queries is handled by the original binary quantization,
+    /// to keep the storage format of existing collections
+    fn is_bit_planes(binary_config: &BinaryQuantizationConfig) -> bool {
+        binary_config.encoding() != BinaryQuantizationEncoding::OneBit
+            || binary_config.query_encoding() != BinaryQuantizationQueryEncoding::Binary
+    }
+
     fn is_ram(always_ram: Option<bool>, on_disk_vector_storage: bool) -> bool {
         !on_disk_vector_storage || always_ram == Some(true)
     }
@@ -900,6 +1334,8 @@ impl QuantizedVectors {
                 Distance::Euclid => quantization::DistanceType::L2,
                 Distance::Dot => quantization::DistanceType::Dot,
                 Distance::Manhattan => quantization::DistanceType::L1,
+                // Rejected in `create`, bit-packed vectors can't be quantized
+                Distance::Hamming | Distance::Jaccard => quantization::DistanceType::L1,
             },
             invert: distance == Distance::Euclid || distance == Distance::Manhattan,
         }
@@ -10,6 +10,7 @@ pub mod metric_query_scorer;
 pub mod multi_custom_query_scorer;
 pub mod multi_metric_query_scorer;
 pub mod sparse_custom_query_scorer;
+pub mod truncated_query_scorer;
 
 pub trait QueryScorer<TVector: ?Sized> {
     fn score_stored(&self, idx: PointOffsetType) -> ScoreType;
@@ -44,6 +45,54 @@ pub fn score_max_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     sum
 }
 
+/// Multi-dense similarity for any comparator, given the similarity of inner vectors `a` and `b`.
+/// `MaxSim` has a dedicated faster implementation in [`score_max_similarity`].
+/// This function is also used by `QuantizedMultivectorStorage` for quantized data.
+pub fn score_multi_similarity(
+    comparator: MultiVectorComparator,
+    count_a: usize,
+    count_b: usize,
+    similarity: impl Fn(usize, usize) -> ScoreType,
+) -> ScoreType {
+    debug_assert!(count_a > 0);
+    debug_assert!(count_b > 0);
+    let mut sum = 0.0;
+    let mut min_max_sim = ScoreType::INFINITY;
+    // best match of each vector of `b`, only needed by the symmetric comparator
+    let mut max_sims_b = match comparator {
+        MultiVectorComparator::Chamfer => vec![ScoreType::NEG_INFINITY; count_b],
+        MultiVectorComparator::MaxSim
+        | MultiVectorComparator::MinSim
+        | MultiVectorComparator::AvgSim => Vec::new(),
+    };
+    for a in 0..count_a {
+        let mut max_sim = ScoreType::NEG_INFINITY;
+        for b in 0..count_b {
+            let sim = similarity(a, b);
+            if sim > max_sim {
+                max_sim = sim;
+            }
+            if let Some(max_sim_b) = max_sims_b.get_mut(b) {
+                if sim > *max_sim_b {
+                    *max_sim_b = sim;
+                }
+            }
+        }
+        sum += max_sim;
+        min_max_sim = min_max_sim.min(max_sim);
+    }
+    match comparator {
+        MultiVectorComparator::MaxSim => sum,
+        MultiVectorComparator::MinSim => min_max_sim,
+        MultiVectorComparator::AvgSim => sum / count_a as ScoreType,
+        MultiVectorComparator::Chamfer => {
+            let avg_a = sum / count_a as ScoreType;
+            let avg_b = max_sims_b.iter().sum::<ScoreType>() / count_b as ScoreType;
+            (avg_a + avg_b) / 2.0
+        }
+    }
+}
+
 fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     multi_vector_config: &MultiVectorConfig,
     multi_dense_a: TypedMultiDenseVectorRef<T>,
@@ -53,5 +102,14 @@ fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
         MultiVectorComparator::MaxSim => {
             score_max_similarity::<T, TMetric>(multi_dense_a, multi_dense_b)
         }
+        comparator @ (MultiVectorComparator::MinSim
+        | MultiVectorComparator::AvgSim
+        | MultiVectorComparator::Chamfer) => {
+            let vectors_a: Vec<_> = multi_dense_a.multi_vectors().collect();
+            let vectors_b: Vec<_> = multi_dense_b.multi_vectors().collect();
+            score_multi_similarity(comparator, vectors_a.len(), vectors_b.len(), |a, b| {
+                TMetric::similarity(vectors_a[a], vectors_b[b])
+            })
+        }
     }
 }
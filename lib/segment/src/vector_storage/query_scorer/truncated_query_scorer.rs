@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{DenseVector, TypedDenseVector};
+use crate::spaces::metric::Metric;
+use crate::types::Distance;
+use crate::vector_storage::query::{Query, TransformInto};
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::DenseVectorStorage;
+
+/// Scores only the leading dimensions of dense vectors, e.g. a prefix of Matryoshka embeddings.
+///
+/// Stored vectors are preprocessed over their full length, so for cosine distance the truncated
+/// stored vector is normalized again on the fly.
+pub struct TruncatedQueryScorer<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+> {
+    vector_storage: &'a TVectorStorage,
+    query: TypedDenseVector<TElement>,
+    dim: usize,
+    renormalize: bool,
+    metric: PhantomData<TMetric>,
+}
+
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: Metric<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+    > TruncatedQueryScorer<'a, TElement, TMetric, TVectorStorage>
+{
+    pub fn new(mut query: DenseVector, vector_storage: &'a TVectorStorage, dim: usize) -> Self {
+        query.truncate(dim);
+        let preprocessed_vector = TMetric::preprocess(query);
+        let query = TypedDenseVector::from(TElement::slice_from_float_cow(Cow::from(
+            preprocessed_vector,
+        )));
+        Self {
+            dim: query.len(),
+            query,
+            vector_storage,
+            renormalize: vector_storage.distance() == Distance::Cosine,
+            metric: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn truncate<'v>(&self, vector: &'v [TElement]) -> &'v [TElement] {
+        truncate(vector, self.dim)
+    }
+
+    #[inline]
+    fn normalized(&self, score: ScoreType, norm_sqr: impl FnOnce() -> ScoreType) -> ScoreType {
+        if self.renormalize {
+            normalize(score, norm_sqr())
+        } else {
+            score
+        }
+    }
+}
+
+#[inline]
+fn truncate<T>(vector: &[T], dim: usize) -> &[T] {
+    &vector[..dim.min(vector.len())]
+}
+
+/// Squared norm of a truncated vector, in terms of the metric
+#[inline]
+fn norm_sqr<TElement: PrimitiveVectorElement, TMetric: Metric<TElement>>(
+    vector: &[TElement],
+) -> ScoreType {
+    TMetric::similarity(vector, vector)
+}
+
+#[inline]
+fn normalize(score: ScoreType, norm_sqr: ScoreType) -> ScoreType {
+    let norm = norm_sqr.sqrt();
+    if norm > 0.0 {
+        score / norm
+    } else {
+        score
+    }
+}
+
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: Metric<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+    > QueryScorer<[TElement]> for TruncatedQueryScorer<'a, TElement, TMetric, TVectorStorage>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score(self.vector_storage.get_dense(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[TElement]) -> ScoreType {
+        let v2 = self.truncate(v2);
+        let score = TMetric::similarity(&self.query, v2);
+        self.normalized(score, || norm_sqr::<TElement, TMetric>(v2))
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        let v1 = self.truncate(self.vector_storage.get_dense(point_a));
+        let v2 = self.truncate(self.vector_storage.get_dense(point_b));
+        let score = TMetric::similarity(v1, v2);
+        self.normalized(score, || {
+            norm_sqr::<TElement, TMetric>(v1) * norm_sqr::<TElement, TMetric>(v2)
+        })
+    }
+}
+
+/// Same as [`TruncatedQueryScorer`], but for queries with multiple vectors,
+/// like recommendation, discovery and context queries.
+pub struct TruncatedCustomQueryScorer<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+    TInputQuery: Query<DenseVector>,
+    TStoredQuery: Query<TypedDenseVector<TElement>>,
+> {
+    vector_storage: &'a TVectorStorage,
+    query: TStoredQuery,
+    dim: usize,
+    renormalize: bool,
+    metric: PhantomData<TMetric>,
+    _input_query: PhantomData<TInputQuery>,
+}
+
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: Metric<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+        TInputQuery: Query<DenseVector> + TransformInto<TStoredQuery, DenseVector, TypedDenseVector<TElement>>,
+        TStoredQuery: Query<TypedDenseVector<TElement>>,
+    > TruncatedCustomQueryScorer<'a, TElement, TMetric, TVectorStorage, TInputQuery, TStoredQuery>
+{
+    pub fn new(query: TInputQuery, vector_storage: &'a TVectorStorage, dim: usize) -> Self {
+        let query = query
+            .transform(|mut vector| {
+                vector.truncate(dim);
+                let preprocessed_vector = TMetric::preprocess(vector);
+                Ok(TypedDenseVector::from(TElement::slice_from_float_cow(
+                    Cow::from(preprocessed_vector),
+                )))
+            })
+            .unwrap();
+
+        Self {
+            query,
+            vector_storage,
+            dim,
+            renormalize: vector_storage.distance() == Distance::Cosine,
+            metric: PhantomData,
+            _input_query: PhantomData,
+        }
+    }
+}
+
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: Metric<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+        TInputQuery: Query<DenseVector>,
+        TStoredQuery: Query<TypedDenseVector<TElement>>,
+    > QueryScorer<[TElement]>
+    for TruncatedCustomQueryScorer<'a, TElement, TMetric, TVectorStorage, TInputQuery, TStoredQuery>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.score(self.vector_storage.get_dense(idx))
+    }
+
+    #[inline]
+    fn score(&self, against: &[TElement]) -> ScoreType {
+        let against = truncate(against, self.dim);
+        // Query vectors are normalized already, only the stored one has to be
+        let against_norm_sqr = self
+            .renormalize
+            .then(|| norm_sqr::<TElement, TMetric>(against));
+        self.query.score_by(|example| {
+            let score = TMetric::similarity(example, against);
+            match against_norm_sqr {
+                Some(norm_sqr) => normalize(score, norm_sqr),
+                None => score,
+            }
+        })
+    }
+
+    fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
+        unimplemented!("Custom scorer can compare against multiple vectors, not just one")
+    }
+}
@@ -10,16 +10,22 @@ use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
 use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeBit,
+    VectorElementTypeByte, VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
-use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use crate::spaces::tools::peek_top_largest_iterable;
-use crate::types::Distance;
+use crate::types::{Distance, SearchParams};
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::multi_metric_query_scorer::MultiMetricQueryScorer;
+use crate::vector_storage::query_scorer::truncated_query_scorer::{
+    TruncatedCustomQueryScorer, TruncatedQueryScorer,
+};
 use crate::vector_storage::query_scorer::QueryScorer;
 
 /// RawScorer composition:
@@ -117,6 +123,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseSimpleHalf(vs) => {
             raw_scorer_half_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseSimpleBit(vs) => {
+            raw_scorer_bit_impl(query, vs, point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::DenseMemmap(vs) => {
             if vs.has_async_reader() {
@@ -144,6 +153,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseMemmapHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseMemmapBit(vs) => {
+            raw_scorer_bit_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
 
         VectorStorageEnum::DenseAppendableMemmap(vs) => {
             raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
@@ -154,9 +166,15 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseAppendableMemmapBit(vs) => {
+            raw_scorer_bit_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::SparseMmap(vs) => {
+            raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
+        }
         VectorStorageEnum::MultiDenseSimple(vs) => {
             raw_multi_scorer_impl(query, vs, point_deleted, is_stopped)
         }
@@ -229,6 +247,209 @@ pub fn new_raw_scorer<'a>(
     new_stoppable_raw_scorer(vector, vector_storage, point_deleted, &DEFAULT_STOPPED)
 }
 
+/// Number of leading dimensions to score dense vectors with.
+///
+/// The index may be built over a truncated view of a vector, and a query may request
+/// fewer dimensions on top of that, the smaller one wins.
+pub fn scoring_dimensions(
+    truncate_to: Option<usize>,
+    params: Option<&SearchParams>,
+) -> Option<usize> {
+    let requested = params.and_then(|params| params.dimensions);
+    match (truncate_to, requested) {
+        (Some(truncate_to), Some(requested)) => Some(truncate_to.min(requested)),
+        (truncate_to, requested) => truncate_to.or(requested),
+    }
+}
+
+/// Same as [`new_stoppable_raw_scorer`], but only scores the first `dimensions` of dense vectors.
+///
+/// Falls back to the regular scorer if `dimensions` is not set.
+pub fn new_truncated_raw_scorer<'a>(
+    query: QueryVector,
+    vector_storage: &'a VectorStorageEnum,
+    dimensions: Option<usize>,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let Some(dimensions) = dimensions else {
+        return new_stoppable_raw_scorer(query, vector_storage, point_deleted, is_stopped);
+    };
+    match vector_storage {
+        VectorStorageEnum::DenseSimple(vs) => {
+            truncated_raw_scorer_impl(query, vs, dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleByte(vs) => {
+            truncated_raw_scorer_impl(query, vs, dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleHalf(vs) => {
+            truncated_raw_scorer_impl(query, vs, dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseMemmap(vs) => {
+            truncated_raw_scorer_impl(query, vs.as_ref(), dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseMemmapByte(vs) => {
+            truncated_raw_scorer_impl(query, vs.as_ref(), dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseMemmapHalf(vs) => {
+            truncated_raw_scorer_impl(query, vs.as_ref(), dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseAppendableMemmap(vs) => {
+            truncated_raw_scorer_impl(query, vs.as_ref(), dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseAppendableMemmapByte(vs) => {
+            truncated_raw_scorer_impl(query, vs.as_ref(), dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
+            truncated_raw_scorer_impl(query, vs.as_ref(), dimensions, point_deleted, is_stopped)
+        }
+        VectorStorageEnum::DenseSimpleBit(_)
+        | VectorStorageEnum::DenseMemmapBit(_)
+        | VectorStorageEnum::DenseAppendableMemmapBit(_)
+        | VectorStorageEnum::SparseSimple(_)
+        | VectorStorageEnum::SparseMmap(_)
+        | VectorStorageEnum::MultiDenseSimple(_)
+        | VectorStorageEnum::MultiDenseSimpleByte(_)
+        | VectorStorageEnum::MultiDenseSimpleHalf(_)
+        | VectorStorageEnum::MultiDenseAppendableMemmap(_)
+        | VectorStorageEnum::MultiDenseAppendableMemmapByte(_)
+        | VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => {
+            Err(OperationError::ValidationError {
+                description: "Truncated dimensions are only supported for dense float, float16 and uint8 vectors".to_string(),
+            })
+        }
+    }
+}
+
+fn truncated_raw_scorer_impl<'a, TElement, TVectorStorage>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    dimensions: usize,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>>
+where
+    TElement: PrimitiveVectorElement,
+    TVectorStorage: DenseVectorStorage<TElement>,
+    CosineMetric: Metric<TElement>,
+    EuclidMetric: Metric<TElement>,
+    DotProductMetric: Metric<TElement>,
+    ManhattanMetric: Metric<TElement>,
+    HammingMetric: Metric<TElement>,
+    JaccardMetric: Metric<TElement>,
+{
+    match vector_storage.distance() {
+        Distance::Cosine => new_truncated_scorer_with_metric::<TElement, CosineMetric, _>(
+            query,
+            vector_storage,
+            dimensions,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_truncated_scorer_with_metric::<TElement, EuclidMetric, _>(
+            query,
+            vector_storage,
+            dimensions,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_truncated_scorer_with_metric::<TElement, DotProductMetric, _>(
+            query,
+            vector_storage,
+            dimensions,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_truncated_scorer_with_metric::<TElement, ManhattanMetric, _>(
+            query,
+            vector_storage,
+            dimensions,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Hamming => new_truncated_scorer_with_metric::<TElement, HammingMetric, _>(
+            query,
+            vector_storage,
+            dimensions,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_truncated_scorer_with_metric::<TElement, JaccardMetric, _>(
+            query,
+            vector_storage,
+            dimensions,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn new_truncated_scorer_with_metric<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement> + 'a,
+    TVectorStorage: DenseVectorStorage<TElement>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    dimensions: usize,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            TruncatedQueryScorer::<TElement, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+                dimensions,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                TruncatedCustomQueryScorer::<TElement, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                    dimensions,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                TruncatedCustomQueryScorer::<TElement, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                    dimensions,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                TruncatedCustomQueryScorer::<TElement, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                    dimensions,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -260,6 +481,18 @@ pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>
             point_deleted,
             is_stopped,
         ),
+        Distance::Hamming => new_scorer_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_scorer_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
     }
 }
 
@@ -354,6 +587,18 @@ pub fn raw_scorer_byte_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
             point_deleted,
             is_stopped,
         ),
+        Distance::Hamming => new_scorer_byte_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_scorer_byte_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
     }
 }
 
@@ -448,6 +693,18 @@ pub fn raw_scorer_half_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
             point_deleted,
             is_stopped,
         ),
+        Distance::Hamming => new_scorer_half_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_scorer_half_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
     }
 }
 
@@ -511,6 +768,112 @@ fn new_scorer_half_with_metric<
     }
 }
 
+pub fn raw_scorer_bit_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeBit>>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_scorer_bit_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_scorer_bit_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_scorer_bit_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_scorer_bit_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Hamming => new_scorer_bit_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_scorer_bit_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn new_scorer_bit_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeBit> + 'a,
+    TVectorStorage: DenseVectorStorage<VectorElementTypeBit>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MetricQueryScorer::<VectorElementTypeBit, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBit, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBit, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeBit, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_scorer_from_query_scorer<'a, TVector, TQueryScorer>(
     query_scorer: TQueryScorer,
     point_deleted: &'a BitSlice,
@@ -561,6 +924,18 @@ pub fn raw_multi_scorer_impl<'a, TVectorStorage: MultiVectorStorage<VectorElemen
             point_deleted,
             is_stopped,
         ),
+        Distance::Hamming => new_multi_scorer_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_multi_scorer_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
     }
 }
 
@@ -657,6 +1032,18 @@ pub fn raw_multi_scorer_byte_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
             point_deleted,
             is_stopped,
         ),
+        Distance::Hamming => new_multi_scorer_byte_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_multi_scorer_byte_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
     }
 }
 
@@ -753,6 +1140,18 @@ pub fn raw_multi_scorer_half_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
             point_deleted,
             is_stopped,
         ),
+        Distance::Hamming => new_multi_scorer_half_with_metric::<HammingMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Jaccard => new_multi_scorer_half_with_metric::<JaccardMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
     }
 }
 
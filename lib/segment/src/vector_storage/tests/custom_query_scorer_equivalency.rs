@@ -158,6 +158,8 @@ fn product_x4() -> Option<WithQuantization> {
 fn binary() -> Option<WithQuantization> {
     let config = BinaryQuantizationConfig {
         always_ram: Some(true),
+        encoding: None,
+        query_encoding: None,
     }
     .into();
 
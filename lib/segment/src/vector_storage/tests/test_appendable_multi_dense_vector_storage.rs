@@ -1,3 +1,4 @@
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -14,7 +15,7 @@ use crate::data_types::vectors::{
 };
 use crate::fixtures::payload_context_fixture::FixtureIdTracker;
 use crate::id_tracker::IdTrackerSS;
-use crate::types::{Distance, MultiVectorConfig};
+use crate::types::{Distance, MultiVectorConfig, MultiVectorPooling, MultiVectorPoolingInfo};
 use crate::vector_storage::chunked_vectors::CHUNK_SIZE;
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::open_appendable_memmap_multi_vector_storage;
 use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
@@ -73,13 +74,17 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
             VectorStorageEnum::DenseSimple(_) => unreachable!(),
             VectorStorageEnum::DenseSimpleByte(_) => unreachable!(),
             VectorStorageEnum::DenseSimpleHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseSimpleBit(_) => unreachable!(),
             VectorStorageEnum::DenseMemmap(_) => unreachable!(),
             VectorStorageEnum::DenseMemmapByte(_) => unreachable!(),
             VectorStorageEnum::DenseMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseMemmapBit(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmap(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmapByte(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseAppendableMemmapBit(_) => unreachable!(),
             VectorStorageEnum::SparseSimple(_) => unreachable!(),
+            VectorStorageEnum::SparseMmap(_) => unreachable!(),
             VectorStorageEnum::MultiDenseSimple(v) => {
                 for (orig, vec) in orig_iter.zip(v.iterate_inner_vectors()) {
                     assert_eq!(orig, vec);
@@ -232,6 +237,15 @@ fn create_vector_storage(
     storage_type: MultiDenseStorageType,
     vec_dim: usize,
     path: &Path,
+) -> VectorStorageEnum {
+    create_vector_storage_with_config(storage_type, vec_dim, path, MultiVectorConfig::default())
+}
+
+fn create_vector_storage_with_config(
+    storage_type: MultiDenseStorageType,
+    vec_dim: usize,
+    path: &Path,
+    multi_vector_config: MultiVectorConfig,
 ) -> VectorStorageEnum {
     match storage_type {
         MultiDenseStorageType::SimpleRamFloat => {
@@ -241,7 +255,7 @@ fn create_vector_storage(
                 DB_VECTOR_CF,
                 vec_dim,
                 Distance::Dot,
-                MultiVectorConfig::default(),
+                multi_vector_config,
                 &AtomicBool::new(false),
             )
             .unwrap()
@@ -250,7 +264,7 @@ fn create_vector_storage(
             path,
             vec_dim,
             Distance::Dot,
-            MultiVectorConfig::default(),
+            multi_vector_config,
         )
         .unwrap(),
     }
@@ -344,3 +358,94 @@ fn test_large_multi_dense_vector_storage(
         }
     }
 }
+
+#[rstest]
+fn test_pooled_multi_dense_vector_storage(
+    #[values(
+        MultiDenseStorageType::SimpleRamFloat,
+        MultiDenseStorageType::AppendableMmapFloat
+    )]
+    storage_type: MultiDenseStorageType,
+) {
+    let vec_dim = 4;
+    let vec_count = 5;
+    let multi_vector_config = MultiVectorConfig {
+        pooling: Some(MultiVectorPooling::TargetCount(
+            NonZeroUsize::new(2).unwrap(),
+        )),
+        ..Default::default()
+    };
+    // 5 identical inner vectors per point are pooled into 2
+    let expected_info = MultiVectorPoolingInfo {
+        num_original_vectors: 5 * vec_count,
+        num_pooled_vectors: 2 * vec_count,
+    };
+
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let mut storage = create_vector_storage_with_config(
+            storage_type,
+            vec_dim,
+            dir.path(),
+            multi_vector_config,
+        );
+        for (i, vec) in multi_points_fixtures(vec_count, vec_dim).iter().enumerate() {
+            storage
+                .insert_vector(i as PointOffsetType, vec.into())
+                .unwrap();
+        }
+        let stored_vec = storage.get_vector(1);
+        let multi_dense: TypedMultiDenseVectorRef<_> = stored_vec.as_vec_ref().try_into().unwrap();
+        assert_eq!(multi_dense.flattened_vectors, &[1.0; 8]);
+        assert_eq!(storage.multi_vector_pooling_info(), Some(expected_info));
+        storage.flusher()().unwrap();
+    }
+
+    // Pooling counts survive reopening
+    {
+        let mut storage = create_vector_storage_with_config(
+            storage_type,
+            vec_dim,
+            dir.path(),
+            multi_vector_config,
+        );
+        assert_eq!(storage.multi_vector_pooling_info(), Some(expected_info));
+
+        // Counts are updated on delete and overwrite
+        storage.delete_vector(0).unwrap();
+        let three_vectors = MultiDenseVectorInternal::new(vec![1.0; 3 * vec_dim], vec_dim);
+        storage.insert_vector(1, (&three_vectors).into()).unwrap();
+        storage.flusher()().unwrap();
+    }
+    let expected_info = MultiVectorPoolingInfo {
+        num_original_vectors: 5 * (vec_count - 2) + 3,
+        num_pooled_vectors: 2 * (vec_count - 1),
+    };
+
+    let storage =
+        create_vector_storage_with_config(storage_type, vec_dim, dir.path(), multi_vector_config);
+    assert_eq!(storage.multi_vector_pooling_info(), Some(expected_info));
+
+    // Pooled vectors are copied as is, keeping the original counts and deleted flags
+    let other_dir = Builder::new()
+        .prefix("other_storage_dir")
+        .tempdir()
+        .unwrap();
+    let mut other_storage = create_vector_storage_with_config(
+        storage_type,
+        vec_dim,
+        other_dir.path(),
+        multi_vector_config,
+    );
+    other_storage
+        .update_from(
+            &storage,
+            &mut Box::new(0..vec_count as u32),
+            &Default::default(),
+        )
+        .unwrap();
+    assert_eq!(
+        other_storage.multi_vector_pooling_info(),
+        Some(expected_info)
+    );
+}
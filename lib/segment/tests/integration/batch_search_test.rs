@@ -44,6 +44,7 @@ fn test_batch_and_single_request_equivalency() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -146,6 +147,7 @@ fn test_batch_and_single_request_equivalency() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -160,6 +162,8 @@ fn test_batch_and_single_request_equivalency() {
         quantized_vectors: quantized_vectors.clone(),
         payload_index: payload_index_ptr,
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
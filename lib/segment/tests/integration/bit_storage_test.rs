@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use rstest::rstest;
+use segment::data_types::vectors::{only_default_vector, Vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageDatatype,
+    VectorStorageType, WithPayload,
+};
+use tempfile::Builder;
+
+fn random_bit_vector<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> Vec<f32> {
+    (0..dim)
+        .map(|_| if rnd.gen_bool(0.5) { 1.0 } else { 0.0 })
+        .collect()
+}
+
+#[rstest]
+#[case::memory(VectorStorageType::Memory, Distance::Hamming)]
+#[case::mmap(VectorStorageType::Mmap, Distance::Jaccard)]
+#[case::chunked_mmap(VectorStorageType::ChunkedMmap, Distance::Hamming)]
+fn test_bit_storage_round_trip(
+    #[case] storage_type: VectorStorageType,
+    #[case] distance: Distance,
+) {
+    let dim = 100 * 8;
+    let num_vectors: u64 = 200;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("segment_dir_bit").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: Some(VectorStorageDatatype::Bit),
+                truncated_view: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    let vectors: Vec<_> = (0..num_vectors)
+        .map(|_| random_bit_vector(&mut rnd, dim))
+        .collect();
+    for (n, vector) in vectors.iter().enumerate() {
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                (n as u64).into(),
+                only_default_vector(vector),
+            )
+            .unwrap();
+    }
+
+    for (n, vector) in vectors.iter().enumerate() {
+        let point_id = (n as u64).into();
+
+        // Stored vectors are packed, but must come back with the original dimensionality
+        let stored = segment.vector(DEFAULT_VECTOR_NAME, point_id).unwrap();
+        assert_eq!(stored, Some(Vector::Dense(vector.clone())));
+
+        // Every vector is its own closest match
+        let res = segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &vector.clone().into(),
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                1,
+                None,
+            )
+            .unwrap();
+        assert_eq!(res[0].id, point_id);
+    }
+}
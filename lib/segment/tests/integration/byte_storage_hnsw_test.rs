@@ -130,6 +130,7 @@ fn test_byte_storage_hnsw(
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -146,6 +147,7 @@ fn test_byte_storage_hnsw(
                 quantization_config: None,
                 multivector_config: None,
                 datatype: Some(storage_data_type),
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -207,6 +209,7 @@ fn test_byte_storage_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -222,6 +225,8 @@ fn test_byte_storage_hnsw(
             .clone(),
         payload_index: segment_byte.payload_index.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
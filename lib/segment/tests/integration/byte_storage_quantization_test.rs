@@ -254,6 +254,7 @@ fn test_byte_storage_binary_quantization_hnsw(
                 quantization_config: None,
                 multivector_config: None,
                 datatype: Some(storage_data_type),
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -308,7 +309,12 @@ fn test_byte_storage_binary_quantization_hnsw(
             always_ram: None,
         }
         .into(),
-        QuantizationVariant::Binary => BinaryQuantizationConfig { always_ram: None }.into(),
+        QuantizationVariant::Binary => BinaryQuantizationConfig {
+            always_ram: None,
+            encoding: None,
+            query_encoding: None,
+        }
+        .into(),
     };
 
     segment_byte
@@ -334,6 +340,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -349,6 +356,8 @@ fn test_byte_storage_binary_quantization_hnsw(
             .clone(),
         payload_index: segment_byte.payload_index.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -52,6 +52,7 @@ fn exact_search_test() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -86,6 +87,7 @@ fn exact_search_test() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     payload_index_ptr
@@ -139,6 +141,8 @@ fn exact_search_test() {
             .clone(),
         payload_index: payload_index_ptr.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -0,0 +1,103 @@
+use std::sync::atomic::AtomicBool;
+
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::facets::{FacetParams, FacetValue};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::{random_filter, BOOL_KEY, FLT_KEY, INT_KEY, STR_KEY};
+use segment::fixtures::segment_fixtures::random_segment;
+use segment::json_path::JsonPath;
+use segment::segment::Segment;
+use segment::types::{Condition, FieldCondition, Filter, PayloadSchemaType};
+use tempfile::Builder;
+
+const NUM_POINTS: usize = 2000;
+const ATTEMPTS: usize = 20;
+
+fn count_with_value(
+    segment: &Segment,
+    key: &JsonPath,
+    filter: Option<&Filter>,
+    value: &FacetValue,
+) -> usize {
+    let value_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        key.clone(),
+        value.clone().into(),
+    )));
+    let filter = match filter {
+        Some(filter) => filter.merge(&value_filter),
+        None => value_filter,
+    };
+    segment.read_filtered(None, None, Some(&filter)).len()
+}
+
+#[test]
+fn test_facet_counts() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+    let mut segment = random_segment(dir.path(), NUM_POINTS);
+
+    let keys = [
+        (JsonPath::new(STR_KEY), PayloadSchemaType::Keyword),
+        (JsonPath::new(INT_KEY), PayloadSchemaType::Integer),
+        (JsonPath::new(BOOL_KEY), PayloadSchemaType::Bool),
+    ];
+    for (key, schema) in &keys {
+        segment
+            .create_field_index(101, key, Some(&(*schema).into()))
+            .unwrap();
+    }
+
+    // Deleted points must not be counted
+    for _ in 0..NUM_POINTS / 10 {
+        let point_id = rng.gen_range(0..NUM_POINTS as u64);
+        segment.delete_point(102, point_id.into()).unwrap();
+    }
+
+    let is_stopped = AtomicBool::new(false);
+
+    for (key, _) in &keys {
+        let mut request = FacetParams {
+            key: key.clone(),
+            limit: 10,
+            filter: None,
+            exact: true,
+        };
+
+        let exact = segment.facet(&request, &is_stopped).unwrap();
+        assert!(!exact.is_empty());
+        for (value, count) in &exact {
+            assert_eq!(
+                *count,
+                count_with_value(&segment, key, None, value),
+                "{value:?}"
+            );
+        }
+
+        // Without filter approximate counts are exact
+        request.exact = false;
+        let approximate = segment.facet(&request, &is_stopped).unwrap();
+        for (value, count) in &approximate {
+            assert_eq!(exact.get(value), Some(count));
+        }
+
+        request.exact = true;
+        for _ in 0..ATTEMPTS {
+            request.filter = Some(random_filter(&mut rng, 2));
+            let filtered = segment.facet(&request, &is_stopped).unwrap();
+            for (value, count) in &filtered {
+                let expected = count_with_value(&segment, key, request.filter.as_ref(), value);
+                assert_eq!(*count, expected, "{value:?}, filter: {:#?}", request.filter);
+            }
+        }
+    }
+
+    let request = FacetParams {
+        key: JsonPath::new(FLT_KEY),
+        limit: 10,
+        filter: None,
+        exact: true,
+    };
+    assert!(segment.facet(&request, &is_stopped).is_err());
+}
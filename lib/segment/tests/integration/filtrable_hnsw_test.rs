@@ -114,6 +114,7 @@ fn _test_filterable_hnsw(
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -147,6 +148,7 @@ fn _test_filterable_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -199,6 +201,8 @@ fn _test_filterable_hnsw(
         quantized_vectors: quantized_vectors.clone(),
         payload_index: payload_index_ptr.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -136,6 +136,7 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
                 (
@@ -148,6 +149,7 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
                 (
@@ -160,6 +162,7 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        truncated_view: None,
                     },
                 ),
             ]),
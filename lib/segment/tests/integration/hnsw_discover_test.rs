@@ -77,6 +77,7 @@ fn hnsw_discover_precision() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -103,6 +104,7 @@ fn hnsw_discover_precision() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -117,6 +119,8 @@ fn hnsw_discover_precision() {
         quantized_vectors: quantized_vectors.clone(),
         payload_index: payload_index_ptr.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -189,6 +193,7 @@ fn filtered_hnsw_discover_precision() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -226,6 +231,7 @@ fn filtered_hnsw_discover_precision() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -240,6 +246,8 @@ fn filtered_hnsw_discover_precision() {
         quantized_vectors: quantized_vectors.clone(),
         payload_index: payload_index_ptr.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -1,23 +1,28 @@
 #![cfg(test)]
 
 pub mod batch_search_test;
+mod bit_storage_test;
 mod byte_storage_hnsw_test;
 pub mod byte_storage_quantization_test;
 pub mod disbalanced_vectors_test;
 pub mod exact_search_test;
+mod facet_test;
 pub mod fail_recovery_test;
 pub mod filtering_context_check;
 pub mod filtrable_hnsw_test;
 pub mod fixtures;
 pub mod hnsw_discover_test;
 pub mod hnsw_quantized_search_test;
+mod multivector_comparator_test;
 mod multivector_filtrable_hnsw_test;
 mod multivector_hnsw_test;
 mod multivector_quantization_test;
 pub mod nested_filtering_test;
 pub mod payload_index_test;
+mod random_read_test;
 pub mod scroll_filtering_test;
 pub mod segment_builder_test;
 pub mod segment_tests;
 mod sparse_discover_test;
 mod sparse_vector_index_search_tests;
+mod truncated_vector_test;
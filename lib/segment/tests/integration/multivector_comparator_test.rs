@@ -0,0 +1,219 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType};
+use itertools::Itertools;
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use rstest::rstest;
+use segment::data_types::vectors::{
+    only_default_multi_vector, MultiDenseVectorInternal, QueryVector, DEFAULT_VECTOR_NAME,
+};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_multi_vector;
+use segment::segment_constructor::build_segment;
+use segment::spaces::metric::Metric;
+use segment::spaces::simple::CosineMetric;
+use segment::types::{
+    Distance, Indexes, MultiVectorComparator, MultiVectorConfig, ScalarQuantizationConfig,
+    SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
+};
+use segment::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use segment::vector_storage::{new_raw_scorer, VectorStorage};
+use tempfile::Builder;
+
+const MAX_VECTORS_COUNT: usize = 4;
+
+fn random_vector<R: Rng + ?Sized>(
+    rnd: &mut R,
+    dim: usize,
+    distance: Distance,
+) -> MultiDenseVectorInternal {
+    let count = rnd.gen_range(1..=MAX_VECTORS_COUNT);
+    let mut vector = random_multi_vector(rnd, dim, count);
+    vector.flattened_vectors.iter_mut().for_each(|x| *x -= 0.5);
+    // normalize in advance, so that brute force can use plain dot product for both distances
+    if distance == Distance::Cosine {
+        let normalized = vector
+            .multi_vectors()
+            .flat_map(|inner| CosineMetric::preprocess(inner.to_vec()))
+            .collect();
+        vector = MultiDenseVectorInternal::new(normalized, dim);
+    }
+    vector
+}
+
+/// Straightforward implementation of the comparators over the full similarity matrix
+fn brute_force_score(
+    comparator: MultiVectorComparator,
+    query: &MultiDenseVectorInternal,
+    stored: &MultiDenseVectorInternal,
+) -> ScoreType {
+    let similarities = query
+        .multi_vectors()
+        .map(|q| {
+            stored
+                .multi_vectors()
+                .map(|s| q.iter().zip(s).map(|(a, b)| a * b).sum::<ScoreType>())
+                .collect_vec()
+        })
+        .collect_vec();
+
+    let best_for_query = similarities
+        .iter()
+        .map(|row| row.iter().copied().fold(ScoreType::NEG_INFINITY, f32::max))
+        .collect_vec();
+    let best_for_stored = (0..stored.vectors_count())
+        .map(|j| {
+            similarities
+                .iter()
+                .map(|row| row[j])
+                .fold(ScoreType::NEG_INFINITY, f32::max)
+        })
+        .collect_vec();
+
+    let mean = |values: &[ScoreType]| values.iter().sum::<ScoreType>() / values.len() as ScoreType;
+    match comparator {
+        MultiVectorComparator::MaxSim => best_for_query.iter().sum(),
+        MultiVectorComparator::MinSim => best_for_query
+            .iter()
+            .copied()
+            .fold(ScoreType::INFINITY, f32::min),
+        MultiVectorComparator::AvgSim => mean(&best_for_query),
+        MultiVectorComparator::Chamfer => (mean(&best_for_query) + mean(&best_for_stored)) / 2.0,
+    }
+}
+
+fn brute_force_top(
+    comparator: MultiVectorComparator,
+    query: &MultiDenseVectorInternal,
+    points: &[MultiDenseVectorInternal],
+    top: usize,
+) -> BTreeSet<PointOffsetType> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(idx, point)| (brute_force_score(comparator, query, point), idx))
+        .sorted_by(|a, b| b.0.total_cmp(&a.0))
+        .take(top)
+        .map(|(_, idx)| idx as PointOffsetType)
+        .collect()
+}
+
+#[rstest]
+fn test_multivector_comparators_brute_force_equivalency(
+    #[values(
+        MultiVectorComparator::MaxSim,
+        MultiVectorComparator::MinSim,
+        MultiVectorComparator::AvgSim,
+        MultiVectorComparator::Chamfer
+    )]
+    comparator: MultiVectorComparator,
+    #[values(Distance::Dot, Distance::Cosine)] distance: Distance,
+) {
+    let stopped = AtomicBool::new(false);
+    let num_vectors: u64 = 500;
+    let dim = 16;
+    let top = 10;
+    let attempts = 20;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let quantized_dir = Builder::new().prefix("quantized_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: Some(MultiVectorConfig {
+                    comparator,
+                    ..Default::default()
+                }),
+                datatype: None,
+                truncated_view: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    let points = (0..num_vectors)
+        .map(|_| random_vector(&mut rnd, dim, distance))
+        .collect_vec();
+    for (n, vector) in points.iter().enumerate() {
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                (n as u64).into(),
+                only_default_multi_vector(vector),
+            )
+            .unwrap();
+    }
+
+    let vector_data = &segment.vector_data[DEFAULT_VECTOR_NAME];
+    let vector_storage = vector_data.vector_storage.borrow();
+    let id_tracker = segment.id_tracker.borrow();
+    let quantized_vectors = QuantizedVectors::create(
+        &vector_storage,
+        &ScalarQuantizationConfig {
+            r#type: Default::default(),
+            quantile: None,
+            always_ram: Some(true),
+        }
+        .into(),
+        quantized_dir.path(),
+        4,
+        &stopped,
+    )
+    .unwrap();
+
+    let mut sames = 0;
+    for _ in 0..attempts {
+        let query = random_vector(&mut rnd, dim, distance);
+
+        // raw scores must match brute force
+        let raw_scorer = new_raw_scorer(
+            QueryVector::Nearest(query.clone().into()),
+            &vector_storage,
+            id_tracker.deleted_point_bitslice(),
+        )
+        .unwrap();
+        for (idx, point) in points.iter().enumerate() {
+            let expected = brute_force_score(comparator, &query, point);
+            let score = raw_scorer.score_point(idx as PointOffsetType);
+            assert!(
+                (score - expected).abs() < 1e-4,
+                "point {idx}: score {score} != brute force {expected}",
+            );
+        }
+
+        // quantized scores must rank points close to brute force
+        let quantized_scorer = quantized_vectors
+            .raw_scorer(
+                QueryVector::Nearest(query.clone().into()),
+                id_tracker.deleted_point_bitslice(),
+                vector_storage.deleted_vector_bitslice(),
+                &stopped,
+            )
+            .unwrap();
+        let quantized_top: BTreeSet<_> = quantized_scorer
+            .peek_top_all(top)
+            .into_iter()
+            .map(|scored| scored.idx)
+            .collect();
+        let expected_top = brute_force_top(comparator, &query, &points, top);
+        sames += quantized_top.intersection(&expected_top).count();
+    }
+
+    let acc = 100.0 * sames as f64 / (attempts * top) as f64;
+    println!("sames = {sames}, attempts = {attempts}, top = {top}, acc = {acc}");
+    assert!(acc > 70.0);
+}
@@ -20,7 +20,9 @@ use segment::index::VectorIndex;
 use segment::json_path::JsonPath;
 use segment::segment_constructor::build_segment;
 use segment::spaces::metric::Metric;
-use segment::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use segment::spaces::simple::{
+    CosineMetric, DotProductMetric, EuclidMetric, HammingMetric, JaccardMetric, ManhattanMetric,
+};
 use segment::types::{
     Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, MultiVectorConfig, Payload,
     PayloadSchemaType, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
@@ -52,6 +54,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -98,6 +101,12 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
             Distance::Manhattan => {
                 <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector.clone())
             }
+            Distance::Hamming => {
+                <HammingMetric as Metric<VectorElementType>>::preprocess(vector.clone())
+            }
+            Distance::Jaccard => {
+                <JaccardMetric as Metric<VectorElementType>>::preprocess(vector.clone())
+            }
         };
         let vector_multi = MultiDenseVectorInternal::new(preprocessed_vector, vector.len());
 
@@ -135,6 +144,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     // single threaded mode to guarantee equivalency between single and multi hnsw
@@ -149,6 +159,8 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         quantized_vectors: quantized_vectors.clone(),
         payload_index: segment.payload_index.clone(),
         hnsw_config: hnsw_config.clone(),
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit.clone()),
         stopped: &stopped,
     })
@@ -163,6 +175,8 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         quantized_vectors: quantized_vectors.clone(),
         payload_index: segment.payload_index.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
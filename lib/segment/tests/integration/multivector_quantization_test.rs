@@ -23,8 +23,8 @@ use segment::segment_constructor::build_segment;
 use segment::types::{
     BinaryQuantizationConfig, CompressionRatio, Condition, Distance, FieldCondition, Filter,
     HnswConfig, Indexes, MultiVectorConfig, Payload, PayloadSchemaType, ProductQuantizationConfig,
-    QuantizationSearchParams, Range, ScalarQuantizationConfig, SearchParams, SegmentConfig,
-    SeqNumberType, VectorDataConfig, VectorStorageType,
+    QuantizationSearchParams, Range, ScalarQuantizationConfig, ScalarType, SearchParams,
+    SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
 };
 use segment::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use segment::vector_storage::query::{ContextPair, DiscoveryQuery, RecoQuery};
@@ -42,6 +42,7 @@ enum QueryVariant {
 
 enum QuantizationVariant {
     Scalar,
+    ScalarInt4,
     PQ,
     Binary,
 }
@@ -172,6 +173,24 @@ fn sames_count(a: &[Vec<ScoredPointOffset>], b: &[Vec<ScoredPointOffset>]) -> us
     false,
     80., // min_acc out of 100
 )]
+#[case::nearest_scalar_int4_dot(
+    QueryVariant::Nearest,
+    QuantizationVariant::ScalarInt4,
+    Distance::Dot,
+    32, // dim
+    32, // ef
+    false,
+    50., // min_acc out of 100
+)]
+#[case::nearest_scalar_int4_cosine_on_disk(
+    QueryVariant::Nearest,
+    QuantizationVariant::ScalarInt4,
+    Distance::Cosine,
+    32, // dim
+    32, // ef
+    true,
+    50., // min_acc out of 100
+)]
 #[case::nearest_pq_dot(
     QueryVariant::Nearest,
     QuantizationVariant::PQ,
@@ -229,6 +248,7 @@ fn test_multivector_quantization_hnsw(
                 quantization_config: None,
                 multivector_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -267,6 +287,12 @@ fn test_multivector_quantization_hnsw(
             always_ram: Some(false),
         }
         .into(),
+        QuantizationVariant::ScalarInt4 => ScalarQuantizationConfig {
+            r#type: ScalarType::Int4,
+            quantile: None,
+            always_ram: Some(false),
+        }
+        .into(),
         QuantizationVariant::PQ => ProductQuantizationConfig {
             compression: CompressionRatio::X8,
             always_ram: Some(false),
@@ -274,6 +300,8 @@ fn test_multivector_quantization_hnsw(
         .into(),
         QuantizationVariant::Binary => BinaryQuantizationConfig {
             always_ram: Some(false),
+            encoding: None,
+            query_encoding: None,
         }
         .into(),
     };
@@ -304,6 +332,7 @@ fn test_multivector_quantization_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        compressed_links: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -319,6 +348,8 @@ fn test_multivector_quantization_hnsw(
             .clone(),
         payload_index: segment.payload_index.clone(),
         hnsw_config,
+        truncate_to: None,
+        old_index: None,
         permit: Some(permit),
         stopped: &stopped,
     })
@@ -9,7 +9,9 @@ use indexmap::IndexSet;
 use itertools::Itertools;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
-use segment::data_types::index::{IntegerIndexParams, IntegerIndexType};
+use segment::data_types::index::{
+    IntegerIndexParams, IntegerIndexType, KeywordIndexParams, KeywordIndexType,
+};
 use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::payload_context_fixture::FixtureIdTracker;
@@ -55,6 +57,7 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -110,6 +113,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: true,
                     range: false,
+                    is_principal: None,
+                    on_disk: None,
                 },
             ))),
         )
@@ -123,6 +128,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: false,
                     range: true,
+                    is_principal: None,
+                    on_disk: None,
                 },
             ))),
         )
@@ -196,6 +203,7 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -881,6 +889,150 @@ fn test_update_payload_index_type() {
     assert_eq!(field_index[1].count_indexed_points(), point_num);
 }
 
+#[test]
+fn test_on_disk_payload_index() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let point_num = 10;
+
+    let mut payload_storage = InMemoryPayloadStorage::default();
+    for i in 0..point_num {
+        let payload: Payload = json!({
+            "field": format!("value{}", i % 3),
+        })
+        .into();
+        payload_storage
+            .assign(i as PointOffsetType, &payload)
+            .unwrap();
+    }
+
+    let field = JsonPath::new("field");
+    let schema = FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+        r#type: KeywordIndexType::Keyword,
+        is_tenant: None,
+        on_disk: Some(true),
+    }));
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        field.clone(),
+        "value1".to_string().into(),
+    )));
+
+    {
+        let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+        let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
+        let mut index =
+            StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), false)
+                .unwrap();
+        index.set_indexed(&field, schema).unwrap();
+
+        assert_eq!(index.query_points(&filter), vec![1, 4, 7]);
+        assert!(index.files().len() > 1);
+        index.flusher()().unwrap();
+    }
+
+    // Index is loaded from files, payload storage is not used
+    let wrapped_payload_storage =
+        Arc::new(AtomicRefCell::new(InMemoryPayloadStorage::default().into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
+    let mut index =
+        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), false).unwrap();
+    assert_eq!(index.query_points(&filter), vec![1, 4, 7]);
+
+    index.drop_index(&field).unwrap();
+    assert_eq!(index.files().len(), 1);
+}
+
+#[test]
+fn test_principal_range_primary_clause() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let point_num = 1000;
+
+    let mut payload_storage = InMemoryPayloadStorage::default();
+    for i in 0..point_num {
+        let payload: Payload = json!({
+            "timestamp": i,
+            "kind": if i % 200 == 0 { "rare" } else { "common" },
+        })
+        .into();
+        payload_storage
+            .assign(i as PointOffsetType, &payload)
+            .unwrap();
+    }
+
+    let timestamp = JsonPath::new("timestamp");
+    let kind = JsonPath::new("kind");
+
+    let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
+    let mut index =
+        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), true).unwrap();
+    index
+        .set_indexed(
+            &timestamp,
+            FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
+                r#type: IntegerIndexType::Integer,
+                lookup: false,
+                range: true,
+                is_principal: Some(true),
+                on_disk: None,
+            })),
+        )
+        .unwrap();
+    index.set_indexed(&kind, FieldType(Keyword)).unwrap();
+
+    let timestamp_range = |gte: f64, lte: f64| {
+        FieldCondition::new_range(
+            timestamp.clone(),
+            Range {
+                lt: None,
+                gt: None,
+                gte: Some(gte),
+                lte: Some(lte),
+            },
+        )
+    };
+
+    // Selective keyword clause is cheaper than a wide principal range
+    let rare_kind = FieldCondition::new_match(kind.clone(), "rare".to_string().into());
+    let filter = Filter {
+        should: None,
+        min_should: None,
+        must: Some(vec![
+            Condition::Field(timestamp_range(100., 1000.)),
+            Condition::Field(rare_kind.clone()),
+        ]),
+        must_not: None,
+    };
+    assert_eq!(
+        index.query_primary_clauses(&filter),
+        vec![PrimaryCondition::Condition(rare_kind)],
+    );
+    assert_eq!(index.query_points(&filter), vec![200, 400, 600, 800]);
+
+    // Narrow principal range is preferred
+    let narrow_range = timestamp_range(100., 110.);
+    let filter = Filter {
+        should: None,
+        min_should: None,
+        must: Some(vec![
+            Condition::Field(FieldCondition::new_match(kind, "common".to_string().into())),
+            Condition::Field(narrow_range.clone()),
+        ]),
+        must_not: None,
+    };
+    assert_eq!(
+        index.query_primary_clauses(&filter),
+        vec![PrimaryCondition::Condition(narrow_range)],
+    );
+    assert_eq!(
+        index
+            .query_points(&filter)
+            .into_iter()
+            .sorted()
+            .collect_vec(),
+        (100..=110).collect_vec(),
+    );
+}
+
 #[test]
 fn test_any_matcher_cardinality_estimation() {
     let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
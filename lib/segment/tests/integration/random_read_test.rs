@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::common::sampling::sample_key;
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::{random_filter, INT_KEY, STR_KEY};
+use segment::fixtures::segment_fixtures::random_segment;
+use segment::json_path::JsonPath;
+use segment::types::{PayloadSchemaType, PointIdType};
+use tempfile::Builder;
+
+const NUM_POINTS: usize = 2000;
+const ATTEMPTS: usize = 20;
+const SEED: u64 = 7;
+
+#[test]
+fn test_read_random_filtered() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+    let mut segment = random_segment(dir.path(), NUM_POINTS);
+
+    // Indexed fields make the segment choose between the index and the id stream
+    segment
+        .create_field_index(
+            101,
+            &JsonPath::new(STR_KEY),
+            Some(&PayloadSchemaType::Keyword.into()),
+        )
+        .unwrap();
+    segment
+        .create_field_index(
+            102,
+            &JsonPath::new(INT_KEY),
+            Some(&PayloadSchemaType::Integer.into()),
+        )
+        .unwrap();
+
+    for _ in 0..NUM_POINTS / 10 {
+        let point_id = rng.gen_range(0..NUM_POINTS as u64);
+        segment.delete_point(103, point_id.into()).unwrap();
+    }
+
+    let all_points: HashSet<_> = segment
+        .read_filtered(None, None, None)
+        .into_iter()
+        .collect();
+
+    let sample = segment.read_random_filtered(10, None, SEED);
+    assert_eq!(sample.len(), 10);
+    assert_eq!(sample_ids(&sample).len(), 10);
+    assert!(sample
+        .iter()
+        .all(|(_, point_id)| all_points.contains(point_id)));
+
+    // Sample is sorted by the key and is the same for the same seed
+    assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(sample.iter().all(|key| *key == sample_key(SEED, key.1)));
+    assert_eq!(segment.read_random_filtered(10, None, SEED), sample);
+
+    // Smaller sample is the beginning of the larger one
+    let smaller_sample = segment.read_random_filtered(5, None, SEED);
+    assert_eq!(smaller_sample, sample[..5]);
+
+    // Limit larger than the segment returns all points
+    let sample = segment.read_random_filtered(NUM_POINTS * 2, None, SEED);
+    assert_eq!(sample_ids(&sample), all_points);
+
+    for _ in 0..ATTEMPTS {
+        let filter = random_filter(&mut rng, 2);
+        let matching: HashSet<_> = segment
+            .read_filtered(None, None, Some(&filter))
+            .into_iter()
+            .collect();
+
+        let limit = rng.gen_range(1..50);
+        let sample = segment.read_random_filtered(limit, Some(&filter), SEED);
+        assert_eq!(sample.len(), limit.min(matching.len()));
+        assert!(sample
+            .iter()
+            .all(|(_, point_id)| matching.contains(point_id)));
+
+        // Both the index and the id stream give the points with the smallest keys
+        let mut expected: Vec<_> = matching
+            .iter()
+            .map(|point_id| sample_key(SEED, *point_id))
+            .collect();
+        expected.sort_unstable();
+        expected.truncate(limit);
+        assert_eq!(sample, expected);
+    }
+}
+
+fn sample_ids<K>(sample: &[(K, PointIdType)]) -> HashSet<PointIdType> {
+    sample.iter().map(|(_, point_id)| *point_id).collect()
+}
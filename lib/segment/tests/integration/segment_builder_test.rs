@@ -6,13 +6,21 @@ use std::time::{Duration, Instant};
 use common::cpu::CpuPermit;
 use itertools::Itertools;
 use segment::common::operation_error::OperationError;
+use segment::data_types::index::{IntegerIndexParams, KeywordIndexParams};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{only_default_vector, VectorRef, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
+use segment::id_tracker::IdTracker;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::PayloadIndex;
+use segment::payload_storage::FilterContext;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::types::{
+    Condition, FieldCondition, Filter, Indexes, Payload, PayloadFieldSchema, PayloadSchemaParams,
+    SegmentConfig, VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
 use sparse::common::sparse_vector::SparseVector;
 use tempfile::Builder;
 
@@ -143,6 +151,154 @@ fn test_building_new_sparse_segment() {
     assert_eq!(merged_segment.point_version(3.into()), Some(100));
 }
 
+#[test]
+fn test_building_new_segment_groups_tenants() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let tenants = ["tenant_a", "tenant_b", "tenant_c"];
+    let tenant_key = "tenant".parse().unwrap();
+
+    let mut segment1 = empty_segment(dir.path());
+    let mut segment2 = empty_segment(dir.path());
+
+    for (offset, segment) in [(0, &mut segment1), (100, &mut segment2)] {
+        for i in 0..30u64 {
+            let point_id = (offset + i).into();
+            segment
+                .upsert_point(1, point_id, only_default_vector(&[i as f32, 0., 0., 0.]))
+                .unwrap();
+            let payload: Payload = json!({ "tenant": tenants[i as usize % tenants.len()] }).into();
+            segment.set_full_payload(2, point_id, &payload).unwrap();
+        }
+    }
+
+    // Tenant index on one of the segments is enough to reorder points of all of them
+    let tenant_schema =
+        PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+            is_tenant: Some(true),
+            ..Default::default()
+        }));
+    segment1
+        .create_field_index(3, &tenant_key, Some(&tenant_schema))
+        .unwrap();
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segment1.segment_config).unwrap();
+
+    builder.update(&[&segment1, &segment2], &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    assert_eq!(merged_segment.available_point_count(), 60);
+
+    let id_tracker = merged_segment.id_tracker.borrow();
+    for (tenant_index, tenant) in tenants.iter().enumerate() {
+        let internal_ids: Vec<_> = (0..30u64)
+            .filter(|i| *i as usize % tenants.len() == tenant_index)
+            .flat_map(|i| [i, 100 + i])
+            .map(|point_id| id_tracker.internal_id(point_id.into()).unwrap())
+            .sorted()
+            .collect();
+
+        // All points of the tenant occupy a contiguous range of internal ids
+        let first = *internal_ids.first().unwrap();
+        let last = *internal_ids.last().unwrap();
+        assert_eq!(
+            (last - first + 1) as usize,
+            internal_ids.len(),
+            "points of {tenant} are not contiguous",
+        );
+
+        // Tenant filter is resolved into the same range, which limits the candidates
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            tenant_key.clone(),
+            tenant.to_string().into(),
+        )));
+        let payload_index = merged_segment.payload_index.borrow();
+        assert_eq!(
+            payload_index.tenant_points_range(&filter),
+            Some(first..last + 1),
+        );
+        assert!(!payload_index.filter_context(&filter).check(last + 1));
+        let matched_ids: Vec<_> = payload_index
+            .query_points(&filter)
+            .into_iter()
+            .sorted()
+            .collect();
+        assert_eq!(matched_ids, internal_ids);
+    }
+
+    // Unknown tenant doesn't match any point
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        tenant_key,
+        "tenant_x".to_string().into(),
+    )));
+    let payload_index = merged_segment.payload_index.borrow();
+    assert_eq!(payload_index.tenant_points_range(&filter), Some(0..0));
+    assert!(payload_index.query_points(&filter).is_empty());
+}
+
+#[test]
+fn test_building_new_segment_orders_by_principal_field() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let timestamp_key = "timestamp".parse().unwrap();
+
+    let mut segment1 = empty_segment(dir.path());
+    let mut segment2 = empty_segment(dir.path());
+
+    // Timestamps are interleaved between segments and decrease with point id
+    for (offset, segment) in [(0, &mut segment1), (1, &mut segment2)] {
+        for i in 0..20i64 {
+            let timestamp = -(2 * i + offset);
+            let point_id = timestamp.unsigned_abs().into();
+            segment
+                .upsert_point(1, point_id, only_default_vector(&[i as f32, 0., 0., 0.]))
+                .unwrap();
+            let payload: Payload = json!({ "timestamp": timestamp }).into();
+            segment.set_full_payload(2, point_id, &payload).unwrap();
+        }
+    }
+
+    let principal_schema =
+        PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
+            is_principal: Some(true),
+            ..Default::default()
+        }));
+    segment2
+        .create_field_index(3, &timestamp_key, Some(&principal_schema))
+        .unwrap();
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segment1.segment_config).unwrap();
+
+    builder.update(&[&segment1, &segment2], &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    assert_eq!(merged_segment.available_point_count(), 40);
+
+    // Internal ids follow ascending timestamps, which is descending point ids
+    let id_tracker = merged_segment.id_tracker.borrow();
+    let internal_ids: Vec<_> = (0..40u64)
+        .rev()
+        .map(|point_id| id_tracker.internal_id(point_id.into()).unwrap())
+        .collect();
+    assert!(internal_ids.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
 fn estimate_build_time(segment: &Segment, stop_delay_millis: Option<u64>) -> (u64, bool) {
     let stopped = Arc::new(AtomicBool::new(false));
 
@@ -160,6 +316,7 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: Option<u64>) -> (u6
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -261,6 +261,8 @@ fn test_update_named_vector() {
         exact: true,
         quantization: None,
         indexed_only: false,
+        dimensions: None,
+        acorn: None,
     };
     let nearest_upsert = segment
         .search(
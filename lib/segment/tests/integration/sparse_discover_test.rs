@@ -136,6 +136,7 @@ fn sparse_index_discover_test() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                truncated_view: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::{QueryVector, Vector};
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, Indexes, SearchParams, SegmentConfig, SeqNumberType, TruncatedViewConfig,
+    VectorDataConfig, VectorStorageType, WithPayload,
+};
+use segment::vector_storage::query::RecoQuery;
+use tempfile::Builder;
+
+const FULL: &str = "full";
+const PREFIX: &str = "prefix";
+
+fn vector_config(size: usize, truncated_view: Option<TruncatedViewConfig>) -> VectorDataConfig {
+    VectorDataConfig {
+        size,
+        distance: Distance::Cosine,
+        storage_type: VectorStorageType::Memory,
+        index: Indexes::Plain {},
+        quantization_config: None,
+        multivector_config: None,
+        datatype: None,
+        truncated_view,
+    }
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+    let norm_a: f32 = a.iter().map(|a| a * a).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|b| b * b).sum::<f32>().sqrt();
+    dot / (norm_a * norm_b)
+}
+
+#[test]
+fn test_truncated_dimensions_search() {
+    let full_dim = 16;
+    let prefix_dim = 4;
+    let num_vectors: u64 = 100;
+    let top = 10;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([
+            (FULL.to_owned(), vector_config(full_dim, None)),
+            (
+                PREFIX.to_owned(),
+                vector_config(
+                    prefix_dim,
+                    Some(TruncatedViewConfig {
+                        source: FULL.to_owned(),
+                    }),
+                ),
+            ),
+        ]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    let vectors: Vec<Vec<f32>> = (0..num_vectors)
+        .map(|_| (0..full_dim).map(|_| rnd.gen_range(-1.0..1.0)).collect())
+        .collect();
+    for (n, vector) in vectors.iter().enumerate() {
+        let named = NamedVectors::from_pairs([(FULL.to_owned(), vector.clone())]);
+        segment
+            .upsert_point(n as SeqNumberType, (n as u64).into(), named)
+            .unwrap();
+    }
+
+    // The view can't be written to directly
+    let named = NamedVectors::from_pairs([(PREFIX.to_owned(), vec![1.0; prefix_dim])]);
+    assert!(segment
+        .upsert_point(num_vectors, num_vectors.into(), named)
+        .is_err());
+
+    // The view exposes the leading dimensions of the source vector
+    let stored = segment.vector(PREFIX, 0u64.into()).unwrap().unwrap();
+    let Vector::Dense(stored) = stored else {
+        panic!("dense vector expected");
+    };
+    assert_eq!(stored.len(), prefix_dim);
+
+    let query: Vec<f32> = (0..full_dim).map(|_| rnd.gen_range(-1.0..1.0)).collect();
+
+    let mut expected: Vec<_> = vectors
+        .iter()
+        .enumerate()
+        .map(|(n, vector)| {
+            (
+                n as u64,
+                cosine(&query[..prefix_dim], &vector[..prefix_dim]),
+            )
+        })
+        .collect();
+    expected.sort_by(|a, b| b.1.total_cmp(&a.1));
+    expected.truncate(top);
+
+    let params = SearchParams {
+        dimensions: Some(prefix_dim),
+        ..Default::default()
+    };
+    let by_params = segment
+        .search(
+            FULL,
+            &query.clone().into(),
+            &WithPayload::default(),
+            &false.into(),
+            None,
+            top,
+            Some(&params),
+        )
+        .unwrap();
+    let by_view = segment
+        .search(
+            PREFIX,
+            &query[..prefix_dim].to_vec().into(),
+            &WithPayload::default(),
+            &false.into(),
+            None,
+            top,
+            None,
+        )
+        .unwrap();
+
+    for result in [by_params, by_view] {
+        assert_eq!(result.len(), top);
+        for (scored, (id, score)) in result.iter().zip(&expected) {
+            assert_eq!(scored.id, (*id).into());
+            assert!((scored.score - score).abs() < 1e-4);
+        }
+    }
+
+    // Queries with multiple vectors are scored over the leading dimensions too
+    let examples: Vec<Vec<f32>> = (0..3)
+        .map(|_| (0..full_dim).map(|_| rnd.gen_range(-1.0..1.0)).collect())
+        .collect();
+    let reco_query = |dim: usize| {
+        let [positive, other_positive, negative] =
+            [0, 1, 2].map(|i| Vector::from(examples[i][..dim].to_vec()));
+        QueryVector::Recommend(RecoQuery::new(
+            vec![positive, other_positive],
+            vec![negative],
+        ))
+    };
+
+    let by_params = segment
+        .search(
+            FULL,
+            &reco_query(full_dim),
+            &WithPayload::default(),
+            &false.into(),
+            None,
+            top,
+            Some(&params),
+        )
+        .unwrap();
+    let by_view = segment
+        .search(
+            PREFIX,
+            &reco_query(prefix_dim),
+            &WithPayload::default(),
+            &false.into(),
+            None,
+            top,
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(by_params.len(), top);
+    for (by_params, by_view) in by_params.iter().zip(&by_view) {
+        assert_eq!(by_params.id, by_view.id);
+        assert!((by_params.score - by_view.score).abs() < 1e-4);
+    }
+}
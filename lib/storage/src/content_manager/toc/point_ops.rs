@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use collection::collection::distance_matrix::CollectionSearchMatrixResponse;
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
@@ -12,6 +13,7 @@ use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
+use segment::data_types::facets::FacetResponse;
 use segment::types::{ScoredPoint, ShardKey};
 
 use super::TableOfContent;
@@ -158,6 +160,66 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count points with each value of the payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    ///
+    /// # Result
+    ///
+    /// The most frequent values of the field with the number of points having them
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        mut request: FacetRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access: Access,
+    ) -> Result<FacetResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .facet(request.into(), read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Find the nearest neighbours of randomly sampled points among the sample
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`SearchMatrixRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    ///
+    /// # Result
+    ///
+    /// Sampled point ids with the nearest neighbours of each of them
+    pub async fn search_points_matrix(
+        &self,
+        collection_name: &str,
+        mut request: SearchMatrixRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        access: Access,
+    ) -> Result<CollectionSearchMatrixResponse, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .search_points_matrix(request.into(), shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -348,10 +410,16 @@ impl TableOfContent {
         // `Collection::update_from_client` is cancel safe, so it's safe to use `TryStreamExt::try_collect`
         let results: Vec<_> = updates.try_collect().await?;
 
-        results
-            .into_iter()
+        let mut results = results.into_iter();
+        let mut result = results
             .next()
-            .ok_or_else(|| StorageError::bad_input("Empty shard keys selection"))
+            .ok_or_else(|| StorageError::bad_input("Empty shard keys selection"))?;
+        for shard_key_result in results {
+            result
+                .unmatched_points
+                .extend(shard_key_result.unmatched_points);
+        }
+        Ok(result)
     }
 
     /// # Cancel safety
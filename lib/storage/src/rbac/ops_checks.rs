@@ -9,7 +9,8 @@ use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloa
 use collection::operations::point_ops::{PointIdsList, PointOperations};
 use collection::operations::types::{
     ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
-    PointRequestInternal, RecommendExample, RecommendRequestInternal, ScrollRequestInternal,
+    FacetRequestInternal, PointRequestInternal, RecommendExample, RecommendRequestInternal,
+    ScrollRequestInternal, SearchMatrixRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
@@ -131,6 +132,9 @@ impl<'a> CollectionAccessView<'a> {
     ) -> Result<(), StorageError> {
         match vector_query {
             VectorQuery::Nearest(nearest) => self.check_vector_input(nearest)?,
+            VectorQuery::NearestWithMmr(nearest_with_mmr) => {
+                self.check_vector_input(&nearest_with_mmr.nearest)?
+            }
             VectorQuery::RecommendBestScore(reco) | VectorQuery::RecommendAverageVector(reco) => {
                 for vector_input in reco.flat_iter() {
                     self.check_vector_input(vector_input)?
@@ -241,6 +245,44 @@ impl CheckableCollectionOperation for CountRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for FacetRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for SearchMatrixRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for GroupRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -393,6 +435,9 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                 PointOperations::UpsertPoints(_) => {
                     view.check_whole_access()?;
                 }
+                PointOperations::UpsertPointsConditional(_) => {
+                    view.check_whole_access()?;
+                }
                 PointOperations::DeletePoints { ids } => {
                     if let Some(payload) = &view.payload {
                         *op = PointOperations::DeletePointsByFilter(
@@ -445,6 +490,7 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                         points,
                         filter,
                         key: _, // TODO: validate
+                        update_filter: _,
                     }) => {
                         let filter = filter.get_or_insert_with(Default::default);
                         if let Some(points) = take(points) {
@@ -475,6 +521,7 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                                 make_filter_from_ids(take(points)).merge_owned(payload.to_filter()),
                             ),
                             key: None,
+                            update_filter: None,
                         });
                     }
                     PayloadOps::ClearPayloadByFilter(filter) => {
@@ -483,6 +530,7 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                             points: None,
                             filter: Some(take(filter).merge_owned(payload.to_filter())),
                             key: None,
+                            update_filter: None,
                         });
                     }
                     PayloadOps::OverwritePayload(SetPayloadOp {
@@ -490,6 +538,7 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                         points,
                         filter,
                         key: _, // TODO: validate
+                        update_filter: _,
                     }) => {
                         let filter = filter.get_or_insert_with(Default::default);
                         if let Some(points) = take(points) {
@@ -619,8 +668,9 @@ mod tests_ops {
     };
     use collection::operations::payload_ops::PayloadOpsDiscriminants;
     use collection::operations::point_ops::{
-        Batch, PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
-        PointOperationsDiscriminants, PointStruct, PointSyncOperation,
+        Batch, ConditionalInsertOperationInternal, PointInsertOperationsInternal,
+        PointInsertOperationsInternalDiscriminants, PointOperationsDiscriminants, PointStruct,
+        PointSyncOperation,
     };
     use collection::operations::query_enum::QueryEnum;
     use collection::operations::types::UsingVector;
@@ -864,6 +914,66 @@ mod tests_ops {
         );
     }
 
+    #[test]
+    fn test_facet_request_internal() {
+        let op = FacetRequestInternal {
+            key: "color".parse().unwrap(),
+            limit: Some(10),
+            filter: None,
+            exact: Some(false),
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_search_matrix_request_internal() {
+        let op = SearchMatrixRequestInternal {
+            filter: None,
+            sample: Some(10),
+            limit: Some(3),
+            using: None,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
     #[test]
     fn test_group_request_source() {
         let op = GroupRequest {
@@ -1119,6 +1229,20 @@ mod tests_ops {
                 }
             }
 
+            PointOperationsDiscriminants::UpsertPointsConditional => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                        points_op: PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                        }]),
+                        condition: make_filter_from_ids(vec![ExtendedPointId::NumId(12345)]),
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
             PointOperationsDiscriminants::DeletePoints => {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
@@ -1262,6 +1386,7 @@ mod tests_ops {
                     points: Some(vec![ExtendedPointId::NumId(12345)]),
                     filter: None,
                     key: None,
+                    update_filter: None,
                 }),
                 PayloadOpsDiscriminants::DeletePayload => {
                     PayloadOps::DeletePayload(DeletePayloadOp {
@@ -1284,6 +1409,7 @@ mod tests_ops {
                         points: Some(vec![ExtendedPointId::NumId(12345)]),
                         filter: None,
                         key: None,
+                        update_filter: None,
                     })
                 }
             };
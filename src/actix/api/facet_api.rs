@@ -0,0 +1,46 @@
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+use actix_web_validator::{Json, Path, Query};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::FacetRequest;
+use storage::dispatcher::Dispatcher;
+
+use super::CollectionPath;
+use crate::actix::api::read_params::ReadParams;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+use crate::common::points::do_facet_points;
+
+#[post("/collections/{name}/facet")]
+async fn facet_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<FacetRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let FacetRequest {
+        facet_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selector = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let response = do_facet_points(
+        dispatcher.toc(&access),
+        &collection.name,
+        facet_request,
+        params.consistency,
+        shard_selector,
+        params.timeout(),
+        access,
+    )
+    .await;
+
+    process_response(response, timing)
+}
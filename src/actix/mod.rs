@@ -26,6 +26,7 @@ use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::facet_points;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
@@ -152,6 +153,7 @@ pub fn init(
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
                 .service(count_points)
+                .service(facet_points)
                 .service(get_point)
                 .service(get_points);
 
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use api::rest::{QueryGroupsRequestInternal, SearchGroupsRequestInternal, ShardKeySelector};
+use collection::collection::distance_matrix::CollectionSearchMatrixResponse;
 use collection::common::batching::batch_requests;
 use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
@@ -15,8 +16,9 @@ use collection::operations::point_ops::{
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, GroupsResult, PointRequestInternal, RecommendGroupsRequestInternal,
-    Record, ScrollRequestInternal, ScrollResult, UpdateResult,
+    DiscoverRequestBatch, FacetRequestInternal, GroupsResult, PointRequestInternal,
+    RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult,
+    SearchMatrixRequestInternal, UpdateResult,
 };
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::vector_ops::{
@@ -27,6 +29,7 @@ use collection::operations::{
 };
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
+use segment::data_types::facets::FacetResponse;
 use segment::json_path::JsonPath;
 use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
 use serde::{Deserialize, Serialize};
@@ -171,8 +174,7 @@ pub async fn do_upsert_points(
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
+    let collection_operation = CollectionUpdateOperations::PointOperation(operation);
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
@@ -330,6 +332,7 @@ pub async fn do_set_payload(
         filter,
         shard_key,
         key,
+        update_filter,
     } = operation;
 
     let collection_operation =
@@ -338,6 +341,7 @@ pub async fn do_set_payload(
             points,
             filter,
             key,
+            update_filter,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -369,6 +373,7 @@ pub async fn do_overwrite_payload(
         payload,
         filter,
         shard_key,
+        update_filter,
         ..
     } = operation;
 
@@ -379,6 +384,7 @@ pub async fn do_overwrite_payload(
             filter,
             // overwrite operation doesn't support payload selector
             key: None,
+            update_filter,
         }));
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
@@ -926,6 +932,46 @@ pub async fn do_count_points(
     .await
 }
 
+pub async fn do_facet_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FacetRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    access: Access,
+) -> Result<FacetResponse, StorageError> {
+    toc.facet(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        timeout,
+        access,
+    )
+    .await
+}
+
+pub async fn do_search_points_matrix(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchMatrixRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    timeout: Option<Duration>,
+    access: Access,
+) -> Result<CollectionSearchMatrixResponse, StorageError> {
+    toc.search_points_matrix(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        timeout,
+        access,
+    )
+    .await
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -12,13 +12,15 @@ use collection::operations::snapshot_ops::{
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    FacetRequest, GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
+    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest, SearchRequest,
     SearchRequestBatch, UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
+use segment::data_types::facets::FacetResponse;
 use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
@@ -87,6 +89,11 @@ struct AllDefinitions {
     bf: QueryRequestBatch,
     bg: QueryResponse,
     bh: QueryGroupsRequest,
+    bi: FacetRequest,
+    bj: FacetResponse,
+    bk: SearchMatrixRequest,
+    bl: SearchMatrixOffsetsResponse,
+    bm: SearchMatrixPairsResponse,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -8,15 +8,16 @@ use api::grpc::qdrant::{
     points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
     CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
     DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, QueryBatchResponse, QueryPoints, QueryResponse,
-    ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
-    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
-    SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponseInternal, PointsSelector, QueryBatchResponse, QueryPoints,
+    QueryResponse, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
+    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
+    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchMatrixPoints,
+    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::collection::distance_matrix::CollectionSearchMatrixResponse;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
     try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
@@ -28,8 +29,9 @@ use collection::operations::point_ops::{
 use collection::operations::query_enum::QueryEnum;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
-    RecommendExample, Record, ScrollRequestInternal,
+    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, FacetRequestInternal,
+    PointRequestInternal, RecommendExample, Record, ScrollRequestInternal,
+    SearchMatrixRequestInternal,
 };
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
@@ -50,9 +52,9 @@ use tonic::{Response, Status};
 use crate::common::points::{
     do_clear_payload, do_core_search_points, do_count_points, do_create_index,
     do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload,
+    do_delete_points, do_delete_vectors, do_facet_points, do_get_points, do_overwrite_payload,
     do_query_batch_points, do_query_points, do_scroll_points, do_search_batch_points,
-    do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_search_points_matrix, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
 };
 
 fn extract_points_selector(
@@ -111,6 +113,7 @@ pub async fn upsert(
         points,
         ordering,
         shard_key_selector,
+        update_filter,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -119,6 +122,7 @@ pub async fn upsert(
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        update_filter: update_filter.map(|f| f.try_into()).transpose()?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -347,6 +351,7 @@ pub async fn set_payload(
         ordering,
         shard_key_selector,
         key,
+        update_filter,
     } = set_payload_points;
     let key = key.map(|k| json_path_from_proto(&k)).transpose()?;
 
@@ -357,6 +362,7 @@ pub async fn set_payload(
         filter,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
         key,
+        update_filter: update_filter.map(|f| f.try_into()).transpose()?,
     };
 
     let timing = Instant::now();
@@ -391,6 +397,7 @@ pub async fn overwrite_payload(
         points_selector,
         ordering,
         shard_key_selector,
+        update_filter,
         ..
     } = set_payload_points;
 
@@ -402,6 +409,7 @@ pub async fn overwrite_payload(
         shard_key: shard_key_selector.map(ShardKeySelector::from),
         // overwrite operation don't support indicate path of property
         key: None,
+        update_filter: update_filter.map(|f| f.try_into()).transpose()?,
     };
 
     let timing = Instant::now();
@@ -529,6 +537,7 @@ pub async fn update_batch(
             points_update_operation::Operation::Upsert(PointStructList {
                 points,
                 shard_key_selector,
+                update_filter,
             }) => {
                 upsert(
                     toc.clone(),
@@ -538,6 +547,7 @@ pub async fn update_batch(
                         wait,
                         ordering,
                         shard_key_selector,
+                        update_filter,
                     },
                     clock_tag,
                     shard_selection,
@@ -567,6 +577,7 @@ pub async fn update_batch(
                     points_selector,
                     shard_key_selector,
                     key,
+                    update_filter,
                 },
             ) => {
                 set_payload(
@@ -579,6 +590,7 @@ pub async fn update_batch(
                         ordering,
                         shard_key_selector,
                         key,
+                        update_filter,
                     },
                     clock_tag,
                     shard_selection,
@@ -591,6 +603,7 @@ pub async fn update_batch(
                     payload,
                     points_selector,
                     shard_key_selector,
+                    update_filter,
                     ..
                 },
             ) => {
@@ -605,6 +618,7 @@ pub async fn update_batch(
                         shard_key_selector,
                         // overwrite operation don't support it
                         key: None,
+                        update_filter,
                     },
                     clock_tag,
                     shard_selection,
@@ -772,6 +786,60 @@ fn convert_field_type(
         ) => Some(PayloadFieldSchema::FieldParams(
             PayloadSchemaParams::Integer(integer_params.try_into()?),
         )),
+        // Parameterized float type
+        (
+            Some(FieldType::Float),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::FloatIndexParams(float_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Float(
+            float_params.try_into()?,
+        ))),
+        // Parameterized datetime type
+        (
+            Some(FieldType::Datetime),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::DatetimeIndexParams(datetime_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(
+            PayloadSchemaParams::Datetime(datetime_params.try_into()?),
+        )),
+        // Parameterized keyword type
+        (
+            Some(FieldType::Keyword),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::KeywordIndexParams(keyword_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(
+            PayloadSchemaParams::Keyword(keyword_params.try_into()?),
+        )),
+        // Parameterized uuid type
+        (
+            Some(FieldType::Uuid),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::UuidIndexParams(uuid_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Uuid(
+            uuid_params.try_into()?,
+        ))),
+        // Parameterized geo type
+        (
+            Some(FieldType::Geo),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::GeoIndexParams(geo_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Geo(
+            geo_params.try_into()?,
+        ))),
+        // Parameterized bool type
+        (
+            Some(FieldType::Bool),
+            Some(PayloadIndexParams {
+                index_params: Some(IndexParams::BoolIndexParams(bool_params)),
+            }),
+        ) => Some(PayloadFieldSchema::FieldParams(PayloadSchemaParams::Bool(
+            bool_params.try_into()?,
+        ))),
         // Regular field types
         (Some(v), None | Some(PayloadIndexParams { index_params: None })) => match v {
             FieldType::Keyword => Some(PayloadSchemaType::Keyword.into()),
@@ -781,6 +849,7 @@ fn convert_field_type(
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
             FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
+            FieldType::Uuid => Some(PayloadSchemaType::Uuid.into()),
         },
         // Parameterized index with mismatching types
         (
@@ -1509,6 +1578,100 @@ pub async fn count(
     Ok(Response::new(response))
 }
 
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_counts: FacetCounts,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<FacetResponse>, Status> {
+    let FacetCounts {
+        collection_name,
+        key,
+        filter,
+        limit,
+        exact,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = facet_counts;
+
+    let facet_request = FacetRequestInternal {
+        key: json_path_from_proto(&key)?,
+        limit: limit.map(|limit| limit as usize),
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        exact,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    let timing = Instant::now();
+    let facet_response = do_facet_points(
+        toc,
+        &collection_name,
+        facet_request,
+        read_consistency,
+        shard_selector,
+        timeout,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_response.hits.into_iter().map(From::from).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_points_matrix(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<CollectionSearchMatrixResponse, Status> {
+    let SearchMatrixPoints {
+        collection_name,
+        filter,
+        sample,
+        limit,
+        using,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = search_matrix_points;
+
+    let search_matrix_request = SearchMatrixRequestInternal {
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        sample: sample.map(|sample| sample as usize),
+        limit: limit.map(|limit| limit as usize),
+        using,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timeout = timeout.map(Duration::from_secs);
+
+    do_search_points_matrix(
+        toc,
+        &collection_name,
+        search_matrix_request,
+        read_consistency,
+        shard_selector,
+        timeout,
+        access,
+    )
+    .await
+    .map_err(error_to_status)
+}
+
 pub async fn get(
     toc: &TableOfContent,
     get_points: GetPoints,
@@ -5,11 +5,12 @@ use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryBatchPointsInternal,
-    QueryBatchResponseInternal, QueryResultInternal, QueryShardPoints, RecommendPointsInternal,
-    RecommendResponse, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
-    SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCounts,
+    FacetCountsInternal, FacetResponse, GetPointsInternal, GetResponse, IntermediateResult,
+    PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
+    QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
+    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
+    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
@@ -24,8 +25,8 @@ use super::points_common::core_search_list;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
     clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    delete_payload, delete_vectors, facet, get, overwrite_payload, recommend, scroll, set_payload,
+    sync, update_vectors, upsert,
 };
 
 const FULL_ACCESS: Access = Access::full("Internal API");
@@ -505,4 +506,40 @@ impl PointsInternal for PointsInternalService {
         )
         .await
     }
+
+    async fn facet(
+        &self,
+        request: Request<FacetCountsInternal>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let FacetCountsInternal {
+            collection_name,
+            key,
+            filter,
+            limit,
+            exact,
+            shard_id,
+            timeout,
+        } = request.into_inner();
+
+        let facet_counts = FacetCounts {
+            collection_name,
+            key,
+            filter,
+            limit: Some(limit),
+            exact: Some(exact),
+            timeout,
+            read_consistency: None,
+            shard_key_selector: None,
+        };
+
+        facet(
+            self.toc.as_ref(),
+            facet_counts,
+            Some(shard_id),
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
 }
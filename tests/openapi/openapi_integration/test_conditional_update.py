@@ -0,0 +1,113 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_conditional_update'
+
+cheap_filter = {
+    "must": [
+        {"key": "price", "range": {"lt": 10}}
+    ]
+}
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_payload(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    assert response.ok
+    return response.json()['result']['payload']
+
+
+def test_conditional_upsert():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"price": 20}},
+                {"id": 4, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"price": 20}},
+                {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"price": 20}},
+            ],
+            "update_filter": cheap_filter,
+        }
+    )
+    assert response.ok
+    # Point 1 costs 10.0, so it doesn't match the filter
+    assert response.json()['result']['unmatched_points'] == [1]
+
+    assert get_payload(1) == {"city": "Berlin", "price": 10.0}
+    assert get_payload(4) == {"price": 20}
+    # New points are inserted regardless of the filter
+    assert get_payload(100) == {"price": 20}
+
+    # Unconditional upsert doesn't report unmatched points
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "batch": {
+                "ids": [1],
+                "vectors": [[0.1, 0.2, 0.3, 0.4]],
+                "payloads": [{"price": 30}],
+            },
+        }
+    )
+    assert response.ok
+    assert 'unmatched_points' not in response.json()['result']
+    assert get_payload(1) == {"price": 30}
+
+
+def test_conditional_set_payload():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"discount": True},
+            "points": [1, 3, 4],
+            "update_filter": cheap_filter,
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['unmatched_points'] == [1]
+
+    assert "discount" not in get_payload(1)
+    assert get_payload(3)["discount"] is True
+    assert get_payload(4)["discount"] is True
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"price": 5},
+            "filter": {
+                "must": [
+                    {"key": "city", "match": {"value": "Berlin"}}
+                ]
+            },
+            "update_filter": cheap_filter,
+        }
+    )
+    assert response.ok
+    assert sorted(response.json()['result']['unmatched_points']) == [1, 2]
+
+    assert get_payload(1) == {"city": "Berlin", "price": 10.0}
+    assert get_payload(3) == {"price": 5}
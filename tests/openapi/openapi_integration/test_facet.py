@@ -0,0 +1,85 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_facet'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword",
+        }
+    )
+    assert response.ok
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+@pytest.mark.parametrize("exact", [True, False])
+def test_facet(exact):
+    response = request_with_validation(
+        api='/collections/{collection_name}/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "city",
+            "exact": exact,
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['hits'] == [
+        {"value": "Berlin", "count": 3},
+        {"value": "London", "count": 2},
+        {"value": "Moscow", "count": 2},
+    ]
+
+
+def test_facet_with_filter():
+    response = request_with_validation(
+        api='/collections/{collection_name}/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "city",
+            "limit": 2,
+            "exact": True,
+            "filter": {
+                "must": [
+                    {
+                        "key": "price",
+                        "range": {
+                            "lt": 10.0
+                        }
+                    }
+                ]
+            }
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['hits'] == [
+        {"value": "Moscow", "count": 2},
+        {"value": "Berlin", "count": 1},
+    ]
+
+
+def test_facet_without_index():
+    response = request_with_validation(
+        api='/collections/{collection_name}/facet',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "key": "price",
+        }
+    )
+    assert response.status_code == 400
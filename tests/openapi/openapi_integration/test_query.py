@@ -380,6 +380,279 @@ def test_basic_rrf():
         assert isclose(expected["score"], result["score"], rel_tol=1e-5)
         
 
+@pytest.mark.parametrize("fusion", [
+    "dbsf",
+    {"weighted_sum": {"weights": [0.8, 0.2]}},
+])
+def test_score_fusion(fusion):
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [
+                { "query": [0.1, 0.2, 0.3, 0.4] },
+                { "query": [0.5, 0.6, 0.7, 0.8] },
+            ],
+            "query": {"fusion": fusion},
+        },
+    )
+    assert response.ok, response.json()
+    points = response.json()["result"]["points"]
+
+    assert len(points) > 0
+    scores = [point["score"] for point in points]
+    assert scores == sorted(scores, reverse=True)
+
+
+def test_weighted_sum_fusion_needs_weight_per_prefetch():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [
+                { "query": [0.1, 0.2, 0.3, 0.4] },
+                { "query": [0.5, 0.6, 0.7, 0.8] },
+            ],
+            "query": {"fusion": {"weighted_sum": {"weights": [1.0]}}},
+        },
+    )
+    assert response.status_code == 400, response.json()
+
+
+def test_formula_query():
+    prefetch_response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "limit": 100,
+            "with_payload": True,
+        },
+    )
+    assert prefetch_response.ok, prefetch_response.json()
+    prefetch_points = prefetch_response.json()["result"]["points"]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": { "query": [0.1, 0.2, 0.3, 0.4], "limit": 100 },
+            "query": {
+                "formula": {
+                    "sum": [
+                        "$score",
+                        { "mult": [0.1, "price"] },
+                        { "mult": [2.0, { "key": "city", "match": { "value": "Berlin" } }] },
+                    ]
+                },
+                "defaults": { "price": 0.0 },
+            },
+            "limit": 100,
+        },
+    )
+    assert response.ok, response.json()
+    points = response.json()["result"]["points"]
+
+    assert len(points) == len(prefetch_points)
+    scores = [point["score"] for point in points]
+    assert scores == sorted(scores, reverse=True)
+
+    def expected_score(point):
+        payload = point["payload"] or {}
+        price = payload.get("price")
+        price = price if isinstance(price, (int, float)) else 0.0
+        city = payload.get("city")
+        cities = city if isinstance(city, list) else [city]
+        return point["score"] + 0.1 * price + (2.0 if "Berlin" in cities else 0.0)
+
+    expected = {point["id"]: expected_score(point) for point in prefetch_points}
+    for point in points:
+        assert isclose(point["score"], expected[point["id"]], rel_tol=1e-5)
+
+
+def test_formula_query_needs_default_for_missing_payload():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": { "query": [0.1, 0.2, 0.3, 0.4], "limit": 100 },
+            "query": { "formula": { "mult": ["$score", "price"] } },
+        },
+    )
+    assert response.status_code == 400, response.json()
+
+
+def test_text_query():
+    response = request_with_validation(
+        api="/collections/{collection_name}/index",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={"field_name": "city", "field_schema": "text"},
+    )
+    assert response.ok, response.json()
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": { "text": { "key": "city", "query": "berlin" } },
+            "limit": 100,
+            "with_payload": True,
+        },
+    )
+    assert response.ok, response.json()
+    points = response.json()["result"]["points"]
+
+    assert len(points) > 0
+    scores = [point["score"] for point in points]
+    assert scores == sorted(scores, reverse=True)
+    for point in points:
+        city = point["payload"]["city"]
+        cities = city if isinstance(city, list) else [city]
+        assert "Berlin" in cities
+        assert point["score"] > 0.0
+
+    # Text query needs a full-text index
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": { "text": { "key": "price", "query": "berlin" } },
+        },
+    )
+    assert response.status_code == 400, response.json()
+
+
+def test_nearest_with_mmr():
+    def query(body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body=body,
+        )
+        assert response.ok, response.json()
+        return response.json()["result"]["points"]
+
+    nearest = query({"query": {"nearest": [0.1, 0.2, 0.3, 0.4]}, "limit": 8})
+
+    # Without diversity, MMR keeps the order of the nearest search
+    relevant = query({
+        "query": {"nearest": [0.1, 0.2, 0.3, 0.4], "mmr": {"diversity": 0.0, "candidates_limit": 8}},
+        "limit": 4,
+    })
+    assert relevant == nearest[:4]
+
+    # With diversity, points are picked from the candidates, starting from the most relevant one
+    diverse = query({
+        "query": {"nearest": [0.1, 0.2, 0.3, 0.4], "mmr": {"diversity": 1.0, "candidates_limit": 8}},
+        "limit": 4,
+    })
+    assert len(diverse) == 4
+    assert diverse[0] == nearest[0]
+    candidates = {point["id"]: point for point in nearest}
+    for point in diverse:
+        assert point == candidates[point["id"]]
+        assert "vector" not in point or point["vector"] is None
+
+    # Vectors are returned only when requested
+    with_vectors = query({
+        "query": {"nearest": [0.1, 0.2, 0.3, 0.4], "mmr": {"diversity": 1.0, "candidates_limit": 8}},
+        "limit": 4,
+        "with_vector": True,
+    })
+    assert [point["id"] for point in with_vectors] == [point["id"] for point in diverse]
+    assert all(point["vector"] is not None for point in with_vectors)
+
+
+def test_nearest_with_mmr_validation():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": {"nearest": [0.1, 0.2, 0.3, 0.4], "mmr": {"candidates_limit": 2}},
+            "limit": 4,
+        },
+    )
+    assert response.status_code == 400, response.json()
+
+    # Re-ranking happens after merging the results of all shards, not in prefetches
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [
+                {"query": {"nearest": [0.1, 0.2, 0.3, 0.4], "mmr": {"diversity": 0.5}}},
+            ],
+            "query": {"fusion": "rrf"},
+        },
+    )
+    assert response.status_code == 400, response.json()
+
+
+def test_sample_query():
+    def query(body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body=body,
+        )
+        assert response.ok, response.json()
+        return response.json()["result"]["points"]
+
+    all_ids = {point["id"] for point in query({"limit": 100})}
+
+    sample = query({"query": {"sample": "random"}, "limit": 5})
+    assert len(sample) == 5
+    assert len({point["id"] for point in sample}) == 5
+    assert {point["id"] for point in sample} <= all_ids
+
+    # Limit larger than the collection returns all points
+    sample = query({"query": {"sample": "random"}, "limit": 100})
+    assert {point["id"] for point in sample} == all_ids
+
+    # Same seed gives the same points, sampling from a prefetch gives the same points too
+    seeded = root_and_rescored_query({"sample": "random", "seed": 42}, limit=5)
+    assert len(seeded) == 5
+    assert query({"query": {"sample": "random", "seed": 42}, "limit": 5}) == seeded
+
+    # Smaller sample with the same seed is the beginning of the larger one
+    assert query({"query": {"sample": "random", "seed": 42}, "limit": 3}) == seeded[:3]
+
+    # Only points matching the filter are sampled
+    sample = query({
+        "query": {"sample": "random"},
+        "filter": {"must": [{"key": "city", "match": {"value": "Berlin"}}]},
+        "limit": 10,
+    })
+    assert {point["id"] for point in sample} == {1, 2, 3}
+
+
+def test_sample_query_validation():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "query": {"sample": "random"},
+            "score_threshold": 0.5,
+        },
+    )
+    assert response.status_code == 400, response.json()
+    assert response.json()["status"]["error"] == "Bad request: Can't use score_threshold with a sample query."
+
+
 @pytest.mark.parametrize("body", [
     {
         "prefetch": [
@@ -0,0 +1,104 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_search_matrix'
+
+# Points 9 and 10 have only the sparse vector
+DENSE_POINTS = [1, 2, 3, 4, 5, 6, 7, 8]
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_matrix_pairs():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/pairs',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 10,
+            "limit": 2,
+        }
+    )
+    assert response.ok, response.text
+    pairs = response.json()['result']['pairs']
+
+    assert len(pairs) == len(DENSE_POINTS) * 2
+    for pair in pairs:
+        assert pair['a'] != pair['b']
+        assert pair['a'] in DENSE_POINTS
+        assert pair['b'] in DENSE_POINTS
+
+    # Neighbours of each point are ordered by score
+    for point_id in DENSE_POINTS:
+        scores = [pair['score'] for pair in pairs if pair['a'] == point_id]
+        assert len(scores) == 2
+        assert scores == sorted(scores, reverse=True)
+
+
+def test_search_matrix_offsets():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/offsets',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 10,
+            "limit": 3,
+        }
+    )
+    assert response.ok, response.text
+    result = response.json()['result']
+
+    assert result['ids'] == DENSE_POINTS
+    assert len(result['offsets_row']) == len(DENSE_POINTS) * 3
+    assert len(result['offsets_col']) == len(DENSE_POINTS) * 3
+    assert len(result['scores']) == len(DENSE_POINTS) * 3
+
+    for row, col in zip(result['offsets_row'], result['offsets_col']):
+        assert row != col
+        assert 0 <= col < len(DENSE_POINTS)
+
+
+def test_search_matrix_with_filter():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/pairs',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 5,
+            "limit": 5,
+            "filter": {
+                "must": [
+                    {
+                        "key": "city",
+                        "match": {"value": "Berlin"}
+                    }
+                ]
+            },
+        }
+    )
+    assert response.ok, response.text
+    pairs = response.json()['result']['pairs']
+
+    # Only 3 points match, each of them has the 2 others as neighbours
+    assert len(pairs) == 6
+    assert {pair['a'] for pair in pairs} == {1, 2, 3}
+    assert {pair['b'] for pair in pairs} == {1, 2, 3}
+
+
+def test_search_matrix_validation():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/matrix/pairs',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "sample": 1,
+        }
+    )
+    assert response.status_code == 422